@@ -0,0 +1,113 @@
+// Copyright (c) 2025 Dmitry Kalashnikov
+// Dual Licensed: Open-Source (see LICENSE) / Commercial (proprietary use)
+// Commercial use requires a Commercial License. See LICENSE file.
+
+// pushd, popd and dirs built-in commands: the shell's directory stack
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// Saved directories, most recent last; the current directory is the implicit top
+static DIR_STACK: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+pub fn directory_stack() -> Vec<PathBuf> {
+    DIR_STACK.lock().map(|stack| stack.clone()).unwrap_or_default()
+}
+
+pub fn set_directory_stack(dirs: Vec<PathBuf>) {
+    if let Ok(mut stack) = DIR_STACK.lock() {
+        *stack = dirs;
+    }
+}
+
+fn split_obj_flag(args: &[String]) -> (bool, Vec<String>) {
+    let is_object_output = args.iter().any(|s| s == "-o" || s == "--obj");
+    let rest = args.iter().filter(|s| *s != "-o" && *s != "--obj").cloned().collect();
+    (is_object_output, rest)
+}
+
+fn change_dir(builtin: &str, path: &str) -> Result<(), String> {
+    super::cd::execute(&[path.to_string()])
+        .map(|_| ())
+        .map_err(|e| e.replacen("cd", builtin, 1))
+}
+
+/// `pushd DIR` saves the current directory and changes to DIR;
+/// `pushd` alone swaps the current directory with the top of the stack
+pub fn execute_pushd(args: &[String], add_obj: bool) -> Result<String, String> {
+    let (is_object_output, rest) = split_obj_flag(args);
+    let current = std::env::current_dir()
+        .map_err(|e| format!("pushd: {}", e))?;
+
+    match rest.first() {
+        Some(dir) => {
+            change_dir("pushd", dir)?;
+            if let Ok(mut stack) = DIR_STACK.lock() {
+                stack.push(current);
+            }
+        }
+        None => {
+            let top = directory_stack().pop()
+                .ok_or_else(|| "pushd: no other directory".to_string())?;
+            change_dir("pushd", &top.to_string_lossy())?;
+            if let Ok(mut stack) = DIR_STACK.lock() {
+                stack.pop();
+                stack.push(current);
+            }
+        }
+    }
+
+    Ok(render_stack(is_object_output || add_obj))
+}
+
+/// `popd` removes the top of the stack and changes to it
+pub fn execute_popd(args: &[String], add_obj: bool) -> Result<String, String> {
+    let (is_object_output, _) = split_obj_flag(args);
+    let top = directory_stack().pop()
+        .ok_or_else(|| "popd: directory stack empty".to_string())?;
+
+    change_dir("popd", &top.to_string_lossy())?;
+    if let Ok(mut stack) = DIR_STACK.lock() {
+        stack.pop();
+    }
+
+    Ok(render_stack(is_object_output || add_obj))
+}
+
+/// `dirs` shows the stack; `dirs -c` clears it
+pub fn execute_dirs(args: &[String], add_obj: bool) -> Result<String, String> {
+    let (is_object_output, rest) = split_obj_flag(args);
+    match rest.first().map(|s| s.as_str()) {
+        None => {}
+        Some("-c") => set_directory_stack(Vec::new()),
+        Some(other) => return Err(format!("dirs: {}: invalid option", other)),
+    }
+
+    Ok(render_stack(is_object_output || add_obj))
+}
+
+/// The stack from the current directory down, one line per entry or as stardust
+fn render_stack(is_object_output: bool) -> String {
+    let mut dirs: Vec<String> = std::env::current_dir()
+        .map(|p| p.to_string_lossy().into_owned())
+        .into_iter()
+        .collect();
+    dirs.extend(directory_stack().iter().rev().map(|p| p.to_string_lossy().into_owned()));
+
+    if is_object_output {
+        let entries: Vec<_> = dirs.iter().enumerate().map(|(index, path)| {
+            json!({
+                "index": index,
+                "path": path,
+            })
+        }).collect();
+
+        let output = json!({
+            "entries": entries,
+            "count": entries.len()
+        });
+        format!("{}\n", output)
+    } else {
+        format!("{}\n", dirs.join(" "))
+    }
+}
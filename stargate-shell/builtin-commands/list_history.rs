@@ -3,108 +3,172 @@
 // Commercial use requires a Commercial License. See LICENSE file.
 
 // list-history built-in command
-use std::time::SystemTime;
-use std::io::{BufRead, BufReader};
+use std::time::{Duration, SystemTime};
 use serde_json::json;
+use super::super::history::{current_dir_string, load_history, HistoryRecord};
 
-pub fn execute(args: &str, history_file: &str) -> Result<(), String> {
-    let is_object_output = args.contains("-o") || args.contains("--obj");
-    
-    // Extract filter (everything that's not a flag)
-    let filter = args.split_whitespace()
-        .filter(|&s| s != "-o" && s != "--obj")
-        .collect::<Vec<_>>()
-        .join(" ");
-    
-    let history_with_ts = load_timestamped_history(history_file);
-    
-    if filter.is_empty() {
-        // No filter, show all history
-        if history_with_ts.is_empty() {
-            if is_object_output {
-                println!("{}", json!({"entries": [], "count": 0}));
-            } else {
-                println!("No command history available.");
-            }
-        } else if is_object_output {
-            let entries: Vec<_> = history_with_ts.iter().map(|(timestamp, cmd)| {
-                json!({
-                    "timestamp": format_timestamp(*timestamp),
-                    "timestamp_unix": timestamp.duration_since(SystemTime::UNIX_EPOCH)
-                        .unwrap_or_default().as_secs(),
-                    "command": cmd
-                })
-            }).collect();
-            
-            let output = json!({
-                "entries": entries,
-                "count": entries.len()
-            });
-            println!("{}", output);
-        } else {
-            for (timestamp, cmd) in &history_with_ts {
-                println!("{}\t{}", format_timestamp(*timestamp), cmd);
+/// Which exit statuses to keep
+enum StatusFilter {
+    Ok,
+    Failed,
+    Code(i32),
+}
+
+/// Options parsed from the list-history argument string
+#[derive(Default)]
+struct HistoryQuery {
+    is_object_output: bool,
+    dir: Option<String>,
+    status: Option<StatusFilter>,
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+    text: String,
+}
+
+impl HistoryQuery {
+    fn parse(args: &str) -> Result<Self, String> {
+        let mut query = Self::default();
+        let mut text = Vec::new();
+        let mut tokens = args.split_whitespace();
+
+        while let Some(token) = tokens.next() {
+            let mut value_for = |flag: &str| {
+                tokens.next().ok_or_else(|| format!("list-history: option '{}' requires a value", flag))
+            };
+            match token {
+                "-o" | "--obj" => query.is_object_output = true,
+                "--here" => query.dir = current_dir_string(),
+                "--dir" => query.dir = Some(resolve_dir(value_for(token)?)),
+                "--status" => query.status = Some(parse_status(value_for(token)?)?),
+                "--since" => query.since = Some(parse_time_bound(value_for(token)?)?),
+                "--until" => query.until = Some(parse_time_bound(value_for(token)?)?),
+                _ => text.push(token),
             }
         }
-    } else {
-        // Filter history
-        let filtered: Vec<_> = history_with_ts.iter()
-            .filter(|(_, cmd)| cmd.contains(&filter))
-            .collect();
-        
-        if filtered.is_empty() {
-            if is_object_output {
-                println!("{}", json!({"entries": [], "count": 0}));
-            } else {
-                println!("No history entries matching '{}'", filter);
-            }
-        } else if is_object_output {
-            let entries: Vec<_> = filtered.iter().map(|(timestamp, cmd)| {
-                json!({
-                    "timestamp": format_timestamp(*timestamp),
-                    "timestamp_unix": timestamp.duration_since(SystemTime::UNIX_EPOCH)
-                        .unwrap_or_default().as_secs(),
-                    "command": cmd
-                })
-            }).collect();
-            
-            let output = json!({
-                "entries": entries,
-                "count": entries.len()
-            });
-            println!("{}", output);
-        } else {
-            for (timestamp, cmd) in filtered {
-                println!("{}\t{}", format_timestamp(*timestamp), cmd);
+
+        query.text = text.join(" ");
+        Ok(query)
+    }
+
+    fn matches(&self, record: &HistoryRecord) -> bool {
+        if !self.text.is_empty() && !record.command.contains(&self.text) {
+            return false;
+        }
+        if self.dir.as_ref().is_some_and(|dir| record.cwd.as_ref() != Some(dir)) {
+            return false;
+        }
+        if let Some(ref status) = self.status {
+            let keep = match (status, record.exit_status) {
+                (StatusFilter::Ok, Some(code)) => code == 0,
+                (StatusFilter::Failed, Some(code)) => code != 0,
+                (StatusFilter::Code(want), Some(code)) => code == *want,
+                (_, None) => false,
+            };
+            if !keep {
+                return false;
             }
         }
+        if self.since.is_some_and(|since| record.timestamp < since) {
+            return false;
+        }
+        if self.until.is_some_and(|until| record.timestamp > until) {
+            return false;
+        }
+        true
+    }
+
+    fn has_filters(&self) -> bool {
+        !self.text.is_empty() || self.dir.is_some() || self.status.is_some()
+            || self.since.is_some() || self.until.is_some()
     }
-    
-    Ok(())
 }
 
-// Load history with timestamps from file (public for main to use)
-pub fn load_timestamped_history(history_file: &str) -> Vec<(SystemTime, String)> {
-    let mut history = Vec::new();
-    
-    if let Ok(file) = std::fs::File::open(history_file) {
-        let reader = BufReader::new(file);
-        
-        for line in reader.lines().flatten() {
-            // Format: timestamp|command
-            if let Some(pos) = line.find('|') {
-                let timestamp_str = &line[..pos];
-                let command = &line[pos + 1..];
-                
-                if let Ok(secs) = timestamp_str.parse::<u64>() {
-                    let timestamp = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
-                    history.push((timestamp, command.to_string()));
-                }
-            }
+fn resolve_dir(dir: &str) -> String {
+    std::fs::canonicalize(dir)
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| dir.to_string())
+}
+
+fn parse_status(value: &str) -> Result<StatusFilter, String> {
+    match value {
+        "ok" | "success" => Ok(StatusFilter::Ok),
+        "failed" | "error" => Ok(StatusFilter::Failed),
+        _ => value.parse::<i32>()
+            .map(StatusFilter::Code)
+            .map_err(|_| format!("list-history: invalid status '{}' (expected ok, failed or a number)", value)),
+    }
+}
+
+/// Parse a time bound: either unix seconds, or an age such as `30m`, `2h`, `1d`, `1w`
+fn parse_time_bound(value: &str) -> Result<SystemTime, String> {
+    let invalid = || format!("list-history: invalid time '{}' (expected unix seconds or an age like 2h, 3d)", value);
+    let out_of_range = || format!("list-history: time '{}' is out of range", value);
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return SystemTime::UNIX_EPOCH
+            .checked_add(Duration::from_secs(secs))
+            .ok_or_else(out_of_range);
+    }
+
+    let split = value.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let amount = value[..split].parse::<u64>().map_err(|_| invalid())?;
+    let unit_secs = match &value[split..] {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        "w" => 604800,
+        _ => return Err(invalid()),
+    };
+
+    let age = amount.checked_mul(unit_secs).ok_or_else(out_of_range)?;
+    SystemTime::now()
+        .checked_sub(Duration::from_secs(age))
+        .ok_or_else(out_of_range)
+}
+
+pub fn execute(args: &str, history_file: &str) -> Result<(), String> {
+    let query = HistoryQuery::parse(args)?;
+
+    let history: Vec<_> = load_history(history_file)
+        .into_iter()
+        .filter(|record| query.matches(record))
+        .collect();
+
+    if query.is_object_output {
+        let entries: Vec<_> = history.iter().map(|record| {
+            json!({
+                "timestamp": format_timestamp(record.timestamp),
+                "timestamp_unix": record.timestamp_unix(),
+                "command": record.command,
+                "cwd": record.cwd,
+                "exit_status": record.exit_status,
+                "duration_ms": record.duration_ms,
+                "session": record.session,
+                "hostname": record.hostname,
+            })
+        }).collect();
+
+        let output = json!({
+            "entries": entries,
+            "count": entries.len()
+        });
+        println!("{}", output);
+    } else if history.is_empty() {
+        if !query.has_filters() {
+            println!("No command history available.");
+        } else if !query.text.is_empty() {
+            println!("No history entries matching '{}'", query.text);
+        } else {
+            println!("No history entries matching the given filters");
+        }
+    } else {
+        for record in &history {
+            println!("{}\t{}", format_timestamp(record.timestamp), record.command);
         }
     }
     
-    history
+    Ok(())
 }
 
 // Format timestamp as human-readable date (short format with minutes)
@@ -172,3 +236,76 @@ fn format_timestamp(timestamp: SystemTime) -> String {
     }
     format!("{}y{}mo", years, remaining_months)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(command: &str, cwd: Option<&str>, exit_status: Option<i32>, ts: u64) -> HistoryRecord {
+        HistoryRecord {
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(ts),
+            command: command.to_string(),
+            cwd: cwd.map(str::to_string),
+            exit_status,
+            duration_ms: None,
+            session: None,
+            hostname: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = HistoryQuery::parse("--obj git --status failed --dir /nonexistent-dir push").unwrap();
+        assert!(query.is_object_output);
+        assert_eq!(query.text, "git push");
+        assert_eq!(query.dir.as_deref(), Some("/nonexistent-dir"));
+        assert!(matches!(query.status, Some(StatusFilter::Failed)));
+        assert!(query.has_filters());
+
+        let query = HistoryQuery::parse("-o --status 2 --since 100 --until 200").unwrap();
+        assert!(matches!(query.status, Some(StatusFilter::Code(2))));
+        assert_eq!(query.since, Some(SystemTime::UNIX_EPOCH + Duration::from_secs(100)));
+        assert_eq!(query.until, Some(SystemTime::UNIX_EPOCH + Duration::from_secs(200)));
+
+        assert!(!HistoryQuery::parse("").unwrap().has_filters());
+    }
+
+    #[test]
+    fn test_parse_query_errors() {
+        let err = HistoryQuery::parse("--since").err().unwrap();
+        assert_eq!(err, "list-history: option '--since' requires a value");
+        assert!(HistoryQuery::parse("--status maybe").err().unwrap().contains("invalid status 'maybe'"));
+        assert!(HistoryQuery::parse("--until 2x").err().unwrap().contains("invalid time '2x'"));
+        assert!(HistoryQuery::parse("--since h").err().unwrap().contains("invalid time 'h'"));
+    }
+
+    #[test]
+    fn test_parse_time_bound() {
+        let age = SystemTime::now().duration_since(parse_time_bound("2h").unwrap()).unwrap();
+        assert!((age.as_secs_f64() - 7200.0).abs() < 60.0, "{age:?}");
+        assert_eq!(parse_time_bound("0").unwrap(), SystemTime::UNIX_EPOCH);
+    }
+
+    #[test]
+    fn test_parse_time_bound_overflow() {
+        let out_of_range = |value: &str| format!("list-history: time '{}' is out of range", value);
+        for value in ["18446744073709551615", "18446744073709551615w", "30500000000000000d", "99999999999999w"] {
+            assert_eq!(parse_time_bound(value), Err(out_of_range(value)), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let query = HistoryQuery::parse("--status ok --since 100 build").unwrap();
+        assert!(query.matches(&record("cargo build", None, Some(0), 150)));
+        assert!(!query.matches(&record("cargo build", None, Some(1), 150)));
+        assert!(!query.matches(&record("cargo build", None, None, 150)));
+        assert!(!query.matches(&record("cargo build", None, Some(0), 50)));
+        assert!(!query.matches(&record("cargo test", None, Some(0), 150)));
+
+        let query = HistoryQuery::parse("--dir /nonexistent-dir --until 100").unwrap();
+        assert!(query.matches(&record("ls", Some("/nonexistent-dir"), None, 100)));
+        assert!(!query.matches(&record("ls", Some("/tmp"), None, 100)));
+        assert!(!query.matches(&record("ls", Some("/nonexistent-dir"), None, 101)));
+    }
+}
@@ -11,11 +11,14 @@ pub mod foreground_job;
 pub mod background_job;
 pub mod list_history;
 pub mod list_variables;
+pub mod set_options;
+pub mod dirs;
 
 pub use cd::execute as execute_cd;
 pub use list_jobs::execute as execute_list_jobs;
 pub use foreground_job::execute as execute_foreground_job;
 pub use background_job::execute as execute_background_job;
 pub use list_history::execute as execute_list_history;
-pub use list_history::load_timestamped_history;
 pub use list_variables::execute as execute_list_variables;
+pub use set_options::execute as execute_set;
+pub use dirs::{execute_dirs, execute_popd, execute_pushd};
@@ -0,0 +1,50 @@
+// Copyright (c) 2025 Dmitry Kalashnikov
+// Dual Licensed: Open-Source (see LICENSE) / Commercial (proprietary use)
+// Commercial use requires a Commercial License. See LICENSE file.
+
+// set built-in command: toggle strict modes (set -e, set -o pipefail),
+// automatic table rendering (set +o table) and the binary stardust wire
+// format between stages (set +o binary)
+use crate::execution::{
+    auto_table_enabled, binary_wire_enabled, errexit_enabled, pipefail_enabled, set_auto_table,
+    set_binary_wire, set_errexit, set_pipefail,
+};
+
+pub fn execute(args: &[String]) -> Result<String, String> {
+    if args.is_empty() || (args.len() == 1 && (args[0] == "-o" || args[0] == "+o")) {
+        return Ok(format!(
+            "errexit\t{}\npipefail\t{}\ntable\t{}\nbinary\t{}\n",
+            if errexit_enabled() { "on" } else { "off" },
+            if pipefail_enabled() { "on" } else { "off" },
+            if auto_table_enabled() { "on" } else { "off" },
+            if binary_wire_enabled() { "on" } else { "off" },
+        ));
+    }
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let enable = arg.starts_with('-');
+        match arg.as_str() {
+            "-e" | "+e" => set_errexit(enable),
+            "-o" | "+o" => {
+                let name = iter.next().ok_or_else(|| format!("set: {}: option name required", arg))?;
+                set_named_option(name, enable)?;
+            }
+            // Combined short flags such as -eo pipefail are not supported; be explicit
+            _ => return Err(format!("set: {}: invalid option (use -e, +e, -o NAME, +o NAME)", arg)),
+        }
+    }
+
+    Ok(String::new())
+}
+
+fn set_named_option(name: &str, enable: bool) -> Result<(), String> {
+    match name {
+        "errexit" => set_errexit(enable),
+        "pipefail" => set_pipefail(enable),
+        "table" => set_auto_table(enable),
+        "binary" => set_binary_wire(enable),
+        _ => return Err(format!("set: {}: invalid option name", name)),
+    }
+    Ok(())
+}
@@ -7,9 +7,12 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
+use crate::parsing::subshell_body;
+
 /// Type of command input detected
 #[derive(Debug, PartialEq)]
 pub enum CommandType {
+    Subshell,
     ScriptStatement,
     ControlFlow,
     ChainedCommands,
@@ -20,6 +23,11 @@ pub enum CommandType {
 impl CommandType {
     /// Detect the type of command from input string
     pub fn detect(input: &str) -> Self {
+        // A parenthesised group runs in a subshell, whatever it contains
+        if subshell_body(input).is_some() {
+            return Self::Subshell;
+        }
+
         // Check for chained commands first (but not in script statements)
         if input.contains("&&") 
             && !input.starts_with("let ") 
@@ -8,7 +8,7 @@ use std::collections::HashMap;
 use std::sync::OnceLock;
 
 // List of built-in shell commands
-pub const SHELL_COMMANDS: &[&str] = &["help", "exit", "quit", "describe-command", "cd", "change-directory", "list-jobs", "foreground-job", "background-job", "list-history", "list-variables"];
+pub const SHELL_COMMANDS: &[&str] = &["help", "exit", "quit", "describe-command", "cd", "change-directory", "pushd", "popd", "dirs", "set", "list-jobs", "foreground-job", "background-job", "list-history", "list-variables"];
 
 // Get all command aliases
 pub fn get_command_aliases() -> Vec<String> {
@@ -2,26 +2,249 @@
 // Dual Licensed: Open-Source (see LICENSE) / Commercial (proprietary use)
 // Commercial use requires a Commercial License. See LICENSE file.
 
-use std::io::{Write, BufRead, BufReader};
-use std::process::{Command, Stdio};
-
-use super::parsing::{parse_pipeline, parse_command};
+use std::io::{Write, BufRead, BufReader, IsTerminal, Read};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+use super::parsing::{parse_command, ParsedCommand};
+use super::traps;
+use super::scripting::Value;
+use std::collections::HashMap;
 use super::commands::is_stargate_command;
 use super::path::find_in_path;
 use super::jobs::add_background_job;
 use super::builtin_commands;
 use std::path::PathBuf;
+use sgcore::render;
+use sgcore::wire;
 
 // Commands that already consume/produce JSON and shouldn't get -o flag
 const OBJECT_NATIVE_COMMANDS: &[&str] = &[
     "slice-object",
     "dice-object",
+    "where-object",
+    "sort-object",
+    "group-object",
+    "join-object",
+    "format-table",
+    "format-list",
+    "format-tree",
+    "from-csv",
+    "to-csv",
+    "from-tsv",
+    "to-tsv",
+    "from-jsonl",
+    "to-jsonl",
+    "from-text",
+    "diff-object",
 ];
 
 fn is_object_native_command(cmd: &str) -> bool {
     OBJECT_NATIVE_COMMANDS.contains(&cmd)
 }
 
+// Commands that read their stdin through sgcore's json_adapter and so also
+// accept binary stardust; the stage before one of these may write CBOR
+const BINARY_WIRE_CONSUMERS: &[&str] = &[
+    "slice-object",
+    "dice-object",
+    "where-object",
+    "sort-object",
+    "group-object",
+    "join-object",
+    "format-table",
+    "format-list",
+    "format-tree",
+    "to-csv",
+    "to-tsv",
+    "to-jsonl",
+    "diff-object",
+    "hashsum",
+    "md5sum",
+    "sha1sum",
+    "sha224sum",
+    "sha256sum",
+    "sha384sum",
+    "sha512sum",
+    "b2sum",
+    "sort",
+    "uniq",
+    "comm",
+];
+
+fn accepts_binary_wire(cmd: &[String]) -> bool {
+    cmd.first().is_some_and(|name| BINARY_WIRE_CONSUMERS.contains(&name.as_str()))
+}
+
+// Commands that describe their --obj output with --schema; the schema tells
+// auto-rendering which fields hold byte counts and timestamps
+const SCHEMA_COMMANDS: &[&str] = &[
+    "list-directory",
+    "list-logins",
+    "get-fqdn",
+    "scan-wifi",
+    "df",
+    "du",
+    "dd",
+    "split",
+    "csplit",
+    "cksum",
+    "hashsum",
+    "cp",
+    "mv",
+    "rm",
+    "install",
+    "tail",
+    "who",
+    "od",
+];
+
+// Exit status of the most recently finished command ($? in POSIX shells)
+static LAST_EXIT_STATUS: AtomicI32 = AtomicI32::new(0);
+
+pub fn last_exit_status() -> i32 {
+    LAST_EXIT_STATUS.load(Ordering::Relaxed)
+}
+
+pub fn set_last_exit_status(code: i32) {
+    LAST_EXIT_STATUS.store(code, Ordering::Relaxed);
+}
+
+// Strict modes toggled with the `set` builtin
+static ERREXIT: AtomicBool = AtomicBool::new(false);
+static PIPEFAIL: AtomicBool = AtomicBool::new(false);
+
+/// `set -e`: stop a script or command sequence at the first failing command
+pub fn errexit_enabled() -> bool {
+    ERREXIT.load(Ordering::Relaxed)
+}
+
+pub fn set_errexit(enabled: bool) {
+    ERREXIT.store(enabled, Ordering::Relaxed);
+}
+
+/// `set -o pipefail`: a failing stage fails the whole pipeline
+pub fn pipefail_enabled() -> bool {
+    PIPEFAIL.load(Ordering::Relaxed)
+}
+
+pub fn set_pipefail(enabled: bool) {
+    PIPEFAIL.store(enabled, Ordering::Relaxed);
+}
+
+// `set -o table`: render record arrays ending a pipeline as a table (on by default)
+static AUTO_TABLE: AtomicBool = AtomicBool::new(true);
+
+pub fn auto_table_enabled() -> bool {
+    AUTO_TABLE.load(Ordering::Relaxed)
+}
+
+pub fn set_auto_table(enabled: bool) {
+    AUTO_TABLE.store(enabled, Ordering::Relaxed);
+}
+
+// `set -o binary`: pass stardust between stargate stages as CBOR instead of
+// JSON text (on by default); output reaching the terminal is always JSON
+static BINARY_WIRE: AtomicBool = AtomicBool::new(true);
+
+pub fn binary_wire_enabled() -> bool {
+    BINARY_WIRE.load(Ordering::Relaxed)
+}
+
+pub fn set_binary_wire(enabled: bool) {
+    BINARY_WIRE.store(enabled, Ordering::Relaxed);
+}
+
+/// Output shown at the end of an interactive pipeline: an array of records
+/// printed to a terminal becomes a table, anything else is passed through.
+/// Sizes and timestamps are found from the schema of the command that
+/// produced the records.
+fn render_for_terminal(output: String, commands: &[Vec<String>]) -> String {
+    if !auto_table_enabled() || !std::io::stdout().is_terminal() {
+        return output;
+    }
+    match wire::decode(output.as_bytes()) {
+        Ok(value) if render::is_record_array(&value) => {
+            let options = render::RenderOptions {
+                schema: pipeline_schema(commands),
+                ..render::RenderOptions::for_stdout()
+            };
+            render::render_table(value.as_array().map_or(&[], Vec::as_slice), &options)
+        }
+        _ => output,
+    }
+}
+
+/// The `--schema` of the first stage that declares one
+fn pipeline_schema(commands: &[Vec<String>]) -> Option<serde_json::Value> {
+    let producer = commands.iter().find_map(|cmd| {
+        let name = cmd.first()?;
+        if SCHEMA_COMMANDS.contains(&name.as_str()) {
+            return Some(name.clone());
+        }
+        // Full command names are hyphenated; anything else may be an alias
+        if name.contains('-') {
+            return None;
+        }
+        let name = resolve_alias(name);
+        SCHEMA_COMMANDS.contains(&name.as_str()).then_some(name)
+    })?;
+
+    let stargate_bin = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|d| d.join("stargate")))
+        .unwrap_or_else(|| "stargate".into());
+    let output = Command::new(&stargate_bin)
+        .arg(&producer)
+        .arg("--schema")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+/// Record a failure that did not come from a child process (parse errors,
+/// missing commands, script errors) without masking a real exit code
+pub fn record_failure() {
+    if last_exit_status() == 0 {
+        set_last_exit_status(1);
+    }
+}
+
+/// Process exit code to use after a failure: the failing command's status,
+/// or 1 when the failure did not come from a command
+pub fn failure_exit_code() -> i32 {
+    match last_exit_status() {
+        0 => 1,
+        code => code,
+    }
+}
+
+fn command_not_found<T>(name: &str) -> Result<T, String> {
+    set_last_exit_status(127);
+    Err(format!("Command not found: {}", name))
+}
+
+fn command_result(status: ExitStatus, output: String) -> Result<String, String> {
+    // Killed by a signal (e.g. Ctrl-C): report it the way POSIX shells do, as 128 + N
+    if let Some(signal) = traps::terminating_signal(&status) {
+        set_last_exit_status(128 + signal as i32);
+        return Err(format!("Command terminated by {}", traps::signal_name(signal)));
+    }
+
+    let code = status.code().unwrap_or(-1);
+    set_last_exit_status(code);
+    if status.success() {
+        Ok(output)
+    } else {
+        Err(format!("Command failed with exit code: {}", code))
+    }
+}
+
 // Resolve alias to actual command name
 fn resolve_alias(cmd: &str) -> String {
     // This will be resolved via the shared alias system in commands module
@@ -81,15 +304,17 @@ fn get_all_commands() -> Vec<String> {
 
 
 
-pub fn execute_single_command(cmd_parts: &[String]) -> Result<String, String> {
-    execute_single_command_impl(cmd_parts, false)
+/// Run one command; `env` holds its `NAME=value` prefix assignments, which
+/// only the spawned process sees
+pub fn execute_single_command(cmd_parts: &[String], env: &[(String, String)]) -> Result<String, String> {
+    execute_single_command_impl(cmd_parts, env, false)
 }
 
-pub fn execute_single_command_with_obj(cmd_parts: &[String]) -> Result<String, String> {
-    execute_single_command_impl(cmd_parts, true)
+pub fn execute_single_command_with_obj(cmd_parts: &[String], env: &[(String, String)]) -> Result<String, String> {
+    execute_single_command_impl(cmd_parts, env, true)
 }
 
-fn execute_single_command_impl(cmd_parts: &[String], add_obj: bool) -> Result<String, String> {
+fn execute_single_command_impl(cmd_parts: &[String], env: &[(String, String)], add_obj: bool) -> Result<String, String> {
     if cmd_parts.is_empty() {
         return Err("Empty command".to_string());
     }
@@ -99,6 +324,14 @@ fn execute_single_command_impl(cmd_parts: &[String], add_obj: bool) -> Result<St
     if cmd_name == "cd" || cmd_name == "change-directory" {
         return builtin_commands::execute_cd(&cmd_parts[1..]);
     }
+
+    if cmd_name == "set" {
+        return builtin_commands::execute_set(&cmd_parts[1..]);
+    }
+
+    if let Some(result) = execute_dir_stack_builtin(&cmd_name, &cmd_parts[1..], add_obj) {
+        return result;
+    }
     
     // Handle pwd as built-in to reflect actual shell's current directory
     if cmd_name == "pwd" {
@@ -118,22 +351,19 @@ fn execute_single_command_impl(cmd_parts: &[String], add_obj: bool) -> Result<St
     if cmd_parts[0].contains('/') {
         let path = PathBuf::from(&cmd_parts[0]);
         if !path.exists() {
-            return Err(format!("Command not found: {}", cmd_parts[0]));
+            return command_not_found(&cmd_parts[0]);
         }
 
         let status = Command::new(path)
             .args(&cmd_parts[1..])
+            .envs(env_pairs(env))
             .stdin(Stdio::inherit())
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
             .status()
             .map_err(|e| format!("Failed to execute command: {}", e))?;
 
-        if status.success() {
-            return Ok(String::new());
-        } else {
-            return Err(format!("Command failed with exit code: {}", status.code().unwrap_or(-1)));
-        }
+        return command_result(status, String::new());
     }
 
     // Check if this is a stargate command or should use PATH
@@ -156,6 +386,7 @@ fn execute_single_command_impl(cmd_parts: &[String], add_obj: bool) -> Result<St
 
         let mut child = Command::new(&stargate_bin)
             .args(&args)
+            .envs(env_pairs(env))
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
@@ -191,33 +422,36 @@ fn execute_single_command_impl(cmd_parts: &[String], add_obj: bool) -> Result<St
             eprint!("{}", error_output);
         }
 
-        if status.success() {
-            Ok(output)
-        } else {
-            Err(format!("Command failed with exit code: {}", status.code().unwrap_or(-1)))
-        }
+        command_result(status, output)
     } else {
         if let Some(path_cmd) = find_in_path(&cmd_name) {
             let status = Command::new(path_cmd)
                 .args(&cmd_parts[1..])
+                .envs(env_pairs(env))
                 .stdin(Stdio::inherit())
                 .stdout(Stdio::inherit())
                 .stderr(Stdio::inherit())
                 .status()
                 .map_err(|e| format!("Failed to execute command: {}", e))?;
 
-            if status.success() {
-                Ok(String::new())
-            } else {
-                Err(format!("Command failed with exit code: {}", status.code().unwrap_or(-1)))
-            }
+            command_result(status, String::new())
         } else {
-            Err(format!("Command not found: {}", cmd_name))
+            command_not_found(&cmd_name)
         }
     }
 }
 
 pub fn execute_with_object_pipe(cmd_parts: &[String], json_input: Option<&str>, should_output_json: bool) -> Result<String, String> {
+    let (status, output) = execute_stage(cmd_parts, &[], json_input.map(str::as_bytes), should_output_json, false)?;
+    command_result(status, stage_text(output))
+}
+
+/// Run one pipeline stage, returning its exit status together with whatever it
+/// wrote to stdout so callers can decide how a failure affects the pipeline.
+/// With `binary_output` a stargate command writes its stardust as CBOR for
+/// the next stage to read (see [`sgcore::wire`]). `env` holds the stage's
+/// `NAME=value` prefix assignments.
+pub fn execute_stage(cmd_parts: &[String], env: &[(String, String)], input: Option<&[u8]>, should_output_json: bool, binary_output: bool) -> Result<(ExitStatus, Vec<u8>), String> {
     if cmd_parts.is_empty() {
         return Err("Empty command".to_string());
     }
@@ -238,60 +472,12 @@ pub fn execute_with_object_pipe(cmd_parts: &[String], json_input: Option<&str>,
     if cmd_parts[0].contains('/') {
         let path = PathBuf::from(&cmd_parts[0]);
         if !path.exists() {
-            return Err(format!("Command not found: {}", cmd_parts[0]));
-        }
-
-        let mut child = Command::new(path)
-            .args(&cmd_parts[1..])
-            .stdin(if json_input.is_some() { Stdio::piped() } else { Stdio::inherit() })
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("Failed to execute command: {}", e))?;
-
-        // If we have JSON input, write it to stdin
-        if let Some(input) = json_input {
-            if let Some(mut stdin) = child.stdin.take() {
-                stdin.write_all(input.as_bytes())
-                    .map_err(|e| format!("Failed to write to stdin: {}", e))?;
-            }
-        }
-
-        let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
-        let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
-
-        let mut output = String::new();
-        let mut error_output = String::new();
-
-        BufReader::new(stdout)
-            .lines()
-            .for_each(|line| {
-                if let Ok(line) = line {
-                    output.push_str(&line);
-                    output.push('\n');
-                }
-            });
-
-        BufReader::new(stderr)
-            .lines()
-            .for_each(|line| {
-                if let Ok(line) = line {
-                    error_output.push_str(&line);
-                    error_output.push('\n');
-                }
-            });
-
-        let status = child.wait().map_err(|e| format!("Failed to wait for command: {}", e))?;
-
-        if !error_output.is_empty() {
-            eprint!("{}", error_output);
+            return command_not_found(&cmd_parts[0]);
         }
 
-        if status.success() {
-            return Ok(output);
-        } else {
-            return Err(format!("Command failed with exit code: {}", status.code().unwrap_or(-1)));
-        }
+        let mut command = Command::new(path);
+        command.args(&cmd_parts[1..]).envs(env_pairs(env)).env_remove(wire::ENV_WIRE);
+        return run_captured(command, input);
     }
 
     // Automatically add --obj for JSON output in pipelines for stargate commands
@@ -305,127 +491,158 @@ pub fn execute_with_object_pipe(cmd_parts: &[String], json_input: Option<&str>,
 
     // If this is a stargate command -> run the stargate binary; else try PATH
     if is_stargate_command(cmd_name) {
-        let mut child = Command::new(&stargate_bin)
-            .args(&args)
-            .stdin(if json_input.is_some() { Stdio::piped() } else { Stdio::inherit() })
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("Failed to execute command: {}", e))?;
-
-        // If we have JSON input, write it to stdin
-        if let Some(input) = json_input {
-            if let Some(mut stdin) = child.stdin.take() {
-                stdin.write_all(input.as_bytes())
-                    .map_err(|e| format!("Failed to write to stdin: {}", e))?;
-            }
+        let mut command = Command::new(&stargate_bin);
+        command.args(&args).envs(env_pairs(env));
+        if binary_output {
+            command.env(wire::ENV_WIRE, wire::WIRE_CBOR);
+        } else {
+            command.env_remove(wire::ENV_WIRE);
         }
+        run_captured(command, input)
+    } else if let Some(path_cmd) = find_in_path(cmd_name) {
+        // Not a stargate command: try PATH
+        let mut command = Command::new(path_cmd);
+        command.args(&cmd_parts[1..]).envs(env_pairs(env)).env_remove(wire::ENV_WIRE);
+        run_captured(command, input)
+    } else {
+        command_not_found(cmd_name)
+    }
+}
 
-        let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
-        let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+/// Prefix assignments in the form [`Command::envs`] takes
+fn env_pairs(env: &[(String, String)]) -> impl Iterator<Item = (&String, &String)> {
+    env.iter().map(|(name, value)| (name, value))
+}
 
-        let mut output = String::new();
-        let mut error_output = String::new();
+/// Spawn `command`, feed it `input` on stdin and capture stdout; stderr is forwarded
+fn run_captured(mut command: Command, input: Option<&[u8]>) -> Result<(ExitStatus, Vec<u8>), String> {
+    let mut child = command
+        .stdin(if input.is_some() { Stdio::piped() } else { Stdio::inherit() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute command: {}", e))?;
+
+    // If we have JSON input, write it to stdin
+    if let Some(input) = input {
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input)
+                .map_err(|e| format!("Failed to write to stdin: {}", e))?;
+        }
+    }
 
-        BufReader::new(stdout)
-            .lines()
-            .for_each(|line| {
-                if let Ok(line) = line {
-                    output.push_str(&line);
-                    output.push('\n');
-                }
-            });
+    let mut stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
 
-        BufReader::new(stderr)
-            .lines()
-            .for_each(|line| {
-                if let Ok(line) = line {
-                    error_output.push_str(&line);
-                    error_output.push('\n');
-                }
-            });
+    // Raw bytes: the stage may have written binary stardust
+    let mut output = Vec::new();
+    let mut error_output = String::new();
 
-        let status = child.wait().map_err(|e| format!("Failed to wait for command: {}", e))?;
+    stdout.read_to_end(&mut output)
+        .map_err(|e| format!("Failed to read command output: {}", e))?;
 
-        if !error_output.is_empty() {
-            eprint!("{}", error_output);
-        }
-
-        if status.success() {
-            Ok(output)
-        } else {
-            Err(format!("Command failed with exit code: {}", status.code().unwrap_or(-1)))
-        }
-    } else {
-        // Not a stargate command: try PATH
-        if let Some(path_cmd) = find_in_path(cmd_name) {
-            let mut child = Command::new(path_cmd)
-                .args(&cmd_parts[1..])
-                .stdin(if json_input.is_some() { Stdio::piped() } else { Stdio::inherit() })
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-                .map_err(|e| format!("Failed to execute command: {}", e))?;
-
-            // If we have JSON input, write it to stdin
-            if let Some(input) = json_input {
-                if let Some(mut stdin) = child.stdin.take() {
-                    stdin.write_all(input.as_bytes())
-                        .map_err(|e| format!("Failed to write to stdin: {}", e))?;
-                }
+    BufReader::new(stderr)
+        .lines()
+        .for_each(|line| {
+            if let Ok(line) = line {
+                error_output.push_str(&line);
+                error_output.push('\n');
             }
+        });
 
-            let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
-            let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+    let status = child.wait().map_err(|e| format!("Failed to wait for command: {}", e))?;
 
-            let mut output = String::new();
-            let mut error_output = String::new();
+    if !error_output.is_empty() {
+        eprint!("{}", error_output);
+    }
 
-            BufReader::new(stdout)
-                .lines()
-                .for_each(|line| {
-                    if let Ok(line) = line {
-                        output.push_str(&line);
-                        output.push('\n');
-                    }
-                });
+    Ok((status, output))
+}
 
-            BufReader::new(stderr)
-                .lines()
-                .for_each(|line| {
-                    if let Ok(line) = line {
-                        error_output.push_str(&line);
-                        error_output.push('\n');
-                    }
-                });
+/// A stage's output as text for the terminal, a script or a caller: binary
+/// stardust becomes JSON, and the text ends with a newline as lines do
+fn stage_text(output: Vec<u8>) -> String {
+    let mut text = wire::to_json_text(output);
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
 
-            let status = child.wait().map_err(|e| format!("Failed to wait for command: {}", e))?;
+/// pushd, popd and dirs, which may also feed a pipeline with their stardust
+fn execute_dir_stack_builtin(cmd_name: &str, args: &[String], add_obj: bool) -> Option<Result<String, String>> {
+    match cmd_name {
+        "pushd" => Some(builtin_commands::execute_pushd(args, add_obj)),
+        "popd" => Some(builtin_commands::execute_popd(args, add_obj)),
+        "dirs" => Some(builtin_commands::execute_dirs(args, add_obj)),
+        _ => None,
+    }
+}
 
-            if !error_output.is_empty() {
-                eprint!("{}", error_output);
+/// Run the stages of a pipeline, each reading the previous stage's output
+/// unless it has its own here-document. Without `pipefail` only the last
+/// stage's status counts, as in POSIX shells; with it the first failing
+/// stage fails the whole pipeline. A stage killed by a signal always stops it.
+/// Stardust passed from one stargate command to another travels as CBOR.
+fn run_stages(parsed: &ParsedCommand, last_outputs_json: bool) -> Result<String, String> {
+    let commands = &parsed.pipelines;
+    let mut stage_data: Option<Vec<u8>> = None;
+
+    for (idx, cmd) in commands.iter().enumerate() {
+        let is_last = idx == commands.len() - 1;
+        let should_output_json = !is_last || last_outputs_json;
+        let binary_output = !is_last
+            && binary_wire_enabled()
+            && parsed.stdin_inputs[idx + 1].is_none()
+            && accepts_binary_wire(&commands[idx + 1]);
+        // A here-document or here-string replaces the piped input of its stage
+        let stage_input = parsed.stdin_inputs[idx].as_deref().map(str::as_bytes).or(stage_data.as_deref());
+
+        if let Some(result) = cmd.first().and_then(|name| execute_dir_stack_builtin(name, &cmd[1..], should_output_json)) {
+            let output = result?;
+            if is_last {
+                return Ok(output);
             }
+            stage_data = Some(output.into_bytes());
+            continue;
+        }
 
-            if status.success() {
-                Ok(output)
-            } else {
-                Err(format!("Command failed with exit code: {}", status.code().unwrap_or(-1)))
-            }
-        } else {
-            Err(format!("Command not found: {}", cmd_name))
+        let (status, output) = execute_stage(cmd, &parsed.env_overrides[idx], stage_input, should_output_json, binary_output)?;
+        let interrupted = traps::terminating_signal(&status).is_some();
+        if is_last || interrupted || (!status.success() && pipefail_enabled()) {
+            return command_result(status, stage_text(output));
         }
+        stage_data = Some(output);
     }
 
+    Ok(String::new())
+}
+
+/// A command line made only of `NAME=value` words sets them for the session
+fn is_assignment_only(parsed: &ParsedCommand) -> bool {
+    parsed.pipelines.len() == 1 && parsed.pipelines[0].is_empty()
 }
 
 pub fn execute_pipeline(input: &str) -> Result<(), String> {
-    let parsed = parse_command(input);
-    let commands = parsed.pipelines;
-    let is_background = parsed.is_background;
+    execute_pipeline_with_vars(input, None)
+}
+
+/// Execute a command line; `variables` are visible to here-document interpolation
+pub fn execute_pipeline_with_vars(input: &str, variables: Option<&HashMap<String, Value>>) -> Result<(), String> {
+    let parsed = parse_command(input)?.expand(variables)?;
+    let commands = &parsed.pipelines;
     
     if commands.is_empty() {
         return Ok(());
     }
 
+    if is_assignment_only(&parsed) {
+        for (name, value) in &parsed.env_overrides[0] {
+            unsafe { std::env::set_var(name, value); }
+        }
+        return Ok(());
+    }
+
     if commands.len() == 1 {
         let cmd = &commands[0][0];
         if cmd == "list-jobs" {
@@ -439,12 +656,12 @@ pub fn execute_pipeline(input: &str) -> Result<(), String> {
         }
     }
 
-    if is_background {
-        return execute_pipeline_background(input.trim_end_matches('&').trim(), commands);
+    if parsed.is_background {
+        return execute_pipeline_background(input.trim_end_matches('&').trim(), commands, &parsed.env_overrides[0]);
     }
 
-    if commands.len() == 1 {
-        match execute_single_command(&commands[0]) {
+    if commands.len() == 1 && parsed.stdin_inputs[0].is_none() {
+        match execute_single_command(&commands[0], &parsed.env_overrides[0]) {
             Ok(output) => {
                 print!("{}", output);
                 Ok(())
@@ -452,29 +669,13 @@ pub fn execute_pipeline(input: &str) -> Result<(), String> {
             Err(e) => Err(e)
         }
     } else {
-        let mut json_data: Option<String> = None;
-
-        for (idx, cmd) in commands.iter().enumerate() {
-            let is_last = idx == commands.len() - 1;
-            let should_output_json = !is_last;
-            
-            match execute_with_object_pipe(cmd, json_data.as_deref(), should_output_json) {
-                Ok(output) => {
-                    if is_last {
-                        print!("{}", output);
-                    } else {
-                        json_data = Some(output);
-                    }
-                }
-                Err(e) => return Err(e)
-            }
-        }
-
+        let output = run_stages(&parsed, false)?;
+        print!("{}", render_for_terminal(output, commands));
         Ok(())
     }
 }
 
-fn execute_pipeline_background(command_str: &str, commands: Vec<Vec<String>>) -> Result<(), String> {
+fn execute_pipeline_background(command_str: &str, commands: &[Vec<String>], env: &[(String, String)]) -> Result<(), String> {
     if commands.is_empty() {
         return Ok(());
     }
@@ -490,7 +691,7 @@ fn execute_pipeline_background(command_str: &str, commands: Vec<Vec<String>>) ->
 
     let cmd_name = resolve_alias(&cmd_parts[0]);
     
-    if cmd_name == "cd" || cmd_name == "pwd" || cmd_name == "list-jobs" || cmd_name == "foreground-job" || cmd_name == "background-job" {
+    if cmd_name == "cd" || cmd_name == "pwd" || cmd_name == "pushd" || cmd_name == "popd" || cmd_name == "list-jobs" || cmd_name == "foreground-job" || cmd_name == "background-job" {
         return Err(format!("{} cannot run in background", cmd_name));
     }
 
@@ -505,6 +706,7 @@ fn execute_pipeline_background(command_str: &str, commands: Vec<Vec<String>>) ->
         
         Command::new(&stargate_bin)
             .args(&args)
+            .envs(env_pairs(env))
             .stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -514,6 +716,7 @@ fn execute_pipeline_background(command_str: &str, commands: Vec<Vec<String>>) ->
         let path = PathBuf::from(&cmd_parts[0]);
         Command::new(path)
             .args(&cmd_parts[1..])
+            .envs(env_pairs(env))
             .stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -522,13 +725,14 @@ fn execute_pipeline_background(command_str: &str, commands: Vec<Vec<String>>) ->
     } else if let Some(path_cmd) = find_in_path(&cmd_name) {
         Command::new(path_cmd)
             .args(&cmd_parts[1..])
+            .envs(env_pairs(env))
             .stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
             .spawn()
             .map_err(|e| format!("Failed to spawn background process: {}", e))?
     } else {
-        return Err(format!("Command not found: {}", cmd_name));
+        return command_not_found(&cmd_name);
     };
 
     let job_id = add_background_job(command_str.to_string(), child);
@@ -539,38 +743,29 @@ fn execute_pipeline_background(command_str: &str, commands: Vec<Vec<String>>) ->
 
 
 
+/// Run a command line for `$(…)` substitution, capturing stdout of every
+/// command and requesting stardust output from all stargate commands
+pub fn execute_capture_stardust(input: &str) -> Result<String, String> {
+    let parsed = parse_command(input)?.expand(None)?;
+    if parsed.pipelines.is_empty() || is_assignment_only(&parsed) {
+        return Ok(String::new());
+    }
+    run_stages(&parsed, true)
+}
+
 pub fn execute_pipeline_capture(input: &str) -> Result<String, String> {
-    let commands = parse_pipeline(input);
+    let parsed = parse_command(input)?.expand(None)?;
+    let commands = &parsed.pipelines;
     
-    if commands.is_empty() {
+    if commands.is_empty() || is_assignment_only(&parsed) {
         return Ok(String::new());
     }
 
-    if commands.len() == 1 {
+    if commands.len() == 1 && parsed.stdin_inputs[0].is_none() {
         // Single command, no pipe - add --obj for script mode
-        execute_single_command_with_obj(&commands[0])
+        execute_single_command_with_obj(&commands[0], &parsed.env_overrides[0])
     } else {
         // Pipeline
-        let mut json_data: Option<String> = None;
-
-        for (idx, cmd) in commands.iter().enumerate() {
-            let is_last = idx == commands.len() - 1;
-            let should_output_json = !is_last;
-            
-            match execute_with_object_pipe(cmd, json_data.as_deref(), should_output_json) {
-                Ok(output) => {
-                    if is_last {
-                        // Last command, return output
-                        return Ok(output);
-                    } else {
-                        // Intermediate command, store JSON for next
-                        json_data = Some(output);
-                    }
-                }
-                Err(e) => return Err(e)
-            }
-        }
-
-        Ok(String::new())
+        run_stages(&parsed, false)
     }
 }
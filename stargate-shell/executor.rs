@@ -7,20 +7,27 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
-use crate::execution::execute_pipeline;
+use crate::execution::{execute_pipeline_with_vars, record_failure};
+use crate::parsing::{has_heredoc, split_and_chain, subshell_body};
+use crate::shell_state::ShellState;
 use crate::interpreter::{execute_stargate_script, Interpreter};
 use super::command_type::CommandType;
 
 /// Execute command, determining if it's a statement or pipeline
 pub fn execute_command(cmd: &str, interp: Option<&mut Interpreter>, is_interactive: bool) -> bool {
-    if CommandType::is_script_statement(cmd) {
+    if let Some(body) = subshell_body(cmd) {
+        return execute_subshell(body, interp, is_interactive);
+    }
+
+    if !has_heredoc(cmd) && CommandType::is_script_statement(cmd) {
         let script = if cmd.ends_with(';') { cmd.to_string() } else { format!("{};", cmd) };
         execute_script(&script, interp, is_interactive)
     } else {
-        execute_pipeline(cmd)
+        execute_pipeline_with_vars(cmd, interp.map(|i| i.get_variables()))
             .map(|_| true)
             .unwrap_or_else(|e| {
                 eprintln!("Error: {}", e);
+                record_failure();
                 false
             })
     }
@@ -40,35 +47,30 @@ pub fn execute_script(script: &str, interp: Option<&mut Interpreter>, is_interac
         Ok(_) => true,
         Err(e) => {
             eprintln!("Script error: {}", e);
+            record_failure();
             false
         }
     }
 }
 
 /// Execute chained commands separated by &&
-pub fn execute_chained_commands(input: &str, interp: Option<&mut Interpreter>, is_interactive: bool) -> bool {
-    let commands: Vec<&str> = input.split("&&").map(|s| s.trim()).collect();
-    
-    // Handle with interpreter
-    if let Some(interp) = interp {
-        for cmd in commands {
-            if cmd.is_empty() {
-                continue;
-            }
-            if !execute_command(cmd, Some(interp), is_interactive) {
-                return false;
-            }
+pub fn execute_chained_commands(input: &str, mut interp: Option<&mut Interpreter>, is_interactive: bool) -> bool {
+    for cmd in split_and_chain(input) {
+        if cmd.is_empty() {
+            continue;
         }
-    } else {
-        // Handle without interpreter (piped mode)
-        for cmd in commands {
-            if cmd.is_empty() {
-                continue;
-            }
-            if !execute_command(cmd, None, is_interactive) {
-                return false;
-            }
+        if !execute_command(&cmd, interp.as_deref_mut(), is_interactive) {
+            return false;
         }
     }
     true
 }
+
+/// Run `body` in a subshell: changes to the working directory, environment,
+/// directory stack and strict modes are undone when it finishes
+pub fn execute_subshell(body: &str, interp: Option<&mut Interpreter>, is_interactive: bool) -> bool {
+    let state = ShellState::capture();
+    let success = execute_chained_commands(body, interp, is_interactive);
+    state.restore();
+    success
+}
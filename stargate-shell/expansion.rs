@@ -0,0 +1,94 @@
+// Copyright (c) 2025 Dmitry Kalashnikov
+// Dual Licensed: Open-Source (see LICENSE) / Commercial (proprietary use)
+// Commercial use requires a Commercial License. See LICENSE file.
+
+// Command substitution and variable interpolation for command lines
+
+use std::collections::HashMap;
+use serde_json::Value as JsonValue;
+use sgcore::json_adapter::extract_file_paths;
+use super::execution::execute_capture_stardust;
+use super::scripting::Value;
+
+/// Run `command` and turn its output into argument words.
+///
+/// Stargate commands are asked for stardust; file-like records (`entries`,
+/// `files`, `results`, objects with a `path`) become one word per path and
+/// scalars become their text. Anything else is split on whitespace.
+pub fn substitute_command(command: &str) -> Result<Vec<String>, String> {
+    let output = execute_capture_stardust(command.trim())?;
+
+    if let Ok(json) = serde_json::from_str::<JsonValue>(output.trim()) {
+        return Ok(stardust_to_words(&json));
+    }
+
+    Ok(output.split_whitespace().map(|s| s.to_string()).collect())
+}
+
+fn stardust_to_words(value: &JsonValue) -> Vec<String> {
+    match value {
+        JsonValue::Null => Vec::new(),
+        JsonValue::Bool(b) => vec![b.to_string()],
+        JsonValue::Number(n) => vec![n.to_string()],
+        JsonValue::String(s) => vec![s.clone()],
+        JsonValue::Array(items) if items.iter().all(|v| !v.is_object()) => {
+            items.iter().flat_map(stardust_to_words).collect()
+        }
+        _ => extract_file_paths(value)
+            .into_iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect(),
+    }
+}
+
+/// Replace `$name` and `${name}` with script variables, falling back to the
+/// environment. Unknown names expand to the empty string, as in POSIX shells.
+pub fn interpolate(text: &str, variables: Option<&HashMap<String, Value>>) -> String {
+    let lookup = |name: &str| -> String {
+        if let Some(value) = variables.and_then(|vars| vars.get(name)) {
+            return value.to_string();
+        }
+        std::env::var(name).unwrap_or_default()
+    };
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch == '\\' && chars.get(i + 1) == Some(&'$') {
+            result.push('$');
+            i += 2;
+            continue;
+        }
+        if ch != '$' {
+            result.push(ch);
+            i += 1;
+            continue;
+        }
+
+        if chars.get(i + 1) == Some(&'{') {
+            if let Some(len) = chars[i + 2..].iter().position(|&c| c == '}') {
+                let name: String = chars[i + 2..i + 2 + len].iter().collect();
+                result.push_str(&lookup(&name));
+                i += len + 3;
+                continue;
+            }
+        }
+
+        let name: String = chars[i + 1..]
+            .iter()
+            .take_while(|c| c.is_alphanumeric() || **c == '_')
+            .collect();
+        if name.is_empty() {
+            result.push('$');
+            i += 1;
+        } else {
+            i += name.chars().count() + 1;
+            result.push_str(&lookup(&name));
+        }
+    }
+
+    result
+}
@@ -0,0 +1,318 @@
+// Copyright (c) 2025 Dmitry Kalashnikov
+// Dual Licensed: Open-Source (see LICENSE) / Commercial (proprietary use)
+// Commercial use requires a Commercial License. See LICENSE file.
+
+// Structured command history: records, persistence and frecency ranking
+
+use rustyline::{Cmd, ConditionalEventHandler, Event, EventContext, Movement, RepeatCount};
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// A single command executed in the shell
+#[derive(Debug, Clone)]
+pub struct HistoryRecord {
+    pub timestamp: SystemTime,
+    pub command: String,
+    pub cwd: Option<String>,
+    pub exit_status: Option<i32>,
+    pub duration_ms: Option<u64>,
+    pub session: Option<String>,
+    pub hostname: Option<String>,
+}
+
+impl HistoryRecord {
+    pub fn timestamp_unix(&self) -> u64 {
+        self.timestamp
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    fn to_json(&self) -> JsonValue {
+        json!({
+            "ts": self.timestamp_unix(),
+            "cmd": self.command,
+            "cwd": self.cwd,
+            "exit": self.exit_status,
+            "duration_ms": self.duration_ms,
+            "session": self.session,
+            "host": self.hostname,
+        })
+    }
+
+    fn from_json(value: &JsonValue) -> Option<Self> {
+        let secs = value.get("ts")?.as_u64()?;
+        let command = value.get("cmd")?.as_str()?.to_string();
+        let text = |key: &str| value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+
+        Some(Self {
+            timestamp: SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(secs))?,
+            command,
+            cwd: text("cwd"),
+            exit_status: value.get("exit").and_then(|v| v.as_i64()).map(|c| c as i32),
+            duration_ms: value.get("duration_ms").and_then(|v| v.as_u64()),
+            session: text("session"),
+            hostname: text("host"),
+        })
+    }
+
+    /// Parse one history line: a JSON record, or the legacy `timestamp|command` form
+    fn parse_line(line: &str) -> Option<Self> {
+        if line.starts_with('{') {
+            let value: JsonValue = serde_json::from_str(line).ok()?;
+            return Self::from_json(&value);
+        }
+
+        let pos = line.find('|')?;
+        let secs = line[..pos].parse::<u64>().ok()?;
+        Some(Self {
+            timestamp: SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(secs))?,
+            command: line[pos + 1..].to_string(),
+            cwd: None,
+            exit_status: None,
+            duration_ms: None,
+            session: None,
+            hostname: None,
+        })
+    }
+}
+
+/// Identifier shared by every record written from this shell process
+pub fn new_session_id() -> String {
+    let started = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format!("{:x}-{}", started, std::process::id())
+}
+
+pub fn current_hostname() -> Option<String> {
+    hostname::get().ok().map(|h| h.to_string_lossy().into_owned())
+}
+
+pub fn current_dir_string() -> Option<String> {
+    std::env::current_dir().ok().map(|p| p.to_string_lossy().into_owned())
+}
+
+/// Load all history records, skipping lines that cannot be parsed
+pub fn load_history(history_file: &str) -> Vec<HistoryRecord> {
+    let mut history = Vec::new();
+
+    if let Ok(file) = std::fs::File::open(history_file) {
+        let reader = BufReader::new(file);
+
+        for line in reader.lines().map_while(Result::ok) {
+            if let Some(record) = HistoryRecord::parse_line(&line) {
+                history.push(record);
+            }
+        }
+    }
+
+    history
+}
+
+/// Append a record to the history file.
+///
+/// The whole line is written with a single `write` on an `O_APPEND` descriptor,
+/// so records from concurrent shells never interleave.
+pub fn append_history(history_file: &str, record: &HistoryRecord) {
+    if let Ok(mut file) = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_file)
+    {
+        let mut line = record.to_json().to_string();
+        line.push('\n');
+        let _ = file.write_all(line.as_bytes());
+    }
+}
+
+/// Score `candidate` against `query` as a case-insensitive subsequence match.
+/// Returns `None` when the query characters do not all appear in order.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<f64> {
+    if query.is_empty() {
+        return Some(1.0);
+    }
+
+    let query = query.to_lowercase();
+    let candidate = candidate.to_lowercase();
+
+    let mut chars = candidate.chars();
+    for q in query.chars() {
+        chars.find(|&c| c == q)?;
+    }
+
+    let score = if candidate.starts_with(&query) {
+        3.0
+    } else if candidate.contains(&query) {
+        2.0
+    } else {
+        1.0
+    };
+    Some(score)
+}
+
+/// Frecency weight of one execution: recent runs, runs in `cwd` and
+/// successful runs count for more.
+fn frecency_weight(record: &HistoryRecord, now: SystemTime, cwd: Option<&str>) -> f64 {
+    let age = now.duration_since(record.timestamp).unwrap_or_default().as_secs();
+    let mut weight = match age {
+        0..3600 => 4.0,
+        3600..86400 => 2.0,
+        86400..604800 => 1.0,
+        _ => 0.5,
+    };
+
+    if cwd.is_some() && record.cwd.as_deref() == cwd {
+        weight *= 2.0;
+    }
+    if matches!(record.exit_status, Some(code) if code != 0) {
+        weight *= 0.5;
+    }
+    weight
+}
+
+/// Rank distinct commands matching `query` by fuzzy score times frecency in `cwd`
+pub fn rank_by_frecency(records: &[HistoryRecord], query: &str, cwd: Option<&str>) -> Vec<String> {
+    let now = SystemTime::now();
+    let mut scores: HashMap<&str, (f64, SystemTime)> = HashMap::new();
+
+    for record in records {
+        let Some(score) = fuzzy_score(query, &record.command) else {
+            continue;
+        };
+        let entry = scores
+            .entry(record.command.as_str())
+            .or_insert((0.0, record.timestamp));
+        entry.0 += score * frecency_weight(record, now, cwd);
+        entry.1 = entry.1.max(record.timestamp);
+    }
+
+    let mut ranked: Vec<_> = scores.into_iter().collect();
+    ranked.sort_by(|a, b| {
+        b.1.0
+            .partial_cmp(&a.1.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.1.1.cmp(&a.1.1))
+    });
+    ranked.into_iter().map(|(cmd, _)| cmd.to_string()).collect()
+}
+
+#[derive(Default)]
+struct PickerState {
+    candidates: Vec<String>,
+    index: usize,
+}
+
+/// Ctrl-R handler: replaces the line with the best frecency match for the
+/// text typed so far; pressing Ctrl-R again cycles through further matches.
+/// Falls back to the regular reverse search when nothing matches.
+pub struct HistoryPicker {
+    history_file: String,
+    state: Mutex<PickerState>,
+}
+
+impl HistoryPicker {
+    pub fn new(history_file: &str) -> Self {
+        Self {
+            history_file: history_file.to_string(),
+            state: Mutex::new(PickerState::default()),
+        }
+    }
+}
+
+impl ConditionalEventHandler for HistoryPicker {
+    fn handle(&self, _evt: &Event, _n: RepeatCount, _positive: bool, ctx: &EventContext) -> Option<Cmd> {
+        let mut state = self.state.lock().ok()?;
+        let line = ctx.line();
+
+        let is_cycling = state.candidates.get(state.index).is_some_and(|c| c == line);
+        if is_cycling {
+            state.index = (state.index + 1) % state.candidates.len();
+        } else {
+            let records = load_history(&self.history_file);
+            let cwd = current_dir_string();
+            state.candidates = rank_by_frecency(&records, line, cwd.as_deref());
+            state.index = 0;
+        }
+
+        let candidate = state.candidates.get(state.index)?.clone();
+        Some(Cmd::Replace(Movement::WholeLine, Some(candidate)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(command: &str, age_secs: u64, cwd: Option<&str>, exit_status: Option<i32>) -> HistoryRecord {
+        HistoryRecord {
+            timestamp: SystemTime::now() - Duration::from_secs(age_secs),
+            command: command.to_string(),
+            cwd: cwd.map(str::to_string),
+            exit_status,
+            duration_ms: None,
+            session: None,
+            hostname: None,
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "ls"), Some(1.0));
+        assert_eq!(fuzzy_score("git", "git status"), Some(3.0));
+        assert_eq!(fuzzy_score("STAT", "git status"), Some(2.0));
+        assert_eq!(fuzzy_score("gs", "git status"), Some(1.0));
+        assert_eq!(fuzzy_score("sg", "git status"), None);
+        assert_eq!(fuzzy_score("xyz", "git status"), None);
+    }
+
+    #[test]
+    fn test_rank_prefers_recent_and_frequent() {
+        let records = [
+            record("make test", 30 * 86400, None, Some(0)),
+            record("make build", 60, None, Some(0)),
+            record("make lint", 30 * 86400, None, Some(0)),
+            record("make lint", 30 * 86400, None, Some(0)),
+            record("ls", 60, None, Some(0)),
+        ];
+        assert_eq!(rank_by_frecency(&records, "make", None), ["make build", "make lint", "make test"]);
+    }
+
+    #[test]
+    fn test_rank_prefers_cwd_and_success() {
+        let records = [
+            record("cargo build", 60, Some("/other"), Some(0)),
+            record("cargo test", 60, Some("/work"), Some(0)),
+        ];
+        assert_eq!(rank_by_frecency(&records, "cargo", Some("/work")), ["cargo test", "cargo build"]);
+
+        let records = [
+            record("cargo build", 60, None, Some(101)),
+            record("cargo test", 60, None, Some(0)),
+        ];
+        assert_eq!(rank_by_frecency(&records, "cargo", None), ["cargo test", "cargo build"]);
+    }
+
+    #[test]
+    fn test_rank_breaks_ties_by_last_use() {
+        let records = [
+            record("echo a", 600, None, None),
+            record("echo b", 60, None, None),
+        ];
+        assert_eq!(rank_by_frecency(&records, "echo", None), ["echo b", "echo a"]);
+    }
+
+    #[test]
+    fn test_parse_line_rejects_out_of_range_timestamp() {
+        assert!(HistoryRecord::parse_line(&format!("{}|ls", u64::MAX)).is_none());
+        assert!(HistoryRecord::parse_line(&format!("{{\"ts\":{},\"cmd\":\"ls\"}}", u64::MAX)).is_none());
+        let record = HistoryRecord::parse_line("{\"ts\":10,\"cmd\":\"ls\",\"exit\":2}").unwrap();
+        assert_eq!(record.timestamp_unix(), 10);
+        assert_eq!(record.exit_status, Some(2));
+    }
+}
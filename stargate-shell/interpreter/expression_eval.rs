@@ -6,6 +6,8 @@ use super::super::scripting::*;
 use super::super::execution::{execute_pipeline_capture, execute_with_object_pipe};
 use super::Interpreter;
 use super::methods::*;
+use sgcore::field_path::{FieldPath, slice_bounds};
+use sgcore::wire;
 use std::collections::HashMap;
 
 impl Interpreter {
@@ -104,8 +106,9 @@ impl Interpreter {
                 let output = execute_pipeline_capture(&cmd)
                     .map_err(|e| format!("Pipeline error: {}", e))?;
                 
-                // Try to parse as JSON first
-                if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(&output) {
+                // Try to parse as stardust first; JSON lines of records
+                // (tail --obj) become a list
+                if let Ok(json_value) = wire::decode(output.as_bytes()) {
                     Ok(Value::Object(json_value))
                 } else {
                     // Fallback to string
@@ -159,8 +162,9 @@ impl Interpreter {
                 let obj_value = self.eval_expression(*object)?;
                 match obj_value {
                     Value::Object(json_obj) => {
-                        if let Some(value) = json_obj.get(&property) {
-                            Ok(self.json_to_value(value.clone()))
+                        // On an array the property is taken from each element, as `--field` does
+                        if let Some(value) = FieldPath::key(property.as_str()).extract(&json_obj) {
+                            Ok(self.json_to_value(value))
                         } else {
                             Err(format!("Property '{}' not found in object", property))
                         }
@@ -248,6 +252,32 @@ impl Interpreter {
                     _ => Err("Cannot index non-list/non-object value".to_string())
                 }
             }
+            Expression::SliceAccess { object, start, end } => {
+                let obj_value = self.eval_expression(*object)?;
+                let start = match start {
+                    Some(start) => Some(self.eval_expression(*start)?.to_number() as i64),
+                    None => None,
+                };
+                let end = match end {
+                    Some(end) => Some(self.eval_expression(*end)?.to_number() as i64),
+                    None => None,
+                };
+                
+                match obj_value {
+                    Value::List(list) => {
+                        let (start, end) = slice_bounds(start, end, list.len());
+                        Ok(Value::List(list[start..end].to_vec()))
+                    }
+                    Value::Object(serde_json::Value::Array(arr)) => {
+                        let (start, end) = slice_bounds(start, end, arr.len());
+                        Ok(Value::Object(serde_json::Value::Array(arr[start..end].to_vec())))
+                    }
+                    Value::Object(serde_json::Value::Object(map)) if start.is_none() && end.is_none() => {
+                        Ok(Value::Object(serde_json::Value::Array(map.into_iter().map(|(_, v)| v).collect())))
+                    }
+                    _ => Err("Cannot slice non-list/non-array value".to_string())
+                }
+            }
             Expression::MethodCall { object, method, args } => {
                 let obj_value = self.eval_expression(*object)?;
                 
@@ -326,6 +356,10 @@ impl Interpreter {
                     }
                     Value::Object(json_obj) => {
                         // Handle methods on JSON objects
+                        if method == "where" {
+                            return handle_where_method(&json_obj, &args, &mut |expr| self.eval_expression(expr));
+                        }
+
                         // If the object is a JSON array, convert it to a List and handle functional methods
                         if let serde_json::Value::Array(arr) = json_obj {
                             // Convert JSON array to Value::List
@@ -494,7 +528,7 @@ impl Interpreter {
                     .map_err(|e| format!("Pipeline error: {}", e))?;
                 
                 // Try to parse output as JSON
-                if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(&output) {
+                if let Ok(json_value) = wire::decode(output.as_bytes()) {
                     Ok(Value::Object(json_value))
                 } else {
                     Ok(Value::String(output.trim().to_string()))
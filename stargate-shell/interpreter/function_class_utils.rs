@@ -38,6 +38,8 @@ impl Interpreter {
                 
                 return Ok(Value::Object(serde_json::Value::Array(numbers)));
             }
+            "trap" => return self.builtin_trap(arg_values),
+            "on_exit" => return self.builtin_on_exit(arg_values),
             "execute-process" => {
                 if arg_values.is_empty() {
                     return Err("execute-process() expects at least 1 argument (command path)".to_string());
@@ -92,7 +94,7 @@ impl Interpreter {
         self.return_value = None;
         for stmt in body {
             self.execute_statement(stmt)?;
-            if self.return_value.is_some() {
+            if self.return_value.is_some() || self.exit_code.is_some() {
                 break;
             }
         }
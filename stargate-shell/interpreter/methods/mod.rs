@@ -8,6 +8,7 @@ pub mod list_methods;
 pub mod dict_methods;
 pub mod set_methods;
 pub mod string_methods;
+pub mod record_methods;
 
 pub use optional_methods::*;
 pub use ut_methods::*;
@@ -15,3 +16,4 @@ pub use list_methods::*;
 pub use dict_methods::*;
 pub use set_methods::*;
 pub use string_methods::*;
+pub use record_methods::*;
@@ -0,0 +1,28 @@
+// Copyright (c) 2025 Dmitry Kalashnikov
+// Dual Licensed: Open-Source (see LICENSE) / Commercial (proprietary use)
+// Commercial use requires a Commercial License. See LICENSE file.
+
+use super::super::super::scripting::{Expression, Value};
+use sgcore::predicate::Predicate;
+
+/// `records.where("size > 10k and name ~ '\.rs$'")`: keep the records of a
+/// command's stardust output that match a where-object predicate
+pub fn handle_where_method(
+    json: &serde_json::Value,
+    args: &[Expression],
+    eval_fn: &mut dyn FnMut(Expression) -> Result<Value, String>,
+) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!("where() expects 1 argument (predicate), got {}", args.len()));
+    }
+    let expression = match eval_fn(args[0].clone())? {
+        Value::String(s) => s,
+        other => return Err(format!("where() predicate must be a string, got {}", other.to_string())),
+    };
+
+    let predicate = Predicate::parse(&expression)
+        .map_err(|e| format!("where(): {e}"))?;
+    predicate.filter(json, None)
+        .map(Value::Object)
+        .map_err(|e| format!("where(): {e}"))
+}
@@ -15,6 +15,7 @@ mod expression_eval;
 mod function_class_utils;
 mod object_methods;
 mod access_control;
+mod signal_handling;
 
 pub struct Interpreter {
     variables: HashMap<String, Value>,
@@ -29,6 +30,8 @@ pub struct Interpreter {
     current_instance: Option<Value>,
     current_class_context: Option<String>,
     script_path: Option<String>,
+    traps: HashMap<usize, Value>,
+    exit_handlers: Vec<Value>,
 }
 
 impl Interpreter {
@@ -46,6 +49,8 @@ impl Interpreter {
             current_instance: None,
             current_class_context: None,
             script_path: None,
+            traps: HashMap::new(),
+            exit_handlers: Vec::new(),
         }
     }
     pub fn new_with_completion(variable_names: Arc<Mutex<HashSet<String>>>) -> Self {
@@ -62,6 +67,8 @@ impl Interpreter {
             current_instance: None,
             current_class_context: None,
             script_path: None,
+            traps: HashMap::new(),
+            exit_handlers: Vec::new(),
         }
     }
 
@@ -191,6 +198,18 @@ impl Interpreter {
     pub fn get_variables(&self) -> &HashMap<String, Value> {
         &self.variables
     }
+
+    /// Expose command-line arguments as the `args` list and the process
+    /// environment as the `env` dict
+    pub fn set_script_args(&mut self, args: &[String]) {
+        let args = args.iter().cloned().map(Value::String).collect();
+        self.variables.insert("args".to_string(), Value::List(args));
+
+        let env = env::vars()
+            .map(|(key, value)| (Value::String(key), Value::String(value)))
+            .collect();
+        self.variables.insert("env".to_string(), Value::Dict(env));
+    }
     
     fn execute_bytecode(&mut self, statements: Vec<Statement>) -> Result<i32, String> {
         // Compile statements to bytecode
@@ -208,13 +227,19 @@ impl Interpreter {
 
 
 
-pub fn execute_script_with_path(script: &str, path: Option<&str>) -> Result<i32, String> {
+pub fn execute_script_with_path(script: &str, path: Option<&str>, args: &[String]) -> Result<i32, String> {
     let mut parser = Parser::new(script);
     let statements = parser.parse()?;
     let mut interpreter = Interpreter::new();
     interpreter.script_path = path.map(|p| p.to_string());
-    let exit_code = interpreter.execute(statements)?;
-    Ok(exit_code)
+    interpreter.set_script_args(args);
+    let result = interpreter.execute(statements);
+    let exit_code = match &result {
+        Ok(code) => *code,
+        Err(_) => super::execution::failure_exit_code(),
+    };
+    interpreter.run_exit_handlers(exit_code);
+    result
 }
 
 pub fn execute_stargate_script(script: &str, interpreter: &mut Interpreter, is_interactive: bool) -> Result<i32, String> {
@@ -0,0 +1,122 @@
+// Copyright (c) 2025 Dmitry Kalashnikov
+// Dual Licensed: Open-Source (see LICENSE) / Commercial (proprietary use)
+// Commercial use requires a Commercial License. See LICENSE file.
+
+// trap() and on_exit(): script handlers for signals and for exit
+
+use super::super::scripting::*;
+use super::super::execution::set_last_exit_status;
+use super::super::traps::{self, EXIT_TRAP};
+use super::Interpreter;
+
+// Signals that would end the script without running on_exit handlers
+const TERMINATING_SIGNALS: [&str; 3] = ["INT", "TERM", "HUP"];
+
+impl Interpreter {
+    /// `trap(signal, handler)`: call `handler` with the signal name when
+    /// `signal` arrives; `none` restores the default action.
+    /// `trap("EXIT", handler)` is the same as `on_exit(handler)`.
+    pub(super) fn builtin_trap(&mut self, args: Vec<Value>) -> Result<Value, String> {
+        let [signal, handler]: [Value; 2] = args
+            .try_into()
+            .map_err(|args: Vec<Value>| format!("trap() expects 2 arguments (signal, handler), got {}", args.len()))?;
+
+        let name = match signal {
+            Value::String(s) => s,
+            Value::SmallInt(n) => n.to_string(),
+            Value::Number(n) => (n as i64).to_string(),
+            other => return Err(format!("trap() signal must be a name or number, got {}", other.to_string())),
+        };
+
+        if name.eq_ignore_ascii_case(EXIT_TRAP) {
+            return self.builtin_on_exit(vec![handler]);
+        }
+
+        let signal = traps::signal_number(&name)
+            .ok_or_else(|| format!("trap(): unknown signal '{}'", name))?;
+
+        match handler {
+            Value::None => {
+                self.traps.remove(&signal);
+                if !self.needs_catch_for_exit_handlers(signal) {
+                    traps::reset(signal)?;
+                }
+            }
+            Value::Closure { .. } => {
+                traps::catch(signal)?;
+                self.traps.insert(signal, handler);
+            }
+            _ => return Err("trap() handler must be a closure (e.g. sig: cleanup(sig)) or none".to_string()),
+        }
+
+        Ok(Value::None)
+    }
+
+    /// `on_exit(handler)`: call `handler` with the exit code when the script
+    /// ends, including when it is stopped by SIGINT, SIGTERM or SIGHUP.
+    /// `on_exit(none)` removes all exit handlers.
+    pub(super) fn builtin_on_exit(&mut self, args: Vec<Value>) -> Result<Value, String> {
+        let [handler]: [Value; 1] = args
+            .try_into()
+            .map_err(|args: Vec<Value>| format!("on_exit() expects 1 argument (handler), got {}", args.len()))?;
+
+        match handler {
+            Value::None => {
+                self.exit_handlers.clear();
+                for signal in TERMINATING_SIGNALS.iter().filter_map(|name| traps::signal_number(name)) {
+                    if !self.traps.contains_key(&signal) {
+                        traps::reset(signal)?;
+                    }
+                }
+            }
+            Value::Closure { .. } => {
+                for signal in TERMINATING_SIGNALS.iter().filter_map(|name| traps::signal_number(name)) {
+                    traps::catch(signal)?;
+                }
+                self.exit_handlers.push(handler);
+            }
+            _ => return Err("on_exit() handler must be a closure (e.g. code: cleanup(code)) or none".to_string()),
+        }
+
+        Ok(Value::None)
+    }
+
+    fn needs_catch_for_exit_handlers(&self, signal: usize) -> bool {
+        !self.exit_handlers.is_empty()
+            && TERMINATING_SIGNALS.iter().any(|name| traps::signal_number(name) == Some(signal))
+    }
+
+    /// Handle signals caught since the last statement: run the trap handler,
+    /// or stop the script when the signal has none (e.g. Ctrl-C in the REPL)
+    pub(super) fn handle_pending_signals(&mut self) -> Result<(), String> {
+        for signal in traps::take_pending() {
+            let name = traps::signal_name(signal);
+            match self.traps.get(&signal).cloned() {
+                Some(handler) => {
+                    self.apply_closure(handler, vec![Value::String(name)])?;
+                }
+                None => {
+                    set_last_exit_status(128 + signal as i32);
+                    return Err(format!("Interrupted by {}", name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Run on_exit handlers, most recently registered first. Each runs once.
+    pub fn run_exit_handlers(&mut self, exit_code: i32) {
+        let handlers = std::mem::take(&mut self.exit_handlers);
+        // Handlers may call functions, which stop early while an exit is pending
+        let pending_exit = self.exit_code.take();
+
+        for handler in handlers.into_iter().rev() {
+            if let Err(e) = self.apply_closure(handler, vec![Value::SmallInt(exit_code)]) {
+                eprintln!("on_exit handler error: {}", e);
+            }
+            self.exit_code = None;
+        }
+
+        self.exit_code = pending_exit;
+    }
+}
@@ -3,11 +3,14 @@
 // Commercial use requires a Commercial License. See LICENSE file.
 
 use super::super::scripting::*;
-use super::super::execution::execute_pipeline;
+use super::super::execution::{errexit_enabled, execute_pipeline_with_vars, record_failure};
 use super::Interpreter;
 
 impl Interpreter {
     pub(super) fn execute_statement(&mut self, stmt: Statement) -> Result<(), String> {
+        // Signals are handled between statements, so loops stay interruptible
+        self.handle_pending_signals()?;
+
         match stmt {
             Statement::Assignment(name, expr) => {
                 let value = self.eval_expression(expr)?;
@@ -103,14 +106,14 @@ impl Interpreter {
                 if cond_value.to_bool() {
                     for stmt in then_block {
                         self.execute_statement(stmt)?;
-                        if self.return_value.is_some() {
+                        if self.return_value.is_some() || self.exit_code.is_some() {
                             break;
                         }
                     }
                 } else if let Some(else_stmts) = else_block {
                     for stmt in else_stmts {
                         self.execute_statement(stmt)?;
-                        if self.return_value.is_some() {
+                        if self.return_value.is_some() || self.exit_code.is_some() {
                             break;
                         }
                     }
@@ -150,12 +153,12 @@ impl Interpreter {
                                 // Execute loop body
                                 for stmt in &body {
                                     self.execute_statement(stmt.clone())?;
-                                    if self.return_value.is_some() {
+                                    if self.return_value.is_some() || self.exit_code.is_some() {
                                         break;
                                     }
                                 }
                                 
-                                if self.return_value.is_some() {
+                                if self.return_value.is_some() || self.exit_code.is_some() {
                                     break;
                                 }
                             }
@@ -174,12 +177,12 @@ impl Interpreter {
                                 // Execute loop body
                                 for stmt in &body {
                                     self.execute_statement(stmt.clone())?;
-                                    if self.return_value.is_some() {
+                                    if self.return_value.is_some() || self.exit_code.is_some() {
                                         break;
                                     }
                                 }
                                 
-                                if self.return_value.is_some() {
+                                if self.return_value.is_some() || self.exit_code.is_some() {
                                     break;
                                 }
                             }
@@ -208,12 +211,12 @@ impl Interpreter {
                             // Execute loop body
                             for stmt in &body {
                                 self.execute_statement(stmt.clone())?;
-                                if self.return_value.is_some() {
+                                if self.return_value.is_some() || self.exit_code.is_some() {
                                     break;
                                 }
                             }
                             
-                            if self.return_value.is_some() {
+                            if self.return_value.is_some() || self.exit_code.is_some() {
                                 break;
                             }
                         }
@@ -241,7 +244,7 @@ impl Interpreter {
                             // Execute loop body
                             for stmt in &body {
                                 self.execute_statement(stmt.clone())?;
-                                if self.return_value.is_some() {
+                                if self.return_value.is_some() || self.exit_code.is_some() {
                                     early_break = true;
                                     break;
                                 }
@@ -288,12 +291,12 @@ impl Interpreter {
                             // Execute loop body
                             for stmt in &body {
                                 self.execute_statement(stmt.clone())?;
-                                if self.return_value.is_some() {
+                                if self.return_value.is_some() || self.exit_code.is_some() {
                                     break;
                                 }
                             }
                             
-                            if self.return_value.is_some() {
+                            if self.return_value.is_some() || self.exit_code.is_some() {
                                 break;
                             }
                         }
@@ -312,7 +315,7 @@ impl Interpreter {
                     
                     for stmt in &body {
                         self.execute_statement(stmt.clone())?;
-                        if self.return_value.is_some() {
+                        if self.return_value.is_some() || self.exit_code.is_some() {
                             return Ok(());
                         }
                     }
@@ -339,7 +342,12 @@ impl Interpreter {
             }
             Statement::Command(cmd) => {
                 // Execute all commands through execute_pipeline (handles built-ins like cd)
-                if let Err(e) = execute_pipeline(&cmd) {
+                if let Err(e) = execute_pipeline_with_vars(&cmd, Some(&self.variables)) {
+                    record_failure();
+                    // set -e: abort the script; the caller exits with the command's status
+                    if errexit_enabled() {
+                        return Err(format!("Pipeline error: {}", e));
+                    }
                     eprintln!("Pipeline error: {}", e);
                 }
             }
@@ -368,6 +376,7 @@ impl Interpreter {
                 let code = if let Some(expr) = expr_opt {
                     let value = self.eval_expression(expr)?;
                     match value {
+                        Value::SmallInt(n) => n,
                         Value::Number(n) => n as i32,
                         Value::Bool(b) => if b { 0 } else { 1 },  // true = 0 (success), false = 1 (failure)
                         _ => return Err("Exit code must be a number or boolean".to_string()),
@@ -4,33 +4,84 @@
 
 // Pipeline parsing
 
+use std::collections::HashMap;
+use super::expansion::{interpolate, substitute_command};
+use super::scripting::Value;
+
 #[derive(Debug, Clone)]
 pub struct ParsedCommand {
     pub pipelines: Vec<Vec<String>>,
+    /// Here-document / here-string input for each pipeline stage
+    pub stdin_inputs: Vec<Option<String>>,
+    /// `NAME=value` words preceding each stage's command
+    pub env_overrides: Vec<Vec<(String, String)>>,
     pub is_background: bool,
 }
 
-pub fn parse_pipeline(input: &str) -> Vec<Vec<String>> {
-    parse_command(input).pipelines
+/// Piece of a command-line word as written
+#[derive(Debug, Clone)]
+enum WordPart {
+    /// Unquoted or double-quoted text
+    Text(String),
+    /// Single-quoted text, never interpolated
+    Verbatim(String),
+    /// `$(…)` or backtick command; a quoted one stays a single word
+    Substitution { command: String, quoted: bool },
 }
 
-pub fn parse_command(input: &str) -> ParsedCommand {
+type Word = Vec<WordPart>;
+
+/// Input a stage reads instead of the previous stage's output
+#[derive(Debug, Clone)]
+enum StageInput {
+    HereString(Word),
+    HereDoc { text: String, interpolate: bool },
+}
+
+#[derive(Debug, Clone, Default)]
+struct Stage {
+    words: Vec<Word>,
+    input: Option<StageInput>,
+}
+
+/// A command line split into stages and words, before any expansion.
+/// [`CommandLine::expand`] runs substitutions when the command executes.
+#[derive(Debug, Clone)]
+pub struct CommandLine {
+    stages: Vec<Stage>,
+    is_background: bool,
+}
+
+/// Parse a command line, collecting `$(…)`/backtick substitutions,
+/// `<<<` here-strings and `<<EOF` here-documents. Here-document bodies follow
+/// the command on subsequent lines of `input`. Nothing is run here.
+pub fn parse_command(input: &str) -> Result<CommandLine, String> {
     let input = input.trim();
-    
+    let (command_line, body) = match input.split_once('\n') {
+        Some((line, rest)) => (line.trim(), rest),
+        None => (input, ""),
+    };
+    let mut body_lines = body.lines();
+
     // Check if command should run in background
-    let (input, is_background) = if input.ends_with('&') {
-        (input[..input.len() - 1].trim(), true)
+    let (command_line, is_background) = if command_line.ends_with('&') && !command_line.ends_with("&&") {
+        (command_line[..command_line.len() - 1].trim(), true)
     } else {
-        (input, false)
+        (command_line, false)
     };
 
-    let mut pipelines = Vec::new();
-    let mut current_cmd = Vec::new();
-    let mut current_arg = String::new();
+    let mut stages = Vec::new();
+    let mut current = Stage::default();
+    let mut current_word = Word::new();
+    let mut here_string = false;
     let mut in_quotes = false;
     let mut quote_char = ' ';
 
-    for ch in input.chars() {
+    let chars: Vec<char> = command_line.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
         match ch {
             '"' | '\'' if !in_quotes => {
                 in_quotes = true;
@@ -39,37 +90,338 @@ pub fn parse_command(input: &str) -> ParsedCommand {
             '"' | '\'' if in_quotes && ch == quote_char => {
                 in_quotes = false;
             }
-            '|' if !in_quotes => {
-                if !current_arg.is_empty() {
-                    current_cmd.push(current_arg.clone());
-                    current_arg.clear();
+            '$' if chars.get(i + 1) == Some(&'(') && !(in_quotes && quote_char == '\'') => {
+                let end = find_closing_paren(&chars, i + 1)
+                    .ok_or_else(|| "Unterminated command substitution '$('".to_string())?;
+                let command: String = chars[i + 2..end].iter().collect();
+                current_word.push(WordPart::Substitution { command, quoted: in_quotes });
+                i = end;
+            }
+            '`' if !(in_quotes && quote_char == '\'') => {
+                let end = chars[i + 1..].iter().position(|&c| c == '`')
+                    .map(|p| i + 1 + p)
+                    .ok_or_else(|| "Unterminated command substitution '`'".to_string())?;
+                let command: String = chars[i + 1..end].iter().collect();
+                current_word.push(WordPart::Substitution { command, quoted: in_quotes });
+                i = end;
+            }
+            '<' if !in_quotes && chars.get(i + 1) == Some(&'<') => {
+                finish_word(&mut current_word, &mut current, &mut here_string);
+                if chars.get(i + 2) == Some(&'<') {
+                    // Here-string: <<< word, taken as the next word
+                    here_string = true;
+                    i += 3;
+                } else {
+                    // Here-document: <<DELIM, <<-DELIM or <<'DELIM'
+                    let strip_tabs = chars.get(i + 2) == Some(&'-');
+                    let start = if strip_tabs { i + 3 } else { i + 2 };
+                    let (delimiter, quoted, next) = read_word(&chars, start);
+                    if delimiter.is_empty() {
+                        return Err("Missing here-document delimiter after '<<'".to_string());
+                    }
+                    let text = read_heredoc(&mut body_lines, &delimiter, strip_tabs)?;
+                    current.input = Some(StageInput::HereDoc { text, interpolate: !quoted });
+                    i = next;
                 }
-                if !current_cmd.is_empty() {
-                    pipelines.push(current_cmd.clone());
-                    current_cmd.clear();
+                continue;
+            }
+            '|' if !in_quotes => {
+                finish_word(&mut current_word, &mut current, &mut here_string);
+                if !current.words.is_empty() {
+                    stages.push(std::mem::take(&mut current));
                 }
             }
             ' ' | '\t' if !in_quotes => {
-                if !current_arg.is_empty() {
-                    current_cmd.push(current_arg.clone());
-                    current_arg.clear();
+                finish_word(&mut current_word, &mut current, &mut here_string);
+            }
+            _ => push_char(&mut current_word, ch, in_quotes && quote_char == '\''),
+        }
+        i += 1;
+    }
+
+    finish_word(&mut current_word, &mut current, &mut here_string);
+    if here_string {
+        return Err("Missing here-string word after '<<<'".to_string());
+    }
+    if !current.words.is_empty() {
+        stages.push(current);
+    }
+
+    Ok(CommandLine { stages, is_background })
+}
+
+impl CommandLine {
+    /// Run the command substitutions and interpolate here-strings and
+    /// unquoted here-documents with `$name`/`${name}` from `variables` or
+    /// the environment, giving the words each stage runs with
+    pub fn expand(&self, variables: Option<&HashMap<String, Value>>) -> Result<ParsedCommand, String> {
+        let mut pipelines = Vec::new();
+        let mut stdin_inputs = Vec::new();
+        let mut env_overrides = Vec::new();
+
+        for stage in &self.stages {
+            let mut cmd = Vec::new();
+            for word in &stage.words {
+                cmd.extend(expand_word(word)?);
+            }
+            let input = match &stage.input {
+                Some(StageInput::HereString(word)) => Some(format!("{}\n", expand_here_string(word, variables)?)),
+                Some(StageInput::HereDoc { text, interpolate: true }) => Some(interpolate(text, variables)),
+                Some(StageInput::HereDoc { text, interpolate: false }) => Some(text.clone()),
+                None => None,
+            };
+            env_overrides.push(take_env_assignments(&mut cmd));
+            pipelines.push(cmd);
+            stdin_inputs.push(input);
+        }
+
+        Ok(ParsedCommand {
+            pipelines,
+            stdin_inputs,
+            env_overrides,
+            is_background: self.is_background,
+        })
+    }
+}
+
+/// Move a finished word to the pending here-string, or else to the stage
+fn finish_word(word: &mut Word, stage: &mut Stage, here_string: &mut bool) {
+    if word.is_empty() {
+        return;
+    }
+    let word = std::mem::take(word);
+    if std::mem::take(here_string) {
+        stage.input = Some(StageInput::HereString(word));
+    } else {
+        stage.words.push(word);
+    }
+}
+
+fn push_char(word: &mut Word, ch: char, verbatim: bool) {
+    match (word.last_mut(), verbatim) {
+        (Some(WordPart::Text(text)), false) | (Some(WordPart::Verbatim(text)), true) => text.push(ch),
+        (_, false) => word.push(WordPart::Text(ch.to_string())),
+        (_, true) => word.push(WordPart::Verbatim(ch.to_string())),
+    }
+}
+
+/// Expand one word into arguments. Quoted substitutions stay in the word;
+/// unquoted ones are split into words.
+fn expand_word(word: &Word) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+
+    for part in word {
+        match part {
+            WordPart::Text(text) | WordPart::Verbatim(text) => current.push_str(text),
+            WordPart::Substitution { command, quoted: true } => {
+                current.push_str(&substitute_command(command)?.join(" "));
+            }
+            WordPart::Substitution { command, quoted: false } => {
+                let mut words = substitute_command(command)?.into_iter();
+                if let Some(first) = words.next() {
+                    current.push_str(&first);
+                }
+                for word in words {
+                    args.push(std::mem::replace(&mut current, word));
                 }
             }
-            _ => {
-                current_arg.push(ch);
+        }
+    }
+
+    if !current.is_empty() {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// The text of a here-string: one word, interpolated outside single quotes
+fn expand_here_string(word: &Word, variables: Option<&HashMap<String, Value>>) -> Result<String, String> {
+    let mut text = String::new();
+    for part in word {
+        match part {
+            WordPart::Text(part) => text.push_str(&interpolate(part, variables)),
+            WordPart::Verbatim(part) => text.push_str(part),
+            WordPart::Substitution { command, .. } => text.push_str(&substitute_command(command)?.join(" ")),
+        }
+    }
+    Ok(text)
+}
+
+/// Whether `word` has the form `NAME=value` of an environment assignment
+fn is_env_assignment(word: &str) -> bool {
+    match word.split_once('=') {
+        Some((name, _)) => {
+            name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+/// Whether `input` runs a command with `NAME=value` prefix assignments, as
+/// in `FOO=1 make`: leading assignments followed by a command word
+pub fn has_prefix_assignments(input: &str) -> bool {
+    let mut words = input.split_whitespace();
+    words.next().is_some_and(is_env_assignment) && words.any(|word| !is_env_assignment(word))
+}
+
+/// Remove leading `NAME=value` words from a stage. A stage made only of
+/// assignments keeps no command words.
+fn take_env_assignments(cmd: &mut Vec<String>) -> Vec<(String, String)> {
+    let count = cmd.iter().take_while(|word| is_env_assignment(word)).count();
+    cmd.drain(..count)
+        .filter_map(|word| word.split_once('=').map(|(name, value)| (name.to_string(), value.to_string())))
+        .collect()
+}
+
+/// Split a command line on `&&` outside quotes and parentheses
+pub fn split_and_chain(input: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quote_char: Option<char> = None;
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match quote_char {
+            Some(q) if ch == q => quote_char = None,
+            Some(_) => {}
+            None => match ch {
+                '"' | '\'' => quote_char = Some(ch),
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                '&' if depth == 0 && chars.peek() == Some(&'&') => {
+                    chars.next();
+                    parts.push(std::mem::take(&mut current).trim().to_string());
+                    continue;
+                }
+                _ => {}
+            },
+        }
+        current.push(ch);
+    }
+    parts.push(current.trim().to_string());
+
+    parts
+}
+
+/// Body of a subshell `( ... )` when the parentheses enclose the whole input
+pub fn subshell_body(input: &str) -> Option<&str> {
+    let input = input.trim();
+    if !input.starts_with('(') {
+        return None;
+    }
+    let chars: Vec<char> = input.chars().collect();
+    let end = find_closing_paren(&chars, 0)?;
+    if end != chars.len() - 1 {
+        return None;
+    }
+    Some(input[1..input.len() - 1].trim())
+}
+
+/// Delimiters of here-documents opened on `line`, in order. Used by the REPL
+/// and line-based script modes to know how many body lines to collect.
+pub fn heredoc_delimiters(line: &str) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut delimiters = Vec::new();
+    let mut in_quotes = false;
+    let mut quote_char = ' ';
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        if (ch == '"' || ch == '\'') && (!in_quotes || ch == quote_char) {
+            in_quotes = !in_quotes;
+            quote_char = ch;
+        } else if !in_quotes && ch == '<' && chars.get(i + 1) == Some(&'<') {
+            if chars.get(i + 2) == Some(&'<') {
+                i += 3;
+                continue;
             }
+            let start = if chars.get(i + 2) == Some(&'-') { i + 3 } else { i + 2 };
+            let (delimiter, _, next) = read_word(&chars, start);
+            if !delimiter.is_empty() {
+                delimiters.push(delimiter);
+            }
+            i = next;
+            continue;
         }
+        i += 1;
+    }
+
+    delimiters
+}
+
+/// Whether `input` is a command line carrying here-document bodies
+pub fn has_heredoc(input: &str) -> bool {
+    input.lines().next().is_some_and(|line| !heredoc_delimiters(line).is_empty())
+}
+
+/// Read one (possibly quoted) word starting at `start`, skipping leading blanks.
+/// Returns the word, whether any part of it was quoted, and the index after it.
+fn read_word(chars: &[char], start: usize) -> (String, bool, usize) {
+    let mut i = start;
+    while i < chars.len() && (chars[i] == ' ' || chars[i] == '\t') {
+        i += 1;
     }
 
-    if !current_arg.is_empty() {
-        current_cmd.push(current_arg);
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut quote_char: Option<char> = None;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        match quote_char {
+            Some(q) if ch == q => quote_char = None,
+            Some(_) => word.push(ch),
+            None if ch == '"' || ch == '\'' => {
+                quote_char = Some(ch);
+                quoted = true;
+            }
+            None if ch == ' ' || ch == '\t' || ch == '|' || ch == '<' => break,
+            None => word.push(ch),
+        }
+        i += 1;
     }
-    if !current_cmd.is_empty() {
-        pipelines.push(current_cmd);
+
+    (word, quoted, i)
+}
+
+fn read_heredoc<'a>(lines: &mut impl Iterator<Item = &'a str>, delimiter: &str, strip_tabs: bool) -> Result<String, String> {
+    let mut doc = String::new();
+    for line in lines {
+        let line = if strip_tabs { line.trim_start_matches('\t') } else { line };
+        if line.trim_end() == delimiter {
+            return Ok(doc);
+        }
+        doc.push_str(line);
+        doc.push('\n');
     }
+    Err(format!("Here-document not terminated (wanted '{}')", delimiter))
+}
 
-    ParsedCommand {
-        pipelines,
-        is_background,
+/// Index of the ')' matching the '(' at `open`, honouring nesting and quotes
+fn find_closing_paren(chars: &[char], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote_char: Option<char> = None;
+
+    for (i, &ch) in chars.iter().enumerate().skip(open) {
+        match quote_char {
+            Some(q) if ch == q => quote_char = None,
+            Some(_) => {}
+            None => match ch {
+                '"' | '\'' => quote_char = Some(ch),
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            },
+        }
     }
+    None
 }
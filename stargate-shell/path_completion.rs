@@ -6,7 +6,7 @@ use rustyline::completion::Pair;
 use std::fs;
 use std::path::Path;
 
-pub const DIRECTORY_COMMANDS: &[&str] = &["cd", "change-directory"];
+pub const DIRECTORY_COMMANDS: &[&str] = &["cd", "change-directory", "pushd"];
 
 pub const COMMANDS: &[&str] = &[
     "get-contents", "cat", "create-file", "touch", "remove-file", "rm",
@@ -7,10 +7,11 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
-use crate::execution::execute_pipeline;
+use crate::execution::{errexit_enabled, execute_pipeline, failure_exit_code, last_exit_status};
 use crate::interpreter::{execute_stargate_script, Interpreter};
 use super::command_type::CommandType;
 use super::executor::{execute_command, execute_chained_commands};
+use super::parsing::heredoc_delimiters;
 
 /// Skip shebang line if present
 pub fn skip_shebang(contents: &str) -> String {
@@ -22,56 +23,68 @@ pub fn skip_shebang(contents: &str) -> String {
 }
 
 /// Handle piped input (stdin)
-pub fn handle_piped_input() {
+pub fn handle_piped_input(args: &[String]) {
     use std::io::Read;
     let mut script_code = String::new();
     if std::io::stdin().read_to_string(&mut script_code).is_err() {
         return;
     }
 
-    let script_code = skip_shebang(&script_code);
+    handle_command_string(&script_code, args);
+}
+
+/// Execute shell input given as a string (piped stdin or `-c`) and exit
+pub fn handle_command_string(script_code: &str, args: &[String]) {
+    let script_code = skip_shebang(script_code);
     let trimmed = script_code.trim();
     
     // Single-line command without semicolons
     if !trimmed.contains('\n') && !trimmed.contains(';') {
-        handle_single_line_piped(trimmed);
+        handle_single_line_piped(trimmed, args);
     } 
     // Multi-line input without semicolons - execute line by line
     else if !trimmed.contains(';') && trimmed.contains('\n') {
-        handle_multiline_piped(trimmed);
+        handle_multiline_piped(trimmed, args);
     } 
     // Script with semicolons
     else {
         let mut interp = Interpreter::new();
-        match execute_stargate_script(&script_code, &mut interp, false) {
-            Ok(exit_code) => std::process::exit(exit_code),
+        interp.set_script_args(args);
+        let exit_code = match execute_stargate_script(&script_code, &mut interp, false) {
+            Ok(exit_code) => exit_code,
             Err(e) => {
                 eprintln!("Script error: {}", e);
-                std::process::exit(1);
+                failure_exit_code()
             }
-        }
+        };
+        interp.run_exit_handlers(exit_code);
+        std::process::exit(exit_code);
     }
 }
 
 /// Handle single-line piped input
-fn handle_single_line_piped(input: &str) {
+fn handle_single_line_piped(input: &str, args: &[String]) {
     let cmd_type = CommandType::detect(input);
     
     let exit_code = match cmd_type {
         CommandType::PropertyAccess => {
             let mut interp = Interpreter::new();
+            interp.set_script_args(args);
             execute_stargate_script(&format!("print {};", input), &mut interp, false)
-                .unwrap_or(1)
+                .unwrap_or_else(|e| {
+                    eprintln!("Script error: {}", e);
+                    failure_exit_code()
+                })
         }
-        CommandType::ChainedCommands => {
-            if execute_chained_commands(input, None, false) { 0 } else { 1 }
+        CommandType::ChainedCommands | CommandType::Subshell => {
+            if execute_chained_commands(input, None, false) { 0 } else { failure_exit_code() }
         }
         _ => {
             execute_pipeline(input)
                 .map(|_| 0)
                 .unwrap_or_else(|e| {
                     eprintln!("Error: {}", e);
-                    1
+                    failure_exit_code()
                 })
         }
     };
@@ -80,11 +93,12 @@ fn handle_single_line_piped(input: &str) {
 }
 
 /// Handle multi-line piped input
-fn handle_multiline_piped(input: &str) {
-    let mut exit_code = 0;
+fn handle_multiline_piped(input: &str, args: &[String]) {
     let mut interp = Interpreter::new();
+    interp.set_script_args(args);
+    let mut lines = input.lines();
     
-    for line in input.lines() {
+    while let Some(line) = lines.next() {
         let line = line.trim();
         if line.is_empty() || line.starts_with('#') {
             continue;
@@ -92,17 +106,50 @@ fn handle_multiline_piped(input: &str) {
         if line == "exit" || line == "quit" {
             break;
         }
+
+        let delimiters = heredoc_delimiters(line);
+        if !delimiters.is_empty() {
+            let command = collect_heredoc_lines(line, &delimiters, &mut lines);
+            if !execute_command(&command, Some(&mut interp), false) && errexit_enabled() {
+                exit_with_handlers(&mut interp, failure_exit_code());
+            }
+            continue;
+        }
         
         let cmd_type = CommandType::detect(line);
         let success = match cmd_type {
-            CommandType::ChainedCommands => execute_chained_commands(line, Some(&mut interp), false),
+            CommandType::ChainedCommands | CommandType::Subshell => execute_chained_commands(line, Some(&mut interp), false),
             _ => execute_command(line, Some(&mut interp), false),
         };
         
-        if !success {
-            exit_code = 1;
+        // set -e: stop at the first failing line
+        if !success && errexit_enabled() {
+            exit_with_handlers(&mut interp, failure_exit_code());
         }
     }
     
+    // Like POSIX shells, the status of the last command is the exit status
+    exit_with_handlers(&mut interp, last_exit_status());
+}
+
+/// Run the script's on_exit handlers, then exit
+fn exit_with_handlers(interp: &mut Interpreter, exit_code: i32) -> ! {
+    interp.run_exit_handlers(exit_code);
     std::process::exit(exit_code);
 }
+
+/// Join a here-document command line with its body lines
+fn collect_heredoc_lines<'a>(first_line: &str, delimiters: &[String], lines: &mut impl Iterator<Item = &'a str>) -> String {
+    let mut command = vec![first_line.to_string()];
+
+    for delimiter in delimiters {
+        for line in lines.by_ref() {
+            command.push(line.to_string());
+            if line.trim_start_matches('\t').trim_end() == delimiter {
+                break;
+            }
+        }
+    }
+
+    command.join("\n")
+}
@@ -10,7 +10,8 @@
 use rustyline::Editor;
 use std::sync::{Arc, Mutex};
 use crate::ui::{describe_command, print_help};
-use crate::execution::execute_pipeline;
+use crate::execution::{execute_pipeline_with_vars, record_failure};
+use crate::parsing::{has_heredoc, has_prefix_assignments, heredoc_delimiters};
 use crate::interpreter::{execute_stargate_script, Interpreter};
 use crate::builtin_commands;
 use super::command_type::CommandType;
@@ -40,6 +41,7 @@ where
             let args = if input == "list-history" { "" } else { &input[13..] };
             if let Err(e) = builtin_commands::execute_list_history(args, history_file) {
                 eprintln!("Error: {}", e);
+                record_failure();
             }
             return true;
         }
@@ -137,22 +139,63 @@ where
     lines.join("\n")
 }
 
+/// Append here-document bodies to a command line read from the REPL,
+/// prompting until every delimiter opened on the first line has been seen
+pub fn collect_heredoc_input<H>(rl: &mut Editor<H, rustyline::history::DefaultHistory>, input: &str) -> String
+where
+    H: rustyline::Helper,
+{
+    let mut lines = vec![input.to_string()];
+
+    for delimiter in heredoc_delimiters(input) {
+        loop {
+            match rl.readline("> ") {
+                Ok(line) => {
+                    let is_end = line.trim_start_matches('\t').trim_end() == delimiter;
+                    lines.push(line);
+                    if is_end {
+                        break;
+                    }
+                }
+                Err(_) => {
+                    eprintln!("Input interrupted");
+                    return lines.join("\n");
+                }
+            }
+        }
+    }
+
+    lines.join("\n")
+}
+
 /// Execute script with locked interpreter
 pub fn execute_with_interpreter(script: &str, interpreter: &Arc<Mutex<Interpreter>>) {
     if let Ok(mut interp) = interpreter.lock() {
         if let Err(e) = execute_stargate_script(script, &mut interp, true) {
             eprintln!("Script error: {}", e);
+            record_failure();
         }
     }
 }
 
 /// Handle general input (statements, expressions, pipelines)
 fn handle_general_input(input: &str, interpreter: &Arc<Mutex<Interpreter>>) {
+    if has_heredoc(input) {
+        run_pipeline_with_interpreter(input, interpreter);
+        return;
+    }
+
+    let first_word = input.split_whitespace().next().unwrap_or("");
     let is_builtin_command = input.starts_with("cd ") || input.starts_with("change-directory ") || 
                               input == "list-variables" || input.starts_with("list-variables ");
     let is_statement = CommandType::is_script_statement(input) || input.ends_with(';') || is_builtin_command;
+
+    // Directory-stack builtins, `set` and commands run with `NAME=value` prefixes are command lines, never script
+    if matches!(first_word, "pushd" | "popd" | "dirs" | "set") || (!is_statement && has_prefix_assignments(input)) {
+        run_pipeline_with_interpreter(input, interpreter);
+        return;
+    }
     
-    let first_word = input.split_whitespace().next().unwrap_or("");
     use super::path::find_in_path;
     let is_external_command = !first_word.is_empty() && find_in_path(first_word).is_some();
     
@@ -169,7 +212,19 @@ fn handle_general_input(input: &str, interpreter: &Arc<Mutex<Interpreter>>) {
         };
         
         execute_with_interpreter(&script_code, interpreter);
-    } else if let Err(e) = execute_pipeline(input) {
+    } else {
+        run_pipeline_with_interpreter(input, interpreter);
+    }
+}
+
+/// Execute a command line with the interpreter's variables visible to here-documents
+fn run_pipeline_with_interpreter(input: &str, interpreter: &Arc<Mutex<Interpreter>>) {
+    let result = match interpreter.lock() {
+        Ok(interp) => execute_pipeline_with_vars(input, Some(interp.get_variables())),
+        Err(_) => execute_pipeline_with_vars(input, None),
+    };
+    if let Err(e) = result {
         eprintln!("Error: {}", e);
+        record_failure();
     }
 }
@@ -105,6 +105,12 @@ pub enum Expression {
         object: Box<Expression>,
         index: Box<Expression>,
     },
+    // `[start:end]` with either bound optional; `[*]` has neither
+    SliceAccess {
+        object: Box<Expression>,
+        start: Option<Box<Expression>>,
+        end: Option<Box<Expression>>,
+    },
     Pipeline {
         input: Box<Expression>,
         command: String,
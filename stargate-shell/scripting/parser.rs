@@ -756,12 +756,47 @@ impl Parser {
                 }
                 Some("[") => {
                     self.advance(); // consume '['
-                    let index = self.parse_expression()?;
-                    self.expect("]")?;
-                    expr = Expression::IndexAccess {
-                        object: Box::new(expr),
-                        index: Box::new(index),
+                    
+                    // `[*]` selects every element
+                    if self.peek().map(|s| s.as_str()) == Some("*")
+                        && self.tokens.get(self.pos + 1).map(|s| s.as_str()) == Some("]")
+                    {
+                        self.advance();
+                        self.advance();
+                        expr = Expression::SliceAccess {
+                            object: Box::new(expr),
+                            start: None,
+                            end: None,
+                        };
+                        continue;
+                    }
+                    
+                    let start = if self.peek().map(|s| s.as_str()) == Some(":") {
+                        None
+                    } else {
+                        Some(Box::new(self.parse_expression()?))
                     };
+                    
+                    if self.peek().map(|s| s.as_str()) == Some(":") {
+                        self.advance(); // consume ':'
+                        let end = if self.peek().map(|s| s.as_str()) == Some("]") {
+                            None
+                        } else {
+                            Some(Box::new(self.parse_expression()?))
+                        };
+                        self.expect("]")?;
+                        expr = Expression::SliceAccess {
+                            object: Box::new(expr),
+                            start,
+                            end,
+                        };
+                    } else {
+                        self.expect("]")?;
+                        expr = Expression::IndexAccess {
+                            object: Box::new(expr),
+                            index: start.ok_or("Expected index after '['")?,
+                        };
+                    }
                 }
                 _ => break,
             }
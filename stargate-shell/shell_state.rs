@@ -0,0 +1,65 @@
+// Copyright (c) 2025 Dmitry Kalashnikov
+// Dual Licensed: Open-Source (see LICENSE) / Commercial (proprietary use)
+// Commercial use requires a Commercial License. See LICENSE file.
+
+// Process-wide shell state that a subshell ( ... ) may change and has to
+// give back when it finishes
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+use crate::builtin_commands::dirs::{directory_stack, set_directory_stack};
+use crate::execution::{
+    auto_table_enabled, binary_wire_enabled, errexit_enabled, pipefail_enabled, set_auto_table,
+    set_binary_wire, set_errexit, set_pipefail,
+};
+
+/// Snapshot of everything a subshell may change: working directory,
+/// environment, directory stack and strict modes
+pub struct ShellState {
+    cwd: Option<PathBuf>,
+    env: Vec<(OsString, OsString)>,
+    dir_stack: Vec<PathBuf>,
+    errexit: bool,
+    pipefail: bool,
+    auto_table: bool,
+    binary_wire: bool,
+}
+
+impl ShellState {
+    pub fn capture() -> Self {
+        Self {
+            cwd: std::env::current_dir().ok(),
+            env: std::env::vars_os().collect(),
+            dir_stack: directory_stack(),
+            errexit: errexit_enabled(),
+            pipefail: pipefail_enabled(),
+            auto_table: auto_table_enabled(),
+            binary_wire: binary_wire_enabled(),
+        }
+    }
+
+    pub fn restore(self) {
+        for (name, _) in std::env::vars_os() {
+            if !self.env.iter().any(|(saved, _)| *saved == name) {
+                unsafe { std::env::remove_var(&name); }
+            }
+        }
+        for (name, value) in &self.env {
+            if std::env::var_os(name).as_ref() != Some(value) {
+                unsafe { std::env::set_var(name, value); }
+            }
+        }
+
+        if let Some(cwd) = &self.cwd {
+            if let Err(e) = std::env::set_current_dir(cwd) {
+                eprintln!("Warning: could not return to {}: {}", cwd.display(), e);
+            }
+        }
+
+        set_directory_stack(self.dir_stack);
+        set_errexit(self.errexit);
+        set_pipefail(self.pipefail);
+        set_auto_table(self.auto_table);
+        set_binary_wire(self.binary_wire);
+    }
+}
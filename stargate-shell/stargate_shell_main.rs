@@ -7,6 +7,7 @@ mod commands;
 mod completion;
 mod execution;
 mod parsing;
+mod expansion;
 mod path;
 mod path_completion;
 mod scripting;
@@ -20,6 +21,9 @@ mod command_type;
 mod executor;
 mod piped_input;
 mod repl_handlers;
+mod history;
+mod shell_state;
+mod traps;
 
 #[path = "builtin-commands/mod.rs"]
 mod builtin_commands;
@@ -29,17 +33,18 @@ use rustyline::{Editor, Config, CompletionType, KeyEvent, ExternalPrinter};
 use rustyline::config::EditMode;
 use std::sync::{Arc, Mutex};
 use std::collections::HashSet;
-use std::io::{IsTerminal, Write};
-use std::fs::OpenOptions;
-use std::time::SystemTime;
+use std::io::IsTerminal;
+use std::time::{Instant, SystemTime};
 
 use completion::StargateCompletion;
 use interpreter::{execute_script_with_path, Interpreter};
 use jobs::start_job_monitor;
 use command_type::CommandType;
 use executor::execute_chained_commands;
-use piped_input::{handle_piped_input, skip_shebang};
-use repl_handlers::handle_repl_command;
+use execution::{failure_exit_code, last_exit_status, set_last_exit_status};
+use history::{HistoryPicker, HistoryRecord};
+use piped_input::{handle_command_string, handle_piped_input, skip_shebang};
+use repl_handlers::{collect_heredoc_input, handle_repl_command};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -59,14 +64,23 @@ fn main() {
         std::process::exit(0);
     }
     
+    // -c CODE [ARGS...]: execute the given code and exit
+    if args.len() > 1 && args[1] == "-c" {
+        let Some(code) = args.get(2) else {
+            eprintln!("stargate-shell: -c: option requires an argument");
+            std::process::exit(2);
+        };
+        handle_command_string(code, args.get(3..).unwrap_or(&[]));
+    }
+    
     // If a script file is provided, execute it and exit
     if args.len() > 1 {
-        handle_script_file(&args[1]);
+        handle_script_file(&args[1], &args[2..]);
     }
     
     // Check if stdin is being piped (not a TTY)
     if !std::io::stdin().is_terminal() {
-        handle_piped_input();
+        handle_piped_input(&[]);
     }
     
     // Interactive REPL mode
@@ -74,15 +88,15 @@ fn main() {
 }
 
 /// Execute a script file and exit
-fn handle_script_file(script_file: &str) {
+fn handle_script_file(script_file: &str, script_args: &[String]) {
     match std::fs::read_to_string(script_file) {
         Ok(contents) => {
             let script_code = skip_shebang(&contents);
-            match execute_script_with_path(&script_code, Some(script_file)) {
+            match execute_script_with_path(&script_code, Some(script_file), script_args) {
                 Ok(exit_code) => std::process::exit(exit_code),
                 Err(e) => {
                     eprintln!("Script error in {}: {}", script_file, e);
-                    std::process::exit(1);
+                    std::process::exit(failure_exit_code());
                 }
             }
         }
@@ -93,7 +107,7 @@ fn handle_script_file(script_file: &str) {
     }
 }
 
-/// Handle && operator in interactive mode
+/// Handle && operator and ( ... ) subshells in interactive mode
 fn handle_and_operator_interactive(input: &str, interpreter: &Arc<Mutex<Interpreter>>) {
     if let Ok(mut interp) = interpreter.lock() {
         execute_chained_commands(input, Some(&mut interp), true);
@@ -122,20 +136,30 @@ fn run_interactive_repl() {
         .map(|home| format!("{}/.stargate_history", home))
         .unwrap_or_else(|_| ".stargate_history".to_string());
     
-    // Load timestamped history
-    let history_with_timestamps = builtin_commands::load_timestamped_history(&history_file);
+    // Load structured history
+    let history_records = history::load_history(&history_file);
     
-    // Load commands into rustyline (without timestamps for navigation)
-    for (_, cmd) in &history_with_timestamps {
-        let _ = rl.add_history_entry(cmd.as_str());
+    // Load commands into rustyline (without metadata for navigation)
+    for record in &history_records {
+        let _ = rl.add_history_entry(record.command.as_str());
     }
     
-    // Bind Ctrl+S for forward search (Ctrl+R for reverse is already default)
+    // Bind Ctrl+S for forward search; Ctrl+R picks by frecency in the current directory
     use rustyline::{Cmd, KeyCode, Modifiers};
     rl.bind_sequence(
         KeyEvent(KeyCode::Char('s'), Modifiers::CTRL),
         rustyline::EventHandler::Simple(Cmd::ForwardSearchHistory)
     );
+    rl.bind_sequence(
+        KeyEvent(KeyCode::Char('r'), Modifiers::CTRL),
+        rustyline::EventHandler::Conditional(Box::new(HistoryPicker::new(&history_file)))
+    );
+
+    let session_id = history::new_session_id();
+    let hostname = history::current_hostname();
+
+    // Ctrl-C interrupts the running command instead of killing the shell
+    traps::catch_interrupts();
 
     let mut printer = rl.create_external_printer().expect("Failed to create external printer");
     let job_monitor_rx = start_job_monitor();
@@ -159,18 +183,39 @@ fn run_interactive_repl() {
                     continue;
                 }
 
+                // Gather here-document bodies (cmd <<EOF ... EOF) before executing
+                let input = &collect_heredoc_input(&mut rl, input);
+
                 // Add to history
                 let _ = rl.add_history_entry(input);
-                save_to_history(&history_file, input);
 
-                // Handle && operator (conditional execution)
-                if CommandType::detect(input) == CommandType::ChainedCommands {
-                    handle_and_operator_interactive(input, &interpreter);
-                    continue;
-                }
+                let cwd = history::current_dir_string();
+                let started_at = SystemTime::now();
+                let timer = Instant::now();
+                set_last_exit_status(0);
 
-                // Handle special commands and regular input
-                if !handle_repl_command(input, &mut rl, &interpreter, &history_file) {
+                // Handle && operator (conditional execution), then special commands and regular input
+                let keep_running = if matches!(CommandType::detect(input), CommandType::ChainedCommands | CommandType::Subshell) {
+                    handle_and_operator_interactive(input, &interpreter);
+                    true
+                } else {
+                    handle_repl_command(input, &mut rl, &interpreter, &history_file)
+                };
+
+                history::append_history(&history_file, &HistoryRecord {
+                    timestamp: started_at,
+                    command: input.to_string(),
+                    cwd,
+                    exit_status: Some(last_exit_status()),
+                    duration_ms: Some(timer.elapsed().as_millis() as u64),
+                    session: Some(session_id.clone()),
+                    hostname: hostname.clone(),
+                });
+
+                // Signals that arrived after the last statement ran have nothing left to interrupt
+                traps::take_pending();
+
+                if !keep_running {
                     break; // exit/quit was called
                 }
             }
@@ -183,21 +228,9 @@ fn run_interactive_repl() {
         }
     }
 
-    println!("\nGoodbye!");
-}
-
-/// Save a command with timestamp to history file
-fn save_to_history(history_file: &str, command: &str) {
-    if let Ok(mut file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(history_file)
-    {
-        let timestamp = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .map(|d| d.as_secs())
-            .unwrap_or(0);
-        
-        let _ = writeln!(file, "{}|{}", timestamp, command);
+    if let Ok(mut interp) = interpreter.lock() {
+        interp.run_exit_handlers(last_exit_status());
     }
+
+    println!("\nGoodbye!");
 }
@@ -0,0 +1,110 @@
+// Copyright (c) 2025 Dmitry Kalashnikov
+// Dual Licensed: Open-Source (see LICENSE) / Commercial (proprietary use)
+// Commercial use requires a Commercial License. See LICENSE file.
+
+// Signal traps: signals are caught and recorded by sgcore::signals, then
+// handled by the interpreter between statements
+
+use std::process::ExitStatus;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Pseudo-signal name for handlers that run when a script exits
+pub const EXIT_TRAP: &str = "EXIT";
+
+// Set by the REPL, which must survive Ctrl-C whatever scripts do with SIGINT
+static INTERACTIVE: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+pub fn signal_number(name: &str) -> Option<usize> {
+    sgcore::signals::signal_by_name_or_value(name).filter(|&n| n != 0)
+}
+
+#[cfg(not(unix))]
+pub fn signal_number(_name: &str) -> Option<usize> {
+    None
+}
+
+#[cfg(unix)]
+pub fn signal_name(signal: usize) -> String {
+    match sgcore::signals::signal_name_by_value(signal) {
+        Some(name) => format!("SIG{}", name),
+        None => signal.to_string(),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn signal_name(signal: usize) -> String {
+    signal.to_string()
+}
+
+pub fn sigint() -> Option<usize> {
+    signal_number("INT")
+}
+
+/// Catch `signal` so that it is reported by `take_pending` instead of
+/// terminating the shell
+pub fn catch(signal: usize) -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        sgcore::signals::catch_signal(signal)
+            .map_err(|e| format!("cannot trap {}: {}", signal_name(signal), e))
+    }
+    #[cfg(not(unix))]
+    {
+        Err(format!("cannot trap signal {}: not supported on this platform", signal))
+    }
+}
+
+/// Give `signal` its default action back. In the REPL SIGINT stays caught so
+/// Ctrl-C keeps returning to the prompt.
+pub fn reset(signal: usize) -> Result<(), String> {
+    if INTERACTIVE.load(Ordering::Relaxed) && Some(signal) == sigint() {
+        return Ok(());
+    }
+    #[cfg(unix)]
+    {
+        sgcore::signals::reset_signal(signal)
+            .map_err(|e| format!("cannot reset {}: {}", signal_name(signal), e))
+    }
+    #[cfg(not(unix))]
+    {
+        Ok(())
+    }
+}
+
+/// Signals caught since the last call
+pub fn take_pending() -> Vec<usize> {
+    #[cfg(unix)]
+    {
+        sgcore::signals::take_pending_signals()
+    }
+    #[cfg(not(unix))]
+    {
+        Vec::new()
+    }
+}
+
+/// Interactive mode: Ctrl-C interrupts the running script or pipeline and
+/// returns to the prompt instead of killing the shell
+pub fn catch_interrupts() {
+    INTERACTIVE.store(true, Ordering::Relaxed);
+    if let Some(signal) = sigint() {
+        if let Err(e) = catch(signal) {
+            eprintln!("Warning: {}", e);
+        }
+    }
+}
+
+/// Signal that terminated a child process, if any
+pub fn terminating_signal(status: &ExitStatus) -> Option<usize> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal().and_then(|s| usize::try_from(s).ok())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        None
+    }
+}
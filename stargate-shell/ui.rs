@@ -11,6 +11,15 @@ pub fn print_help() {
     println!("  describe-command <cmd>    - Show help for a stargate command");
     println!("  <cmd> [args...]           - Execute a stargate command");
     println!("  <cmd> | <cmd> | ...       - Chain commands with JSON pipes");
+    println!("  <cmd> $(<cmd>) `<cmd>`    - Substitute command output as arguments");
+    println!("  <cmd> <<EOF ... EOF       - Here-document on stdin ($name expands variables)");
+    println!("  <cmd> <<< \"text\"          - Here-string on stdin");
+    println!("  set -e, set -o pipefail   - Stop on the first failing command / fail on any failed stage");
+    println!("  set +o table              - Print records ending a pipeline as JSON instead of a table");
+    println!("  set +o binary             - Pass stardust between stages as JSON text instead of CBOR");
+    println!("  NAME=value <cmd>          - Run a command with a temporary environment variable");
+    println!("  (cd build && <cmd>)       - Run commands in a subshell; cwd and environment are restored");
+    println!("  pushd <dir>, popd, dirs   - Directory stack (dirs --obj for stardust, dirs -c to clear)");
     println!();
     println!("Scripting Language:");
     println!("  script <code>             - Execute inline script");
@@ -22,6 +31,10 @@ pub fn print_help() {
     println!("  Commands:      exec \"ls -la\";");
     println!("  Substitution:  let files = $(ls);");
     println!("  Print:         print x;");
+    println!("  Arguments:     args[0], env[\"HOME\"]   (stargate-shell script.sg a b, or -c '...' a b)");
+    println!("  Exit status:   exit(3);");
+    println!("  Signals:       trap(\"TERM\", sig: cleanup(sig)); trap(\"TERM\", none);");
+    println!("  Exit hooks:    on_exit(code: cleanup(code));");
     println!();
     println!("  Operators:     +, -, *, /, ==, !=, <, >, <=, >=, &&, ||");
     println!();
@@ -33,7 +46,13 @@ pub fn print_help() {
     println!("                              Example: (list-directory).<TAB> shows 'entries', 'count'");
     println!("  Command hints             - Grayed suggestions appear as you type");
     println!("  Command history           - Use Up/Down arrows or Ctrl-P/Ctrl-N");
+    println!("  History search            - Ctrl-R picks the most frecent fuzzy match in this directory");
+    println!("                              Press Ctrl-R again to cycle through further matches");
+    println!("  list-history [--obj]      - Show history with cwd, exit status and duration");
+    println!("                              Filters: --here, --dir <path>, --status ok|failed|<n>,");
+    println!("                                       --since <when>, --until <when> (e.g. 2h, 3d)");
     println!("  Line editing              - Emacs-style keybindings (Ctrl-A, Ctrl-E, etc.)");
+    println!("  Ctrl-C                    - Interrupt the running script or pipeline");
     println!();
     println!("Property Access in Scripts:");
     println!("  Object properties:        let host = (get-hostname).hostname;");
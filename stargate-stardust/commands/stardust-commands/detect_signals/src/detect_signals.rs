@@ -2,7 +2,7 @@
 
 use clap::{Arg, Command};
 use sgcore::error::SGResult;
-use sgcore::stardust_output::StardustOutputOptions;
+use sgcore::stardust_output::{self, StardustOutputOptions};
 use serde_json::json;
 use std::process::{Command as ProcessCommand, Stdio};
 use std::collections::HashMap;
@@ -269,7 +269,7 @@ fn sgmain_impl(args: impl sgcore::Args) -> SGResult<()> {
         let signals = detect_signals(preset_info.freq_min, preset_info.freq_max, preset_info.threshold, preset_info.duration, ppm)?;
         
         if opts.stardust_output {
-            output_json(&signals)?;
+            output_json(&signals, opts)?;
         } else {
             output_text(&signals);
         }
@@ -322,7 +322,7 @@ fn sgmain_impl(args: impl sgcore::Args) -> SGResult<()> {
     let signals = detect_signals(freq_start, freq_end, threshold, duration, ppm)?;
 
     if opts.stardust_output {
-        output_json(&signals)?;
+        output_json(&signals, opts)?;
     } else {
         output_text(&signals);
     }
@@ -525,7 +525,7 @@ fn output_text(signals: &[DetectedSignal]) {
     println!("Scan completed at: {}", Local::now().format("%Y-%m-%d %H:%M:%S"));
 }
 
-fn output_json(signals: &[DetectedSignal]) -> SGResult<()> {
+fn output_json(signals: &[DetectedSignal], opts: StardustOutputOptions) -> SGResult<()> {
     let signals_json: Vec<_> = signals.iter().enumerate().map(|(idx, s)| {
         json!({
             "index": idx + 1,
@@ -546,10 +546,7 @@ fn output_json(signals: &[DetectedSignal]) -> SGResult<()> {
         "signals": signals_json,
     });
 
-    let json_str = serde_json::to_string_pretty(&output)
-        .unwrap_or_else(|_| "{}".to_string());
-    println!("{}", json_str);
-    
+    stardust_output::output(opts, output, || Ok(()))?;
     Ok(())
 }
 
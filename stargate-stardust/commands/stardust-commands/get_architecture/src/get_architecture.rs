@@ -11,13 +11,12 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
     sgcore::pledge::apply_pledge(&["stdio"])?;
     let opts = StardustOutputOptions::from_matches(&matches);
-    let field_filter = matches.get_one::<String>(stardust_output::ARG_FIELD).map(|s| s.as_str());
 
     let uts =
         PlatformInfo::new().map_err(|_e| SGSimpleError::new(1, translate!("cannot-get-system")))?;
     let arch = uts.machine().to_string_lossy().trim().to_string();
 
-    let output = stardust_output::filter_fields(json!({"architecture": arch}), field_filter);
+    let output = json!({"architecture": arch});
     stardust_output::output(opts, output, || {
         println!("{}", arch);
         Ok(())
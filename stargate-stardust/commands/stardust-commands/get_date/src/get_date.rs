@@ -160,7 +160,6 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     sgcore::pledge::apply_pledge(&["stdio"])?;
 
     let json_output_options = StardustOutputOptions::from_matches(&matches);
-    let field_filter = matches.get_one::<String>(stardust_output::ARG_FIELD).map(|s| s.as_str());
 
     let format = if let Some(form) = matches.get_one::<String>(OPT_FORMAT) {
         if !form.starts_with('+') {
@@ -386,9 +385,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
             json!({ "dates": date_outputs })
         };
 
-        let filtered_output = stardust_output::filter_fields(output_json, field_filter);
-
-        stardust_output::output(json_output_options, filtered_output, || Ok(()))?;
+        stardust_output::output(json_output_options, output_json, || Ok(()))?;
     } else {
         for date in dates {
             match date {
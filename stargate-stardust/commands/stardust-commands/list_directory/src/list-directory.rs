@@ -40,13 +40,14 @@ use sgcore::libc::{S_IXGRP, S_IXOTH, S_IXUSR};
 use sgcore::libc::{dev_t, major, minor};
 use sgcore::{
     display::Quotable,
-    error::{SGError, SGResult, set_exit_code},
+    error::{SGError, SGResult, SGSimpleError, set_exit_code},
+    field_path::{Segment, Selector, parse_selectors, select_fields},
     format::human::{SizeFormat, human_readable},
     format_usage,
     fs::FileInformation,
     fs::display_permissions,
     fsext::{MetadataTimeField, metadata_get_time},
-    stardust_output::{self, StardustOutputOptions},
+    stardust_output::{self, FORMAT_BYTES, FORMAT_UNIX_TIME, Property, StardustOutputOptions, array_schema, enum_schema, object_schema},
     line_ending::LineEnding,
     os_str_as_bytes_lossy,
     parser::parse_glob,
@@ -352,7 +353,7 @@ pub struct Config {
     hyperlink: bool,
     tab_size: usize,
     stardust_options: StardustOutputOptions,
-    object_fields: Vec<String>,
+    object_fields: Vec<Selector>,
 }
 
 
@@ -1146,9 +1147,12 @@ impl Config {
             tab_size,
             stardust_options: StardustOutputOptions::from_matches(options),
             object_fields: if let Some(field) = options.get_one::<String>("object_field") {
-                vec![field.clone()]
+                parse_selectors(field).map_err(|e| SGSimpleError::new(2, e))?
             } else if let Some(fields) = options.get_many::<String>("object_fields") {
-                fields.map(|s| s.to_string()).collect()
+                fields
+                    .map(|field| Selector::parse(field))
+                    .collect::<Result<_, _>>()
+                    .map_err(|e| SGSimpleError::new(2, e))?
             } else {
                 Vec::new()
             },
@@ -1162,7 +1166,18 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 
     // Handle --schema flag
     if stardust_output::self_describe(&matches, sgcore::schema!(
-        "entries" => "array", "List of directory entries with file information";
+        "entries" => array_schema(object_schema(vec![
+            Property::new("path", "string").description("Path of the entry"),
+            Property::new("name", "string").description("File name"),
+            Property::new("type", enum_schema(&["file", "directory", "symlink", "other"])).description("Kind of entry"),
+            Property::new("size", "integer").format(FORMAT_BYTES).description("Size in bytes"),
+            Property::new("permissions", "string").description("Permission bits in octal"),
+            Property::new("inode", "integer").description("Inode number"),
+            Property::new("nlink", "integer").description("Number of hard links"),
+            Property::new("uid", "integer").description("Owner user id"),
+            Property::new("gid", "integer").description("Owner group id"),
+            Property::new("modified", "integer").format(FORMAT_UNIX_TIME).description("Modification time in seconds since the epoch").optional(),
+        ])), "List of directory entries with file information";
         "count" => "integer", "Total number of entries";
         "recursive" => "boolean", "Whether recursive listing was enabled";
     ))? {
@@ -1855,7 +1870,7 @@ pub fn sg_app() -> Command {
             Arg::new("object_field")
                 .long("field")
                 .value_name("FIELD")
-                .help("Filter object output to a single field (use with -o)")
+                .help("Filter object output to field paths, comma-separated; names other than entries, count and recursive apply to each entry (use with -o)")
                 .conflicts_with("object_fields")
                 .action(ArgAction::Set),
         )
@@ -1863,7 +1878,7 @@ pub fn sg_app() -> Command {
             Arg::new("object_fields")
                 .long("fields")
                 .value_name("FIELD")
-                .help("Filter object output to multiple fields (use with -o)")
+                .help("Filter object output to multiple field paths (use with -o)")
                 .conflicts_with("object_field")
                 .action(ArgAction::Append),
         );
@@ -2156,14 +2171,7 @@ fn list_json(locs: Vec<&Path>, config: &Config) -> SGResult<()> {
 
 
 
-                if !config.object_fields.is_empty() {
-                    let is_top_level = config.object_fields.iter().all(|f|
-                        f == "entries" || f == "count" || f == "recursive"
-                    );
-                    if !is_top_level {
-                        file_info = filter_object_fields(&file_info, &config.object_fields);
-                    }
-                }
+                file_info = filter_entry_fields(file_info, &config.object_fields);
 
                 entries.push(file_info);
 
@@ -2199,14 +2207,7 @@ fn list_json(locs: Vec<&Path>, config: &Config) -> SGResult<()> {
 
 
 
-            if !config.object_fields.is_empty() {
-                let is_top_level = config.object_fields.iter().all(|f|
-                    f == "entries" || f == "count" || f == "recursive"
-                );
-                if !is_top_level {
-                    file_info = filter_object_fields(&file_info, &config.object_fields);
-                }
-            }
+            file_info = filter_entry_fields(file_info, &config.object_fields);
 
             entries.push(file_info);
         }
@@ -2221,37 +2222,50 @@ fn list_json(locs: Vec<&Path>, config: &Config) -> SGResult<()> {
 
 
 
-    let has_top_level_fields = !config.object_fields.is_empty() &&
-        config.object_fields.iter().any(|f|
-            f == "entries" || f == "count" || f == "recursive"
-        );
-
     let mut output = json!({
         "entries": all_entries,
         "count": all_entries.len(),
         "recursive": config.recursive,
     });
 
-
-    if has_top_level_fields {
-        output = filter_object_fields(&output, &config.object_fields);
+    let top_level: Vec<Selector> = config
+        .object_fields
+        .iter()
+        .filter(|selector| is_top_level_field(selector))
+        .cloned()
+        .collect();
+    if !top_level.is_empty() {
+        let mut filtered = select_fields(&output, &top_level);
+        // Entry fields were picked from each entry, so the entries stay
+        if top_level.len() < config.object_fields.len() {
+            filtered.insert("entries".to_string(), output["entries"].take());
+        }
+        output = serde_json::Value::Object(filtered);
     }
 
-    Ok(stardust_output::output(config.stardust_options, output, || Ok(()))?)
+    Ok(stardust_output::output(config.stardust_options.clone(), output, || Ok(()))?)
 }
 
-fn filter_object_fields(value: &serde_json::Value, fields: &[String]) -> serde_json::Value {
-    if let serde_json::Value::Object(map) = value {
-        let mut result = serde_json::Map::new();
-        for field in fields {
-            if let Some(v) = map.get(field) {
-                result.insert(field.clone(), v.clone());
-            }
-        }
-        serde_json::Value::Object(result)
-    } else {
-        value.clone()
+/// Whether `selector` picks from the listing itself (`entries`, `count`,
+/// `recursive`) rather than from each entry
+fn is_top_level_field(selector: &Selector) -> bool {
+    matches!(
+        selector.path.0.first(),
+        Some(Segment::Key(key)) if key == "entries" || key == "count" || key == "recursive"
+    )
+}
+
+/// Keep the fields of an entry named by the selectors that are not top-level
+fn filter_entry_fields(file_info: serde_json::Value, fields: &[Selector]) -> serde_json::Value {
+    let entry_fields: Vec<Selector> = fields
+        .iter()
+        .filter(|selector| !is_top_level_field(selector))
+        .cloned()
+        .collect();
+    if entry_fields.is_empty() {
+        return file_info;
     }
+    serde_json::Value::Object(select_fields(&file_info, &entry_fields))
 }
 
 #[allow(clippy::cognitive_complexity)]
@@ -0,0 +1,21 @@
+list-logins-about = List the login history recorded in wtmp, or the failed logins recorded in btmp.
+list-logins-usage = list-logins [OPTION]...
+list-logins-long-usage = Logins are read from { $wtmp }, or from { $btmp } with --failed.
+    A login is listed when any part of it falls between --since and --until.
+    TIME is a date such as "2024-05-01 08:00", "yesterday" or "3 days ago".
+
+# Help text for command-line arguments
+list-logins-help-file = read the history from FILE
+list-logins-help-failed = list failed login attempts instead of logins
+list-logins-help-since = only logins that were still going on at TIME or started later
+list-logins-help-until = only logins that started at TIME or earlier
+
+# Output messages
+list-logins-still-logged-in = still logged in
+list-logins-gone = gone - no logout
+list-logins-down = down
+list-logins-crash = crash
+
+# Error messages
+list-logins-error-invalid-time = invalid time { $time }
+list-logins-error-no-history = no login history file is known on this platform, use --file
@@ -0,0 +1,21 @@
+list-logins-about = Liste l'historique des connexions enregistré dans wtmp, ou les échecs de connexion enregistrés dans btmp.
+list-logins-usage = list-logins [OPTION]...
+list-logins-long-usage = Les connexions sont lues depuis { $wtmp }, ou depuis { $btmp } avec --failed.
+    Une connexion est listée dès qu'une partie de celle-ci se situe entre --since et --until.
+    TEMPS est une date telle que « 2024-05-01 08:00 », « yesterday » ou « 3 days ago ».
+
+# Help text for command-line arguments
+list-logins-help-file = lit l'historique depuis FICHIER
+list-logins-help-failed = liste les tentatives de connexion échouées au lieu des connexions
+list-logins-help-since = seulement les connexions encore en cours à TEMPS ou commencées après
+list-logins-help-until = seulement les connexions commencées à TEMPS ou avant
+
+# Output messages
+list-logins-still-logged-in = toujours connecté
+list-logins-gone = parti - pas de déconnexion
+list-logins-down = arrêt
+list-logins-crash = plantage
+
+# Error messages
+list-logins-error-invalid-time = temps invalide { $time }
+list-logins-error-no-history = aucun fichier d'historique des connexions n'est connu sur cette plateforme, utilisez --file
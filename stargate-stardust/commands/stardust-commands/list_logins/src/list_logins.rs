@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::fs::File;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde_json::{Map, Value, json};
+use sgcore::display::Quotable;
+use sgcore::error::{FromIo, SGResult, SGUsageError};
+use sgcore::format_usage;
+use sgcore::stardust_output::{
+    self, Property, StardustOutputOptions, array_schema, create_schema, enum_schema,
+    object_schema,
+};
+use sgcore::translate;
+use sgcore::utmpx::{self, Utmpx, UtmpxRecord, time};
+
+mod options {
+    pub const FILE: &str = "file";
+    pub const FAILED: &str = "failed";
+    pub const SINCE: &str = "since";
+    pub const UNTIL: &str = "until";
+}
+
+/// How a login in the history ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    /// No logout yet
+    Active,
+    LoggedOut,
+    /// The system was shut down
+    Down,
+    /// The system booted again without a shutdown
+    Crash,
+    /// Another login took the line without a logout in between
+    Gone,
+    /// A failed attempt from btmp
+    Failed,
+}
+
+impl Status {
+    const ALL: [Self; 6] = [
+        Self::Active,
+        Self::LoggedOut,
+        Self::Down,
+        Self::Crash,
+        Self::Gone,
+        Self::Failed,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::LoggedOut => "logged_out",
+            Self::Down => "down",
+            Self::Crash => "crash",
+            Self::Gone => "gone",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// One login, paired with its end when the history has one
+struct Login {
+    session: Map<String, Value>,
+    login: time::OffsetDateTime,
+    logout: Option<time::OffsetDateTime>,
+    status: Status,
+}
+
+impl Login {
+    fn new(ut: &UtmpxRecord, status: Status) -> Self {
+        Self {
+            session: ut.session_fields(),
+            login: ut.login_time(),
+            logout: None,
+            status,
+        }
+    }
+
+    fn end(&mut self, when: time::OffsetDateTime, status: Status) {
+        self.logout = Some(when);
+        self.status = status;
+    }
+
+    fn duration(&self) -> Option<i64> {
+        self.logout.map(|logout| (logout - self.login).whole_seconds())
+    }
+
+    /// Whether any part of the login falls between `since` and `until`
+    /// (Unix seconds). A login without an end is open towards the future.
+    fn overlaps(&self, since: Option<i64>, until: Option<i64>) -> bool {
+        let start = self.login.unix_timestamp();
+        let end = if self.status == Status::Failed {
+            Some(start)
+        } else {
+            self.logout.map(time::OffsetDateTime::unix_timestamp)
+        };
+        since.is_none_or(|since| end.is_none_or(|end| end >= since))
+            && until.is_none_or(|until| start <= until)
+    }
+
+    fn field(&self, name: &str) -> &str {
+        self.session.get(name).and_then(Value::as_str).unwrap_or_default()
+    }
+
+    fn into_value(self) -> Value {
+        let duration = self.duration();
+        let mut record = self.session;
+        record.insert("logout_time".into(), self.logout.map(utmpx::format_time).into());
+        record.insert("duration".into(), duration.into());
+        record.insert("status".into(), self.status.as_str().into());
+        Value::Object(record)
+    }
+}
+
+/// Whether `ut` records the system going down
+#[cfg(target_os = "linux")]
+fn is_shutdown(ut: &UtmpxRecord) -> bool {
+    ut.record_type() == utmpx::RUN_LVL && ut.user() == "shutdown"
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_shutdown(ut: &UtmpxRecord) -> bool {
+    ut.record_type() == utmpx::SHUTDOWN_TIME
+}
+
+fn end_all(
+    logins: &mut [Login],
+    open: &mut HashMap<String, usize>,
+    when: time::OffsetDateTime,
+    status: Status
+) {
+    for (_, i) in open.drain() {
+        logins[i].end(when, status);
+    }
+}
+
+/// Pair the records of a wtmp file into logins the way `last` does: a dead
+/// process on the same line ends a login, and a shutdown or a boot ends
+/// every login still open.
+fn paired_logins(records: impl Iterator<Item = UtmpxRecord>) -> Vec<Login> {
+    let mut logins: Vec<Login> = Vec::new();
+    let mut open: HashMap<String, usize> = HashMap::new();
+
+    for ut in records {
+        match ut.record_type() {
+            utmpx::USER_PROCESS if ut.is_user_process() => {
+                if let Some(i) = open.insert(ut.tty_device(), logins.len()) {
+                    logins[i].status = Status::Gone;
+                }
+                logins.push(Login::new(&ut, Status::Active));
+            }
+            utmpx::DEAD_PROCESS => {
+                if let Some(i) = open.remove(&ut.tty_device()) {
+                    logins[i].end(ut.login_time(), Status::LoggedOut);
+                }
+            }
+            utmpx::BOOT_TIME => end_all(&mut logins, &mut open, ut.login_time(), Status::Crash),
+            _ if is_shutdown(&ut) => {
+                end_all(&mut logins, &mut open, ut.login_time(), Status::Down);
+            }
+            _ => {}
+        }
+    }
+    logins
+}
+
+/// Every attempt in a btmp file is a login of its own
+fn failed_logins(records: impl Iterator<Item = UtmpxRecord>) -> Vec<Login> {
+    records
+        .filter(|ut| !ut.user().is_empty())
+        .map(|ut| Login::new(&ut, Status::Failed))
+        .collect()
+}
+
+/// The Unix time given to `name`, which takes anything `get-date -d` does
+fn time_arg(matches: &ArgMatches, name: &str) -> SGResult<Option<i64>> {
+    matches
+        .get_one::<String>(name)
+        .map(|s| {
+            parse_datetime::parse_datetime(s)
+                .map(|zoned| zoned.timestamp().as_second())
+                .map_err(|_| {
+                    SGUsageError::new(
+                        1,
+                        translate!("list-logins-error-invalid-time", "time" => s.quote())
+                    )
+                })
+        })
+        .transpose()
+}
+
+fn format_duration(seconds: i64) -> String {
+    let (days, rest) = (seconds / 86400, seconds % 86400);
+    let (hours, minutes) = (rest / 3600, rest % 3600 / 60);
+    if days > 0 {
+        format!("({days}+{hours:02}:{minutes:02})")
+    } else {
+        format!("({hours:02}:{minutes:02})")
+    }
+}
+
+fn print_text(logins: &[Login]) {
+    let date_format =
+        time::format_description::parse("[year]-[month]-[day] [hour]:[minute]").unwrap();
+    let clock_format = time::format_description::parse("[hour]:[minute]").unwrap();
+
+    for login in logins {
+        let start = login.login.format(&date_format).unwrap_or_default();
+        let end = match login.status {
+            Status::Failed => String::new(),
+            Status::Active => format!("  {}", translate!("list-logins-still-logged-in")),
+            Status::Gone => format!("  {}", translate!("list-logins-gone")),
+            Status::LoggedOut | Status::Down | Status::Crash => {
+                let until = match login.status {
+                    Status::Down => translate!("list-logins-down"),
+                    Status::Crash => translate!("list-logins-crash"),
+                    _ => login
+                        .logout
+                        .and_then(|logout| logout.format(&clock_format).ok())
+                        .unwrap_or_default(),
+                };
+                let duration = format_duration(login.duration().unwrap_or_default());
+                format!(" - {until:<5}  {duration}")
+            }
+        };
+        let line = format!(
+            "{:<8} {:<12} {:<16} {start}{end}",
+            login.field("user"),
+            login.field("tty"),
+            login.field("host")
+        );
+        println!("{}", line.trim_end());
+    }
+}
+
+fn schema() -> Value {
+    let mut login = utmpx::session_properties();
+    login.extend([
+        Property::new("logout_time", json!({"type": ["string", "null"]}))
+            .description("When the login ended (RFC 3339), null while it has no end"),
+        Property::new("duration", json!({"type": ["integer", "null"]}))
+            .description("Seconds from login to logout, null while the login has no end"),
+        Property::new("status", enum_schema(&Status::ALL.map(Status::as_str))).description(
+            "How the login ended: logged_out, down (shutdown), crash (boot without shutdown), \
+             gone (line reused without a logout), still active, or a failed attempt"
+        ),
+    ]);
+    create_schema(vec![
+        Property::new("logins", array_schema(object_schema(login)))
+            .description("Logins in the order they were recorded"),
+        Property::new("count", "integer").description("Number of logins listed"),
+    ])
+}
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
+    if stardust_output::self_describe(&matches, schema())? {
+        return Ok(());
+    }
+    sgcore::pledge::apply_pledge(&["stdio", "rpath"])?;
+    let opts = StardustOutputOptions::from_matches(&matches);
+
+    let failed = matches.get_flag(options::FAILED);
+    let since = time_arg(&matches, options::SINCE)?;
+    let until = time_arg(&matches, options::UNTIL)?;
+
+    let path = match matches.get_one::<String>(options::FILE) {
+        Some(path) => path.as_str(),
+        None if failed => utmpx::BTMP_FILE,
+        None => utmpx::WTMP_FILE,
+    };
+    if path.is_empty() {
+        return Err(SGUsageError::new(1, translate!("list-logins-error-no-history")));
+    }
+    // The utmpx functions read a missing or unreadable file as an empty
+    // history, which an audit must not mistake for "nobody logged in"
+    File::open(path).map_err_context(|| path.maybe_quote().to_string())?;
+
+    let records = Utmpx::iter_all_records_from(path);
+    let mut logins = if failed {
+        failed_logins(records)
+    } else {
+        paired_logins(records)
+    };
+    logins.retain(|login| login.overlaps(since, until));
+
+    if opts.stardust_output {
+        let logins: Vec<Value> = logins.into_iter().map(Login::into_value).collect();
+        let output = json!({
+            "logins": logins,
+            "count": logins.len(),
+        });
+        stardust_output::output(opts, output, || Ok(()))?;
+    } else {
+        print_text(&logins);
+    }
+
+    Ok(())
+}
+
+pub fn sg_app() -> Command {
+    let cmd = Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .help_template(sgcore::localized_help_template(sgcore::util_name()))
+        .about(translate!("list-logins-about"))
+        .override_usage(format_usage(&translate!("list-logins-usage")))
+        .after_help(translate!(
+            "list-logins-long-usage",
+            "wtmp" => utmpx::WTMP_FILE,
+            "btmp" => utmpx::BTMP_FILE
+        ))
+        .infer_long_args(true)
+        .arg(
+            Arg::new(options::FILE)
+                .short('f')
+                .long(options::FILE)
+                .value_name("FILE")
+                .value_hint(clap::ValueHint::FilePath)
+                .help(translate!("list-logins-help-file"))
+        )
+        .arg(
+            Arg::new(options::FAILED)
+                .short('b')
+                .long(options::FAILED)
+                .help(translate!("list-logins-help-failed"))
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new(options::SINCE)
+                .short('s')
+                .long(options::SINCE)
+                .value_name("TIME")
+                .help(translate!("list-logins-help-since"))
+        )
+        .arg(
+            Arg::new(options::UNTIL)
+                .short('t')
+                .long(options::UNTIL)
+                .value_name("TIME")
+                .help(translate!("list-logins-help-until"))
+        );
+
+    stardust_output::add_json_args(cmd)
+}
@@ -0,0 +1 @@
+sgcore::bin!(sg_list_logins);
@@ -2,7 +2,7 @@
 
 use clap::{Arg, Command};
 use sgcore::error::SGResult;
-use sgcore::stardust_output::StardustOutputOptions;
+use sgcore::stardust_output::{self, StardustOutputOptions};
 use serde_json::json;
 use std::process::{Command as ProcessCommand, Stdio};
 use std::io;
@@ -44,7 +44,7 @@ fn sgmain_impl(args: impl sgcore::Args) -> SGResult<()> {
         .unwrap_or(DEFAULT_GAIN);
 
     if opts.stardust_output {
-        output_json(frequency, ppm, squelch, gain)?;
+        output_json(opts, frequency, ppm, squelch, gain)?;
     } else {
         play_radio(frequency, ppm, squelch, gain)?;
     }
@@ -193,7 +193,7 @@ fn play_radio(frequency: f64, ppm: i32, squelch: i32, gain: &str) -> SGResult<()
     Ok(())
 }
 
-fn output_json(frequency: f64, ppm: i32, squelch: i32, gain: &str) -> SGResult<()> {
+fn output_json(opts: StardustOutputOptions, frequency: f64, ppm: i32, squelch: i32, gain: &str) -> SGResult<()> {
     let output = json!({
         "command": "listen-radio",
         "frequency_mhz": frequency,
@@ -204,10 +204,7 @@ fn output_json(frequency: f64, ppm: i32, squelch: i32, gain: &str) -> SGResult<(
         "status": "starting"
     });
 
-    let json_str = serde_json::to_string_pretty(&output)
-        .unwrap_or_else(|_| "{}".to_string());
-    println!("{}", json_str);
-    
+    stardust_output::output(opts, output, || Ok(()))?;
     Ok(())
 }
 
@@ -2,7 +2,7 @@
 
 use clap::{Arg, Command};
 use sgcore::error::SGResult;
-use sgcore::stardust_output::StardustOutputOptions;
+use sgcore::stardust_output::{self, StardustOutputOptions};
 use serde_json::json;
 use std::process::{Command as ProcessCommand, Stdio};
 use std::io::{BufRead, BufReader, Write};
@@ -95,7 +95,7 @@ fn sgmain_impl(args: impl sgcore::Args) -> SGResult<()> {
     }
 
     if opts.stardust_output {
-        output_json(freq_start, freq_end, bin_size, interval, gain, ppm, mode)?;
+        output_json(opts, freq_start, freq_end, bin_size, interval, gain, ppm, mode)?;
     } else {
         monitor_spectrum(freq_start, freq_end, bin_size, interval, gain, ppm, height, mode)?;
     }
@@ -396,7 +396,9 @@ fn print_legend() {
     println!("Weakest: \x1b[90m░\x1b[0m");
 }
 
+#[allow(clippy::too_many_arguments)]
 fn output_json(
+    opts: StardustOutputOptions,
     freq_start: u32,
     freq_end: u32,
     bin_size: u32,
@@ -421,10 +423,7 @@ fn output_json(
         "status": "starting"
     });
 
-    let json_str = serde_json::to_string_pretty(&output)
-        .unwrap_or_else(|_| "{}".to_string());
-    println!("{}", json_str);
-    
+    stardust_output::output(opts, output, || Ok(()))?;
     Ok(())
 }
 
@@ -177,13 +177,8 @@ fn produce_json(matches: &ArgMatches, options: StardustOutputOptions) -> SGResul
         },
     };
 
-    let json = if options.pretty {
-        serde_json::to_string_pretty(&result).unwrap()
-    } else {
-        serde_json::to_string(&result).unwrap()
-    };
-
-    println!("{}", json);
+    let value = serde_json::to_value(&result).map_err(std::io::Error::other)?;
+    stardust_output::output(options, value, || Ok(()))?;
     Ok(())
 }
 
@@ -292,13 +287,8 @@ fn produce_json(matches: &ArgMatches, options: StardustOutputOptions) -> SGResul
         }
     });
 
-    let json = if options.pretty {
-        serde_json::to_string_pretty(&result).unwrap()
-    } else {
-        serde_json::to_string(&result).unwrap()
-    };
-
-    println!("{}", json);
+    let value = serde_json::to_value(&result).map_err(std::io::Error::other)?;
+    stardust_output::output(options, value, || Ok(()))?;
     Ok(())
 }
 
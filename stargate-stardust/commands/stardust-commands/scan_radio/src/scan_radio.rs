@@ -1,6 +1,6 @@
 use clap::{Arg, Command};
 use sgcore::error::SGResult;
-use sgcore::stardust_output::StardustOutputOptions;
+use sgcore::stardust_output::{self, StardustOutputOptions};
 use serde_json::json;
 use std::process::{Command as ProcessCommand, Stdio};
 use std::collections::HashMap;
@@ -257,7 +257,7 @@ fn output_text(signals: &[RFSignal]) {
     }
 }
 
-fn output_json(signals: &[RFSignal], _opts: StardustOutputOptions) -> SGResult<()> {
+fn output_json(signals: &[RFSignal], opts: StardustOutputOptions) -> SGResult<()> {
     let json_signals: Vec<serde_json::Value> = signals.iter()
         .map(|s| {
             let mut obj = json!({
@@ -278,9 +278,7 @@ fn output_json(signals: &[RFSignal], _opts: StardustOutputOptions) -> SGResult<(
         "signals": json_signals
     });
 
-    let json_str = serde_json::to_string_pretty(&output)
-        .unwrap_or_else(|_| "{}".to_string());
-    println!("{}", json_str);
+    stardust_output::output(opts, output, || Ok(()))?;
     Ok(())
 }
 
@@ -4,7 +4,7 @@ use clap::{Arg, Command};
 use sgcore::error::SGResult;
 use sgcore::format_usage;
 use sgcore::translate;
-use sgcore::stardust_output::{self, StardustOutputOptions};
+use sgcore::stardust_output::{self, Property, StardustOutputOptions, array_schema, object_schema};
 use serde_json::json;
 
 #[cfg(target_os = "macos")]
@@ -52,7 +52,24 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 
     // Handle --schema flag
     if stardust_output::self_describe(&matches, sgcore::schema!(
-        "networks" => "array", "List of detected WiFi networks with their properties";
+        "networks" => array_schema(object_schema(vec![
+            Property::new("ssid", "string").description("Network name, empty when hidden"),
+            Property::new("bssid", "string").description("Access point MAC address"),
+            Property::new("channel", "string"),
+            Property::new("signal_strength", "string"),
+            Property::new("encryption", "string"),
+            Property::new("clients", "integer").optional(),
+            Property::new("packets", "integer").optional(),
+            Property::new("beacons", "integer").optional(),
+            Property::new("distance_meters", "number").description("Estimated distance from the signal strength").optional(),
+            Property::new("distance", "string").optional(),
+            Property::new("proximity", "string").optional(),
+            Property::new("client_details", array_schema(object_schema(vec![
+                Property::new("mac", "string"),
+                Property::new("signal", "string"),
+                Property::new("packets", "integer"),
+            ]))).optional(),
+        ])), "List of detected WiFi networks with their properties";
         "count" => "integer", "Total number of networks found";
     ))? {
         return Ok(());
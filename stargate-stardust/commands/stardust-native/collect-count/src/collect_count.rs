@@ -89,7 +89,7 @@ impl<'a> Settings<'a> {
             show_max_line_length: matches.get_flag(options::MAX_LINE_LENGTH),
             files0_from,
             total_when,
-            stardust_options,
+            stardust_options: stardust_options.clone(),
         };
 
         if settings.number_enabled() > 0 {
@@ -844,7 +844,7 @@ fn wc(inputs: &Inputs, settings: &Settings, metadata: &InputMetadata) -> SGResul
         );
 
         let output = collected_count.to_json();
-        stardust_output::output(settings.stardust_options, output, || Ok(()))?;
+        stardust_output::output(settings.stardust_options.clone(), output, || Ok(()))?;
     } else if settings.total_when.is_total_row_visible(num_inputs) {
         let wc_total_msg = translate!("wc-total");
         let title = are_stats_visible.then_some(OsStr::new(&wc_total_msg));
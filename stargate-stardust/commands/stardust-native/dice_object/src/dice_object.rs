@@ -1,7 +1,9 @@
 use clap::{Arg, ArgAction, Command};
 use serde_json::Value;
-use std::io::{self, Read};
 use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::field_path::{Selector, select_fields};
+use sgcore::json_adapter::read_stdin_value;
+use sgcore::wire;
 
 pub mod options {
     pub static FIELD: &str = "field";
@@ -14,13 +16,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sg_app().try_get_matches_from(args)?;
     sgcore::pledge::apply_pledge(&["stdio"])?;
 
-    let mut input = String::new();
-    io::stdin()
-        .read_to_string(&mut input)
-        .map_err(|e| SGSimpleError::new(1, format!("Failed to read stdin: {}", e)))?;
-
-    let json: Value = serde_json::from_str(&input)
-        .map_err(|e| SGSimpleError::new(1, format!("Failed to parse JSON: {}", e)))?;
+    let json = read_stdin_value().map_err(|e| SGSimpleError::new(1, e))?;
 
     let pretty = matches.get_flag(options::PRETTY);
 
@@ -38,36 +34,21 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 }
 
 fn filter_columns(json: &Value, fields: &[String]) -> SGResult<Value> {
+    let selectors = fields
+        .iter()
+        .map(|field| Selector::parse(field))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| SGSimpleError::new(1, e))?;
+
     match json {
-        Value::Object(map) => {
-            let mut result = serde_json::Map::new();
-            for field in fields {
-                if let Some(value) = map.get(field) {
-                    result.insert(field.clone(), value.clone());
-                }
-            }
-            Ok(Value::Object(result))
-        }
+        Value::Object(_) => Ok(Value::Object(select_fields(json, &selectors))),
         Value::Array(arr) => {
             let results: Vec<Value> = arr
                 .iter()
-                .filter_map(|item| {
-                    if let Value::Object(map) = item {
-                        let mut obj = serde_json::Map::new();
-                        for field in fields {
-                            if let Some(value) = map.get(field) {
-                                obj.insert(field.clone(), value.clone());
-                            }
-                        }
-                        if !obj.is_empty() {
-                            Some(Value::Object(obj))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
+                .filter(|item| item.is_object())
+                .map(|item| select_fields(item, &selectors))
+                .filter(|obj| !obj.is_empty())
+                .map(Value::Object)
                 .collect();
             Ok(Value::Array(results))
         }
@@ -79,15 +60,8 @@ fn filter_columns(json: &Value, fields: &[String]) -> SGResult<Value> {
 }
 
 fn output_json(value: &Value, pretty: bool) -> SGResult<()> {
-    let output = if pretty {
-        serde_json::to_string_pretty(value)
-    } else {
-        serde_json::to_string(value)
-    }
-    .map_err(|e| SGSimpleError::new(1, format!("Failed to serialize JSON: {}", e)))?;
-
-    println!("{}", output);
-    Ok(())
+    wire::print_value(value, pretty)
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))
 }
 
 pub fn sg_app() -> Command {
@@ -95,10 +69,19 @@ pub fn sg_app() -> Command {
         .version(sgcore::crate_version!())
         .about("Filter JSON object columns/fields")
         .override_usage("dice-object [FIELD]...")
+        .after_help(
+            "Each FIELD is a field path: dotted keys, [N] indices (negative from \
+             the end), [START:END] slices and [*] wildcards. `PATH as NAME` gives \
+             the result another name; otherwise it is named by the path as written.\n\n\
+             Examples:\n  \
+             list-directory -l | slice-object entries | dice-object name size\n  \
+             list-processes | slice-object processes | dice-object pid 'name as command'\n  \
+             find-text TODO src | slice-object results | dice-object file 'matches[*].line as lines'",
+        )
         .arg(
             Arg::new(options::FIELDS)
                 .value_name("FIELD")
-                .help("Extract multiple fields (columns)")
+                .help("Field paths to keep (columns), each optionally renamed with `as NAME`")
                 .num_args(1..)
                 .action(ArgAction::Append),
         )
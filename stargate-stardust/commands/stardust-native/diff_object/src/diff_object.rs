@@ -0,0 +1,468 @@
+use clap::{Arg, ArgAction, ArgMatches, Command, builder::PossibleValuesParser};
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::io::{self, IsTerminal, Write};
+use sgcore::error::{SGResult, SGSimpleError, set_exit_code};
+use sgcore::json_adapter::{extract_records, load_source, lookup_field, number_key, read_stdin_value};
+use sgcore::wire;
+
+pub mod options {
+    pub static SOURCES: &str = "sources";
+    pub static KEY: &str = "key";
+    pub static IGNORE: &str = "ignore";
+    pub static FIELD: &str = "field";
+    pub static TEXT: &str = "text";
+    pub static COLOR: &str = "color";
+    pub static EXIT_CODE: &str = "exit-code";
+    pub static PRETTY: &str = "pretty";
+}
+
+/// Fields tried in order as the identity of a record when no key is given
+const KEY_CANDIDATES: [&str; 4] = ["path", "pid", "id", "name"];
+
+const RESET: &str = "\x1b[0m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const DIM: &str = "\x1b[2m";
+
+/// Exit status for inputs that cannot be read, as for diff(1), so that
+/// --exit-code can tell trouble from differences
+const TROUBLE: i32 = 2;
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sg_app().try_get_matches_from(args)?;
+
+    let sources: Vec<&String> = matches
+        .get_many::<String>(options::SOURCES)
+        .expect("OLD is required")
+        .collect();
+    let old = load(sources[0])?;
+    let new = match sources.get(1) {
+        Some(source) => load(source)?,
+        None => read_stdin()?,
+    };
+
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let ignore: Vec<String> = matches
+        .get_many::<String>(options::IGNORE)
+        .into_iter()
+        .flatten()
+        .flat_map(|fields| fields.split(','))
+        .map(|field| field.trim().to_string())
+        .filter(|field| !field.is_empty())
+        .collect();
+    let field = matches.get_one::<String>(options::FIELD).map(String::as_str);
+
+    let diff = match (extract_records(&old, field), extract_records(&new, field)) {
+        (Ok(old_records), Ok(new_records)) => {
+            let key = matches
+                .get_one::<String>(options::KEY)
+                .cloned()
+                .or_else(|| detect_key(old_records, new_records));
+            diff_records(old_records, new_records, key.as_deref(), &ignore)
+        }
+        // Documents without records are compared field by field
+        _ if field.is_none() && old.is_object() && new.is_object() => diff_documents(&old, &new, &ignore),
+        (Err(e), _) | (_, Err(e)) => return Err(SGSimpleError::new(TROUBLE, e)),
+    };
+
+    if matches.get_flag(options::TEXT) {
+        let text = render_text(&diff, use_color(&matches));
+        io::stdout()
+            .write_all(text.as_bytes())
+            .map_err(|e| SGSimpleError::new(TROUBLE, format!("Failed to write output: {e}")))?;
+    } else {
+        output_json(&diff, matches.get_flag(options::PRETTY))?;
+    }
+
+    if matches.get_flag(options::EXIT_CODE) && has_differences(&diff) {
+        set_exit_code(1);
+    }
+    Ok(())
+}
+
+fn load(source: &str) -> SGResult<Value> {
+    if source == "-" {
+        return read_stdin();
+    }
+    load_source(source).map_err(|e| SGSimpleError::new(TROUBLE, e))
+}
+
+fn read_stdin() -> SGResult<Value> {
+    read_stdin_value().map_err(|e| SGSimpleError::new(TROUBLE, e))
+}
+
+/// The first of [`KEY_CANDIDATES`] that every record on both sides has
+fn detect_key(old: &[Value], new: &[Value]) -> Option<String> {
+    KEY_CANDIDATES
+        .into_iter()
+        .find(|key| {
+            let mut records = old.iter().chain(new).peekable();
+            records.peek().is_some() && records.all(|record| lookup_field(record, key).is_some())
+        })
+        .map(str::to_string)
+}
+
+/// Comparable form of a record's identity; numbers match whatever their
+/// notation, as for join-object
+fn identity(record: &Value, key: &str) -> Option<String> {
+    match lookup_field(record, key)? {
+        Value::Null => None,
+        Value::Number(n) => Some(number_key(n)),
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Pair the records of both sides by `key` (by position without one) and
+/// report the records only in `old` as removed, those only in `new` as
+/// added, and the per-field changes of the pairs that differ
+fn diff_records(old: &[Value], new: &[Value], key: Option<&str>, ignore: &[String]) -> Value {
+    let mut by_identity: HashMap<String, VecDeque<usize>> = HashMap::new();
+    if let Some(key) = key {
+        for (i, record) in new.iter().enumerate() {
+            if let Some(id) = identity(record, key) {
+                by_identity.entry(id).or_default().push_back(i);
+            }
+        }
+    }
+
+    let mut matched_new = vec![false; new.len()];
+    let mut removed = Vec::new();
+    let mut changed = Vec::new();
+    let mut unchanged = 0;
+    for (i, record) in old.iter().enumerate() {
+        let pair = match key {
+            Some(key) => identity(record, key)
+                .and_then(|id| by_identity.get_mut(&id))
+                .and_then(VecDeque::pop_front),
+            None => (i < new.len()).then_some(i),
+        };
+        let Some(j) = pair else {
+            removed.push(record.clone());
+            continue;
+        };
+        matched_new[j] = true;
+
+        let mut changes = Map::new();
+        diff_fields(record, &new[j], "", ignore, &mut changes);
+        if changes.is_empty() {
+            unchanged += 1;
+        } else {
+            let id = match key {
+                Some(key) => lookup_field(record, key).cloned().unwrap_or(Value::Null),
+                None => Value::from(i),
+            };
+            changed.push(json!({"key": id, "changes": changes}));
+        }
+    }
+
+    let added: Vec<Value> = new
+        .iter()
+        .zip(&matched_new)
+        .filter(|(_, matched)| !**matched)
+        .map(|(record, _)| record.clone())
+        .collect();
+
+    json!({
+        "key": key,
+        "summary": {
+            "added": added.len(),
+            "removed": removed.len(),
+            "changed": changed.len(),
+            "unchanged": unchanged,
+        },
+        "added": added,
+        "removed": removed,
+        "changed": changed,
+    })
+}
+
+/// Compare two documents that hold no records field by field
+fn diff_documents(old: &Value, new: &Value, ignore: &[String]) -> Value {
+    let mut changes = Map::new();
+    diff_fields(old, new, "", ignore, &mut changes);
+    json!({
+        "summary": {"changed": changes.len()},
+        "changes": changes,
+    })
+}
+
+/// Record under its dotted path each field that differs between `old` and
+/// `new` as `{"before": ..., "after": ...}`, leaving out the side that lacks
+/// it. Objects are compared field by field; arrays and scalars as a whole.
+fn diff_fields(old: &Value, new: &Value, path: &str, ignore: &[String], changes: &mut Map<String, Value>) {
+    if is_ignored(path, ignore) {
+        return;
+    }
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for name in names {
+                let child = if path.is_empty() { name.clone() } else { format!("{path}.{name}") };
+                match (old.get(name), new.get(name)) {
+                    (Some(old), Some(new)) => diff_fields(old, new, &child, ignore, changes),
+                    (old, new) if !is_ignored(&child, ignore) => {
+                        changes.insert(child, change(old, new));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        (Value::Number(a), Value::Number(b)) if a.as_f64() == b.as_f64() => {}
+        _ if old != new => {
+            let path = if path.is_empty() { "value" } else { path };
+            changes.insert(path.to_string(), change(Some(old), Some(new)));
+        }
+        _ => {}
+    }
+}
+
+fn change(before: Option<&Value>, after: Option<&Value>) -> Value {
+    let mut change = Map::new();
+    if let Some(before) = before {
+        change.insert("before".to_string(), before.clone());
+    }
+    if let Some(after) = after {
+        change.insert("after".to_string(), after.clone());
+    }
+    Value::Object(change)
+}
+
+/// Whether `path` is one of the ignored fields or nested inside one
+fn is_ignored(path: &str, ignore: &[String]) -> bool {
+    ignore.iter().any(|field| {
+        path.strip_prefix(field.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+fn has_differences(diff: &Value) -> bool {
+    ["added", "removed", "changed"]
+        .iter()
+        .any(|count| diff["summary"][count].as_u64().is_some_and(|n| n > 0))
+}
+
+fn use_color(matches: &ArgMatches) -> bool {
+    match matches.get_one::<String>(options::COLOR).map(String::as_str) {
+        Some("always") => true,
+        Some("never") => false,
+        _ => io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+    }
+}
+
+/// The diff as text: `+` for added records, `-` for removed ones and `~`
+/// for changed ones followed by their fields, then a summary line
+fn render_text(diff: &Value, color: bool) -> String {
+    let paint = |text: &str, code: &str| {
+        if color { format!("{code}{text}{RESET}") } else { text.to_string() }
+    };
+    let key = diff["key"].as_str();
+    let label = |record: &Value| match key.and_then(|key| lookup_field(record, key)) {
+        Some(id) => format!("{}={}", key.unwrap_or_default(), display(id)),
+        None => record.to_string(),
+    };
+
+    let mut out = String::new();
+    let write_changes = |out: &mut String, changes: &Value, indent: &str| {
+        for (field, change) in changes.as_object().into_iter().flatten() {
+            let line = match (change.get("before"), change.get("after")) {
+                (Some(before), Some(after)) => {
+                    format!("{field}: {} → {}", paint(&display(before), RED), paint(&display(after), GREEN))
+                }
+                (None, Some(after)) => paint(&format!("+ {field}: {}", display(after)), GREEN),
+                (Some(before), _) => paint(&format!("- {field}: {}", display(before)), RED),
+                (None, None) => continue,
+            };
+            out.push_str(&format!("{indent}{line}\n"));
+        }
+    };
+
+    if let Some(changes) = diff.get("changes") {
+        write_changes(&mut out, changes, "");
+    }
+    for record in diff["removed"].as_array().into_iter().flatten() {
+        out.push_str(&format!("{}\n", paint(&format!("- {}", label(record)), RED)));
+    }
+    for record in diff["added"].as_array().into_iter().flatten() {
+        out.push_str(&format!("{}\n", paint(&format!("+ {}", label(record)), GREEN)));
+    }
+    for record in diff["changed"].as_array().into_iter().flatten() {
+        let id = match key {
+            Some(key) => format!("{key}={}", display(&record["key"])),
+            None => format!("#{}", record["key"]),
+        };
+        out.push_str(&format!("{}\n", paint(&format!("~ {id}"), YELLOW)));
+        write_changes(&mut out, &record["changes"], "    ");
+    }
+
+    let summary: Vec<String> = diff["summary"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, count)| format!("{count} {name}"))
+        .collect();
+    out.push_str(&format!("{}\n", paint(&summary.join(", "), DIM)));
+    out
+}
+
+/// A value as shown in the text view: strings bare, the rest as JSON
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn output_json(value: &Value, pretty: bool) -> SGResult<()> {
+    wire::print_value(value, pretty)
+        .map_err(|e| SGSimpleError::new(TROUBLE, format!("Failed to write output: {e}")))
+}
+
+pub fn sg_app() -> Command {
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about("Compare two stardust documents record by record")
+        .override_usage("diff-object [OPTIONS] OLD [NEW]")
+        .after_help(
+            "OLD and NEW are JSON files, inline JSON, commands in parentheses such \
+             as \"(list-processes)\" (run with --obj), or - for stdin; NEW is read \
+             from stdin when absent.\n\
+             Records are paired by --key, or by the first of path, pid, id and name \
+             that every record has, or else by position. The output lists the added \
+             and removed records and, for each changed record, the fields that \
+             differ with their before and after values. Documents without records \
+             are compared field by field.\n\
+             With --exit-code the status is 1 when there are differences, 0 when \
+             there are none and 2 when an input cannot be read.\n\n\
+             Examples:\n  \
+             list-directory -R -l | diff-object --key path --ignore modified snapshot.json\n  \
+             diff-object --text before.json after.json\n  \
+             list-processes | diff-object -k pid -i cpu_time --exit-code '(list-processes)'\n  \
+             diff-object old.json new.json | slice-object changed | format-table",
+        )
+        .arg(
+            Arg::new(options::SOURCES)
+                .value_name("OLD [NEW]")
+                .help("The documents to compare: JSON files, inline JSON, (command) or -")
+                .num_args(1..=2)
+                .required(true),
+        )
+        .arg(
+            Arg::new(options::KEY)
+                .short('k')
+                .long(options::KEY)
+                .value_name("PATH")
+                .help("Field path identifying a record, such as path or pid"),
+        )
+        .arg(
+            Arg::new(options::IGNORE)
+                .short('i')
+                .long(options::IGNORE)
+                .value_name("FIELDS")
+                .help("Comma-separated field paths left out of the comparison")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new(options::FIELD)
+                .short('f')
+                .long("field")
+                .value_name("FIELD")
+                .help("Compare the arrays under FIELD instead of detecting them"),
+        )
+        .arg(
+            Arg::new(options::TEXT)
+                .short('t')
+                .long(options::TEXT)
+                .help("Show the differences as text instead of stardust")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::COLOR)
+                .long(options::COLOR)
+                .value_name("WHEN")
+                .help("Color the text view")
+                .value_parser(PossibleValuesParser::new(["auto", "always", "never"]))
+                .default_value("auto"),
+        )
+        .arg(
+            Arg::new(options::EXIT_CODE)
+                .long(options::EXIT_CODE)
+                .help("Exit with status 1 when the documents differ")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::PRETTY)
+                .short('p')
+                .long("pretty")
+                .help("Output pretty-printed JSON")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(value: &Value) -> &[Value] {
+        value.as_array().unwrap()
+    }
+
+    #[test]
+    fn pairs_records_by_key() {
+        let old = json!([
+            {"pid": 1, "name": "init", "cpu": 5},
+            {"pid": 7, "name": "sh", "cpu": 1},
+            {"pid": 9, "name": "vim", "cpu": 2},
+        ]);
+        let new = json!([
+            {"pid": 9, "name": "vim", "cpu": 3},
+            {"pid": 1.0, "name": "init", "cpu": 5},
+            {"pid": 12, "name": "top", "cpu": 0},
+        ]);
+        let diff = diff_records(records(&old), records(&new), Some("pid"), &[]);
+        assert_eq!(diff["summary"], json!({"added": 1, "removed": 1, "changed": 1, "unchanged": 1}));
+        assert_eq!(diff["removed"][0]["pid"], 7);
+        assert_eq!(diff["added"][0]["pid"], 12);
+        assert_eq!(diff["changed"], json!([{"key": 9, "changes": {"cpu": {"before": 2, "after": 3}}}]));
+
+        let ignored = diff_records(records(&old), records(&new), Some("pid"), &["cpu".to_string()]);
+        assert_eq!(ignored["summary"]["changed"], 0);
+    }
+
+    #[test]
+    fn large_integer_keys_stay_distinct() {
+        let old = json!([{"id": 9_007_199_254_740_992_u64, "v": 1}, {"id": 9_007_199_254_740_993_u64, "v": 2}]);
+        let new = json!([{"id": 9_007_199_254_740_993_u64, "v": 2}, {"id": 9_007_199_254_740_992_u64, "v": 1}]);
+        let diff = diff_records(records(&old), records(&new), Some("id"), &[]);
+        assert_eq!(diff["summary"], json!({"added": 0, "removed": 0, "changed": 0, "unchanged": 2}));
+    }
+
+    #[test]
+    fn nested_fields_and_detected_keys() {
+        let old = json!([{"path": "a", "owner": {"name": "root", "uid": 0}}]);
+        let new = json!([{"path": "a", "owner": {"name": "ann"}, "mode": "644"}]);
+        assert_eq!(detect_key(records(&old), records(&new)).as_deref(), Some("path"));
+        let diff = diff_records(records(&old), records(&new), Some("path"), &[]);
+        assert_eq!(
+            diff["changed"][0]["changes"],
+            json!({
+                "mode": {"after": "644"},
+                "owner.name": {"before": "root", "after": "ann"},
+                "owner.uid": {"before": 0},
+            })
+        );
+        assert!(has_differences(&diff));
+        assert!(is_ignored("owner.uid", &["owner".to_string()]));
+        assert!(!is_ignored("owners", &["owner".to_string()]));
+    }
+
+    #[test]
+    fn text_view_lists_changes() {
+        let diff = diff_documents(&json!({"host": "a", "up": 1}), &json!({"host": "b", "up": 1}), &[]);
+        assert_eq!(render_text(&diff, false), "host: a → b\n1 changed\n");
+    }
+}
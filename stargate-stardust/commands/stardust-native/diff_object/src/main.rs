@@ -0,0 +1 @@
+sgcore::bin!(sg_diff_object);
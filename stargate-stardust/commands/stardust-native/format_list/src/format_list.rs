@@ -0,0 +1,145 @@
+use clap::{Arg, ArgAction, ArgMatches, Command, builder::PossibleValuesParser, value_parser};
+use serde_json::{Map, Value};
+use std::io::{self, Write};
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::json_adapter::{extract_records, read_stdin_value};
+use sgcore::render::{RenderOptions, render_list};
+
+pub mod options {
+    pub static FIELDS: &str = "fields";
+    pub static WIDTH: &str = "width";
+    pub static COLOR: &str = "color";
+    pub static RAW: &str = "raw";
+    pub static FIELD: &str = "field";
+}
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sg_app().try_get_matches_from(args)?;
+
+    let render_options = render_options(&matches);
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let json = read_stdin_value().map_err(|e| SGSimpleError::new(1, e))?;
+
+    let field = matches.get_one::<String>(options::FIELD).map(String::as_str);
+    let records = match extract_records(&json, field) {
+        Ok(records) => records.to_vec(),
+        // Not a collection: show the object itself
+        Err(_) if field.is_none() => vec![json],
+        Err(e) => return Err(SGSimpleError::new(1, e)),
+    };
+
+    let records = match matches.get_many::<String>(options::FIELDS) {
+        Some(fields) => {
+            let fields: Vec<&String> = fields.collect();
+            records.iter().map(|record| select_fields(record, &fields)).collect()
+        }
+        None => records,
+    };
+
+    let output = render_list(&Value::Array(records), &render_options);
+    io::stdout()
+        .write_all(output.as_bytes())
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))?;
+
+    Ok(())
+}
+
+fn render_options(matches: &ArgMatches) -> RenderOptions {
+    let mut render_options = RenderOptions::for_stdout();
+
+    if let Some(&width) = matches.get_one::<usize>(options::WIDTH) {
+        render_options.width = (width > 0).then_some(width);
+    }
+    match matches.get_one::<String>(options::COLOR).map(String::as_str) {
+        Some("always") => render_options.color = true,
+        Some("never") => render_options.color = false,
+        _ => {}
+    }
+    render_options.human = !matches.get_flag(options::RAW);
+
+    render_options
+}
+
+/// The record with only `fields`
+fn select_fields(record: &Value, fields: &[&String]) -> Value {
+    let Value::Object(map) = record else {
+        return record.clone();
+    };
+    let selected: Map<String, Value> = fields
+        .iter()
+        .filter_map(|&name| map.get(name).map(|value| (name.clone(), value.clone())))
+        .collect();
+    Value::Object(selected)
+}
+
+pub fn sg_app() -> Command {
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about("Render stardust records as lists of fields")
+        .override_usage("format-list [OPTIONS] [FIELD]...")
+        .after_help(
+            "Shows each record as aligned `name : value` lines, with nested objects \
+             and arrays indented beneath their field, and a blank line between \
+             records. Suits records with many or nested fields that do not fit a \
+             table. A stardust object without a record array is shown as one record.\n\n\
+             Examples:\n  \
+             list-processes | where-object -f processes 'pid == 1' | format-list\n  \
+             list-directory -l | format-list name permissions owner\n  \
+             get-hostname | format-list",
+        )
+        .arg(
+            Arg::new(options::FIELDS)
+                .value_name("FIELD")
+                .help("Fields to show (default: all)")
+                .num_args(0..)
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new(options::WIDTH)
+                .short('w')
+                .long(options::WIDTH)
+                .value_name("COLS")
+                .help("Truncate values to fit COLS columns; 0 for no limit (default: terminal width)")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new(options::COLOR)
+                .long(options::COLOR)
+                .value_name("WHEN")
+                .help("Color values by type")
+                .value_parser(PossibleValuesParser::new(["auto", "always", "never"]))
+                .default_value("auto"),
+        )
+        .arg(
+            Arg::new(options::RAW)
+                .short('r')
+                .long(options::RAW)
+                .help("Show sizes and timestamps as the raw numbers")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::FIELD)
+                .short('f')
+                .long("field")
+                .value_name("FIELD")
+                .help("Render the array under FIELD instead of detecting it"),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn selects_fields() {
+        let record = json!({"name": "a", "size": 1, "mode": "rw"});
+        let fields = ["mode".to_string(), "name".to_string(), "missing".to_string()];
+        let fields: Vec<&String> = fields.iter().collect();
+        let selected = select_fields(&record, &fields);
+        assert_eq!(selected, json!({"mode": "rw", "name": "a"}));
+        assert_eq!(select_fields(&json!(3), &fields), json!(3));
+    }
+}
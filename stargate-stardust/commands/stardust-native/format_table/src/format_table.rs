@@ -0,0 +1,135 @@
+use clap::{Arg, ArgAction, ArgMatches, Command, builder::PossibleValuesParser, value_parser};
+use std::io::{self, Write};
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::json_adapter::{extract_records, read_stdin_value};
+use sgcore::render::{RenderOptions, render_list, render_table};
+
+pub mod options {
+    pub static COLUMNS: &str = "columns";
+    pub static WIDTH: &str = "width";
+    pub static COLOR: &str = "color";
+    pub static RAW: &str = "raw";
+    pub static WRAP: &str = "wrap";
+    pub static SCHEMA: &str = "schema";
+    pub static FIELD: &str = "field";
+}
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sg_app().try_get_matches_from(args)?;
+
+    let render_options = render_options(&matches)?;
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let json = read_stdin_value().map_err(|e| SGSimpleError::new(1, e))?;
+
+    let field = matches.get_one::<String>(options::FIELD).map(String::as_str);
+    let output = match extract_records(&json, field) {
+        Ok(records) => render_table(records, &render_options),
+        // A single object has no rows; show it as a list instead
+        Err(_) if field.is_none() && json.is_object() => render_list(&json, &render_options),
+        Err(e) => return Err(SGSimpleError::new(1, e)),
+    };
+
+    io::stdout()
+        .write_all(output.as_bytes())
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))?;
+
+    Ok(())
+}
+
+fn render_options(matches: &ArgMatches) -> SGResult<RenderOptions> {
+    let mut render_options = RenderOptions::for_stdout();
+
+    if let Some(&width) = matches.get_one::<usize>(options::WIDTH) {
+        render_options.width = (width > 0).then_some(width);
+    }
+    match matches.get_one::<String>(options::COLOR).map(String::as_str) {
+        Some("always") => render_options.color = true,
+        Some("never") => render_options.color = false,
+        _ => {}
+    }
+    render_options.human = !matches.get_flag(options::RAW);
+    render_options.wrap = matches.get_flag(options::WRAP);
+    render_options.columns = matches
+        .get_many::<String>(options::COLUMNS)
+        .map(|columns| columns.cloned().collect());
+
+    if let Some(path) = matches.get_one::<String>(options::SCHEMA) {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| SGSimpleError::new(1, format!("Failed to read schema '{path}': {e}")))?;
+        let schema = serde_json::from_str(&text)
+            .map_err(|e| SGSimpleError::new(1, format!("Failed to parse schema '{path}': {e}")))?;
+        render_options.schema = Some(schema);
+    }
+
+    Ok(render_options)
+}
+
+pub fn sg_app() -> Command {
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about("Render stardust records as an aligned table")
+        .override_usage("format-table [OPTIONS] [COLUMN]...")
+        .after_help(
+            "Renders an array of records, or the entries/files/results array of a \
+             stardust object, with one row per record and one column per field.\n\
+             Columns are sized to fit the terminal; long cells are truncated with \
+             … unless --wrap is given. Sizes and unix timestamps are shown in \
+             human-readable form, from the field name or a schema \
+             (\"format\": \"bytes\" or \"unix-time\").\n\n\
+             Examples:\n  \
+             list-directory -l | format-table name size modified\n  \
+             list-processes | sort-object -f processes cpu:desc --top 10 | format-table\n  \
+             list-directory -l | format-table --raw --color never > listing.txt",
+        )
+        .arg(
+            Arg::new(options::COLUMNS)
+                .value_name("COLUMN")
+                .help("Fields to show, in order (default: all)")
+                .num_args(0..)
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new(options::WIDTH)
+                .short('w')
+                .long(options::WIDTH)
+                .value_name("COLS")
+                .help("Fit the table in COLS columns; 0 for no limit (default: terminal width)")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new(options::COLOR)
+                .long(options::COLOR)
+                .value_name("WHEN")
+                .help("Color values by type")
+                .value_parser(PossibleValuesParser::new(["auto", "always", "never"]))
+                .default_value("auto"),
+        )
+        .arg(
+            Arg::new(options::RAW)
+                .short('r')
+                .long(options::RAW)
+                .help("Show sizes and timestamps as the raw numbers")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::WRAP)
+                .long(options::WRAP)
+                .help("Wrap long cells onto several lines instead of truncating them")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::SCHEMA)
+                .long(options::SCHEMA)
+                .value_name("FILE")
+                .help("JSON schema describing the record fields"),
+        )
+        .arg(
+            Arg::new(options::FIELD)
+                .short('f')
+                .long("field")
+                .value_name("FIELD")
+                .help("Render the array under FIELD instead of detecting it"),
+        )
+}
@@ -0,0 +1,78 @@
+use clap::{Arg, ArgAction, Command, builder::PossibleValuesParser};
+use std::io::{self, Write};
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::json_adapter::{lookup_field, read_stdin_value};
+use sgcore::render::{RenderOptions, render_tree};
+
+pub mod options {
+    pub static COLOR: &str = "color";
+    pub static RAW: &str = "raw";
+    pub static FIELD: &str = "field";
+}
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sg_app().try_get_matches_from(args)?;
+
+    let mut render_options = RenderOptions::for_stdout();
+    match matches.get_one::<String>(options::COLOR).map(String::as_str) {
+        Some("always") => render_options.color = true,
+        Some("never") => render_options.color = false,
+        _ => {}
+    }
+    render_options.human = !matches.get_flag(options::RAW);
+
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let json = read_stdin_value().map_err(|e| SGSimpleError::new(1, e))?;
+
+    let root = match matches.get_one::<String>(options::FIELD) {
+        Some(path) => lookup_field(&json, path)
+            .ok_or_else(|| SGSimpleError::new(1, format!("Field '{path}' not found")))?,
+        None => &json,
+    };
+
+    io::stdout()
+        .write_all(render_tree(root, &render_options).as_bytes())
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))?;
+
+    Ok(())
+}
+
+pub fn sg_app() -> Command {
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about("Render nested stardust as a tree")
+        .override_usage("format-tree [OPTIONS]")
+        .after_help(
+            "Draws any stardust value as a tree: objects branch into their fields \
+             and arrays into their elements, which are labelled by their name, \
+             path or id field when they have one.\n\n\
+             Examples:\n  \
+             list-directory -l | format-tree\n  \
+             list-processes | format-tree -f processes.0\n  \
+             group-object type -i < listing.json | format-tree",
+        )
+        .arg(
+            Arg::new(options::COLOR)
+                .long(options::COLOR)
+                .value_name("WHEN")
+                .help("Color values by type")
+                .value_parser(PossibleValuesParser::new(["auto", "always", "never"]))
+                .default_value("auto"),
+        )
+        .arg(
+            Arg::new(options::RAW)
+                .short('r')
+                .long(options::RAW)
+                .help("Show sizes and timestamps as the raw numbers")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::FIELD)
+                .short('f')
+                .long("field")
+                .value_name("PATH")
+                .help("Render only the value at PATH, such as entries.0.owner"),
+        )
+}
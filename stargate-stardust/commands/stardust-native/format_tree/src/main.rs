@@ -0,0 +1 @@
+sgcore::bin!(sg_format_tree);
@@ -0,0 +1,190 @@
+//! Shared implementation of `from-csv` and `from-tsv`
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde_json::Value;
+use std::io::{self, Read};
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::tabular::{
+    Dialect, default_name, header_names, parse_delimited, parse_delimiter, records_output,
+    rows_to_records,
+};
+use sgcore::wire;
+
+pub mod options {
+    pub static FILE: &str = "file";
+    pub static DELIMITER: &str = "delimiter";
+    pub static QUOTE: &str = "quote";
+    pub static HEADER: &str = "header";
+    pub static NO_HEADER: &str = "no-header";
+    pub static NO_INFER: &str = "no-infer";
+    pub static PRETTY: &str = "pretty";
+}
+
+/// Parse the arguments and convert the input, with `dialect` giving the
+/// default delimiter and quote
+pub fn run(args: impl sgcore::Args, app: Command, dialect: Dialect) -> SGResult<()> {
+    let matches = app.try_get_matches_from(args)?;
+    let dialect = dialect_from(&matches, dialect)?;
+
+    let file = matches.get_one::<String>(options::FILE).map(String::as_str);
+    let input = read_input(file)?;
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let rows = parse_delimited(&input, &dialect)
+        .map_err(|e| SGSimpleError::new(1, format!("{}: {e}", file.unwrap_or("stdin"))))?;
+    let (names, records) = to_records(rows, &matches);
+
+    output_json(&records_output(records, Some(&names)), matches.get_flag(options::PRETTY))
+}
+
+fn dialect_from(matches: &ArgMatches, mut dialect: Dialect) -> SGResult<Dialect> {
+    if let Some(delimiter) = matches.get_one::<String>(options::DELIMITER) {
+        dialect.delimiter = parse_delimiter(delimiter).map_err(|e| SGSimpleError::new(1, e))?;
+    }
+    if let Some(quote) = matches.get_one::<String>(options::QUOTE) {
+        dialect.quote = parse_delimiter(quote)
+            .map_err(|_| SGSimpleError::new(1, format!("quote must be a single character, got '{quote}'")))?;
+    }
+    if dialect.delimiter == dialect.quote {
+        return Err(SGSimpleError::new(1, "delimiter and quote must differ".to_string()));
+    }
+    Ok(dialect)
+}
+
+/// Read FILE, or stdin when it is absent or `-`
+pub fn read_input(file: Option<&str>) -> SGResult<String> {
+    match file {
+        Some(path) if path != "-" => std::fs::read_to_string(path)
+            .map_err(|e| SGSimpleError::new(1, format!("Failed to read '{path}': {e}"))),
+        _ => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| SGSimpleError::new(1, format!("Failed to read stdin: {e}")))?;
+            Ok(input)
+        }
+    }
+}
+
+/// Name the fields from the header row or `--header`; fields without a
+/// name are `columnN`
+fn to_records(mut rows: Vec<Vec<String>>, matches: &ArgMatches) -> (Vec<String>, Vec<Value>) {
+    let infer = !matches.get_flag(options::NO_INFER);
+    let mut names = if let Some(names) = matches.get_one::<String>(options::HEADER) {
+        header_names(&names.split(',').collect::<Vec<_>>())
+    } else if matches.get_flag(options::NO_HEADER) || rows.is_empty() {
+        Vec::new()
+    } else {
+        header_names(&rows.remove(0))
+    };
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    names.extend((names.len()..width).map(default_name));
+
+    let records = rows_to_records(&names, rows, infer);
+    (names, records)
+}
+
+fn output_json(value: &Value, pretty: bool) -> SGResult<()> {
+    wire::print_value(value, pretty)
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))
+}
+
+/// The command line of a delimited reader; `format` names the format in help
+pub fn app(format: &str, dialect: Dialect) -> Command {
+    let name = format!("from-{}", format.to_lowercase());
+    let default_delimiter = if dialect.delimiter == '\t' { "tab".to_string() } else { dialect.delimiter.to_string() };
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about(format!("Read {format} into stardust records"))
+        .override_usage(format!("{name} [OPTIONS] [FILE]"))
+        .after_help(format!(
+            "Reads FILE (or stdin) as {format} and outputs {{\"records\": [...], \"count\": N, \
+             \"columns\": [...]}}, one record per row named by the header row. Quoting \
+             follows RFC 4180.\n\
+             Numbers and true/false become JSON values and empty fields null unless \
+             --no-infer is given. columns lists the fields in their original order \
+             with their types, including date and datetime for dates, which \
+             where-object compares as times.\n\n\
+             Examples:\n  \
+             {name} users.{ext} | where-object 'age >= 18' | to-{ext}\n  \
+             {name} data.{ext} | slice-object records | dice-object name email | to-{ext}\n  \
+             {name} --header id,name,score scores.{ext}",
+            ext = format.to_lowercase(),
+        ))
+        .arg(
+            Arg::new(options::FILE)
+                .value_name("FILE")
+                .help("File to read; stdin when absent or -"),
+        )
+        .arg(
+            Arg::new(options::DELIMITER)
+                .short('d')
+                .long(options::DELIMITER)
+                .value_name("CHAR")
+                .help(format!("Field delimiter; \\t or tab for a tab (default: {default_delimiter})")),
+        )
+        .arg(
+            Arg::new(options::QUOTE)
+                .short('q')
+                .long(options::QUOTE)
+                .value_name("CHAR")
+                .help("Quote character (default: \")"),
+        )
+        .arg(
+            Arg::new(options::HEADER)
+                .short('H')
+                .long(options::HEADER)
+                .value_name("NAMES")
+                .help("Comma-separated field names; the first row is then data"),
+        )
+        .arg(
+            Arg::new(options::NO_HEADER)
+                .short('n')
+                .long(options::NO_HEADER)
+                .help("The first row is data; fields are named column1, column2, ...")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::NO_INFER)
+                .short('S')
+                .long(options::NO_INFER)
+                .help("Keep every field as a string")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::PRETTY)
+                .short('p')
+                .long("pretty")
+                .help("Output pretty-printed JSON")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn convert(text: &str, args: &[&str]) -> Vec<Value> {
+        let matches = app("CSV", Dialect::csv()).get_matches_from(std::iter::once("from-csv").chain(args.iter().copied()));
+        let dialect = dialect_from(&matches, Dialect::csv()).unwrap();
+        to_records(parse_delimited(text, &dialect).unwrap(), &matches).1
+    }
+
+    #[test]
+    fn header_row_names_fields() {
+        assert_eq!(
+            convert("name,age\nann,31\nbob,\n", &[]),
+            vec![json!({"name": "ann", "age": 31}), json!({"name": "bob", "age": null})]
+        );
+    }
+
+    #[test]
+    fn explicit_names_and_delimiter() {
+        assert_eq!(
+            convert("1;007\n", &["-d", ";", "-H", "id,code"]),
+            vec![json!({"id": 1, "code": "007"})]
+        );
+        assert_eq!(convert("1;2\n", &["-d", ";", "-n", "-S"]), vec![json!({"column1": "1", "column2": "2"})]);
+    }
+}
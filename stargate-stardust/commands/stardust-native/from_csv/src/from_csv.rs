@@ -0,0 +1,14 @@
+pub mod delimited_input;
+
+use clap::Command;
+use sgcore::error::SGResult;
+use sgcore::tabular::Dialect;
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    delimited_input::run(args, sg_app(), Dialect::csv())
+}
+
+pub fn sg_app() -> Command {
+    delimited_input::app("CSV", Dialect::csv())
+}
@@ -0,0 +1,109 @@
+use clap::{Arg, ArgAction, Command};
+use serde_json::Value;
+use std::io::{self, Read};
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::show_warning;
+use sgcore::tabular::records_output;
+use sgcore::wire;
+
+pub mod options {
+    pub static FILE: &str = "file";
+    pub static SKIP_INVALID: &str = "skip-invalid";
+    pub static PRETTY: &str = "pretty";
+}
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sg_app().try_get_matches_from(args)?;
+
+    let file = matches.get_one::<String>(options::FILE).map(String::as_str);
+    let input = match file {
+        Some(path) if path != "-" => std::fs::read_to_string(path)
+            .map_err(|e| SGSimpleError::new(1, format!("Failed to read '{path}': {e}")))?,
+        _ => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| SGSimpleError::new(1, format!("Failed to read stdin: {e}")))?;
+            input
+        }
+    };
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let records = parse_lines(&input, matches.get_flag(options::SKIP_INVALID))
+        .map_err(|e| SGSimpleError::new(1, format!("{}: {e}", file.unwrap_or("stdin"))))?;
+
+    output_json(&records_output(records, None), matches.get_flag(options::PRETTY))?;
+
+    Ok(())
+}
+
+/// One value per non-blank line. Invalid lines are an error, or are reported
+/// and skipped with `skip_invalid`.
+fn parse_lines(input: &str, skip_invalid: bool) -> Result<Vec<Value>, String> {
+    let mut records = Vec::new();
+    for (number, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(value) => records.push(value),
+            Err(e) if skip_invalid => show_warning!("line {}: skipped: {e}", number + 1),
+            Err(e) => return Err(format!("line {}: {e}", number + 1)),
+        }
+    }
+    Ok(records)
+}
+
+fn output_json(value: &Value, pretty: bool) -> SGResult<()> {
+    wire::print_value(value, pretty)
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))
+}
+
+pub fn sg_app() -> Command {
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about("Read JSON Lines into stardust records")
+        .override_usage("from-jsonl [OPTIONS] [FILE]")
+        .after_help(
+            "Reads FILE (or stdin) with one JSON value per line, as written by \
+             to-jsonl and many logging tools, and outputs \
+             {\"records\": [...], \"count\": N}. Blank lines are ignored.\n\n\
+             Examples:\n  \
+             from-jsonl events.jsonl | where-object 'level == \"error\"' | format-table\n  \
+             from-jsonl --skip-invalid app.log | group-object level",
+        )
+        .arg(
+            Arg::new(options::FILE)
+                .value_name("FILE")
+                .help("File to read; stdin when absent or -"),
+        )
+        .arg(
+            Arg::new(options::SKIP_INVALID)
+                .short('s')
+                .long(options::SKIP_INVALID)
+                .help("Warn about lines that are not valid JSON and skip them")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::PRETTY)
+                .short('p')
+                .long("pretty")
+                .help("Output pretty-printed JSON")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_lines() {
+        let input = "{\"a\": 1}\n\n  {\"a\": 2}  \nnot json\n";
+        assert!(parse_lines(input, false).unwrap_err().starts_with("line 4:"));
+        assert_eq!(parse_lines(input, true).unwrap(), vec![json!({"a": 1}), json!({"a": 2})]);
+    }
+}
@@ -0,0 +1,292 @@
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::io::{self, Read};
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::tabular::{default_name, field_value, header_names, records_output, rows_to_records};
+use sgcore::wire;
+
+pub mod options {
+    pub static FILE: &str = "file";
+    pub static REGEX: &str = "regex";
+    pub static COLUMNS: &str = "columns";
+    pub static HEADER: &str = "header";
+    pub static NO_HEADER: &str = "no-header";
+    pub static SKIP: &str = "skip";
+    pub static NO_INFER: &str = "no-infer";
+    pub static PRETTY: &str = "pretty";
+}
+
+/// How lines are split into fields
+enum Splitter {
+    /// Runs of whitespace; the last field takes the rest of the line
+    Whitespace,
+    /// Capture groups, named or `columnN`
+    Regex(Regex),
+    /// Fixed character ranges, `(name, start, end)` with `end` exclusive
+    Columns(Vec<(String, usize, Option<usize>)>),
+}
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sg_app().try_get_matches_from(args)?;
+    let splitter = splitter(&matches)?;
+
+    let file = matches.get_one::<String>(options::FILE).map(String::as_str);
+    let input = match file {
+        Some(path) if path != "-" => std::fs::read_to_string(path)
+            .map_err(|e| SGSimpleError::new(1, format!("Failed to read '{path}': {e}")))?,
+        _ => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| SGSimpleError::new(1, format!("Failed to read stdin: {e}")))?;
+            input
+        }
+    };
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let (names, records) = split_text(&input, &splitter, &matches);
+    output_json(&records_output(records, Some(&names)), matches.get_flag(options::PRETTY))?;
+
+    Ok(())
+}
+
+fn splitter(matches: &ArgMatches) -> SGResult<Splitter> {
+    if let Some(pattern) = matches.get_one::<String>(options::REGEX) {
+        let regex = Regex::new(pattern)
+            .map_err(|e| SGSimpleError::new(1, format!("Invalid regex '{pattern}': {e}")))?;
+        return Ok(Splitter::Regex(regex));
+    }
+    if let Some(spec) = matches.get_one::<String>(options::COLUMNS) {
+        return parse_columns(spec).map(Splitter::Columns).map_err(|e| SGSimpleError::new(1, e));
+    }
+    Ok(Splitter::Whitespace)
+}
+
+/// Parse `name:1-10,size:12-` into 0-based ranges; positions are 1-based
+/// and inclusive, as for `cut -c`, and an open end runs to the end of the line
+fn parse_columns(spec: &str) -> Result<Vec<(String, usize, Option<usize>)>, String> {
+    spec.split(',')
+        .map(|column| {
+            let invalid = || format!("invalid column '{column}': use NAME:START-END or NAME:START-");
+            let (name, range) = column.split_once(':').ok_or_else(invalid)?;
+            let (start, end) = range.split_once('-').ok_or_else(invalid)?;
+            let start: usize = start.trim().parse().map_err(|_| invalid())?;
+            let end: Option<usize> = match end.trim() {
+                "" => None,
+                end => Some(end.parse().map_err(|_| invalid())?),
+            };
+            if start == 0 || end.is_some_and(|end| end < start) {
+                return Err(invalid());
+            }
+            Ok((name.trim().to_string(), start - 1, end))
+        })
+        .collect()
+}
+
+/// The field names and the records of `input`
+fn split_text(input: &str, splitter: &Splitter, matches: &ArgMatches) -> (Vec<String>, Vec<Value>) {
+    let infer = !matches.get_flag(options::NO_INFER);
+    let skip = matches.get_one::<usize>(options::SKIP).copied().unwrap_or(0);
+    let mut lines = input.lines().skip(skip).filter(|line| !line.trim().is_empty());
+
+    match splitter {
+        Splitter::Whitespace => {
+            let mut names = if let Some(names) = matches.get_one::<String>(options::HEADER) {
+                header_names(&names.split(',').collect::<Vec<_>>())
+            } else if matches.get_flag(options::NO_HEADER) {
+                Vec::new()
+            } else {
+                lines
+                    .next()
+                    .map(|header| header_names(&header.split_whitespace().collect::<Vec<_>>()))
+                    .unwrap_or_default()
+            };
+            let rows: Vec<Vec<String>> = lines.map(|line| split_whitespace(line, names.len())).collect();
+            let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+            names.extend((names.len()..width).map(default_name));
+            let records = rows_to_records(&names, rows, infer);
+            (names, records)
+        }
+        Splitter::Regex(regex) => {
+            let names: Vec<String> = regex
+                .capture_names()
+                .enumerate()
+                .skip(1)
+                .map(|(i, name)| name.map_or_else(|| default_name(i - 1), str::to_string))
+                .collect();
+            let records = lines
+                .filter_map(|line| regex.captures(line))
+                .map(|captures| {
+                    let record: Map<String, Value> = names
+                        .iter()
+                        .enumerate()
+                        .map(|(i, name)| {
+                            let value = captures.get(i + 1).map_or(Value::Null, |m| field_value(m.as_str(), infer));
+                            (name.clone(), value)
+                        })
+                        .collect();
+                    Value::Object(record)
+                })
+                .collect();
+            (names, records)
+        }
+        Splitter::Columns(columns) => {
+            let names = columns.iter().map(|(name, _, _)| name.clone()).collect();
+            let records = lines
+                .map(|line| {
+                    let chars: Vec<char> = line.chars().collect();
+                    let record: Map<String, Value> = columns
+                        .iter()
+                        .map(|(name, start, end)| {
+                            let end = end.unwrap_or(chars.len()).min(chars.len());
+                            let text: String = chars.get(*start..end).unwrap_or_default().iter().collect();
+                            (name.clone(), field_value(text.trim(), infer))
+                        })
+                        .collect();
+                    Value::Object(record)
+                })
+                .collect();
+            (names, records)
+        }
+    }
+}
+
+/// Split on whitespace into at most `limit` fields (any number when 0), the
+/// last keeping the rest of the line, such as a command with its arguments
+fn split_whitespace(line: &str, limit: usize) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut rest = line.trim();
+    while !rest.is_empty() {
+        if limit > 0 && fields.len() + 1 == limit {
+            fields.push(rest.to_string());
+            break;
+        }
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        fields.push(rest[..end].to_string());
+        rest = rest[end..].trim_start();
+    }
+    fields
+}
+
+fn output_json(value: &Value, pretty: bool) -> SGResult<()> {
+    wire::print_value(value, pretty)
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))
+}
+
+pub fn sg_app() -> Command {
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about("Split the text output of other tools into stardust records")
+        .override_usage("from-text [OPTIONS] [FILE]")
+        .after_help(
+            "Turns each line of FILE (or stdin) into a record and outputs \
+             {\"records\": [...], \"count\": N, \"columns\": [...]}. By default lines are split on \
+             whitespace with field names from the first line, and the last field \
+             keeps the rest of the line. With --regex the capture groups are the \
+             fields and lines that do not match are skipped; with --columns fields \
+             are fixed character ranges.\n\
+             Numbers and true/false become JSON values unless --no-infer is given; \
+             columns lists the fields in order with their types.\n\n\
+             Examples:\n  \
+             ps aux | from-text | where-object 'USER == root' | format-table\n  \
+             from-text --regex '(?P<user>\\S+):x:(?P<uid>\\d+):(?P<gid>\\d+)' /etc/passwd\n  \
+             from-text --skip 1 --columns 'name:1-20,size:21-30,date:31-' report.txt\n  \
+             mount | from-text --header device,on,dir,type,fstype,options",
+        )
+        .arg(
+            Arg::new(options::FILE)
+                .value_name("FILE")
+                .help("File to read; stdin when absent or -"),
+        )
+        .arg(
+            Arg::new(options::REGEX)
+                .short('r')
+                .long(options::REGEX)
+                .value_name("REGEX")
+                .help("Fields are the capture groups of REGEX; (?P<name>...) names a field")
+                .conflicts_with(options::COLUMNS),
+        )
+        .arg(
+            Arg::new(options::COLUMNS)
+                .short('c')
+                .long(options::COLUMNS)
+                .value_name("SPEC")
+                .help("Fixed-width fields such as name:1-20,size:21- (1-based character positions)"),
+        )
+        .arg(
+            Arg::new(options::HEADER)
+                .short('H')
+                .long(options::HEADER)
+                .value_name("NAMES")
+                .help("Comma-separated field names for whitespace splitting; the first line is then data")
+                .conflicts_with_all([options::REGEX, options::COLUMNS]),
+        )
+        .arg(
+            Arg::new(options::NO_HEADER)
+                .short('n')
+                .long(options::NO_HEADER)
+                .help("The first line is data; fields are named column1, column2, ...")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::SKIP)
+                .short('s')
+                .long(options::SKIP)
+                .value_name("N")
+                .help("Ignore the first N lines")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new(options::NO_INFER)
+                .short('S')
+                .long(options::NO_INFER)
+                .help("Keep every field as a string")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::PRETTY)
+                .short('p')
+                .long("pretty")
+                .help("Output pretty-printed JSON")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn convert(text: &str, args: &[&str]) -> Vec<Value> {
+        let matches = sg_app().get_matches_from(std::iter::once("from-text").chain(args.iter().copied()));
+        split_text(text, &splitter(&matches).unwrap(), &matches).1
+    }
+
+    #[test]
+    fn whitespace_with_header() {
+        let text = "USER PID COMMAND\nroot 1 /sbin/init splash\nann  42 vim  notes.txt\n";
+        assert_eq!(
+            convert(text, &[]),
+            vec![
+                json!({"USER": "root", "PID": 1, "COMMAND": "/sbin/init splash"}),
+                json!({"USER": "ann", "PID": 42, "COMMAND": "vim  notes.txt"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn regex_and_columns() {
+        let text = "root:x:0:0\nbad line\n";
+        assert_eq!(
+            convert(text, &["-r", r"(?P<user>\w+):x:(?P<uid>\d+):(\d+)"]),
+            vec![json!({"user": "root", "uid": 0, "column3": 0})]
+        );
+        assert_eq!(
+            convert("abc  12\n", &["-c", "name:1-4,n:5-"]),
+            vec![json!({"name": "abc", "n": 12})]
+        );
+        assert!(parse_columns("name:5-2").is_err());
+    }
+}
@@ -0,0 +1,13 @@
+use clap::Command;
+use sg_from_csv::delimited_input;
+use sgcore::error::SGResult;
+use sgcore::tabular::Dialect;
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    delimited_input::run(args, sg_app(), Dialect::tsv())
+}
+
+pub fn sg_app() -> Command {
+    delimited_input::app("TSV", Dialect::tsv())
+}
@@ -0,0 +1 @@
+sgcore::bin!(sg_from_tsv);
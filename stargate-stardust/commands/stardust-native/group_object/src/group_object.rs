@@ -0,0 +1,269 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::json_adapter::{compare_values, extract_records, lookup_field, read_stdin_value};
+use sgcore::wire;
+
+pub mod options {
+    pub static KEYS: &str = "keys";
+    pub static SUM: &str = "sum";
+    pub static MIN: &str = "min";
+    pub static MAX: &str = "max";
+    pub static AVG: &str = "avg";
+    pub static ITEMS: &str = "items";
+    pub static FIELD: &str = "field";
+    pub static PRETTY: &str = "pretty";
+}
+
+#[derive(Clone, Copy)]
+enum Aggregate {
+    Sum,
+    Min,
+    Max,
+    Avg,
+}
+
+impl Aggregate {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Avg => "avg",
+        }
+    }
+
+    fn apply(self, values: &[&Value]) -> Value {
+        match self {
+            Self::Sum => number_value(numbers(values).sum()),
+            Self::Avg => {
+                let numbers: Vec<f64> = numbers(values).collect();
+                if numbers.is_empty() {
+                    Value::Null
+                } else {
+                    number_value(numbers.iter().sum::<f64>() / numbers.len() as f64)
+                }
+            }
+            Self::Min => non_null(values).min_by(|a, b| compare_values(a, b)).cloned().unwrap_or(Value::Null),
+            Self::Max => non_null(values).max_by(|a, b| compare_values(a, b)).cloned().unwrap_or(Value::Null),
+        }
+    }
+}
+
+struct Group {
+    key: Vec<Value>,
+    records: Vec<Value>,
+}
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sg_app().try_get_matches_from(args)?;
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let keys: Vec<String> = matches
+        .get_many::<String>(options::KEYS)
+        .map(|keys| keys.cloned().collect())
+        .unwrap_or_default();
+    let aggregates = requested_aggregates(&matches);
+
+    let json = read_stdin_value().map_err(|e| SGSimpleError::new(1, e))?;
+
+    let field = matches.get_one::<String>(options::FIELD).map(String::as_str);
+    let records = extract_records(&json, field).map_err(|e| SGSimpleError::new(1, e))?;
+
+    let groups = group_records(records, &keys);
+    let result: Vec<Value> = groups
+        .into_iter()
+        .map(|group| summarize(group, &keys, &aggregates, matches.get_flag(options::ITEMS)))
+        .collect();
+
+    output_json(&Value::Array(result), matches.get_flag(options::PRETTY))?;
+
+    Ok(())
+}
+
+fn requested_aggregates(matches: &ArgMatches) -> Vec<(Aggregate, String)> {
+    let kinds = [
+        (options::SUM, Aggregate::Sum),
+        (options::MIN, Aggregate::Min),
+        (options::MAX, Aggregate::Max),
+        (options::AVG, Aggregate::Avg),
+    ];
+    kinds
+        .into_iter()
+        .flat_map(|(option, aggregate)| {
+            matches
+                .get_many::<String>(option)
+                .into_iter()
+                .flatten()
+                .map(move |path| (aggregate, path.clone()))
+        })
+        .collect()
+}
+
+/// Group records by the values at `keys`, in order of first appearance.
+/// Without keys every record falls into a single group.
+fn group_records(records: &[Value], keys: &[String]) -> Vec<Group> {
+    let mut groups: Vec<Group> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for record in records {
+        let key: Vec<Value> = keys
+            .iter()
+            .map(|path| lookup_field(record, path).cloned().unwrap_or(Value::Null))
+            .collect();
+        let id = Value::Array(key.clone()).to_string();
+
+        let slot = *index.entry(id).or_insert_with(|| {
+            groups.push(Group { key, records: Vec::new() });
+            groups.len() - 1
+        });
+        groups[slot].records.push(record.clone());
+    }
+
+    groups
+}
+
+/// One output record per group: the key fields, `count`, then
+/// `<aggregate>_<field>` for each requested aggregate
+fn summarize(group: Group, keys: &[String], aggregates: &[(Aggregate, String)], items: bool) -> Value {
+    let mut summary = Map::new();
+    for (path, value) in keys.iter().zip(group.key) {
+        summary.insert(path.clone(), value);
+    }
+    summary.insert("count".to_string(), Value::from(group.records.len()));
+
+    for (aggregate, path) in aggregates {
+        let values: Vec<&Value> = group
+            .records
+            .iter()
+            .filter_map(|record| lookup_field(record, path))
+            .collect();
+        summary.insert(format!("{}_{path}", aggregate.name()), aggregate.apply(&values));
+    }
+
+    if items {
+        summary.insert("items".to_string(), Value::Array(group.records));
+    }
+    Value::Object(summary)
+}
+
+fn numbers<'a>(values: &'a [&'a Value]) -> impl Iterator<Item = f64> + 'a {
+    values.iter().filter_map(|value| value.as_f64())
+}
+
+fn non_null<'a>(values: &'a [&'a Value]) -> impl Iterator<Item = &'a Value> + 'a {
+    values.iter().copied().filter(|value| !value.is_null())
+}
+
+/// Whole numbers stay integers so sums of sizes and counts read naturally
+fn number_value(n: f64) -> Value {
+    if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
+        Value::from(n as i64)
+    } else {
+        Value::from(n)
+    }
+}
+
+fn output_json(value: &Value, pretty: bool) -> SGResult<()> {
+    wire::print_value(value, pretty)
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))
+}
+
+fn aggregate_arg(name: &'static str, help: &'static str) -> Arg {
+    Arg::new(name)
+        .long(name)
+        .value_name("FIELD")
+        .help(help)
+        .action(ArgAction::Append)
+}
+
+pub fn sg_app() -> Command {
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about("Group stardust records by fields and aggregate them")
+        .override_usage("group-object [OPTIONS] [FIELD]...")
+        .after_help(
+            "Outputs one record per distinct combination of FIELD values, in order \
+             of first appearance, with a count and the requested aggregates. \
+             Without FIELD all records are aggregated into a single record.\n\n\
+             Examples:\n  \
+             list-directory -l | group-object type --sum size --max modified\n  \
+             list-processes | group-object -f processes user | sort-object count:desc --top 3\n  \
+             list-directory -l | group-object --sum size --avg size",
+        )
+        .arg(
+            Arg::new(options::KEYS)
+                .value_name("FIELD")
+                .help("Field path to group by")
+                .num_args(0..)
+                .action(ArgAction::Append),
+        )
+        .arg(aggregate_arg(options::SUM, "Add sum_FIELD: the total of FIELD in each group"))
+        .arg(aggregate_arg(options::MIN, "Add min_FIELD: the smallest FIELD in each group"))
+        .arg(aggregate_arg(options::MAX, "Add max_FIELD: the largest FIELD in each group"))
+        .arg(aggregate_arg(options::AVG, "Add avg_FIELD: the mean of FIELD in each group"))
+        .arg(
+            Arg::new(options::ITEMS)
+                .short('i')
+                .long(options::ITEMS)
+                .help("Include each group's records as items")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::FIELD)
+                .short('f')
+                .long("field")
+                .value_name("FIELD")
+                .help("Group the array under FIELD instead of detecting it"),
+        )
+        .arg(
+            Arg::new(options::PRETTY)
+                .short('p')
+                .long("pretty")
+                .help("Output pretty-printed JSON")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn groups_and_aggregates() {
+        let records = json!([
+            {"type": "file", "size": 10},
+            {"type": "dir", "size": 4},
+            {"type": "file", "size": 5},
+        ]);
+        let keys = vec!["type".to_string()];
+        let aggregates = vec![
+            (Aggregate::Sum, "size".to_string()),
+            (Aggregate::Max, "size".to_string()),
+            (Aggregate::Avg, "size".to_string()),
+        ];
+        let result: Vec<Value> = group_records(records.as_array().unwrap(), &keys)
+            .into_iter()
+            .map(|group| summarize(group, &keys, &aggregates, false))
+            .collect();
+
+        assert_eq!(
+            Value::Array(result),
+            json!([
+                {"type": "file", "count": 2, "sum_size": 15, "max_size": 10, "avg_size": 7.5},
+                {"type": "dir", "count": 1, "sum_size": 4, "max_size": 4, "avg_size": 4},
+            ])
+        );
+    }
+
+    #[test]
+    fn no_keys_is_one_group() {
+        let records = json!([{"a": 1}, {"a": 2}]);
+        let groups = group_records(records.as_array().unwrap(), &[]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(summarize(groups.into_iter().next().unwrap(), &[], &[], false), json!({"count": 2}));
+    }
+}
@@ -0,0 +1 @@
+sgcore::bin!(sg_group_object);
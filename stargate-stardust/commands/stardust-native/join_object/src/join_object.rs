@@ -0,0 +1,283 @@
+use clap::{Arg, ArgAction, Command, builder::PossibleValuesParser};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::json_adapter::{extract_records, load_source, lookup_field, number_key, read_stdin_value};
+use sgcore::wire;
+
+pub mod options {
+    pub static SOURCE: &str = "source";
+    pub static ON: &str = "on";
+    pub static TYPE: &str = "type";
+    pub static PREFIX: &str = "prefix";
+    pub static FIELD: &str = "field";
+    pub static RIGHT_FIELD: &str = "right-field";
+    pub static PRETTY: &str = "pretty";
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+}
+
+impl JoinType {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "left" => Self::Left,
+            "right" => Self::Right,
+            "full" => Self::Full,
+            _ => Self::Inner,
+        }
+    }
+
+    fn keeps_unmatched_left(self) -> bool {
+        matches!(self, Self::Left | Self::Full)
+    }
+
+    fn keeps_unmatched_right(self) -> bool {
+        matches!(self, Self::Right | Self::Full)
+    }
+}
+
+struct JoinSpec<'a> {
+    left_key: &'a str,
+    right_key: &'a str,
+    join_type: JoinType,
+    prefix: &'a str,
+}
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sg_app().try_get_matches_from(args)?;
+
+    let source = matches
+        .get_one::<String>(options::SOURCE)
+        .expect("source is required");
+    let right_json = load_source(source).map_err(|e| SGSimpleError::new(1, e))?;
+
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let on = matches.get_one::<String>(options::ON).expect("--on is required");
+    let (left_key, right_key) = on.split_once('=').unwrap_or((on, on));
+    let spec = JoinSpec {
+        left_key,
+        right_key,
+        join_type: JoinType::from_name(
+            matches.get_one::<String>(options::TYPE).map_or("inner", String::as_str),
+        ),
+        prefix: matches.get_one::<String>(options::PREFIX).map_or("right_", String::as_str),
+    };
+
+    let left_json = read_stdin_value().map_err(|e| SGSimpleError::new(1, e))?;
+
+    let left = extract_records(&left_json, matches.get_one::<String>(options::FIELD).map(String::as_str))
+        .map_err(|e| SGSimpleError::new(1, e))?;
+    let right = extract_records(&right_json, matches.get_one::<String>(options::RIGHT_FIELD).map(String::as_str))
+        .map_err(|e| SGSimpleError::new(1, format!("{source}: {e}")))?;
+
+    let joined = join_records(left, right, &spec);
+    output_json(&Value::Array(joined), matches.get_flag(options::PRETTY))?;
+
+    Ok(())
+}
+
+/// Hash join: index the right records by key, then probe with each left
+/// record, so neither input needs to be sorted. Records without the key
+/// never match.
+fn join_records(left: &[Value], right: &[Value], spec: &JoinSpec) -> Vec<Value> {
+    let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, record) in right.iter().enumerate() {
+        if let Some(key) = join_key(record, spec.right_key) {
+            index.entry(key).or_default().push(i);
+        }
+    }
+
+    let mut joined = Vec::new();
+    let mut matched_right = HashSet::new();
+    for record in left {
+        let matches = join_key(record, spec.left_key).and_then(|key| index.get(&key));
+        match matches {
+            Some(rows) => {
+                for &i in rows {
+                    matched_right.insert(i);
+                    joined.push(merge(record, &right[i], spec));
+                }
+            }
+            None if spec.join_type.keeps_unmatched_left() => joined.push(record.clone()),
+            None => {}
+        }
+    }
+
+    if spec.join_type.keeps_unmatched_right() {
+        joined.extend(
+            right
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !matched_right.contains(i))
+                .map(|(_, record)| record.clone()),
+        );
+    }
+
+    joined
+}
+
+/// Comparable form of a record's key; numbers match whatever their notation
+/// (`1000`, `1000.0`) and strings match verbatim, so `"7"` joins `7` but not `"007"`
+fn join_key(record: &Value, path: &str) -> Option<String> {
+    match lookup_field(record, path)? {
+        Value::Null => None,
+        Value::Number(n) => Some(number_key(n)),
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// The left record's fields followed by the right record's. A right field
+/// whose name is already taken gets `prefix`, unless it holds the same
+/// value (such as the join key itself).
+fn merge(left: &Value, right: &Value, spec: &JoinSpec) -> Value {
+    let mut merged = as_map(left);
+    for (name, value) in as_map(right) {
+        match merged.get(&name) {
+            None => {
+                merged.insert(name, value);
+            }
+            Some(existing) if *existing == value => {}
+            Some(_) => {
+                merged.insert(format!("{}{name}", spec.prefix), value);
+            }
+        }
+    }
+    Value::Object(merged)
+}
+
+fn as_map(record: &Value) -> Map<String, Value> {
+    match record {
+        Value::Object(map) => map.clone(),
+        other => Map::from_iter([("value".to_string(), other.clone())]),
+    }
+}
+
+fn output_json(value: &Value, pretty: bool) -> SGResult<()> {
+    wire::print_value(value, pretty)
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))
+}
+
+pub fn sg_app() -> Command {
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about("Join the piped stardust records with a second input on a key")
+        .override_usage("join-object [OPTIONS] --on KEY[=RIGHT_KEY] SOURCE")
+        .after_help(
+            "SOURCE is a JSON file, inline JSON, or a command in parentheses such \
+             as \"(list-processes)\", which is run with --obj.\n\
+             Records need not be sorted. Right-hand fields whose names clash with \
+             left-hand ones are prefixed with right_ (see --prefix).\n\n\
+             Examples:\n  \
+             list-processes | join-object --on user=name --type left '(users)'\n  \
+             list-directory -l | join-object --on path sums.json\n  \
+             list-directory -l | join-object --on name --type full '(list-directory -l /tmp)'",
+        )
+        .arg(
+            Arg::new(options::SOURCE)
+                .value_name("SOURCE")
+                .help("Second input: a JSON file, inline JSON or (command)")
+                .required(true),
+        )
+        .arg(
+            Arg::new(options::ON)
+                .short('k')
+                .long(options::ON)
+                .value_name("KEY[=RIGHT_KEY]")
+                .help("Field path to join on; use LEFT=RIGHT when the names differ")
+                .required(true),
+        )
+        .arg(
+            Arg::new(options::TYPE)
+                .short('t')
+                .long(options::TYPE)
+                .value_name("TYPE")
+                .help("Join type")
+                .value_parser(PossibleValuesParser::new(["inner", "left", "right", "full"]))
+                .default_value("inner"),
+        )
+        .arg(
+            Arg::new(options::PREFIX)
+                .long(options::PREFIX)
+                .value_name("PREFIX")
+                .help("Prefix for clashing right-hand field names")
+                .default_value("right_"),
+        )
+        .arg(
+            Arg::new(options::FIELD)
+                .short('f')
+                .long("field")
+                .value_name("FIELD")
+                .help("Join the piped array under FIELD instead of detecting it"),
+        )
+        .arg(
+            Arg::new(options::RIGHT_FIELD)
+                .long(options::RIGHT_FIELD)
+                .value_name("FIELD")
+                .help("Join the SOURCE array under FIELD instead of detecting it"),
+        )
+        .arg(
+            Arg::new(options::PRETTY)
+                .short('p')
+                .long("pretty")
+                .help("Output pretty-printed JSON")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn join(join_type: JoinType) -> Value {
+        let left = json!([
+            {"pid": 1, "uid": 0, "name": "init"},
+            {"pid": 2, "uid": 1000, "name": "sh"},
+            {"pid": 3, "name": "kthread"},
+        ]);
+        let right = json!([
+            {"uid": "0", "name": "root"},
+            {"uid": 2000, "name": "guest"},
+        ]);
+        let spec = JoinSpec { left_key: "uid", right_key: "uid", join_type, prefix: "right_" };
+        Value::Array(join_records(left.as_array().unwrap(), right.as_array().unwrap(), &spec))
+    }
+
+    #[test]
+    fn inner_join_prefixes_conflicts() {
+        assert_eq!(
+            join(JoinType::Inner),
+            json!([{"pid": 1, "uid": 0, "name": "init", "right_uid": "0", "right_name": "root"}])
+        );
+    }
+
+    #[test]
+    fn outer_joins_keep_unmatched() {
+        assert_eq!(join(JoinType::Left).as_array().unwrap().len(), 3);
+        assert_eq!(join(JoinType::Right).as_array().unwrap().len(), 2);
+        let full = join(JoinType::Full);
+        assert_eq!(full.as_array().unwrap().len(), 4);
+        assert_eq!(full[3], json!({"uid": 2000, "name": "guest"}));
+    }
+
+    #[test]
+    fn keys_match_exactly() {
+        let key = |value: Value| join_key(&json!({"k": value}), "k");
+        assert_eq!(key(json!(1000)), key(json!(1000.0)));
+        assert_eq!(key(json!(7)), key(json!("7")));
+        assert_ne!(key(json!("007")), key(json!("7")));
+        assert_ne!(key(json!("1e3")), key(json!(1000)));
+        assert_ne!(key(json!(9_007_199_254_740_993_u64)), key(json!(9_007_199_254_740_992_u64)));
+        assert_ne!(key(json!("9007199254740993")), key(json!("9007199254740992")));
+        assert_eq!(key(json!(u64::MAX)), key(json!(u64::MAX.to_string())));
+    }
+}
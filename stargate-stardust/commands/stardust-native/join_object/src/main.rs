@@ -0,0 +1 @@
+sgcore::bin!(sg_join_object);
@@ -1,7 +1,9 @@
 use clap::{Arg, ArgAction, Command};
 use serde_json::Value;
-use std::io::{self, Read};
 use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::field_path::FieldPath;
+use sgcore::json_adapter::read_stdin_value;
+use sgcore::wire;
 
 pub mod options {
     pub static FIELD: &str = "field";
@@ -15,13 +17,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sg_app().try_get_matches_from(args)?;
     sgcore::pledge::apply_pledge(&["stdio"])?;
 
-    let mut input = String::new();
-    io::stdin()
-        .read_to_string(&mut input)
-        .map_err(|e| SGSimpleError::new(1, format!("Failed to read stdin: {}", e)))?;
-
-    let json: Value = serde_json::from_str(&input)
-        .map_err(|e| SGSimpleError::new(1, format!("Failed to parse JSON: {}", e)))?;
+    let json = read_stdin_value().map_err(|e| SGSimpleError::new(1, e))?;
 
     let pretty = matches.get_flag(options::PRETTY);
 
@@ -35,104 +31,24 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     Ok(())
 }
 
+/// The value at the field path `field`; on an array the path applies to
+/// each element, as in `slice-object name` on a list of records
 fn extract_field(json: &Value, field: &str) -> SGResult<Value> {
-    match json {
-        Value::Object(map) => {
-            if let Some(value) = map.get(field) {
-                Ok(value.clone())
-            } else {
-                Err(SGSimpleError::new(
-                    1,
-                    format!("Field '{}' not found in JSON object", field),
-                ))
-            }
-        }
-        Value::Array(arr) => {
-            let results: Vec<Value> = arr
-                .iter()
-                .filter_map(|item| {
-                    if let Value::Object(map) = item {
-                        map.get(field).cloned()
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-            Ok(Value::Array(results))
-        }
-        _ => Err(SGSimpleError::new(
-            1,
-            "Input JSON must be an object or array".to_string(),
-        )),
-    }
-}
-
-fn extract_multiple_fields(json: &Value, fields: &[&str]) -> SGResult<Value> {
-    match json {
-        Value::Object(map) => {
-            let mut result = serde_json::Map::new();
-            for field in fields {
-                if let Some(value) = map.get(*field) {
-                    result.insert(field.to_string(), value.clone());
-                }
-            }
-            Ok(Value::Object(result))
-        }
-        Value::Array(arr) => {
-            let results: Vec<Value> = arr
-                .iter()
-                .filter_map(|item| {
-                    if let Value::Object(map) = item {
-                        let mut obj = serde_json::Map::new();
-                        for field in fields {
-                            if let Some(value) = map.get(*field) {
-                                obj.insert(field.to_string(), value.clone());
-                            }
-                        }
-                        Some(Value::Object(obj))
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-            Ok(Value::Array(results))
-        }
-        _ => Err(SGSimpleError::new(
+    if !matches!(json, Value::Object(_) | Value::Array(_)) {
+        return Err(SGSimpleError::new(
             1,
             "Input JSON must be an object or array".to_string(),
-        )),
-    }
-}
-
-fn extract_by_index(json: &Value, index: usize) -> SGResult<Value> {
-    match json {
-        Value::Array(arr) => {
-            if index < arr.len() {
-                Ok(arr[index].clone())
-            } else {
-                Err(SGSimpleError::new(
-                    1,
-                    format!("Index {} out of bounds (array length: {})", index, arr.len()),
-                ))
-            }
-        }
-        _ => Err(SGSimpleError::new(
-            1,
-            "Input JSON must be an array for index extraction".to_string(),
-        )),
+        ));
     }
+    let path = FieldPath::parse(field).map_err(|e| SGSimpleError::new(1, e))?;
+    path.extract(json).ok_or_else(|| {
+        SGSimpleError::new(1, format!("Field '{field}' not found in JSON object"))
+    })
 }
 
 fn output_json(value: &Value, pretty: bool) -> SGResult<()> {
-    let output = if pretty {
-        serde_json::to_string_pretty(value)
-    } else {
-        serde_json::to_string(value)
-    }
-    .map_err(|e| SGSimpleError::new(1, format!("Failed to serialize JSON: {}", e)))?;
-
-    println!("{}", output);
-    Ok(())
+    wire::print_value(value, pretty)
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))
 }
 
 pub fn sg_app() -> Command {
@@ -140,10 +56,19 @@ pub fn sg_app() -> Command {
         .version(sgcore::crate_version!())
         .about("Extract fields from JSON objects")
         .override_usage("slice-object [FIELD]")
+        .after_help(
+            "FIELD is a field path: dotted keys, [N] indices (negative from the end), \
+             [START:END] slices and [*] wildcards. A key applied to an array is \
+             applied to each of its elements.\n\n\
+             Examples:\n  \
+             list-directory | slice-object entries.name\n  \
+             list-directory -l | slice-object 'entries[0:5]'\n  \
+             find-text TODO src | slice-object 'results[*].matches[*].line'",
+        )
         .arg(
             Arg::new(options::FIELD)
                 .value_name("FIELD")
-                .help("Extract a single field from JSON object(s)")
+                .help("Extract the value at a field path from JSON object(s)")
                 .index(1)
                 .action(ArgAction::Set),
         )
@@ -0,0 +1 @@
+sgcore::bin!(sg_sort_object);
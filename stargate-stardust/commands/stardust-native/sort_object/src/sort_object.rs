@@ -0,0 +1,267 @@
+use clap::{Arg, ArgAction, Command, value_parser};
+use serde_json::Value;
+use std::cmp::Ordering;
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::i18n::collator::{AlternateHandling, CollatorOptions, locale_cmp, try_init_collator};
+use sgcore::json_adapter::{compare_values, extract_records, lookup_field, read_stdin_value};
+use sgcore::version_cmp::version_cmp;
+use sgcore::wire;
+
+pub mod options {
+    pub static KEYS: &str = "keys";
+    pub static REVERSE: &str = "reverse";
+    pub static NATURAL: &str = "natural";
+    pub static LOCALE: &str = "locale";
+    pub static TOP: &str = "top";
+    pub static FIELD: &str = "field";
+    pub static PRETTY: &str = "pretty";
+}
+
+/// How strings are compared
+#[derive(Clone, Copy)]
+enum StringOrder {
+    Bytes,
+    /// Natural order: `file2` before `file10`
+    Version,
+    Locale,
+}
+
+/// A sort key: `size`, `owner.name:desc`
+struct SortKey {
+    path: String,
+    descending: bool,
+}
+
+impl SortKey {
+    fn parse(spec: &str) -> SGResult<Self> {
+        let (path, descending) = match spec.rsplit_once(':') {
+            Some((path, "asc")) => (path, false),
+            Some((path, "desc")) => (path, true),
+            Some((_, order)) => {
+                return Err(SGSimpleError::new(
+                    1,
+                    format!("Invalid sort order '{order}' in '{spec}': use asc or desc"),
+                ));
+            }
+            None => (spec, false),
+        };
+        Ok(Self { path: path.to_string(), descending })
+    }
+}
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sg_app().try_get_matches_from(args)?;
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let keys = matches
+        .get_many::<String>(options::KEYS)
+        .map(|specs| specs.map(|spec| SortKey::parse(spec)).collect::<SGResult<Vec<_>>>())
+        .transpose()?
+        .unwrap_or_default();
+
+    let order = if matches.get_flag(options::NATURAL) {
+        StringOrder::Version
+    } else if matches.get_flag(options::LOCALE) {
+        let mut opts = CollatorOptions::default();
+        opts.alternate_handling = Some(AlternateHandling::Shifted);
+        try_init_collator(opts);
+        StringOrder::Locale
+    } else {
+        StringOrder::Bytes
+    };
+
+    let json = read_stdin_value().map_err(|e| SGSimpleError::new(1, e))?;
+
+    let field = matches.get_one::<String>(options::FIELD).map(String::as_str);
+    let mut records = extract_records(&json, field)
+        .map_err(|e| SGSimpleError::new(1, e))?
+        .to_vec();
+
+    sort_records(&mut records, &keys, order, matches.get_flag(options::REVERSE));
+    if let Some(&top) = matches.get_one::<usize>(options::TOP) {
+        records.truncate(top);
+    }
+
+    output_json(&Value::Array(records), matches.get_flag(options::PRETTY))?;
+
+    Ok(())
+}
+
+/// Sort stably, so records with equal keys keep their input order, also
+/// when `reverse` flips the order of the keys
+fn sort_records(records: &mut [Value], keys: &[SortKey], order: StringOrder, reverse: bool) {
+    records.sort_by(|a, b| {
+        let ordering = compare_records(a, b, keys, order);
+        if reverse { ordering.reverse() } else { ordering }
+    });
+}
+
+fn compare_records(a: &Value, b: &Value, keys: &[SortKey], order: StringOrder) -> Ordering {
+    if keys.is_empty() {
+        return compare_field(a, b, order);
+    }
+
+    keys.iter()
+        .map(|key| {
+            let a = lookup_field(a, &key.path).unwrap_or(&Value::Null);
+            let b = lookup_field(b, &key.path).unwrap_or(&Value::Null);
+            let ordering = compare_field(a, b, order);
+            if key.descending { ordering.reverse() } else { ordering }
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+fn compare_field(a: &Value, b: &Value, order: StringOrder) -> Ordering {
+    match (a, b, order) {
+        (Value::String(a), Value::String(b), StringOrder::Version) => {
+            version_cmp(a.as_bytes(), b.as_bytes())
+        }
+        (Value::String(a), Value::String(b), StringOrder::Locale) => {
+            locale_cmp(a.as_bytes(), b.as_bytes())
+        }
+        _ => compare_values(a, b),
+    }
+}
+
+fn output_json(value: &Value, pretty: bool) -> SGResult<()> {
+    wire::print_value(value, pretty)
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))
+}
+
+pub fn sg_app() -> Command {
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about("Sort stardust records by one or more fields")
+        .override_usage("sort-object [OPTIONS] [FIELD[:asc|:desc]]...")
+        .after_help(
+            "Sorts an array of records, or the entries/files/results array of a \
+             stardust object, and outputs the sorted records as an array.\n\
+             Later fields break ties between earlier ones. Numbers compare \
+             numerically; missing fields sort first.\n\n\
+             Examples:\n  \
+             list-directory -l | sort-object size:desc --top 5 | dice-object name size\n  \
+             list-directory -l | sort-object type name --natural\n  \
+             list-processes | sort-object -f processes user pid:desc",
+        )
+        .arg(
+            Arg::new(options::KEYS)
+                .value_name("FIELD")
+                .help("Field path to sort by, optionally suffixed with :asc or :desc")
+                .num_args(0..)
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new(options::REVERSE)
+                .short('r')
+                .long("reverse")
+                .help("Reverse the result of comparisons")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::NATURAL)
+                .short('N')
+                .long(options::NATURAL)
+                .visible_alias("version-sort")
+                .help("Natural (version) sort of strings: numbers within text compare by value")
+                .action(ArgAction::SetTrue)
+                .conflicts_with(options::LOCALE),
+        )
+        .arg(
+            Arg::new(options::LOCALE)
+                .short('l')
+                .long(options::LOCALE)
+                .help("Compare strings using the current locale's collation")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::TOP)
+                .short('n')
+                .long(options::TOP)
+                .value_name("N")
+                .help("Output only the first N records after sorting")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new(options::FIELD)
+                .short('f')
+                .long("field")
+                .value_name("FIELD")
+                .help("Sort the array under FIELD instead of detecting it"),
+        )
+        .arg(
+            Arg::new(options::PRETTY)
+                .short('p')
+                .long("pretty")
+                .help("Output pretty-printed JSON")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sorted(records: Value, specs: &[&str], order: StringOrder) -> Value {
+        sorted_by(records, specs, order, false)
+    }
+
+    fn sorted_by(records: Value, specs: &[&str], order: StringOrder, reverse: bool) -> Value {
+        let keys: Vec<SortKey> = specs.iter().map(|spec| SortKey::parse(spec).unwrap()).collect();
+        let mut records = records.as_array().unwrap().clone();
+        sort_records(&mut records, &keys, order, reverse);
+        Value::Array(records)
+    }
+
+    #[test]
+    fn sorts_by_several_keys() {
+        let records = json!([
+            {"type": "file", "size": 10},
+            {"type": "dir", "size": 5},
+            {"type": "file", "size": 30},
+        ]);
+        assert_eq!(
+            sorted(records, &["type", "size:desc"], StringOrder::Bytes),
+            json!([
+                {"type": "dir", "size": 5},
+                {"type": "file", "size": 30},
+                {"type": "file", "size": 10},
+            ])
+        );
+    }
+
+    #[test]
+    fn natural_order_and_missing_fields() {
+        let records = json!([{"name": "f10"}, {"name": "f9"}, {}]);
+        assert_eq!(
+            sorted(records, &["name"], StringOrder::Version),
+            json!([{}, {"name": "f9"}, {"name": "f10"}])
+        );
+    }
+
+    #[test]
+    fn reverse_keeps_ties_in_input_order() {
+        let records = json!([
+            {"size": 1, "name": "a"},
+            {"size": 2, "name": "b"},
+            {"size": 1, "name": "c"},
+            {"size": 2, "name": "d"},
+        ]);
+        assert_eq!(
+            sorted_by(records, &["size"], StringOrder::Bytes, true),
+            json!([
+                {"size": 2, "name": "b"},
+                {"size": 2, "name": "d"},
+                {"size": 1, "name": "a"},
+                {"size": 1, "name": "c"},
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_bad_order() {
+        assert!(SortKey::parse("size:up").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! Shared implementation of `to-csv` and `to-tsv`
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde_json::Value;
+use std::io::{self, Write};
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::json_adapter::{extract_records, read_stdin_value};
+use sgcore::tabular::{
+    Dialect, cell_text, column_names, parse_delimiter, record_columns, write_delimited_row,
+};
+
+pub mod options {
+    pub static COLUMNS: &str = "columns";
+    pub static DELIMITER: &str = "delimiter";
+    pub static QUOTE: &str = "quote";
+    pub static NO_HEADER: &str = "no-header";
+    pub static CRLF: &str = "crlf";
+    pub static FIELD: &str = "field";
+}
+
+/// Parse the arguments and write the piped records, with `dialect` giving
+/// the default delimiter and quote
+pub fn run(args: impl sgcore::Args, app: Command, dialect: Dialect) -> SGResult<()> {
+    let matches = app.try_get_matches_from(args)?;
+    let dialect = dialect_from(&matches, dialect)?;
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let json = read_stdin_value().map_err(|e| SGSimpleError::new(1, e))?;
+
+    let field = matches.get_one::<String>(options::FIELD).map(String::as_str);
+    let records = match extract_records(&json, field) {
+        Ok(records) => records,
+        // A single object is one row
+        Err(_) if field.is_none() && json.is_object() => std::slice::from_ref(&json),
+        Err(e) => return Err(SGSimpleError::new(1, e)),
+    };
+
+    // Without COLUMN, keep the order recorded by from-csv and friends
+    let columns: Option<Vec<String>> = matches
+        .get_many::<String>(options::COLUMNS)
+        .map(|columns| columns.cloned().collect())
+        .or_else(|| json.get("columns").and_then(column_names));
+    let output = write_records(records, columns, !matches.get_flag(options::NO_HEADER), &dialect);
+
+    io::stdout()
+        .write_all(output.as_bytes())
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))?;
+
+    Ok(())
+}
+
+fn dialect_from(matches: &ArgMatches, mut dialect: Dialect) -> SGResult<Dialect> {
+    if let Some(delimiter) = matches.get_one::<String>(options::DELIMITER) {
+        dialect.delimiter = parse_delimiter(delimiter).map_err(|e| SGSimpleError::new(1, e))?;
+    }
+    if let Some(quote) = matches.get_one::<String>(options::QUOTE) {
+        dialect.quote = parse_delimiter(quote)
+            .map_err(|_| SGSimpleError::new(1, format!("quote must be a single character, got '{quote}'")))?;
+    }
+    if dialect.delimiter == dialect.quote {
+        return Err(SGSimpleError::new(1, "delimiter and quote must differ".to_string()));
+    }
+    if matches.get_flag(options::CRLF) {
+        dialect.line_ending = "\r\n";
+    }
+    Ok(dialect)
+}
+
+/// One row per record under a header of `columns`, or of every field in
+/// order of first appearance. Records that are not objects fill a `value` column.
+fn write_records(records: &[Value], columns: Option<Vec<String>>, header: bool, dialect: &Dialect) -> String {
+    let columns = columns.unwrap_or_else(|| {
+        let columns = record_columns(records);
+        if columns.is_empty() && !records.is_empty() { vec!["value".to_string()] } else { columns }
+    });
+
+    let mut out = String::new();
+    if header && !columns.is_empty() {
+        write_delimited_row(&columns, dialect, &mut out);
+    }
+    for record in records {
+        let row: Vec<String> = match record {
+            Value::Object(map) => columns
+                .iter()
+                .map(|column| map.get(column).map(cell_text).unwrap_or_default())
+                .collect(),
+            other => vec![cell_text(other)],
+        };
+        write_delimited_row(&row, dialect, &mut out);
+    }
+    out
+}
+
+/// The command line of a delimited writer; `format` names the format in help
+pub fn app(format: &str, dialect: Dialect) -> Command {
+    let ext = format.to_lowercase();
+    let default_delimiter = if dialect.delimiter == '\t' { "tab".to_string() } else { dialect.delimiter.to_string() };
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about(format!("Write stardust records as {format}"))
+        .override_usage(format!("to-{ext} [OPTIONS] [COLUMN]..."))
+        .after_help(format!(
+            "Writes an array of records, or the records/entries/files/results array \
+             of a stardust object, as {format} with a header row. Columns keep the \
+             order listed in the input's columns field, as written by from-csv, \
+             from-tsv and from-text. Fields are quoted as RFC 4180 requires; null \
+             is an empty field and nested values are written as JSON.\n\n\
+             Examples:\n  \
+             list-directory -l | to-{ext} name size modified > listing.{ext}\n  \
+             from-{ext} data.{ext} | sort-object score:desc | to-{ext}\n  \
+             list-processes | to-{ext} -f processes pid user name --crlf"
+        ))
+        .arg(
+            Arg::new(options::COLUMNS)
+                .value_name("COLUMN")
+                .help("Fields to write, in order (default: all)")
+                .num_args(0..)
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new(options::DELIMITER)
+                .short('d')
+                .long(options::DELIMITER)
+                .value_name("CHAR")
+                .help(format!("Field delimiter; \\t or tab for a tab (default: {default_delimiter})")),
+        )
+        .arg(
+            Arg::new(options::QUOTE)
+                .short('q')
+                .long(options::QUOTE)
+                .value_name("CHAR")
+                .help("Quote character (default: \")"),
+        )
+        .arg(
+            Arg::new(options::NO_HEADER)
+                .short('n')
+                .long(options::NO_HEADER)
+                .help("Do not write a header row")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::CRLF)
+                .long(options::CRLF)
+                .help("End rows with CRLF, as RFC 4180 specifies")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::FIELD)
+                .short('f')
+                .long("field")
+                .value_name("FIELD")
+                .help("Write the array under FIELD instead of detecting it"),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn writes_header_and_rows() {
+        let records = json!([
+            {"name": "a,b", "size": 1, "tags": ["x"]},
+            {"name": "c", "size": null},
+        ]);
+        let records = records.as_array().unwrap();
+        assert_eq!(
+            write_records(records, None, true, &Dialect::csv()),
+            "name,size,tags\n\"a,b\",1,\"[\"\"x\"\"]\"\nc,,\n"
+        );
+        assert_eq!(
+            write_records(records, Some(vec!["size".into(), "name".into()]), false, &Dialect::tsv()),
+            "1\ta,b\n\tc\n"
+        );
+    }
+}
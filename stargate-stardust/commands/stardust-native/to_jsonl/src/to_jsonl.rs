@@ -0,0 +1,56 @@
+use clap::{Arg, Command};
+use std::io::{self, Write};
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::json_adapter::{extract_records, read_stdin_value};
+
+pub mod options {
+    pub static FIELD: &str = "field";
+}
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sg_app().try_get_matches_from(args)?;
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let json = read_stdin_value().map_err(|e| SGSimpleError::new(1, e))?;
+
+    let field = matches.get_one::<String>(options::FIELD).map(String::as_str);
+    let records = match extract_records(&json, field) {
+        Ok(records) => records,
+        // Anything else is a single line
+        Err(_) if field.is_none() => std::slice::from_ref(&json),
+        Err(e) => return Err(SGSimpleError::new(1, e)),
+    };
+
+    let mut output = String::new();
+    for record in records {
+        output.push_str(&record.to_string());
+        output.push('\n');
+    }
+    io::stdout()
+        .write_all(output.as_bytes())
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))?;
+
+    Ok(())
+}
+
+pub fn sg_app() -> Command {
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about("Write stardust records as JSON Lines")
+        .override_usage("to-jsonl [OPTIONS]")
+        .after_help(
+            "Writes each record of an array, or of the records/entries/files/results \
+             array of a stardust object, as compact JSON on its own line.\n\n\
+             Examples:\n  \
+             list-directory -l | to-jsonl > listing.jsonl\n  \
+             list-processes | to-jsonl -f processes | find-text root",
+        )
+        .arg(
+            Arg::new(options::FIELD)
+                .short('f')
+                .long("field")
+                .value_name("FIELD")
+                .help("Write the array under FIELD instead of detecting it"),
+        )
+}
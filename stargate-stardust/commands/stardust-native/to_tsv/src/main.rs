@@ -0,0 +1 @@
+sgcore::bin!(sg_to_tsv);
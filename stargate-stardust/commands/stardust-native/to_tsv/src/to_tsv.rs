@@ -0,0 +1,13 @@
+use clap::Command;
+use sg_to_csv::delimited_output;
+use sgcore::error::SGResult;
+use sgcore::tabular::Dialect;
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    delimited_output::run(args, sg_app(), Dialect::tsv())
+}
+
+pub fn sg_app() -> Command {
+    delimited_output::app("TSV", Dialect::tsv())
+}
@@ -0,0 +1 @@
+sgcore::bin!(sg_where_object);
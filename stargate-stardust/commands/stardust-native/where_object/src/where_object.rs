@@ -0,0 +1,78 @@
+use clap::{Arg, ArgAction, Command};
+use serde_json::Value;
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::json_adapter::read_stdin_value;
+use sgcore::predicate::Predicate;
+use sgcore::wire;
+
+pub mod options {
+    pub static PREDICATE: &str = "predicate";
+    pub static FIELD: &str = "field";
+    pub static PRETTY: &str = "pretty";
+}
+
+#[sgcore::main]
+pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
+    let matches = sg_app().try_get_matches_from(args)?;
+    sgcore::pledge::apply_pledge(&["stdio"])?;
+
+    let expression = matches
+        .get_one::<String>(options::PREDICATE)
+        .expect("predicate is required");
+    let predicate = Predicate::parse(expression)
+        .map_err(|e| SGSimpleError::new(1, format!("Invalid predicate: {e}")))?;
+
+    let json = read_stdin_value().map_err(|e| SGSimpleError::new(1, e))?;
+
+    let field = matches.get_one::<String>(options::FIELD).map(String::as_str);
+    let result = predicate
+        .filter(&json, field)
+        .map_err(|e| SGSimpleError::new(1, e))?;
+
+    output_json(&result, matches.get_flag(options::PRETTY))?;
+
+    Ok(())
+}
+
+fn output_json(value: &Value, pretty: bool) -> SGResult<()> {
+    wire::print_value(value, pretty)
+        .map_err(|e| SGSimpleError::new(1, format!("Failed to write output: {e}")))
+}
+
+pub fn sg_app() -> Command {
+    Command::new(sgcore::util_name())
+        .version(sgcore::crate_version!())
+        .about("Filter stardust records with a predicate expression")
+        .override_usage("where-object [OPTIONS] PREDICATE")
+        .after_help(
+            "Filters an array of records, or the entries/files/results array of a \
+             stardust object.\n\n\
+             Examples:\n  \
+             list-directory -l | where-object 'size > 10k and name ~ \"\\.rs$\"'\n  \
+             list-directory -l | where-object 'type in [\"file\", \"symlink\"] and modified > 7d ago'\n  \
+             list-processes | where-object -f processes 'user == \"root\" and cmdline[0] ~ \"^/usr/\"'\n\n\
+             Operators: == != < <= > >= ~ !~ in, combined with and, or, not.\n\
+             Sizes: 10k 1.5M 2GiB (1024-based), 10KB (1000-based).\n\
+             Dates: 2024-01-01, 2024-01-01T12:00:00, 3d ago.",
+        )
+        .arg(
+            Arg::new(options::PREDICATE)
+                .value_name("PREDICATE")
+                .help("Predicate each record must satisfy")
+                .required(true),
+        )
+        .arg(
+            Arg::new(options::FIELD)
+                .short('f')
+                .long("field")
+                .value_name("FIELD")
+                .help("Filter the array under FIELD instead of detecting it"),
+        )
+        .arg(
+            Arg::new(options::PRETTY)
+                .short('p')
+                .long("pretty")
+                .help("Output pretty-printed JSON")
+                .action(ArgAction::SetTrue),
+        )
+}
@@ -29,7 +29,6 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 
     let line_ending = LineEnding::from_zero_flag(matches.get_flag(options::ZERO));
     let opts = StardustOutputOptions::from_matches(&matches);
-    let field_filter = matches.get_one::<String>(stardust_output::ARG_FIELD).map(|s| s.as_str());
 
     let mut name_args = matches
         .get_many::<OsString>(options::NAME)
@@ -70,7 +69,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
             let basename_str = String::from_utf8_lossy(&basename_bytes).to_string();
             results.push(basename_str);
         }
-        let output = stardust_output::filter_fields(json!({"name": results}), field_filter);
+        let output = json!({"name": results});
         stardust_output::output(opts, output, || Ok(()))?;
     } else {
         for path in name_args {
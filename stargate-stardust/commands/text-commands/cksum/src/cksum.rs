@@ -2,6 +2,7 @@
 
 use clap::builder::ValueParser;
 use clap::{Arg, ArgAction, Command};
+use serde_json::json;
 use std::ffi::{OsStr, OsString};
 use std::fs::File;
 use std::io::{BufReader, Read, Write, stdin, stdout};
@@ -10,10 +11,12 @@ use std::path::Path;
 use sgcore::checksum::{
     ALGORITHM_OPTIONS_BLAKE2B, ALGORITHM_OPTIONS_BSD, ALGORITHM_OPTIONS_CRC,
     ALGORITHM_OPTIONS_CRC32B, ALGORITHM_OPTIONS_SHA2, ALGORITHM_OPTIONS_SHA3,
-    ALGORITHM_OPTIONS_SYSV, ChecksumError, ChecksumOptions, ChecksumVerbose, HashAlgorithm,
-    LEGACY_ALGORITHMS, SUPPORTED_ALGORITHMS, calculate_blake2b_length_str, detect_algo,
-    digest_reader, perform_checksum_validation, sanitize_sha2_sha3_length_str,
+    ALGORITHM_OPTIONS_SYSV, CheckReport, ChecksumError, ChecksumOptions, ChecksumVerbose,
+    HashAlgorithm, LEGACY_ALGORITHMS, SUPPORTED_ALGORITHMS, calculate_blake2b_length_str,
+    detect_algo, digest_reader, digest_record, perform_checksum_validation,
+    perform_checksum_validation_report, sanitize_sha2_sha3_length_str, stardust_schema,
 };
+use sgcore::stardust_output::{self, StardustOutputOptions};
 use sgcore::translate;
 
 use sgcore::{
@@ -32,6 +35,7 @@ struct Options {
     length: Option<usize>,
     output_format: OutputFormat,
     line_ending: LineEnding,
+    stardust: StardustOutputOptions,
 }
 
 /// Reading mode used to compute digest.
@@ -173,6 +177,7 @@ where
     I: Iterator<Item = &'a OsStr>,
 {
     let mut files = files.peekable();
+    let mut records = Vec::new();
 
     while let Some(filename) = files.next() {
         if options.output_format.is_raw() && files.peek().is_some() {
@@ -216,6 +221,17 @@ where
             }
         };
 
+        if options.stardust.stardust_output {
+            let digest = match options.output_format {
+                OutputFormat::Tagged(digest_format) | OutputFormat::Untagged(digest_format, _) => {
+                    encode_sum(sum_hex, digest_format)
+                }
+                OutputFormat::Raw | OutputFormat::Legacy => sum_hex,
+            };
+            records.push(digest_record(filename, options.algo_name, &digest, sz));
+            continue;
+        }
+
         match options.output_format {
             OutputFormat::Raw => {
                 let bytes = match options.algo_name {
@@ -247,6 +263,10 @@ where
 
         print!("{}", options.line_ending);
     }
+
+    if options.stardust.stardust_output {
+        stardust_output::output(options.stardust, json!({ "files": records }), || Ok(()))?;
+    }
     Ok(())
 }
 
@@ -362,7 +382,11 @@ fn maybe_sanitize_length(
 #[sgcore::main]
 pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
+    if stardust_output::self_describe(&matches, stardust_schema())? {
+        return Ok(());
+    }
     sgcore::pledge::apply_pledge(&["stdio", "rpath"])?;
+    let stardust = StardustOutputOptions::from_matches(&matches);
 
     let check = matches.get_flag(options::CHECK);
 
@@ -407,6 +431,13 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
             verbose,
         };
 
+        if stardust.stardust_output {
+            let mut report = CheckReport::default();
+            let result = perform_checksum_validation_report(files, algo_cli, length, opts, &mut report);
+            stardust_output::output(stardust, report.to_value(), || Ok(()))?;
+            return result;
+        }
+
         return perform_checksum_validation(files, algo_cli, length, opts);
     }
 
@@ -432,6 +463,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         length,
         output_format,
         line_ending,
+        stardust,
     };
 
     cksum(opts, files)?;
@@ -440,7 +472,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 }
 
 pub fn sg_app() -> Command {
-    Command::new(sgcore::util_name())
+    let cmd = Command::new(sgcore::util_name())
         .version(sgcore::crate_version!())
         .help_template(sgcore::localized_help_template(sgcore::util_name()))
         .about(translate!("cksum-about"))
@@ -560,6 +592,8 @@ pub fn sg_app() -> Command {
                 .help(translate!("cksum-help-zero"))
                 .action(ArgAction::SetTrue)
         )
-        .after_help(translate!("cksum-after-help"))
+        .after_help(translate!("cksum-after-help"));
+
+    stardust_output::add_json_args(cmd)
 }
 
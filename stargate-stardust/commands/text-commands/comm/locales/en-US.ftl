@@ -16,12 +16,17 @@ comm-help-zero-terminated = line delimiter is NUL, not newline
 comm-help-total = output a summary
 comm-help-check-order = check that the input is correctly sorted, even if all input lines are pairable
 comm-help-no-check-order = do not check that the input is correctly sorted
+comm-help-obj = compare two stardust inputs sorted by --key, listing each record with the side it is on
+comm-help-key = with --obj, match records by these comma-separated field paths instead of whole
+comm-help-records = with --obj, use the records in top-level FIELD of each input object
 
 # Error messages
 comm-error-file-not-sorted = comm: file { $file_num } is not in sorted order
 comm-error-input-not-sorted = comm: input is not in sorted order
 comm-error-is-directory = Is a directory
 comm-error-multiple-conflicting-delimiters = multiple conflicting output delimiters specified
+comm-obj-invalid-key = invalid --key field: { $error }
+comm-obj-invalid-input = invalid stardust input: { $error }
 
 # Other messages
 comm-total = total
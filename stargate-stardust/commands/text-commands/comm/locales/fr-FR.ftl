@@ -16,12 +16,17 @@ comm-help-zero-terminated = le délimiteur de ligne est NUL, pas nouvelle ligne
 comm-help-total = afficher un résumé
 comm-help-check-order = vérifier que l'entrée est correctement triée, même si toutes les lignes d'entrée sont appariables
 comm-help-no-check-order = ne pas vérifier que l'entrée est correctement triée
+comm-help-obj = comparer deux entrées stardust triées par --key, en listant chaque enregistrement avec le côté où il se trouve
+comm-help-key = avec --obj, associer les enregistrements selon ces chemins de champs séparés par des virgules plutôt qu'en entier
+comm-help-records = avec --obj, utiliser les enregistrements du CHAMP de premier niveau de chaque objet d'entrée
 
 # Messages d'erreur
 comm-error-file-not-sorted = comm : le fichier { $file_num } n'est pas dans l'ordre trié
 comm-error-input-not-sorted = comm : l'entrée n'est pas dans l'ordre trié
 comm-error-is-directory = Est un répertoire
 comm-error-multiple-conflicting-delimiters = plusieurs délimiteurs de sortie en conflit spécifiés
+comm-obj-invalid-key = champ --key invalide : { $error }
+comm-obj-invalid-input = entrée stardust invalide : { $error }
 
 # Autres messages
 comm-total = total
@@ -13,6 +13,8 @@ use sgcore::translate;
 
 use clap::{Arg, ArgAction, ArgMatches, Command};
 
+mod records;
+
 mod options {
     pub const COLUMN_1: &str = "1";
     pub const COLUMN_2: &str = "2";
@@ -25,6 +27,9 @@ mod options {
     pub const ZERO_TERMINATED: &str = "zero-terminated";
     pub const CHECK_ORDER: &str = "check-order";
     pub const NO_CHECK_ORDER: &str = "nocheck-order";
+    pub const OBJ: &str = "obj";
+    pub const KEY: &str = "key";
+    pub const RECORDS: &str = "records";
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -295,6 +300,22 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let line_ending = LineEnding::from_zero_flag(matches.get_flag(options::ZERO_TERMINATED));
     let filename1 = matches.get_one::<OsString>(options::FILE_1).unwrap();
     let filename2 = matches.get_one::<OsString>(options::FILE_2).unwrap();
+    if matches.get_flag(options::OBJ) {
+        let record_comm = records::RecordComm {
+            keys: records::RecordComm::parse_keys(
+                matches.get_many::<String>(options::KEY).into_iter().flatten()
+            )?,
+            records_field: matches.get_one::<String>(options::RECORDS).cloned(),
+            show: [
+                !matches.get_flag(options::COLUMN_1),
+                !matches.get_flag(options::COLUMN_2),
+                !matches.get_flag(options::COLUMN_3),
+            ],
+            check_order: matches.get_flag(options::CHECK_ORDER),
+            no_check_order: matches.get_flag(options::NO_CHECK_ORDER),
+        };
+        return record_comm.comm(filename1, filename2);
+    }
     let mut f1 = open_file(filename1, line_ending)
         .map_err_context(|| filename1.to_string_lossy().to_string())?;
     let mut f2 = open_file(filename2, line_ending)
@@ -396,5 +417,27 @@ pub fn sg_app() -> Command {
                 .action(ArgAction::SetTrue)
                 .conflicts_with(options::CHECK_ORDER)
         )
+        .arg(
+            Arg::new(options::OBJ)
+                .long(options::OBJ)
+                .help(translate!("comm-help-obj"))
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all([options::DELIMITER, options::ZERO_TERMINATED, options::TOTAL])
+        )
+        .arg(
+            Arg::new(options::KEY)
+                .long(options::KEY)
+                .help(translate!("comm-help-key"))
+                .value_name("FIELDS")
+                .action(ArgAction::Append)
+                .requires(options::OBJ)
+        )
+        .arg(
+            Arg::new(options::RECORDS)
+                .long(options::RECORDS)
+                .help(translate!("comm-help-records"))
+                .value_name("FIELD")
+                .requires(options::OBJ)
+        )
 }
 
@@ -0,0 +1,180 @@
+//! Comparing two sorted record lists for `comm --obj`.
+//!
+//! Records are matched by the values of the `--key` field paths, or whole
+//! when there are none, and both inputs must be sorted by that key as
+//! `sort --obj` leaves them. Every record becomes an entry saying which side
+//! it is on: `first`, `second` or `both`.
+
+use std::cmp::Ordering;
+use std::ffi::OsString;
+
+use serde_json::{Map, Value, json};
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::field_path::FieldPath;
+use sgcore::json_adapter::{compare_values, read_input_value, take_records};
+use sgcore::translate;
+use sgcore::wire;
+
+/// What `comm --obj` compares and which entries it prints
+pub struct RecordComm {
+    pub keys: Vec<FieldPath>,
+    pub records_field: Option<String>,
+    pub show: [bool; 3],
+    pub check_order: bool,
+    pub no_check_order: bool,
+}
+
+impl RecordComm {
+    /// Parse `--key` values, each a comma-separated list of field paths
+    pub fn parse_keys<'a>(specs: impl Iterator<Item = &'a String>) -> SGResult<Vec<FieldPath>> {
+        specs
+            .flat_map(|spec| spec.split(','))
+            .map(|path| {
+                FieldPath::parse(path).map_err(|e| {
+                    SGSimpleError::new(1, translate!("comm-obj-invalid-key", "error" => e))
+                })
+            })
+            .collect()
+    }
+
+    pub fn comm(&self, file1: &OsString, file2: &OsString) -> SGResult<()> {
+        let first = self.read_records(file1)?;
+        let second = self.read_records(file2)?;
+
+        let mut entries = Vec::new();
+        let mut totals = [0usize; 3];
+        let mut order = [OrderCheck::default(), OrderCheck::default()];
+        let (mut a, mut b) = (first.into_iter().peekable(), second.into_iter().peekable());
+        loop {
+            let a_key = a.peek().map(|record| self.key(record));
+            let b_key = b.peek().map(|record| self.key(record));
+            let side = match (&a_key, &b_key) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(a_key), Some(b_key)) => compare_keys(a_key, b_key),
+            };
+
+            let ordered = self.no_check_order
+                || match side {
+                    Ordering::Less => order[0].verify(a_key.as_deref().unwrap(), "1"),
+                    Ordering::Greater => order[1].verify(b_key.as_deref().unwrap(), "2"),
+                    Ordering::Equal => {
+                        order[0].verify(a_key.as_deref().unwrap(), "1")
+                            & order[1].verify(b_key.as_deref().unwrap(), "2")
+                    }
+                };
+            if !ordered && self.check_order {
+                break;
+            }
+
+            let (column, key, first, second) = match side {
+                Ordering::Less => (0, a_key, a.next(), None),
+                Ordering::Greater => (1, b_key, None, b.next()),
+                Ordering::Equal => (2, a_key, a.next(), b.next()),
+            };
+            totals[column] += 1;
+            if self.show[column] {
+                let mut entry = Map::new();
+                entry.insert("side".to_string(), ["first", "second", "both"][column].into());
+                entry.insert("key".to_string(), key_value(key.unwrap()));
+                if let Some(first) = first {
+                    entry.insert("first".to_string(), first);
+                }
+                if let Some(second) = second {
+                    entry.insert("second".to_string(), second);
+                }
+                entries.push(Value::Object(entry));
+            }
+        }
+
+        let output = json!({
+            "count": entries.len(),
+            "records": entries,
+            "totals": {
+                "first": totals[0],
+                "second": totals[1],
+                "both": totals[2],
+            },
+        });
+        wire::print_value(&output, false).map_err(|e| SGSimpleError::new(1, e.to_string()))?;
+
+        if order.iter().any(|check| check.unsorted) {
+            if !self.check_order {
+                eprintln!("{}", translate!("comm-error-input-not-sorted"));
+            }
+            return Err(SGSimpleError::new(1, ""));
+        }
+        Ok(())
+    }
+
+    fn read_records(&self, file: &OsString) -> SGResult<Vec<Value>> {
+        let invalid = |e: String| {
+            SGSimpleError::new(1, translate!("comm-obj-invalid-input", "error" => e))
+        };
+        let mut value = read_input_value(file).map_err(invalid)?;
+        take_records(&mut value, self.records_field.as_deref()).map_err(invalid)
+    }
+
+    fn key(&self, record: &Value) -> Vec<Value> {
+        if self.keys.is_empty() {
+            return vec![record.clone()];
+        }
+        self.keys
+            .iter()
+            .map(|path| path.get(record).cloned().unwrap_or(Value::Null))
+            .collect()
+    }
+}
+
+/// The key as printed: the value of a single key, an array of several
+fn key_value(mut key: Vec<Value>) -> Value {
+    if key.len() == 1 {
+        key.pop().unwrap()
+    } else {
+        Value::Array(key)
+    }
+}
+
+/// Order keys as `sort --obj` does, falling back to the JSON text of values
+/// `compare_values` does not tell apart, such as two objects
+fn compare_keys(a: &[Value], b: &[Value]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| {
+            compare_values(a, b).then_with(|| {
+                if a == b {
+                    Ordering::Equal
+                } else {
+                    a.to_string().cmp(&b.to_string())
+                }
+            })
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+/// The last key seen in one input, to report the first that goes backwards
+#[derive(Default)]
+struct OrderCheck {
+    last: Option<Vec<Value>>,
+    unsorted: bool,
+}
+
+impl OrderCheck {
+    fn verify(&mut self, key: &[Value], file_num: &str) -> bool {
+        let ordered = self
+            .last
+            .as_deref()
+            .is_none_or(|last| compare_keys(last, key) != Ordering::Greater);
+        if !ordered && !self.unsorted {
+            eprintln!(
+                "{}",
+                translate!("comm-error-file-not-sorted", "file_num" => file_num)
+            );
+            self.unsorted = true;
+        }
+        self.last = Some(key.to_vec());
+        ordered
+    }
+}
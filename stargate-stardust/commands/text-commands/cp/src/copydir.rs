@@ -16,6 +16,7 @@ use sgcore::error::SGIoError;
 use sgcore::fs::{
     FileInformation, MissingHandling, ResolveMode, canonicalize, path_ends_with_terminator,
 };
+use sgcore::operation_report::{self, Action, Operation};
 use sgcore::show;
 use sgcore::show_error;
 use sgcore::translate;
@@ -223,6 +224,11 @@ fn copy_direntry(
                 options,
                 Some(&entry.source_absolute)
             )?;
+            operation_report::record(
+                Operation::new(Action::Created)
+                    .source(&entry.source_relative)
+                    .destination(&entry.local_to_target)
+            );
             if options.verbose {
                 println!(
                     "{}",
@@ -252,14 +258,21 @@ fn copy_direntry(
             } else {
                 match err {
                     CpError::IoErrContext(e, _) if e.kind() == io::ErrorKind::PermissionDenied => {
-                        show!(uio_error!(
+                        let error = uio_error!(
                             e,
                             "{}",
                             translate!(
                                 "cp-error-cannot-open-for-reading",
                                 "source" => entry.source_relative.quote()
                             )
-                        ));
+                        );
+                        operation_report::record(
+                            Operation::new(Action::Copied)
+                                .source(&entry.source_relative)
+                                .destination(&entry.local_to_target)
+                                .error(&error)
+                        );
+                        show!(error);
                     }
                     e => return Err(e),
                 }
@@ -26,6 +26,8 @@ use sgcore::fs::{
     get_filename, is_symlink_loop, normalize_path, path_ends_with_terminator,
     paths_refer_to_same_file,
 };
+use sgcore::operation_report::{self, Action, Operation};
+use sgcore::stardust_output::{self, StardustOutputOptions};
 use sgcore::{backup_control, update_control};
 pub use sgcore::{backup_control::BackupMode, update_control::UpdateMode};
 use sgcore::{
@@ -361,13 +363,28 @@ impl Default for Options {
     }
 }
 
-/// Enum representing if a file has been skipped.
+/// Enum representing what was done to a file.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum PerformedAction {
     Copied,
+    Reflinked,
+    Linked,
+    Symlinked,
     Skipped,
 }
 
+impl PerformedAction {
+    fn report_action(self) -> Action {
+        match self {
+            Self::Copied => Action::Copied,
+            Self::Reflinked => Action::Reflinked,
+            Self::Linked => Action::Linked,
+            Self::Symlinked => Action::Symlinked,
+            Self::Skipped => Action::Skipped,
+        }
+    }
+}
+
 /// Enum representing various debug states of the offload and reflink actions.
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -498,7 +515,7 @@ pub fn sg_app() -> Command {
         options::ATTRIBUTES_ONLY,
         options::COPY_CONTENTS,
     ];
-    Command::new(sgcore::util_name())
+    let cmd = Command::new(sgcore::util_name())
         .version(sgcore::crate_version!())
         .about(translate!("cp-about"))
         .help_template(sgcore::localized_help_template(sgcore::util_name()))
@@ -738,18 +755,28 @@ pub fn sg_app() -> Command {
             Arg::new(options::PATHS)
                 .action(ArgAction::Append)
                 .num_args(1..)
-                .required(true)
+                .required_unless_present(stardust_output::ARG_SCHEMA)
                 .value_hint(clap::ValueHint::AnyPath)
                 .value_parser(ValueParser::os_string())
-        )
+        );
+    stardust_output::add_json_args(cmd)
 }
 
 #[sgcore::main]
 pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
+    if stardust_output::self_describe(&matches, operation_report::schema())? {
+        return Ok(());
+    }
     sgcore::pledge::apply_pledge(&["stdio", "rpath", "wpath", "cpath", "fattr"])?;
 
-    let options = Options::from_matches(&matches)?;
+    let mut options = Options::from_matches(&matches)?;
+    let stardust = StardustOutputOptions::from_matches(&matches);
+    if stardust.stardust_output {
+        // The report replaces the verbose lines on stdout
+        options.verbose = false;
+        operation_report::start();
+    }
 
     if options.overwrite == OverwriteMode::NoClobber && options.backup != BackupMode::None {
         return Err(SGUsageError::new(
@@ -773,6 +800,11 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         set_exit_code(EXIT_ERR);
     }
 
+    if stardust.stardust_output {
+        let report = operation_report::to_value(&operation_report::finish());
+        stardust_output::output(stardust, report, || Ok(()))?;
+    }
+
     Ok(())
 }
 
@@ -860,6 +892,22 @@ impl Attributes {
         ..Self::NONE
     };
 
+    /// Names of the attributes that are kept, for `--obj` reports
+    pub fn preserved(&self) -> Vec<&'static str> {
+        [
+            ("ownership", self.ownership),
+            ("mode", self.mode),
+            ("timestamps", self.timestamps),
+            ("context", self.context),
+            ("links", self.links),
+            ("xattr", self.xattr),
+        ]
+        .into_iter()
+        .filter(|(_, preserve)| matches!(preserve, Preserve::Yes { .. }))
+        .map(|(name, _)| name)
+        .collect()
+    }
+
     pub fn union(self, other: &Self) -> Self {
         Self {
             ownership: self.ownership.max(other.ownership),
@@ -1198,6 +1246,16 @@ fn show_error_if_needed(error: &CpError) {
     }
 }
 
+/// Add a source that could not be copied to the `--obj` report.
+fn report_error(source: &Path, dest: &Path, error: &CpError) {
+    let operation = match error {
+        CpError::NotAllFilesCopied => return,
+        CpError::Skipped(_) => Operation::new(Action::Skipped),
+        _ => Operation::new(Action::Copied).error(error),
+    };
+    operation_report::record(operation.source(source).destination(dest));
+}
+
 /// Copy all `sources` to `target`.
 ///
 /// Returns an `Err(Error::NotAllFilesCopied)` if at least one non-fatal error
@@ -1270,6 +1328,7 @@ pub fn copy(sources: &[PathBuf], target: &Path, options: &Options) -> CopyResult
                 &mut created_parent_dirs
             ) {
                 show_error_if_needed(&error);
+                report_error(source, &dest, &error);
                 if !matches!(error, CpError::Skipped(false)) {
                     non_fatal_errors = true;
                 }
@@ -1605,6 +1664,11 @@ fn backup_dest(dest: &Path, backup_path: &Path, is_dest_symlink: bool) -> CopyRe
     } else {
         fs::copy(dest, backup_path)?;
     }
+    operation_report::record(
+        Operation::new(Action::BackedUp)
+            .source(dest)
+            .destination(backup_path)
+    );
     Ok(backup_path.into())
 }
 
@@ -1748,6 +1812,7 @@ fn delete_dest_if_needed_and_allowed(
 fn delete_path(path: &Path, options: &Options) -> CopyResult<()> {
     match fs::remove_file(path) {
         Ok(()) => {
+            operation_report::record(Operation::new(Action::Removed).source(path));
             if options.verbose {
                 println!(
                     "{}",
@@ -1876,6 +1941,7 @@ fn handle_copy_mode(
                     translate!("cp-error-cannot-create-hard-link", "dest" => get_filename(dest).unwrap_or("?").quote(), "source" => get_filename(source).unwrap_or("?").quote())
                 )
             })?;
+            Ok(PerformedAction::Linked)
         }
         CopyMode::Copy => {
             copy_helper(
@@ -1889,13 +1955,14 @@ fn handle_copy_mode(
                 symlinked_files,
                 created_parent_dirs,
                 source_is_stream
-            )?;
+            )
         }
         CopyMode::SymLink => {
             if dest.exists() && options.overwrite == OverwriteMode::Clobber(ClobberMode::Force) {
                 fs::remove_file(dest)?;
             }
             symlink_file(source, dest, symlinked_files)?;
+            Ok(PerformedAction::Symlinked)
         }
         CopyMode::Update => {
             if dest.exists() {
@@ -1912,20 +1979,18 @@ fn handle_copy_mode(
                             symlinked_files,
                             created_parent_dirs,
                             source_is_stream
-                        )?;
+                        )
                     }
                     UpdateMode::None => {
                         if options.debug {
                             println!("skipped {}", dest.quote());
                         }
 
-                        return Ok(PerformedAction::Skipped);
-                    }
-                    UpdateMode::NoneFail => {
-                        return Err(CpError::Error(
-                            translate!("cp-error-not-replacing", "file" => dest.quote())
-                        ));
+                        Ok(PerformedAction::Skipped)
                     }
+                    UpdateMode::NoneFail => Err(CpError::Error(
+                        translate!("cp-error-not-replacing", "file" => dest.quote())
+                    )),
                     UpdateMode::IfOlder => {
                         let dest_metadata = fs::symlink_metadata(dest)?;
 
@@ -1948,7 +2013,7 @@ fn handle_copy_mode(
                             symlinked_files,
                             created_parent_dirs,
                             source_is_stream
-                        )?;
+                        )
                     }
                 }
             } else {
@@ -1963,7 +2028,7 @@ fn handle_copy_mode(
                     symlinked_files,
                     created_parent_dirs,
                     source_is_stream
-                )?;
+                )
             }
         }
         CopyMode::AttrOnly => {
@@ -1973,10 +2038,9 @@ fn handle_copy_mode(
                 .create(true)
                 .open(dest)
                 .unwrap();
+            Ok(PerformedAction::Copied)
         }
     }
-
-    Ok(PerformedAction::Copied)
 }
 
 /// Calculates the permissions for the destination file in a copy operation.
@@ -2148,6 +2212,11 @@ fn copy_file(
                 .map_err(|e| CpError::IoErrContext(e, format!("cannot stat {}", source.quote())))?
         ) {
             fs::hard_link(new_source, dest)?;
+            operation_report::record(
+                Operation::new(Action::Linked)
+                    .source(source)
+                    .destination(dest)
+            );
 
             if options.verbose {
                 print_verbose_output(options.parents, progress_bar, source, dest);
@@ -2206,6 +2275,20 @@ fn copy_file(
         source_is_stream
     )?;
 
+    let mut operation = Operation::new(performed_action.report_action())
+        .source(source)
+        .destination(dest);
+    if matches!(
+        performed_action,
+        PerformedAction::Copied | PerformedAction::Reflinked
+    ) {
+        if source_metadata.is_file() && !options.attributes_only {
+            operation = operation.bytes(source_metadata.len());
+        }
+        operation = operation.preserved(options.attributes.preserved());
+    }
+    operation_report::record(operation);
+
     if options.verbose && performed_action != PerformedAction::Skipped {
         print_verbose_output(options.parents, progress_bar, source, dest);
     }
@@ -2296,7 +2379,7 @@ fn copy_helper(
     symlinked_files: &mut HashSet<FileInformation>,
     created_parent_dirs: &mut HashSet<PathBuf>,
     source_is_stream: bool
-) -> CopyResult<()> {
+) -> CopyResult<PerformedAction> {
     if options.parents {
         let parent = dest.parent().unwrap_or(dest);
         if created_parent_dirs.insert(parent.to_path_buf()) {
@@ -2327,9 +2410,12 @@ fn copy_helper(
         if !options.attributes_only && options.debug {
             show_debug(&copy_debug);
         }
+        if matches!(copy_debug.reflink, OffloadReflinkDebug::Yes) {
+            return Ok(PerformedAction::Reflinked);
+        }
     }
 
-    Ok(())
+    Ok(PerformedAction::Copied)
 }
 
 fn copy_fifo(dest: &Path, overwrite: OverwriteMode, debug: bool) -> CopyResult<()> {
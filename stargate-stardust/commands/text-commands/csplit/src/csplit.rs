@@ -12,9 +12,13 @@ use std::{
 
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use regex::Regex;
+use serde_json::{Value, json};
 use sgcore::display::Quotable;
 use sgcore::error::{FromIo, SGResult};
 use sgcore::format_usage;
+use sgcore::stardust_output::{
+    self, FORMAT_BYTES, Property, StardustOutputOptions, array_schema, object_schema,
+};
 
 mod csplit_error;
 mod patterns;
@@ -44,6 +48,7 @@ pub struct CsplitOptions {
     quiet: bool,
     elide_empty_files: bool,
     suppress_matched: bool,
+    stardust: StardustOutputOptions,
 }
 
 impl CsplitOptions {
@@ -63,6 +68,7 @@ impl CsplitOptions {
             quiet,
             elide_empty_files,
             suppress_matched,
+            stardust: StardustOutputOptions::from_matches(matches),
         })
     }
 }
@@ -139,10 +145,32 @@ where
     }
     if ret.is_err() && !options.keep_files {
         split_writer.delete_all_splits()?;
+    } else if options.stardust.stardust_output {
+        let splits = std::mem::take(&mut split_writer.splits);
+        let output = json!({
+            "count": splits.len(),
+            "files": splits,
+        });
+        stardust_output::output(options.stardust.clone(), output, || Ok(()))?;
     }
     ret
 }
 
+/// Schema of the `--obj` output
+fn stardust_schema() -> Value {
+    let file = object_schema(vec![
+        Property::new("path", "string").description("Name of the split file"),
+        Property::new("bytes", "integer").format(FORMAT_BYTES).description("Number of bytes written to it"),
+        Property::new("lines", "integer").description("Number of lines written to it"),
+        Property::new("first_line", json!({"type": ["string", "null"]}))
+            .description("Its first line without the newline; null when empty"),
+    ]);
+    sgcore::schema!(
+        "count" => "integer", "Number of split files";
+        "files" => array_schema(file), "Split files in the order they were written";
+    )
+}
+
 fn do_csplit<I>(
     split_writer: &mut SplitWriter,
     patterns: Vec<patterns::Pattern>,
@@ -214,6 +242,12 @@ struct SplitWriter<'a> {
     current_writer: Option<BufWriter<File>>,
     /// the size in bytes of the current split
     size: usize,
+    /// the number of lines in the current split
+    lines: usize,
+    /// the first line of the current split, for `--obj`
+    first_line: Option<String>,
+    /// the splits completed so far, as `--obj` reports them
+    splits: Vec<Value>,
     /// flag to indicate that no content should be written to a split
     dev_null: bool,
 }
@@ -234,6 +268,9 @@ impl SplitWriter<'_> {
             counter: 0,
             current_writer: None,
             size: 0,
+            lines: 0,
+            first_line: None,
+            splits: Vec::new(),
             dev_null: false,
         }
     }
@@ -249,6 +286,8 @@ impl SplitWriter<'_> {
         self.current_writer = Some(BufWriter::new(file));
         self.counter += 1;
         self.size = 0;
+        self.lines = 0;
+        self.first_line = None;
         self.dev_null = false;
         Ok(())
     }
@@ -271,6 +310,11 @@ impl SplitWriter<'_> {
                     let bytes = line.as_bytes();
                     current_writer.write_all(bytes)?;
                     self.size += bytes.len();
+                    self.lines += 1;
+                    if self.options.stardust.stardust_output && self.first_line.is_none() {
+                        let first_line = line.strip_suffix('\n').unwrap_or(line);
+                        self.first_line = Some(first_line.to_string());
+                    }
                 }
                 None => panic!("{}", translate!("csplit-write-split-not-created")),
             }
@@ -280,7 +324,7 @@ impl SplitWriter<'_> {
 
     /// Perform some operations after completing a split, i.e., either remove it
     /// if the [`options::ELIDE_EMPTY_FILES`] option is enabled, or print how much bytes were written
-    /// to it if [`options::QUIET`] is disabled.
+    /// to it if [`options::QUIET`] is disabled, or keep its record for `--obj`.
     ///
     /// # Errors
     ///
@@ -289,6 +333,13 @@ impl SplitWriter<'_> {
         if !self.dev_null {
             if self.options.elide_empty_files && self.size == 0 {
                 self.counter -= 1;
+            } else if self.options.stardust.stardust_output {
+                self.splits.push(json!({
+                    "path": self.options.split_name.get(self.counter - 1),
+                    "bytes": self.size,
+                    "lines": self.lines,
+                    "first_line": self.first_line.take(),
+                }));
             } else if !self.options.quiet {
                 println!("{}", self.size);
             }
@@ -578,6 +629,10 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
     sgcore::pledge::apply_pledge(&["stdio", "rpath", "wpath", "cpath"])?;
 
+    if stardust_output::self_describe(&matches, stardust_schema())? {
+        return Ok(());
+    }
+
     let file_name = matches.get_one::<OsString>(options::FILE).unwrap();
 
     let patterns: Vec<String> = matches
@@ -597,7 +652,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 }
 
 pub fn sg_app() -> Command {
-    Command::new(sgcore::util_name())
+    let cmd = Command::new(sgcore::util_name())
         .version(sgcore::crate_version!())
         .help_template(sgcore::localized_help_template(sgcore::util_name()))
         .about(translate!("csplit-about"))
@@ -657,7 +712,7 @@ pub fn sg_app() -> Command {
         .arg(
             Arg::new(options::FILE)
                 .hide(true)
-                .required(true)
+                .required_unless_present(stardust_output::ARG_SCHEMA)
                 .value_hint(clap::ValueHint::FilePath)
                 .value_parser(clap::value_parser!(OsString))
         )
@@ -665,9 +720,11 @@ pub fn sg_app() -> Command {
             Arg::new(options::PATTERN)
                 .hide(true)
                 .action(ArgAction::Append)
-                .required(true)
+                .required_unless_present(stardust_output::ARG_SCHEMA)
         )
-        .after_help(translate!("csplit-after-help"))
+        .after_help(translate!("csplit-after-help"));
+
+    stardust_output::add_json_args(cmd)
 }
 
 #[cfg(test)]
@@ -114,11 +114,16 @@ dd-after-help = ### Operands
   - noctty : do not assign a controlling tty.
   - nofollow : do not follow system links.
 
+# Help messages
+dd-help-obj = Write progress updates and the final statistics as stardust records, one JSON object per line, to stderr
+dd-help-obj-fd = With --obj, write the records to file descriptor FD instead of stderr
+
 # Error messages
 dd-error-failed-to-open = failed to open { $path }
 dd-error-write-error = write error
 dd-error-failed-to-seek = failed to seek in output file
 dd-error-io-error = IO error
+dd-error-bad-obj-fd = --obj-fd { $fd }: Bad file descriptor
 dd-error-cannot-skip-offset = '{ $file }': cannot skip to specified offset
 dd-error-cannot-skip-invalid = '{ $file }': cannot skip: Invalid argument
 dd-error-cannot-seek-invalid = '{ $output }': cannot seek: Invalid argument
@@ -114,11 +114,16 @@ dd-after-help = ### Opérandes
   - noctty : ne pas assigner un tty de contrôle.
   - nofollow : ne pas suivre les liens système.
 
+# Help messages
+dd-help-obj = Écrire la progression et les statistiques finales comme enregistrements stardust, un objet JSON par ligne, sur la sortie d'erreur
+dd-help-obj-fd = Avec --obj, écrire les enregistrements sur le descripteur de fichier FD au lieu de la sortie d'erreur
+
 # Error messages
 dd-error-failed-to-open = échec de l'ouverture de { $path }
 dd-error-write-error = erreur d'écriture
 dd-error-failed-to-seek = échec de la recherche dans le fichier de sortie
 dd-error-io-error = erreur E/S
+dd-error-bad-obj-fd = --obj-fd { $fd } : Mauvais descripteur de fichier
 dd-error-cannot-skip-offset = '{ $file }' : impossible d'ignorer jusqu'au décalage spécifié
 dd-error-cannot-skip-invalid = '{ $file }' : impossible d'ignorer : Argument invalide
 dd-error-cannot-seek-invalid = '{ $output }' : impossible de rechercher : Argument invalide
@@ -84,4 +84,5 @@ pub struct OFlags {
 
 pub mod options {
     pub const OPERANDS: &str = "operands";
+    pub const OBJ_FD: &str = "obj-fd";
 }
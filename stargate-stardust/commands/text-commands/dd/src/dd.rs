@@ -17,7 +17,9 @@ use nix::fcntl::FcntlArg::F_SETFL;
 use nix::fcntl::OFlag;
 use parseargs::Parser;
 use progress::ProgUpdateType;
-use progress::{ProgUpdate, ReadStat, StatusLevel, WriteStat, gen_prog_updater};
+use progress::{
+    ProgUpdate, ReadStat, StatusLevel, WriteStat, gen_obj_prog_updater, gen_prog_updater,
+};
 use sgcore::io::OwnedFileDescriptorOrHandle;
 use sgcore::translate;
 
@@ -32,7 +34,7 @@ use std::os::fd::AsFd;
 use std::os::unix::fs::OpenOptionsExt;
 use std::os::unix::{
     fs::FileTypeExt,
-    io::{AsRawFd, FromRawFd},
+    io::{AsRawFd, FromRawFd, RawFd},
 };
 
 use std::path::Path;
@@ -41,7 +43,7 @@ use std::sync::{Arc, atomic::Ordering::Relaxed, mpsc};
 use std::thread;
 use std::time::{Duration, Instant};
 
-use clap::{Arg, Command};
+use clap::{Arg, ArgAction, Command, value_parser};
 use gcd::Gcd;
 #[cfg(target_os = "linux")]
 use nix::{
@@ -53,6 +55,7 @@ use sgcore::error::{FromIo, SGResult};
 use sgcore::error::{SGSimpleError, set_exit_code};
 #[cfg(target_os = "linux")]
 use sgcore::show_if_err;
+use sgcore::stardust_output;
 use sgcore::{format_usage, show_error};
 
 const BUF_INIT_BYTE: u8 = 0xDD;
@@ -74,6 +77,8 @@ struct Settings {
     status: Option<StatusLevel>,
     /// Whether the output writer should buffer partial blocks until complete.
     buffered: bool,
+    /// With `--obj`, the descriptor progress records are written to.
+    obj_fd: Option<RawFd>,
 }
 
 /// A timer which triggers on a given interval
@@ -992,7 +997,14 @@ fn dd_copy(mut i: Input, o: Output) -> io::Result<()> {
     let bsize = calc_bsize(i.settings.ibs, o.settings.obs);
 
     let (prog_tx, rx) = mpsc::channel();
-    let output_thread = thread::spawn(gen_prog_updater(rx, i.settings.status));
+    let output_thread = match i.settings.obj_fd {
+        Some(fd) => {
+            let sink = progress::record_sink(fd)?;
+            let total = expected_bytes(&i);
+            thread::spawn(gen_obj_prog_updater(rx, i.settings.status, total, sink))
+        }
+        None => thread::spawn(gen_prog_updater(rx, i.settings.status)),
+    };
 
     let truncate = !o.settings.oconv.notrunc;
 
@@ -1077,6 +1089,20 @@ fn dd_copy(mut i: Input, o: Output) -> io::Result<()> {
     finalize(o, rstat, wstat, start, &prog_tx, output_thread, truncate)
 }
 
+/// The number of bytes the copy will read, if the input is a file of known
+/// size or `count=N` limits it.
+fn expected_bytes(i: &Input) -> Option<u64> {
+    let limit = i.settings.count.map(|count| count.to_bytes(i.settings.ibs as u64));
+    let available = match i.src.len() {
+        Ok(len) if len > 0 => Some((len as u64).saturating_sub(i.settings.skip)),
+        _ => None,
+    };
+    match (limit, available) {
+        (Some(limit), Some(available)) => Some(limit.min(available)),
+        (limit, available) => limit.or(available),
+    }
+}
+
 /// Flush output, print final stats, and join with the progress thread.
 fn finalize<T>(
     mut output: BlockWriter,
@@ -1283,12 +1309,30 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
     sgcore::pledge::apply_pledge(&["stdio"])?;
 
-    let settings: Settings = Parser::new().parse(
+    if stardust_output::self_describe(&matches, progress::schema())? {
+        return Ok(());
+    }
+
+    let mut settings: Settings = Parser::new().parse(
         matches
             .get_many::<String>(options::OPERANDS)
             .unwrap_or_default()
     )?;
 
+    if matches.get_flag(stardust_output::ARG_STARDUST_OUTPUT) {
+        let fd = matches
+            .get_one::<RawFd>(options::OBJ_FD)
+            .copied()
+            .unwrap_or(libc::STDERR_FILENO);
+        if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+            return Err(SGSimpleError::new(
+                1,
+                translate!("dd-error-bad-obj-fd", "fd" => fd)
+            ));
+        }
+        settings.obj_fd = Some(fd);
+    }
+
     let i = match settings.infile {
         Some(ref infile) if is_fifo(infile) => Input::new_fifo(Path::new(&infile), &settings)?,
         Some(ref infile) => Input::new_file(Path::new(&infile), &settings)?,
@@ -1312,6 +1356,27 @@ pub fn sg_app() -> Command {
         .after_help(translate!("dd-after-help"))
         .infer_long_args(true)
         .arg(Arg::new(options::OPERANDS).num_args(1..))
+        .arg(
+            Arg::new(stardust_output::ARG_STARDUST_OUTPUT)
+                .long("obj")
+                .help(translate!("dd-help-obj"))
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new(options::OBJ_FD)
+                .long(options::OBJ_FD)
+                .value_name("FD")
+                .value_parser(value_parser!(RawFd))
+                .requires(stardust_output::ARG_STARDUST_OUTPUT)
+                .help(translate!("dd-help-obj-fd"))
+        )
+        .arg(
+            Arg::new(stardust_output::ARG_SCHEMA)
+                .long("schema")
+                .help("Print JSON schema of output structure")
+                .action(ArgAction::SetTrue)
+                .hide(true)
+        )
 }
 
 #[cfg(test)]
@@ -239,6 +239,7 @@ impl Parser {
             iflags: self.iflag,
             oflags: self.oflag,
             status: self.status,
+            obj_fd: None,
         })
     }
 
@@ -4,16 +4,20 @@
 //! The [`ProgUpdate`] struct represents summary statistics for the
 //! read and write progress of a running `dd` process. The
 //! [`gen_prog_updater`] function can be used to implement a progress
-//! updater that runs in its own thread.
+//! updater that runs in its own thread; [`gen_obj_prog_updater`] is its
+//! counterpart for `--obj`, writing each update as a stardust record.
 
 use std::io::Write;
+use std::os::fd::{FromRawFd, RawFd};
 use std::sync::mpsc;
 #[cfg(target_os = "linux")]
 use std::thread::JoinHandle;
 use std::time::Duration;
 
+use serde_json::{Value, json};
 #[cfg(target_os = "linux")]
 use signal_hook::iterator::Handle;
+use sgcore::stardust_output::{FORMAT_BYTES, Property, create_schema, enum_schema, object_schema};
 use sgcore::{
     error::SGResult,
     format::num_format::{FloatVariant, Formatter},
@@ -238,6 +242,52 @@ impl ProgUpdate {
         self.write_transfer_stats(&mut stderr, new_line).unwrap();
     }
 
+    /// The update as a stardust record. `total` is the number of bytes the
+    /// copy will read, when the size of the input (or `count=N`) tells.
+    pub(crate) fn to_value(&self, total: Option<u64>) -> Value {
+        let elapsed = self.duration.as_secs_f64();
+        let bytes_read = self.read_stat.bytes_total;
+        let bytes_written = self.write_stat.bytes_total as u64;
+        let per_second = |bytes: u64| {
+            if elapsed > 0.0 {
+                bytes as f64 / elapsed
+            } else {
+                0.0
+            }
+        };
+        let read_rate = per_second(bytes_read);
+
+        let percent = total.map(|total| match total {
+            0 => 100.0,
+            total => (bytes_read as f64 * 100.0 / total as f64).min(100.0),
+        });
+        let eta = total.and_then(|total| match total.saturating_sub(bytes_read) {
+            0 => Some(0.0),
+            remaining if read_rate > 0.0 => Some(remaining as f64 / read_rate),
+            _ => None,
+        });
+
+        json!({
+            "type": if self.update_type == ProgUpdateType::Final { "summary" } else { "progress" },
+            "bytes_read": bytes_read,
+            "bytes_written": bytes_written,
+            "records_in": {
+                "full": self.read_stat.reads_complete,
+                "partial": self.read_stat.reads_partial,
+            },
+            "records_out": {
+                "full": self.write_stat.writes_complete,
+                "partial": self.write_stat.writes_partial,
+            },
+            "truncated_records": self.read_stat.records_truncated,
+            "elapsed": elapsed,
+            "rate": per_second(bytes_written),
+            "total": total,
+            "percent": percent,
+            "eta": eta,
+        })
+    }
+
     /// Write all the final statistics.
     pub(crate) fn print_final_stats(
         &self,
@@ -427,6 +477,81 @@ pub(crate) fn gen_prog_updater(
     }
 }
 
+/// Return a closure that can be used in its own thread to write `--obj`
+/// progress records.
+///
+/// Each [`ProgUpdate`] received through `rx` is written to `sink` as one
+/// line of JSON: periodic ones unless `print_level` is `noxfer` or `none`,
+/// those asked for with `SIGUSR1`, and the final summary unless
+/// `print_level` is `none`.
+pub(crate) fn gen_obj_prog_updater(
+    rx: mpsc::Receiver<ProgUpdate>,
+    print_level: Option<StatusLevel>,
+    total: Option<u64>,
+    mut sink: Box<dyn Write + Send>
+) -> impl FnOnce() {
+    move || {
+        while let Ok(update) = rx.recv() {
+            let wanted = match update.update_type {
+                ProgUpdateType::Periodic => {
+                    matches!(print_level, None | Some(StatusLevel::Progress))
+                }
+                ProgUpdateType::Signal => true,
+                ProgUpdateType::Final => print_level != Some(StatusLevel::None),
+            };
+            if wanted {
+                let mut line = update.to_value(total).to_string();
+                line.push('\n');
+                let _ = sink.write_all(line.as_bytes()).and_then(|()| sink.flush());
+            }
+            if update.update_type == ProgUpdateType::Final {
+                return;
+            }
+        }
+    }
+}
+
+/// Where `--obj` records go: stderr, or a duplicate of the descriptor given
+/// with `--obj-fd` so that it stays open for the rest of the process
+pub(crate) fn record_sink(fd: RawFd) -> std::io::Result<Box<dyn Write + Send>> {
+    if fd == libc::STDERR_FILENO {
+        return Ok(Box::new(std::io::stderr()));
+    }
+    let dup = unsafe { libc::dup(fd) };
+    if dup < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(Box::new(unsafe { std::fs::File::from_raw_fd(dup) }))
+}
+
+/// Schema of one `dd --obj` record
+pub(crate) fn schema() -> Value {
+    let records = || {
+        object_schema(vec![
+            Property::new("full", "integer").description("Complete blocks"),
+            Property::new("partial", "integer").description("Partial blocks"),
+        ])
+    };
+    create_schema(vec![
+        Property::new("type", enum_schema(&["progress", "summary"]))
+            .description("A periodic update, or the final statistics"),
+        Property::new("bytes_read", "integer").format(FORMAT_BYTES).description("Bytes read from the input"),
+        Property::new("bytes_written", "integer").format(FORMAT_BYTES).description("Bytes written to the output"),
+        Property::new("records_in", records()).description("Blocks read"),
+        Property::new("records_out", records()).description("Blocks written"),
+        Property::new("truncated_records", "integer")
+            .description("Records cut short by conv=block"),
+        Property::new("elapsed", "number").description("Seconds since the copy started"),
+        Property::new("rate", "number").description("Bytes written per second"),
+        Property::new("total", json!({ "type": ["integer", "null"] }))
+            .description("Bytes the copy will read, when the input size or count=N tells"),
+        Property::new("percent", json!({ "type": ["number", "null"] }))
+            .description("Share of total read so far"),
+        Property::new("eta", json!({ "type": ["number", "null"] }))
+            .description("Estimated seconds until the copy is done"),
+    ])
+}
+
 /// signal handler listens for SIGUSR1 signal and runs provided closure.
 #[cfg(target_os = "linux")]
 pub(crate) struct SignalHandler {
@@ -695,6 +820,27 @@ mod tests {
         assert!(iter.next().is_none());
     }
 
+    #[test]
+    fn test_prog_update_to_value() {
+        let prog_update = ProgUpdate {
+            read_stat: ReadStat::new(3, 1, 0, 2000),
+            write_stat: WriteStat::new(3, 1, 2000),
+            duration: Duration::new(2, 0),
+            update_type: super::ProgUpdateType::Periodic,
+        };
+        let record = prog_update.to_value(Some(8000));
+        assert_eq!(record["type"], "progress");
+        assert_eq!(record["records_in"]["full"], 3);
+        assert_eq!(record["records_out"]["partial"], 1);
+        assert_eq!(record["rate"], 1000.0);
+        assert_eq!(record["percent"], 25.0);
+        assert_eq!(record["eta"], 6.0);
+
+        let record = prog_update.to_value(None);
+        assert!(record["percent"].is_null());
+        assert!(record["eta"].is_null());
+    }
+
     #[test]
     fn test_duration_precision() {
         init();
@@ -3,6 +3,7 @@
 mod blocks;
 mod columns;
 mod filesystem;
+mod records;
 mod table;
 
 use blocks::HumanReadable;
@@ -12,6 +13,7 @@ use sgcore::display::Quotable;
 use sgcore::error::{SGError, SGResult, SGSimpleError, get_exit_code};
 use sgcore::fsext::{MountInfo, read_fs_list};
 use sgcore::parser::parse_size::ParseSizeError;
+use sgcore::stardust_output::{self, StardustOutputOptions};
 use sgcore::translate;
 use sgcore::{format_usage, show};
 
@@ -355,6 +357,9 @@ impl SGError for DfError {
 #[sgcore::main]
 pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
+    if stardust_output::self_describe(&matches, records::schema())? {
+        return Ok(());
+    }
     sgcore::pledge::apply_pledge(&["stdio", "rpath"])?;
 
     let opt = Options::from(&matches).map_err(DfError::OptionsError)?;
@@ -389,13 +394,19 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         }
     };
 
-    Table::new(&opt, filesystems).write_to(&mut stdout())?;
+    let stardust_options = StardustOutputOptions::from_matches(&matches);
+    if stardust_options.stardust_output {
+        let value = records::filesystems_value(&opt, filesystems);
+        stardust_output::output(stardust_options, value, || Ok(()))?;
+    } else {
+        Table::new(&opt, filesystems).write_to(&mut stdout())?;
+    }
 
     Ok(())
 }
 
 pub fn sg_app() -> Command {
-    Command::new(sgcore::util_name())
+    let cmd = Command::new(sgcore::util_name())
         .version(sgcore::crate_version!())
         .help_template(sgcore::localized_help_template(sgcore::util_name()))
         .about(translate!("df-about"))
@@ -539,7 +550,9 @@ pub fn sg_app() -> Command {
                 .action(ArgAction::Append)
                 .value_parser(ValueParser::os_string())
                 .value_hint(clap::ValueHint::AnyPath)
-        )
+        );
+
+    stardust_output::add_json_args(cmd)
 }
 
 #[cfg(test)]
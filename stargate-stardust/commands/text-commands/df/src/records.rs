@@ -0,0 +1,105 @@
+//! Stardust output for `df --obj`.
+//!
+//! Each filesystem becomes one record holding the same rows the text table
+//! would show, but with exact byte and inode counts and every column at
+//! once, whatever `--output`, `-h` or `-B` asked for.
+use serde_json::{Map, Value, json};
+use sgcore::stardust_output::{FORMAT_BYTES, Property, array_schema, create_schema, object_schema};
+use sgcore::translate;
+
+use crate::Options;
+use crate::filesystem::Filesystem;
+use crate::table::Row;
+
+/// The filesystem record for `fs`
+fn filesystem_record(fs: Filesystem) -> Value {
+    let mount_info = &fs.mount_info;
+    let mut record = Map::new();
+    record.insert("source".into(), mount_info.dev_name.clone().into());
+    record.insert("device_id".into(), mount_info.dev_id.clone().into());
+    record.insert("fs_type".into(), mount_info.fs_type.clone().into());
+    record.insert("mount_point".into(), mount_info.mount_dir.to_string_lossy().into());
+    record.insert(
+        "mount_options".into(),
+        mount_info
+            .mount_option
+            .split(',')
+            .filter(|option| !option.is_empty())
+            .collect::<Vec<_>>()
+            .into(),
+    );
+    record.insert("remote".into(), mount_info.remote.into());
+    record.insert("block_size".into(), fs.usage.blocksize.into());
+    record.insert(
+        "file".into(),
+        fs.file.as_ref().map(|file| file.to_string_lossy().into_owned()).into(),
+    );
+    record.extend(Row::from(fs).usage_fields());
+    Value::Object(record)
+}
+
+/// The `df --obj` document: the filesystems the table would list and,
+/// with `--total`, their sums
+pub(crate) fn filesystems_value(options: &Options, filesystems: Vec<Filesystem>) -> Value {
+    let mut total = Row::new(&translate!("df-total"));
+    let records: Vec<Value> = filesystems
+        .into_iter()
+        .filter(|fs| options.show_all_fs || fs.usage.blocks > 0)
+        .map(|fs| {
+            if options.show_total {
+                total += Row::from(fs.clone());
+            }
+            filesystem_record(fs)
+        })
+        .collect();
+
+    let mut value = json!({
+        "count": records.len(),
+        "filesystems": records,
+    });
+    if options.show_total {
+        value["total"] = Value::Object(total.usage_fields());
+    }
+    value
+}
+
+fn usage_properties() -> Vec<Property> {
+    let percent = json!({"type": ["number", "null"]});
+    vec![
+        Property::new("size", "integer").format(FORMAT_BYTES).description("Total size in bytes"),
+        Property::new("used", "integer").format(FORMAT_BYTES).description("Bytes in use"),
+        Property::new("available", "integer").format(FORMAT_BYTES).description("Bytes available to unprivileged users"),
+        Property::new("use_percent", percent.clone())
+            .description("Share of used bytes in percent, null for an empty filesystem"),
+        Property::new("inodes", "integer").description("Total number of inodes"),
+        Property::new("inodes_used", "integer").description("Inodes in use"),
+        Property::new("inodes_free", "integer").description("Free inodes"),
+        Property::new("inodes_use_percent", percent)
+            .description("Share of used inodes in percent, null when there are none"),
+    ]
+}
+
+/// The schema printed by `df --schema`
+pub(crate) fn schema() -> Value {
+    let mut record = vec![
+        Property::new("source", "string").description("Device or source the filesystem is mounted from"),
+        Property::new("device_id", "string").description("Device id (major:minor on Linux)"),
+        Property::new("fs_type", "string").description("Filesystem type, e.g. ext4 or tmpfs"),
+        Property::new("mount_point", "string").description("Directory the filesystem is mounted on"),
+        Property::new("mount_options", array_schema("string")).description("Mount options, e.g. rw and relatime"),
+        Property::new("remote", "boolean").description("Whether the filesystem is a network filesystem"),
+        Property::new("block_size", "integer").format(FORMAT_BYTES).description("Fundamental block size in bytes"),
+        Property::new("file", json!({"type": ["string", "null"]}))
+            .description("The path given on the command line, null when listing all filesystems"),
+    ];
+    record.extend(usage_properties());
+
+    create_schema(vec![
+        Property::new("filesystems", array_schema(object_schema(record)))
+            .description("Mounted filesystems with their usage"),
+        Property::new("count", "integer").description("Number of filesystems listed"),
+        Property::new("total", object_schema(usage_properties()))
+            .description("Sums over all listed filesystems (with --total)")
+            .optional(),
+    ])
+}
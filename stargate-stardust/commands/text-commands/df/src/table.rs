@@ -9,6 +9,7 @@ use crate::blocks::{SuffixType, to_magnitude_and_suffix};
 use crate::columns::{Alignment, Column};
 use crate::filesystem::Filesystem;
 use crate::{BlockSize, Options};
+use serde_json::{Map, Value};
 use sgcore::fsext::{FsUsage, MountInfo};
 use sgcore::translate;
 
@@ -188,6 +189,26 @@ impl From<Filesystem> for Row {
     }
 }
 
+impl Row {
+    /// The byte and inode counts of this row as stardust fields. Counts are
+    /// exact bytes whatever the block size; percentages are `null` for an
+    /// empty filesystem.
+    pub(crate) fn usage_fields(&self) -> Map<String, Value> {
+        let percent = |fraction: Option<f64>| fraction.map(|x| (x * 10000.0).round() / 100.0);
+        let inodes = |n: u128| u64::try_from(n).unwrap_or(u64::MAX);
+        let mut fields = Map::new();
+        fields.insert("size".into(), self.bytes.into());
+        fields.insert("used".into(), self.bytes_used.into());
+        fields.insert("available".into(), self.bytes_avail.into());
+        fields.insert("use_percent".into(), percent(self.bytes_usage).into());
+        fields.insert("inodes".into(), inodes(self.inodes).into());
+        fields.insert("inodes_used".into(), inodes(self.inodes_used).into());
+        fields.insert("inodes_free".into(), inodes(self.inodes_free).into());
+        fields.insert("inodes_use_percent".into(), percent(self.inodes_usage).into());
+        fields
+    }
+}
+
 /// A `Cell` in the table. We store raw `bytes` as the data (e.g. directory name
 /// may be non-Unicode). We also record the printed `width` for alignment purpose,
 /// as it is easier to compute on the original string.
@@ -1078,6 +1099,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_row_usage_fields() {
+        let fields = Row::default().usage_fields();
+        assert_eq!(fields["size"], 100);
+        assert_eq!(fields["available"], 75);
+        assert_eq!(fields["use_percent"], 25.0);
+        assert_eq!(fields["inodes_free"], 8);
+
+        let empty = Row::new("empty").usage_fields();
+        assert!(empty["use_percent"].is_null());
+        assert!(empty["inodes_use_percent"].is_null());
+    }
+
     #[test]
     fn test_row_accumulation_u64_overflow() {
         init();
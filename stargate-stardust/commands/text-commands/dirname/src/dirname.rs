@@ -65,7 +65,6 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 
     let line_ending = LineEnding::from_zero_flag(matches.get_flag(options::ZERO));
     let opts = StardustOutputOptions::from_matches(&matches);
-    let field_filter = matches.get_one::<String>(stardust_output::ARG_FIELD).map(|s| s.as_str());
 
     let dirnames: Vec<OsString> = matches
         .get_many::<OsString>(options::DIR)
@@ -123,7 +122,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 
             results.push(dirname_str);
         }
-        let output = stardust_output::filter_fields(json!({"path": results}), field_filter);
+        let output = json!({"path": results});
         stardust_output::output(opts, output, || Ok(()))?;
     } else {
         for path in &dirnames {
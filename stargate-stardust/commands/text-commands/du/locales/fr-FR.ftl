@@ -43,6 +43,8 @@ du-help-exclude-from = exclure les fichiers qui correspondent à n'importe quel
 du-help-files0-from = résumer l'utilisation du périphérique des noms de fichiers terminés par NUL spécifiés dans le fichier F ; si F est -, alors lire les noms depuis l'entrée standard
 du-help-time = montrer l'heure de la dernière modification de n'importe quel fichier dans le répertoire, ou n'importe lequel de ses sous-répertoires. Si MOT est donné, montrer l'heure comme MOT au lieu de l'heure de modification : atime, access, use, ctime, status, birth ou creation
 du-help-time-style = montrer les heures en utilisant le style STYLE : full-iso, long-iso, iso, +FORMAT FORMAT est interprété comme 'date'
+du-help-threads = parcourir les répertoires avec jusqu'à N threads (0 pour un par CPU) ; s'applique au parcours par défaut sous Linux, l'ordre de sortie peut varier
+du-help-flat = avec --obj, lister les entrées avec leur profondeur au lieu d'un arbre
 
 # Messages d'erreur
 du-error-invalid-max-depth = profondeur maximale invalide { $depth }
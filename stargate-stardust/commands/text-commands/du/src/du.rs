@@ -11,7 +11,13 @@ use std::fs::{self, DirEntry, File};
 use std::io::{BufRead, BufReader, stdout};
 use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
+#[cfg(target_os = "linux")]
+use std::num::NonZero;
 use std::str::FromStr;
+#[cfg(target_os = "linux")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(target_os = "linux")]
+use std::sync::{Mutex, PoisonError};
 use std::sync::mpsc;
 use std::thread;
 use thiserror::Error;
@@ -21,6 +27,7 @@ use sgcore::fsext::{MetadataTimeField, metadata_get_time};
 use sgcore::line_ending::LineEnding;
 #[cfg(target_os = "linux")]
 use sgcore::safe_traversal::DirFd;
+use sgcore::stardust_output::{self, StardustOutputOptions};
 use sgcore::translate;
 
 use sgcore::parser::parse_glob;
@@ -29,6 +36,8 @@ use sgcore::parser::shortcut_value_parser::ShortcutValueParser;
 use sgcore::time::{FormatSystemTimeFallback, format, format_system_time};
 use sgcore::{format_usage, show, show_error, show_warning};
 
+mod records;
+
 mod options {
     pub const HELP: &str = "help";
     pub const NULL: &str = "0";
@@ -57,6 +66,8 @@ mod options {
     pub const EXCLUDE_FROM: &str = "exclude-from";
     pub const FILES0_FROM: &str = "files0-from";
     pub const VERBOSE: &str = "verbose";
+    pub const THREADS: &str = "threads";
+    pub const FLAT: &str = "flat";
     pub const FILE: &str = "FILE";
 }
 
@@ -82,6 +93,9 @@ struct StatPrinter {
     line_ending: LineEnding,
     summarize: bool,
     total_text: String,
+    stardust: StardustOutputOptions,
+    /// With `--obj`, list the entries with their depth instead of as a tree
+    flat: bool,
 }
 
 #[derive(PartialEq, Clone)]
@@ -106,6 +120,7 @@ struct FileInfo {
 
 struct Stat {
     path: PathBuf,
+    is_dir: bool,
     size: u64,
     blocks: u64,
     inodes: u64,
@@ -138,6 +153,7 @@ impl Stat {
 
         Ok(Self {
             path: path.to_path_buf(),
+            is_dir: metadata.is_dir(),
             size: if metadata.is_dir() { 0 } else { metadata.len() },
             blocks,
             inodes: 1,
@@ -146,6 +162,13 @@ impl Stat {
         })
     }
 
+    /// Add the size, blocks and inodes of a subdirectory
+    fn add_usage(&mut self, (size, blocks, inodes): (u64, u64, u64)) {
+        self.size += size;
+        self.blocks += blocks;
+        self.inodes += inodes;
+    }
+
     /// Create a Stat using safe traversal methods with `DirFd` for the root directory
     #[cfg(target_os = "linux")]
     fn new_from_dirfd(dir_fd: &DirFd, full_path: &Path) -> std::io::Result<Self> {
@@ -163,6 +186,7 @@ impl Stat {
 
         Ok(Self {
             path: full_path.to_path_buf(),
+            is_dir: safe_metadata.is_dir(),
             size: if safe_metadata.is_dir() {
                 0
             } else {
@@ -210,15 +234,41 @@ fn read_block_size(s: Option<&str>) -> SGResult<u64> {
     }
 }
 
+/// What a walker returns when the printing thread has gone away
+type WalkResult<T> = Result<T, Box<mpsc::SendError<SGResult<StatPrintInfo>>>>;
+
+/// The extra threads `--threads` allows; while one is free, the safe
+/// traversal walks a subdirectory on it instead of in line.
+#[cfg(target_os = "linux")]
+struct Workers(AtomicUsize);
+
+#[cfg(target_os = "linux")]
+impl Workers {
+    fn new(count: usize) -> Self {
+        Self(AtomicUsize::new(count))
+    }
+
+    fn try_acquire(&self) -> bool {
+        self.0
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |free| free.checked_sub(1))
+            .is_ok()
+    }
+
+    fn release(&self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
 #[cfg(target_os = "linux")]
 fn safe_du(
     path: &Path,
     options: &TraversalOptions,
     depth: usize,
-    seen_inodes: &mut HashSet<FileInfo>,
+    seen_inodes: &Mutex<HashSet<FileInfo>>,
     print_tx: &mpsc::Sender<SGResult<StatPrintInfo>>,
-    parent_fd: Option<&DirFd>
-) -> Result<Stat, Box<mpsc::SendError<SGResult<StatPrintInfo>>>> {
+    parent_fd: Option<&DirFd>,
+    workers: &Workers
+) -> WalkResult<Stat> {
     let mut my_stat = if let Some(parent_fd) = parent_fd {
         let dir_name = path.file_name().unwrap_or(path.as_os_str());
         match parent_fd.metadata_at(dir_name, false) {
@@ -236,6 +286,7 @@ fn safe_du(
 
                 Stat {
                     path: path.to_path_buf(),
+                    is_dir: safe_metadata.is_dir(),
                     size: if safe_metadata.is_dir() {
                         0
                     } else {
@@ -325,123 +376,168 @@ fn safe_du(
         }
     };
 
-    'file_loop: for entry_name in entries {
-        let entry_path = path.join(&entry_name);
+    // Subdirectories handed to worker threads (see `--threads`) are added
+    // to this directory's usage once they are all done
+    thread::scope(|scope| -> WalkResult<()> {
+        let mut subdirs = Vec::new();
+        'file_loop: for entry_name in entries {
+            let entry_path = path.join(&entry_name);
+
+            let lstat = match dir_fd.stat_at(&entry_name, false) {
+                Ok(stat) => stat,
+                Err(e) => {
+                    print_tx.send(Err(e.map_err_context(
+                        || translate!("du-error-cannot-access", "path" => entry_path.quote())
+                    )))?;
+                    continue;
+                }
+            };
 
-        let lstat = match dir_fd.stat_at(&entry_name, false) {
-            Ok(stat) => stat,
-            Err(e) => {
-                print_tx.send(Err(e.map_err_context(
-                    || translate!("du-error-cannot-access", "path" => entry_path.quote())
-                )))?;
+            const S_IFMT: u32 = 0o170_000;
+            const S_IFDIR: u32 = 0o040_000;
+            const S_IFLNK: u32 = 0o120_000;
+            let is_symlink = (lstat.st_mode & S_IFMT) == S_IFLNK;
+
+            if is_symlink && options.dereference == Deref::All {
                 continue;
             }
-        };
-
-        const S_IFMT: u32 = 0o170_000;
-        const S_IFDIR: u32 = 0o040_000;
-        const S_IFLNK: u32 = 0o120_000;
-        let is_symlink = (lstat.st_mode & S_IFMT) == S_IFLNK;
-
-        if is_symlink && options.dereference == Deref::All {
-            continue;
-        }
 
-        let is_dir = (lstat.st_mode & S_IFMT) == S_IFDIR;
-        let entry_stat = lstat;
+            let is_dir = (lstat.st_mode & S_IFMT) == S_IFDIR;
+            let entry_stat = lstat;
 
-        let file_info = (entry_stat.st_ino != 0).then_some(FileInfo {
-            file_id: entry_stat.st_ino as u128,
-            dev_id: entry_stat.st_dev,
-        });
+            let file_info = (entry_stat.st_ino != 0).then_some(FileInfo {
+                file_id: entry_stat.st_ino as u128,
+                dev_id: entry_stat.st_dev,
+            });
 
-        let this_stat = if is_dir {
-            Stat {
-                path: entry_path.clone(),
-                size: 0,
-                blocks: entry_stat.st_blocks as u64,
-                inodes: 1,
-                inode: file_info,
-                metadata: my_stat.metadata.clone(),
-            }
-        } else {
-            Stat {
-                path: entry_path.clone(),
-                size: entry_stat.st_size as u64,
-                blocks: entry_stat.st_blocks as u64,
-                inodes: 1,
-                inode: file_info,
-                metadata: my_stat.metadata.clone(),
-            }
-        };
-
-        for pattern in &options.excludes {
-            if pattern.matches(&this_stat.path.to_string_lossy())
-                || pattern.matches(&entry_name.to_string_lossy())
-            {
-                if options.verbose {
-                    println!(
-                        "{}",
-                        translate!("du-verbose-ignored", "path" => this_stat.path.quote())
-                    );
+            let this_stat = if is_dir {
+                Stat {
+                    path: entry_path.clone(),
+                    is_dir: true,
+                    size: 0,
+                    blocks: entry_stat.st_blocks as u64,
+                    inodes: 1,
+                    inode: file_info,
+                    metadata: my_stat.metadata.clone(),
                 }
-                continue 'file_loop;
-            }
-        }
+            } else {
+                Stat {
+                    path: entry_path.clone(),
+                    is_dir: false,
+                    size: entry_stat.st_size as u64,
+                    blocks: entry_stat.st_blocks as u64,
+                    inodes: 1,
+                    inode: file_info,
+                    metadata: my_stat.metadata.clone(),
+                }
+            };
 
-        if let Some(inode) = this_stat.inode {
-            if seen_inodes.contains(&inode) && (!options.count_links || !options.all) {
-                if options.count_links && !options.all {
-                    my_stat.inodes += 1;
+            for pattern in &options.excludes {
+                if pattern.matches(&this_stat.path.to_string_lossy())
+                    || pattern.matches(&entry_name.to_string_lossy())
+                {
+                    if options.verbose {
+                        println!(
+                            "{}",
+                            translate!("du-verbose-ignored", "path" => this_stat.path.quote())
+                        );
+                    }
+                    continue 'file_loop;
                 }
-                continue;
             }
-            seen_inodes.insert(inode);
-        }
 
-        if is_dir {
-            if options.one_file_system {
-                if let (Some(this_inode), Some(my_inode)) = (this_stat.inode, my_stat.inode) {
-                    if this_inode.dev_id != my_inode.dev_id {
-                        continue;
+            if let Some(inode) = this_stat.inode {
+                let mut seen_inodes = seen_inodes.lock().unwrap_or_else(PoisonError::into_inner);
+                if seen_inodes.contains(&inode) && (!options.count_links || !options.all) {
+                    if options.count_links && !options.all {
+                        my_stat.inodes += 1;
                     }
+                    continue;
                 }
+                seen_inodes.insert(inode);
             }
 
-            let this_stat = safe_du(
-                &entry_path,
-                options,
-                depth + 1,
-                seen_inodes,
-                print_tx,
-                Some(&dir_fd)
-            )?;
+            if is_dir {
+                if options.one_file_system
+                    && let (Some(this_inode), Some(my_inode)) = (this_stat.inode, my_stat.inode)
+                    && this_inode.dev_id != my_inode.dev_id
+                {
+                    continue;
+                }
 
-            if !options.separate_dirs {
+                if workers.try_acquire() {
+                    let dir_fd = &dir_fd;
+                    subdirs.push(scope.spawn(move || {
+                        let usage = safe_du_subdir(
+                            &entry_path,
+                            options,
+                            depth + 1,
+                            seen_inodes,
+                            print_tx,
+                            dir_fd,
+                            workers
+                        );
+                        workers.release();
+                        usage
+                    }));
+                    continue;
+                }
+
+                let usage = safe_du_subdir(
+                    &entry_path,
+                    options,
+                    depth + 1,
+                    seen_inodes,
+                    print_tx,
+                    &dir_fd,
+                    workers
+                )?;
+                if !options.separate_dirs {
+                    my_stat.add_usage(usage);
+                }
+            } else {
                 my_stat.size += this_stat.size;
                 my_stat.blocks += this_stat.blocks;
-                my_stat.inodes += this_stat.inodes;
+                my_stat.inodes += 1;
+                if options.all {
+                    print_tx.send(Ok(StatPrintInfo {
+                        stat: this_stat,
+                        depth: depth + 1,
+                    }))?;
+                }
             }
-            print_tx.send(Ok(StatPrintInfo {
-                stat: this_stat,
-                depth: depth + 1,
-            }))?;
-        } else {
-            my_stat.size += this_stat.size;
-            my_stat.blocks += this_stat.blocks;
-            my_stat.inodes += 1;
-            if options.all {
-                print_tx.send(Ok(StatPrintInfo {
-                    stat: this_stat,
-                    depth: depth + 1,
-                }))?;
+        }
+
+        for subdir in subdirs {
+            let usage = subdir.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))?;
+            if !options.separate_dirs {
+                my_stat.add_usage(usage);
             }
         }
-    }
+        Ok(())
+    })?;
 
     Ok(my_stat)
 }
 
+/// Walk the subdirectory `path` of `dir_fd`, report it, and give back its
+/// size, blocks and inodes
+#[cfg(target_os = "linux")]
+fn safe_du_subdir(
+    path: &Path,
+    options: &TraversalOptions,
+    depth: usize,
+    seen_inodes: &Mutex<HashSet<FileInfo>>,
+    print_tx: &mpsc::Sender<SGResult<StatPrintInfo>>,
+    dir_fd: &DirFd,
+    workers: &Workers
+) -> WalkResult<(u64, u64, u64)> {
+    let stat = safe_du(path, options, depth, seen_inodes, print_tx, Some(dir_fd), workers)?;
+    let usage = (stat.size, stat.blocks, stat.inodes);
+    print_tx.send(Ok(StatPrintInfo { stat, depth }))?;
+    Ok(usage)
+}
+
 #[allow(clippy::cognitive_complexity)]
 fn du_regular(
     mut my_stat: Stat,
@@ -451,7 +547,7 @@ fn du_regular(
     print_tx: &mpsc::Sender<SGResult<StatPrintInfo>>,
     ancestors: Option<&mut HashSet<FileInfo>>,
     symlink_depth: Option<usize>
-) -> Result<Stat, Box<mpsc::SendError<SGResult<StatPrintInfo>>>> {
+) -> WalkResult<Stat> {
     let mut default_ancestors = HashSet::new();
     let ancestors = ancestors.unwrap_or(&mut default_ancestors);
     let symlink_depth = symlink_depth.unwrap_or(0);
@@ -683,7 +779,22 @@ impl StatPrinter {
         }
     }
 
+    /// Whether the entry passes `--threshold`, `--max-depth` and `--summarize`
+    fn is_shown(&self, stat_info: &StatPrintInfo) -> bool {
+        let size = self.choose_size(&stat_info.stat);
+        !self
+            .threshold
+            .is_some_and(|threshold| threshold.should_exclude(size))
+            && self
+                .max_depth
+                .is_none_or(|max_depth| stat_info.depth <= max_depth)
+            && (!self.summarize || stat_info.depth == 0)
+    }
+
     fn print_stats(&self, rx: &mpsc::Receiver<SGResult<StatPrintInfo>>) -> SGResult<()> {
+        if self.stardust.stardust_output {
+            return self.print_objects(rx);
+        }
         let mut grand_total = 0;
         loop {
             let received = rx.recv();
@@ -697,14 +808,7 @@ impl StatPrinter {
                             grand_total += size;
                         }
 
-                        if !self
-                            .threshold
-                            .is_some_and(|threshold| threshold.should_exclude(size))
-                            && self
-                                .max_depth
-                                .is_none_or(|max_depth| stat_info.depth <= max_depth)
-                            && (!self.summarize || stat_info.depth == 0)
-                        {
+                        if self.is_shown(&stat_info) {
                             self.print_stat(&stat_info.stat, size)?;
                         }
                     }
@@ -816,6 +920,9 @@ fn read_files_from(file_name: &OsStr) -> Result<Vec<PathBuf>, std::io::Error> {
 #[allow(clippy::cognitive_complexity)]
 pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
+    if stardust_output::self_describe(&matches, records::schema())? {
+        return Ok(());
+    }
     sgcore::pledge::apply_pledge(&["stdio", "rpath"])?;
 
     let summarize = matches.get_flag(options::SUMMARIZE);
@@ -925,6 +1032,8 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         time_format,
         line_ending: LineEnding::from_zero_flag(matches.get_flag(options::NULL)),
         total_text: translate!("du-total"),
+        stardust: StardustOutputOptions::from_matches(&matches),
+        flat: matches.get_flag(options::FLAT),
     };
 
     if stat_printer.inodes
@@ -936,6 +1045,14 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         );
     }
 
+    // Only the safe traversal walks directories in parallel
+    #[cfg(target_os = "linux")]
+    let workers = Workers::new(match matches.get_one::<usize>(options::THREADS) {
+        Some(0) => thread::available_parallelism().map_or(1, NonZero::get) - 1,
+        Some(threads) => threads - 1,
+        None => 0,
+    });
+
     let (print_tx, rx) = mpsc::channel::<SGResult<StatPrintInfo>>();
     let printing_thread = thread::spawn(move || stat_printer.print_stats(&rx));
 
@@ -975,9 +1092,10 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
                     &path,
                     &traversal_options,
                     0,
-                    &mut seen_inodes,
+                    &Mutex::new(seen_inodes),
                     &print_tx,
-                    None
+                    None,
+                    &workers
                 ) {
                     Ok(stat) => {
                         print_tx
@@ -1079,7 +1197,7 @@ fn parse_depth(max_depth_str: Option<&str>, summarize: bool) -> SGResult<Option<
 }
 
 pub fn sg_app() -> Command {
-    Command::new(sgcore::util_name())
+    let cmd = Command::new(sgcore::util_name())
         .version(sgcore::crate_version!())
         .help_template(sgcore::localized_help_template(sgcore::util_name()))
         .about(translate!("du-about"))
@@ -1283,13 +1401,28 @@ pub fn sg_app() -> Command {
                 .value_name("STYLE")
                 .help(translate!("du-help-time-style"))
         )
+        .arg(
+            Arg::new(options::THREADS)
+                .long(options::THREADS)
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help(translate!("du-help-threads"))
+        )
+        .arg(
+            Arg::new(options::FLAT)
+                .long(options::FLAT)
+                .help(translate!("du-help-flat"))
+                .action(ArgAction::SetTrue)
+        )
         .arg(
             Arg::new(options::FILE)
                 .hide(true)
                 .value_hint(clap::ValueHint::AnyPath)
                 .value_parser(clap::value_parser!(OsString))
                 .action(ArgAction::Append)
-        )
+        );
+
+    stardust_output::add_json_args(cmd)
 }
 
 #[derive(Clone, Copy)]
@@ -0,0 +1,125 @@
+//! Stardust output for `du --obj`.
+//!
+//! The walkers report every directory after its contents, so a directory's
+//! children are always complete by the time it arrives, whatever order
+//! parallel walkers finish in. Entries hidden by `--max-depth`,
+//! `--threshold` or `--summarize` are left out of the tree; children of a
+//! hidden entry that are themselves shown move up to its parent.
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use serde_json::{Value, json};
+use sgcore::error::SGResult;
+use sgcore::show;
+use sgcore::stardust_output::{self, FORMAT_BYTES, Property, array_schema, create_schema, enum_schema, object_schema};
+
+use crate::{Stat, StatPrintInfo, StatPrinter};
+
+impl StatPrinter {
+    /// Gather the reported entries into a `du --obj` document and print it
+    pub(crate) fn print_objects(&self, rx: &mpsc::Receiver<SGResult<StatPrintInfo>>) -> SGResult<()> {
+        // The tree's roots, or every entry with --flat
+        let mut entries = Vec::new();
+        // Shown children waiting for the directory they are in
+        let mut pending: HashMap<PathBuf, Vec<Value>> = HashMap::new();
+        let mut total = [0u64; 3];
+
+        for message in rx {
+            let stat_info = match message {
+                Ok(stat_info) => stat_info,
+                Err(e) => {
+                    show!(e);
+                    continue;
+                }
+            };
+            let stat = &stat_info.stat;
+            if stat_info.depth == 0 {
+                total[0] += stat.blocks * 512;
+                total[1] += stat.size;
+                total[2] += stat.inodes;
+            }
+
+            let shown = self.is_shown(&stat_info);
+            if self.flat {
+                if shown {
+                    let mut record = record(stat);
+                    record["depth"] = stat_info.depth.into();
+                    entries.push(record);
+                }
+                continue;
+            }
+
+            let mut children = pending.remove(&stat.path).unwrap_or_default();
+            let siblings = match stat.path.parent() {
+                Some(parent) if stat_info.depth > 0 => pending.entry(parent.to_path_buf()).or_default(),
+                _ => &mut entries,
+            };
+            if shown {
+                let mut record = record(stat);
+                if stat.is_dir {
+                    record["children"] = children.into();
+                }
+                siblings.push(record);
+            } else {
+                siblings.append(&mut children);
+            }
+        }
+
+        let mut value = json!({ "entries": entries });
+        if self.total {
+            value["total"] = json!({
+                "size": total[0],
+                "apparent_size": total[1],
+                "inodes": total[2],
+            });
+        }
+        stardust_output::output(self.stardust.clone(), value, || Ok(()))?;
+        Ok(())
+    }
+}
+
+fn record(stat: &Stat) -> Value {
+    json!({
+        "path": stat.path.to_string_lossy(),
+        "type": if stat.is_dir { "directory" } else { "file" },
+        "size": stat.blocks * 512,
+        "apparent_size": stat.size,
+        "inodes": stat.inodes,
+    })
+}
+
+fn usage_properties() -> Vec<Property> {
+    vec![
+        Property::new("size", "integer").format(FORMAT_BYTES).description("Disk usage in bytes (allocated blocks)"),
+        Property::new("apparent_size", "integer").format(FORMAT_BYTES).description("Sum of file lengths in bytes"),
+        Property::new("inodes", "integer").description("Number of inodes"),
+    ]
+}
+
+/// The schema printed by `du --schema`
+pub(crate) fn schema() -> Value {
+    let mut entry = vec![
+        Property::new("path", "string").description("Path of the directory, or of the file with -a"),
+        Property::new("type", enum_schema(&["directory", "file"])).description("Kind of entry"),
+    ];
+    entry.extend(usage_properties());
+    entry.push(
+        Property::new("depth", "integer")
+            .description("Depth below the argument it was found under (with --flat)")
+            .optional(),
+    );
+    entry.push(
+        Property::new("children", json!({ "type": "array", "items": { "$ref": "#/properties/entries/items" } }))
+            .description("Entries inside the directory, shaped like this one (tree form)")
+            .optional(),
+    );
+
+    create_schema(vec![
+        Property::new("entries", array_schema(object_schema(entry)))
+            .description("The arguments as trees, or every entry with --flat"),
+        Property::new("total", object_schema(usage_properties()))
+            .description("Sums over all arguments (with -c)")
+            .optional(),
+    ])
+}
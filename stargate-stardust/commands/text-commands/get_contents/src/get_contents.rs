@@ -205,7 +205,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     sgcore::pledge::apply_pledge(&["stdio", "rpath"])?;
 
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
-    let opts = StardustOutputOptions { stardust_output: matches.get_flag("object_output"), verbose: false, pretty: matches.get_flag(stardust_output::ARG_PRETTY) };
+    let opts = StardustOutputOptions { stardust_output: matches.get_flag("object_output"), verbose: false, pretty: matches.get_flag(stardust_output::ARG_PRETTY), field: None };
 
     let mut requested_lines: HashSet<usize> = HashSet::new();
     if let Some(filter_spec) = matches.get_one::<String>(stardust_output::ARG_FIELD) {
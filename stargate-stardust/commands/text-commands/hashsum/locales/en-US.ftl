@@ -17,6 +17,7 @@ hashsum-help-strict = exit non-zero for improperly formatted checksum lines
 hashsum-help-ignore-missing = don't fail or report status for missing files
 hashsum-help-warn = warn about improperly formatted checksum lines
 hashsum-help-zero = end each output line with NUL, not newline
+hashsum-help-files-from-stdin = hash the files listed by stardust (JSON) on standard input, e.g. from list-directory
 hashsum-help-length = digest length in bits; must not exceed the max for the blake2 algorithm and must be a multiple of 8
 hashsum-help-no-names = Omits filenames in the output (option not present in GNU/Coreutils)
 hashsum-help-bits = set the size of the output (only for SHAKE)
@@ -40,3 +41,4 @@ hashsum-help-b3sum = work with BLAKE3
 
 # Error messages
 hashsum-error-failed-to-read-input = failed to read input
+hashsum-error-no-files-on-stdin = standard input lists no files
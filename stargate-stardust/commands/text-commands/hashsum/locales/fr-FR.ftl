@@ -14,6 +14,7 @@ hashsum-help-strict = sortir avec un code non-zéro pour les lignes de somme de
 hashsum-help-ignore-missing = ne pas échouer ou rapporter le statut pour les fichiers manquants
 hashsum-help-warn = avertir des lignes de somme de contrôle mal formatées
 hashsum-help-zero = terminer chaque ligne de sortie avec NUL, pas de retour à la ligne
+hashsum-help-files-from-stdin = hacher les fichiers listés par le stardust (JSON) de l'entrée standard, par exemple de list-directory
 hashsum-help-length = longueur de l'empreinte en bits ; ne doit pas dépasser le maximum pour l'algorithme blake2 et doit être un multiple de 8
 hashsum-help-no-names = Omet les noms de fichiers dans la sortie (option non présente dans GNU/Coreutils)
 hashsum-help-bits = définir la taille de la sortie (uniquement pour SHAKE)
@@ -37,3 +38,4 @@ hashsum-help-b3sum = travailler avec BLAKE3
 
 # Messages d'erreur
 hashsum-error-failed-to-read-input = échec de la lecture de l'entrée
+hashsum-error-no-files-on-stdin = l'entrée standard ne liste aucun fichier
@@ -4,12 +4,15 @@ use clap::ArgAction;
 use clap::builder::ValueParser;
 use clap::value_parser;
 use clap::{Arg, ArgMatches, Command};
+use serde_json::json;
 use std::ffi::{OsStr, OsString};
 use std::fs::File;
-use std::io::{BufReader, Read, stdin};
+use std::io::{self, BufRead, BufReader, Cursor, Read, stdin};
 use std::iter;
+use std::mem;
 use std::num::ParseIntError;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use sgcore::checksum::CheckReport;
 use sgcore::checksum::ChecksumError;
 use sgcore::checksum::ChecksumOptions;
 use sgcore::checksum::ChecksumVerbose;
@@ -18,10 +21,16 @@ use sgcore::checksum::calculate_blake2b_length;
 use sgcore::checksum::create_sha3;
 use sgcore::checksum::detect_algo;
 use sgcore::checksum::digest_reader;
+use sgcore::checksum::digest_record;
 use sgcore::checksum::escape_filename;
 use sgcore::checksum::perform_checksum_validation;
+use sgcore::checksum::perform_checksum_validation_report;
+use sgcore::checksum::stardust_schema;
 use sgcore::error::{SGResult, strip_errno};
 use sgcore::format_usage;
+use sgcore::json_adapter::{extract_file_paths, parse_value};
+use sgcore::stardust_output::{self, StardustOutputOptions};
+use sgcore::wire;
 use sgcore::sum::{Digest, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Shake128, Shake256};
 use sgcore::translate;
 
@@ -37,6 +46,9 @@ struct Options<'a> {
     nonames: bool,
     output_bits: usize,
     zero: bool,
+    stardust: StardustOutputOptions,
+    /// Bytes of standard input already read while looking for a file list
+    stdin_prefix: Vec<u8>,
 }
 
 /// Creates a hasher instance based on the command-line flags.
@@ -152,6 +164,46 @@ fn parse_bit_num(arg: &str) -> Result<usize, ParseIntError> {
     arg.parse()
 }
 
+/// What standard input holds when no FILE is given
+enum StdinInput {
+    /// A stardust document listing files, e.g. from `list-directory -R`
+    Paths(Vec<PathBuf>),
+    /// Data to hash, starting with the bytes read to tell the two apart
+    Data(Vec<u8>),
+}
+
+/// Look at standard input for a stardust file list. Only binary stardust,
+/// which a stargate stage writes, is taken as a list on its own; JSON text
+/// is a list only under `--files-from-stdin`, so piping a JSON document in
+/// still hashes its bytes. Anything else is left to be hashed as a stream.
+fn read_stdin_input(files_from_stdin: bool) -> io::Result<StdinInput> {
+    let mut stdin = stdin().lock();
+    let binary = wire::is_binary(stdin.fill_buf()?);
+    if !binary && !files_from_stdin {
+        return Ok(StdinInput::Data(Vec::new()));
+    }
+
+    let mut bytes = Vec::new();
+    stdin.read_to_end(&mut bytes)?;
+    let paths = match parse_value(&bytes) {
+        Ok(value) => extract_file_paths(&value),
+        Err(e) if files_from_stdin => {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+        }
+        Err(_) => Vec::new(),
+    };
+    if !paths.is_empty() {
+        Ok(StdinInput::Paths(paths))
+    } else if files_from_stdin {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            translate!("hashsum-error-no-files-on-stdin")
+        ))
+    } else {
+        Ok(StdinInput::Data(bytes))
+    }
+}
+
 #[sgcore::main]
 pub fn sgmain(mut args: impl sgcore::Args) -> SGResult<()> {
     let program = args.next().unwrap_or_else(|| OsString::from(NAME));
@@ -168,6 +220,10 @@ pub fn sgmain(mut args: impl sgcore::Args) -> SGResult<()> {
     let (command, is_hashsum_bin) = sg_app(&binary_name);
 
     let matches = sgcore::clap_localization::handle_clap_result(command, args)?;
+    if stardust_output::self_describe(&matches, stardust_schema())? {
+        return Ok(());
+    }
+    let stardust = StardustOutputOptions::from_matches(&matches);
 
     let input_length: Option<&usize> = if binary_name == "b2sum" {
         matches.get_one::<usize>(options::LENGTH)
@@ -230,6 +286,19 @@ pub fn sgmain(mut args: impl sgcore::Args) -> SGResult<()> {
             verbose,
         };
 
+        if stardust.stardust_output {
+            let mut report = CheckReport::default();
+            let result = perform_checksum_validation_report(
+                input.iter().copied(),
+                Some(algo.name),
+                Some(algo.bits),
+                opts,
+                &mut report
+            );
+            stardust_output::output(stardust, report.to_value(), || Ok(()))?;
+            return result;
+        }
+
         return perform_checksum_validation(
             input.iter().copied(),
             Some(algo.name),
@@ -248,7 +317,7 @@ pub fn sgmain(mut args: impl sgcore::Args) -> SGResult<()> {
         .unwrap_or(&false);
     let zero = matches.get_flag("zero");
 
-    let opts = Options {
+    let mut opts = Options {
         algoname: algo.name,
         digest: (algo.create_fn)(),
         output_bits: algo.bits,
@@ -257,11 +326,19 @@ pub fn sgmain(mut args: impl sgcore::Args) -> SGResult<()> {
         tag: matches.get_flag("tag"),
         nonames,
         zero,
+        stardust,
+        stdin_prefix: Vec::new(),
     };
 
     match matches.get_many::<OsString>(options::FILE) {
         Some(files) => hashsum(opts, files.map(|f| f.as_os_str())),
-        None => hashsum(opts, iter::once(OsStr::new("-"))),
+        None => match read_stdin_input(matches.get_flag(options::FILES_FROM_STDIN))? {
+            StdinInput::Paths(paths) => hashsum(opts, paths.iter().map(|p| p.as_os_str())),
+            StdinInput::Data(prefix) => {
+                opts.stdin_prefix = prefix;
+                hashsum(opts, iter::once(OsStr::new("-")))
+            }
+        },
     }
 }
 
@@ -276,10 +353,11 @@ mod options {
     pub const STATUS: &str = "status";
     pub const WARN: &str = "warn";
     pub const QUIET: &str = "quiet";
+    pub const FILES_FROM_STDIN: &str = "files-from-stdin";
 }
 
 pub fn sg_app_common() -> Command {
-    Command::new(sgcore::util_name())
+    let cmd = Command::new(sgcore::util_name())
         .version(sgcore::crate_version!())
         .help_template(sgcore::localized_help_template(sgcore::util_name()))
         .about(translate!("hashsum-about"))
@@ -363,6 +441,13 @@ pub fn sg_app_common() -> Command {
                 .help(translate!("hashsum-help-zero"))
                 .action(ArgAction::SetTrue)
         )
+        .arg(
+            Arg::new(options::FILES_FROM_STDIN)
+                .long(options::FILES_FROM_STDIN)
+                .help(translate!("hashsum-help-files-from-stdin"))
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all([options::FILE, options::CHECK])
+        )
         .arg(
             Arg::new(options::FILE)
                 .index(1)
@@ -370,7 +455,9 @@ pub fn sg_app_common() -> Command {
                 .value_name(options::FILE)
                 .value_hint(clap::ValueHint::FilePath)
                 .value_parser(ValueParser::os_string())
-        )
+        );
+
+    stardust_output::add_json_args(cmd)
 }
 
 pub fn sg_app_length() -> Command {
@@ -477,13 +564,15 @@ where
 {
     let binary_marker = if options.binary { "*" } else { " " };
     let mut err_found = None;
+    let mut records = Vec::new();
     for filename in files {
         let filename = Path::new(filename);
 
         let mut file = BufReader::with_capacity(
             READ_BUFFER_SIZE,
             if filename == OsStr::new("-") {
-                Box::new(stdin()) as Box<dyn Read>
+                let prefix = Cursor::new(mem::take(&mut options.stdin_prefix));
+                Box::new(prefix.chain(stdin())) as Box<dyn Read>
             } else {
                 let file_buf = match File::open(filename) {
                     Ok(f) => f,
@@ -502,13 +591,13 @@ where
             }
         );
 
-        let sum = match digest_reader(
+        let (sum, size) = match digest_reader(
             &mut options.digest,
             &mut file,
             options.binary,
             options.output_bits
         ) {
-            Ok((sum, _)) => sum,
+            Ok(digest) => digest,
             Err(e) => {
                 eprintln!(
                     "{}: {}: {}",
@@ -521,6 +610,11 @@ where
             }
         };
 
+        if options.stardust.stardust_output {
+            records.push(digest_record(filename.as_os_str(), options.algoname, &sum, size));
+            continue;
+        }
+
         let (escaped_filename, prefix) = escape_filename(filename);
         if options.tag {
             if options.algoname == "blake2b" {
@@ -546,6 +640,10 @@ where
             println!("{prefix}{sum} {binary_marker}{escaped_filename}");
         }
     }
+    if options.stardust.stardust_output {
+        let value = json!({ "files": records });
+        stardust_output::output(options.stardust, value, || Ok(()))?;
+    }
     match err_found {
         None => Ok(()),
         Some(e) => Err(Box::new(e)),
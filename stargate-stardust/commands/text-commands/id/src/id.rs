@@ -96,7 +96,6 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
     let opts = StardustOutputOptions::from_matches(&matches);
-    let field_filter = matches.get_one::<String>(stardust_output::ARG_FIELD).map(|s| s.as_str());
 
     let users: Vec<String> = matches
         .get_many::<String>(options::ARG_USERS)
@@ -281,8 +280,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
                     "egid": state.ids.as_ref().unwrap().egid,
                     "groups": groups_array
                 });
-                let filtered = stardust_output::filter_fields(output, field_filter);
-                stardust_output::output(opts, filtered, || {
+                stardust_output::output(opts.clone(), output, || {
                     id_print(&state, &groups);
                     Ok(())
                 })?;
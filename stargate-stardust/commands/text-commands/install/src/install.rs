@@ -7,7 +7,7 @@ use file_diff::diff;
 use filetime::{FileTime, set_file_times};
 
 use std::ffi::OsString;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::fs::File;
 use std::fs::{self, metadata};
 use std::path::{MAIN_SEPARATOR, Path, PathBuf};
@@ -19,8 +19,10 @@ use sgcore::display::Quotable;
 use sgcore::entries::{grp2gid, usr2uid};
 use sgcore::error::{FromIo, SGError, SGResult, SGUsageError};
 use sgcore::fs::dir_strip_dot_for_creation;
+use sgcore::operation_report::{self, Action, Operation};
 use sgcore::perms::{Verbosity, VerbosityLevel, wrap_chown};
 use sgcore::process::{getegid, geteuid};
+use sgcore::stardust_output::{self, StardustOutputOptions};
 
 use sgcore::translate;
 use sgcore::{format_usage, show, show_error, show_if_err};
@@ -157,6 +159,9 @@ static ARG_FILES: &str = "files";
 #[sgcore::main]
 pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
+    if stardust_output::self_describe(&matches, operation_report::schema())? {
+        return Ok(());
+    }
     sgcore::pledge::apply_pledge(&["stdio", "rpath", "wpath", "cpath", "fattr"])?;
 
     let paths: Vec<OsString> = matches
@@ -164,16 +169,31 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         .map(|v| v.cloned().collect())
         .unwrap_or_default();
 
-    let behavior = behavior(&matches)?;
+    let mut behavior = behavior(&matches)?;
+    let stardust = StardustOutputOptions::from_matches(&matches);
+    if stardust.stardust_output {
+        // The report replaces the verbose lines on stdout
+        behavior.verbose = false;
+        operation_report::start();
+    }
 
-    match behavior.main_function {
+    let result = match behavior.main_function {
         MainFunction::Directory => directory(&paths, &behavior),
         MainFunction::Standard => standard(paths, &behavior),
+    };
+
+    if stardust.stardust_output {
+        if let Err(e) = &result {
+            operation_report::record(Operation::new(Action::Copied).error(e));
+        }
+        let report = operation_report::to_value(&operation_report::finish());
+        stardust_output::output(stardust, report, || Ok(()))?;
     }
+    result
 }
 
 pub fn sg_app() -> Command {
-    Command::new(sgcore::util_name())
+    let cmd = Command::new(sgcore::util_name())
         .version(sgcore::crate_version!())
         .help_template(sgcore::localized_help_template(sgcore::util_name()))
         .about(translate!("install-about"))
@@ -301,7 +321,8 @@ pub fn sg_app() -> Command {
                 .num_args(1..)
                 .value_hint(clap::ValueHint::AnyPath)
                 .value_parser(clap::value_parser!(OsString))
-        )
+        );
+    stardust_output::add_json_args(cmd)
 }
 
 /// Determine behavior, given command line arguments.
@@ -442,10 +463,14 @@ fn directory(paths: &[OsString], b: &Behavior) -> SGResult<()> {
                 if let Err(e) = fs::create_dir_all(path_to_create.as_path())
                     .map_err_context(|| translate!("install-error-create-dir-failed", "path" => path_to_create.as_path().quote()))
                 {
+                    report_failure(path, None, &e);
                     show!(e);
                     continue;
                 }
 
+                operation_report::record(
+                    Operation::new(Action::Created).destination(&path_to_create)
+                );
                 if b.verbose {
                     println!(
                         "{}",
@@ -566,6 +591,7 @@ fn standard(mut paths: Vec<OsString>, b: &Behavior) -> SGResult<()> {
                 if let Err(e) = fs::create_dir_all(to_create) {
                     return Err(InstallError::CreateDirFailed(to_create.to_path_buf(), e).into());
                 }
+                operation_report::record(Operation::new(Action::Created).destination(to_create));
             }
         }
         if b.target_dir.is_some() {
@@ -623,12 +649,14 @@ fn copy_files_into_dir(files: &[PathBuf], target_dir: &Path, b: &Behavior) -> SG
             .metadata()
             .map_err_context(|| format!("cannot stat {}", sourcepath.quote()))
         {
+            report_failure(sourcepath, None, &err);
             show!(err);
             continue;
         }
 
         if sourcepath.is_dir() {
             let err = InstallError::OmittingDirectory(sourcepath.clone());
+            report_failure(sourcepath, None, &err);
             show!(err);
             continue;
         }
@@ -637,11 +665,23 @@ fn copy_files_into_dir(files: &[PathBuf], target_dir: &Path, b: &Behavior) -> SG
         let filename = sourcepath.components().next_back().unwrap();
         targetpath.push(filename);
 
-        show_if_err!(copy(sourcepath, &targetpath, b));
+        if let Err(err) = copy(sourcepath, &targetpath, b) {
+            report_failure(sourcepath, Some(&targetpath), &err);
+            show!(err);
+        }
     }
     Ok(())
 }
 
+/// Add a path that could not be installed to the `--obj` report.
+fn report_failure(source: &Path, target: Option<&Path>, error: &dyn Display) {
+    let mut operation = Operation::new(Action::Copied).source(source);
+    if let Some(target) = target {
+        operation = operation.destination(target);
+    }
+    operation_report::record(operation.error(error));
+}
+
 /// Handle incomplete user/group parings for change_owner.
 ///
 /// Returns a Result type with the Err variant containing the error message.
@@ -707,6 +747,11 @@ fn perform_backup(to: &Path, b: &Behavior) -> SGResult<Option<PathBuf>> {
             fs::rename(to, backup_path).map_err(|err| {
                 InstallError::BackupFailed(to.to_path_buf(), backup_path.clone(), err)
             })?;
+            operation_report::record(
+                Operation::new(Action::BackedUp)
+                    .source(to)
+                    .destination(backup_path)
+            );
         }
         Ok(backup_path)
     } else {
@@ -887,6 +932,7 @@ fn preserve_timestamps(from: &Path, to: &Path) -> SGResult<()> {
 ///
 fn copy(from: &Path, to: &Path, b: &Behavior) -> SGResult<()> {
     if b.compare && !need_copy(from, to, b) {
+        operation_report::record(Operation::new(Action::Skipped).source(from).destination(to));
         return Ok(());
     }
     let backup_path = perform_backup(to, b)?;
@@ -903,6 +949,24 @@ fn copy(from: &Path, to: &Path, b: &Behavior) -> SGResult<()> {
         preserve_timestamps(from, to)?;
     }
 
+    if operation_report::is_active() {
+        let mut preserved = Vec::new();
+        if b.preserve_timestamps {
+            preserved.push("timestamps");
+        }
+        if b.preserve_context {
+            preserved.push("context");
+        }
+        let bytes = metadata(from).map_or(0, |meta| if meta.is_file() { meta.len() } else { 0 });
+        operation_report::record(
+            Operation::new(Action::Copied)
+                .source(from)
+                .destination(to)
+                .bytes(bytes)
+                .preserved(preserved)
+        );
+    }
+
     if b.verbose {
         print!(
             "{}",
@@ -37,7 +37,6 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
     sgcore::pledge::apply_pledge(&["stdio"])?;
     let opts = StardustOutputOptions::from_matches(&matches);
-    let field_filter = matches.get_one::<String>(stardust_output::ARG_FIELD).map(|s| s.as_str());
 
     let mode = if matches.get_flag(options::TABLE) {
         Mode::Table
@@ -78,14 +77,11 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
             } else {
                 if opts.stardust_output {
                     let results = kill_with_results(sig, &pids);
-                    let output = stardust_output::filter_fields(
-                        json!({
-                            "signal": sig.map_or(0, |s| s as i32),
-                            "signal_name": sig_name,
-                            "processes": results
-                        }),
-                        field_filter
-                    );
+                    let output = json!({
+                        "signal": sig.map_or(0, |s| s as i32),
+                        "signal_name": sig_name,
+                        "processes": results
+                    });
                     stardust_output::output(opts, output, || Ok(()))?;
                 } else {
                     kill(sig, &pids);
@@ -101,7 +97,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
                         "name": signal
                     }))
                     .collect();
-                let output = stardust_output::filter_fields(json!({"signals": signals}), field_filter);
+                let output = json!({"signals": signals});
                 stardust_output::output(opts, output, || Ok(()))?;
             } else {
                 table();
@@ -111,8 +107,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         Mode::List => {
             if opts.stardust_output {
                 let output = list_as_json(&pids_or_signals)?;
-                let filtered = stardust_output::filter_fields(output, field_filter);
-                stardust_output::output(opts, filtered, || Ok(()))?;
+                stardust_output::output(opts, output, || Ok(()))?;
             } else {
                 list(&pids_or_signals);
             }
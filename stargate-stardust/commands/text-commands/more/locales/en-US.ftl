@@ -23,8 +23,14 @@ more-help-lines = The number of lines per screen full
 more-help-number = Same as --lines option argument
 more-help-from-line = Start displaying each file at line number
 more-help-pattern = The string to be searched in each file before starting to display it
+more-help-obj = Browse stardust (JSON) as a collapsible tree and print the selected part on exit
 more-help-files = Path to the files to be read
 
 # Other messages
 more-help-message = [Press space to continue, 'q' to quit.]
 more-press-return = press RETURN
+
+# Object viewer (--obj)
+more-obj-help = [/ search, t table, y copy path, o print, q quit]
+more-obj-no-records = Not inside an array of records
+more-obj-copied = Copied {$path}
@@ -23,8 +23,14 @@ more-help-lines = Le nombre de lignes par écran complet
 more-help-number = Identique à l'argument de l'option --lines
 more-help-from-line = Commencer l'affichage de chaque fichier au numéro de ligne
 more-help-pattern = La chaîne à rechercher dans chaque fichier avant de commencer à l'afficher
+more-help-obj = Parcourir du stardust (JSON) sous forme d'arbre repliable et afficher la partie sélectionnée en quittant
 more-help-files = Chemin vers les fichiers à lire
 
 # Autres messages
 more-help-message = [Appuyez sur espace pour continuer, 'q' pour quitter.]
 more-press-return = appuyez sur ENTRÉE
+
+# Visionneuse d'objets (--obj)
+more-obj-help = [/ rechercher, t tableau, y copier le chemin, o afficher, q quitter]
+more-obj-no-records = Pas dans un tableau d'enregistrements
+more-obj-copied = Copié {$path}
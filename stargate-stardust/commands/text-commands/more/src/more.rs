@@ -24,6 +24,8 @@ use sgcore::{display::Quotable, show};
 
 use sgcore::translate;
 
+mod object_view;
+
 #[derive(Debug)]
 enum MoreError {
     IsDirectory(String),
@@ -92,6 +94,7 @@ pub mod options {
     pub const NUMBER: &str = "number";
     pub const PATTERN: &str = "pattern";
     pub const FROM_LINE: &str = "from-line";
+    pub const OBJ: &str = "obj";
     pub const FILES: &str = "files";
 }
 
@@ -145,6 +148,9 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     }));
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
     sgcore::pledge::apply_pledge(&["stdio", "rpath", "tty"])?;
+    if matches.get_flag(options::OBJ) {
+        return view_object(matches.get_one::<OsString>(options::FILES));
+    }
     let mut options = Options::from(&matches);
     if let Some(files) = matches.get_many::<OsString>(options::FILES) {
         let length = files.len();
@@ -298,6 +304,12 @@ pub fn sg_app() -> Command {
                 .value_name("pattern")
                 .help(translate!("more-help-pattern"))
         )
+        .arg(
+            Arg::new(options::OBJ)
+                .long(options::OBJ)
+                .help(translate!("more-help-obj"))
+                .action(ArgAction::SetTrue)
+        )
         .arg(
             Arg::new(options::FILES)
                 .required(false)
@@ -308,6 +320,29 @@ pub fn sg_app() -> Command {
         )
 }
 
+/// Browse the stardust in `file` (or stdin) with the object viewer
+fn view_object(file: Option<&OsString>) -> SGResult<()> {
+    let value = match file {
+        Some(file) => {
+            let path = Path::new(file);
+            let bytes = std::fs::read(path).map_err(|why| {
+                SGSimpleError::new(
+                    1,
+                    MoreError::CannotOpenIOError(path.to_string_lossy().to_string(), why.kind())
+                        .to_string()
+                )
+            })?;
+            sgcore::json_adapter::parse_value(&bytes)
+        }
+        None if stdin().is_tty() => {
+            return Err(SGUsageError::new(1, MoreError::BadUsage.to_string()));
+        }
+        None => sgcore::json_adapter::read_stdin_value(),
+    }
+    .map_err(|e| SGSimpleError::new(1, e))?;
+    object_view::view(value)
+}
+
 enum InputType {
     File(BufReader<File>),
     Stdin(Stdin),
@@ -0,0 +1,584 @@
+//! `more --obj`: browse stardust as a collapsible tree.
+//!
+//! j/k or the arrows move, l/h or Right/Left expand and collapse, Enter
+//! toggles, `/` searches keys and values (n/N repeat it), `t` shows the
+//! selected array of records as a table, `y` copies the selected field's
+//! path, `o` prints the selected subtree and exits, `q` exits. Quitting
+//! also prints the selection when stdout is not a terminal, so the viewer
+//! can sit in the middle of a pipeline.
+
+use std::{
+    collections::HashSet,
+    fs::OpenOptions,
+    io::{IsTerminal, Write, stdout},
+    time::Duration,
+};
+
+use crossterm::{
+    ExecutableCommand,
+    QueueableCommand,
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    style::Attribute,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use serde_json::Value;
+
+use sgcore::error::SGResult;
+use sgcore::field_path::{FieldPath, Segment};
+use sgcore::render::{RenderOptions, is_record_array, render_table};
+use sgcore::translate;
+use sgcore::wire;
+
+/// What a key press asks the viewer to do next
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Continue,
+    /// Leave, printing the selected subtree when `emit` is set
+    Exit { emit: bool },
+}
+
+enum Mode {
+    Tree,
+    /// Typing a search query
+    Search(String),
+    /// An array of records rendered as a table, scrolled to `top`
+    Table { lines: Vec<String>, top: usize },
+}
+
+/// One visible line of the tree
+struct Row {
+    path: FieldPath,
+    depth: usize,
+}
+
+struct ObjectView {
+    root: Value,
+    /// Paths (as text) of the expanded objects and arrays
+    expanded: HashSet<String>,
+    rows: Vec<Row>,
+    selected: usize,
+    top: usize,
+    mode: Mode,
+    query: Option<String>,
+    /// Shown in the status bar until the next key press
+    message: Option<String>,
+    /// A path waiting to be sent to the terminal's clipboard
+    clipboard: Option<String>,
+    /// Whether `q` prints the selection
+    emit_on_quit: bool,
+    width: usize,
+    /// Lines available above the status bar
+    content_rows: usize,
+}
+
+/// Show `value` on the terminal until the user leaves, then print the
+/// selected subtree if asked to. Without a terminal the value passes
+/// through unchanged.
+pub fn view(value: Value) -> SGResult<()> {
+    let stdout_is_terminal = stdout().is_terminal();
+    let mut screen: Box<dyn Write> = if stdout_is_terminal {
+        Box::new(stdout())
+    } else {
+        match OpenOptions::new().write(true).open("/dev/tty") {
+            Ok(tty) => Box::new(tty),
+            Err(_) => {
+                wire::print_value(&value, false)?;
+                return Ok(());
+            }
+        }
+    };
+
+    let (cols, rows) = terminal::size().unwrap_or((80, 24));
+    let mut view = ObjectView::new(value, cols, rows, !stdout_is_terminal);
+
+    terminal::enable_raw_mode()?;
+    screen.execute(EnterAlternateScreen)?.execute(Hide)?;
+    let action = run(&mut view, &mut screen);
+    screen.queue(Show)?.queue(LeaveAlternateScreen)?.flush()?;
+    terminal::disable_raw_mode()?;
+
+    if action? == (Action::Exit { emit: true }) {
+        wire::print_value(view.selected_value(), stdout_is_terminal)?;
+    }
+    Ok(())
+}
+
+fn run(view: &mut ObjectView, screen: &mut impl Write) -> SGResult<Action> {
+    view.draw(screen)?;
+    loop {
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => {
+                let action = view.handle_key(key);
+                if let Some(path) = view.clipboard.take() {
+                    // OSC 52: most terminals (and tmux) put this on the clipboard
+                    write!(screen, "\x1b]52;c;{}\x07", base64_simd::STANDARD.encode_to_string(path.as_bytes()))?;
+                }
+                if action != Action::Continue {
+                    return Ok(action);
+                }
+            }
+            Event::Resize(cols, rows) => view.resize(cols, rows),
+            _ => continue,
+        }
+        view.draw(screen)?;
+    }
+}
+
+impl ObjectView {
+    fn new(root: Value, cols: u16, rows: u16, emit_on_quit: bool) -> Self {
+        let mut view = Self {
+            root,
+            expanded: HashSet::from([String::new()]),
+            rows: Vec::new(),
+            selected: 0,
+            top: 0,
+            mode: Mode::Tree,
+            query: None,
+            message: None,
+            clipboard: None,
+            emit_on_quit,
+            width: 80,
+            content_rows: 23,
+        };
+        view.resize(cols, rows);
+        view.rebuild_rows();
+        view
+    }
+
+    fn resize(&mut self, cols: u16, rows: u16) {
+        self.width = usize::from(cols.max(1));
+        self.content_rows = usize::from(rows.saturating_sub(1).max(1));
+        self.scroll_to_selected();
+    }
+
+    fn rebuild_rows(&mut self) {
+        self.rows.clear();
+        push_rows(&self.root, FieldPath(Vec::new()), 0, &self.expanded, &mut self.rows);
+        self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+        self.scroll_to_selected();
+    }
+
+    fn selected_path(&self) -> &FieldPath {
+        &self.rows[self.selected].path
+    }
+
+    fn selected_value(&self) -> &Value {
+        self.selected_path().get(&self.root).unwrap_or(&Value::Null)
+    }
+
+    fn is_expanded(&self, path: &FieldPath) -> bool {
+        self.expanded.contains(&path.to_string())
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        self.message = None;
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Exit { emit: false };
+        }
+        match &mut self.mode {
+            Mode::Search(query) => {
+                match key.code {
+                    KeyCode::Char(c) => query.push(c),
+                    KeyCode::Backspace => {
+                        query.pop();
+                    }
+                    KeyCode::Enter => {
+                        self.query = Some(std::mem::take(query)).filter(|q| !q.is_empty());
+                        self.mode = Mode::Tree;
+                        self.find(true);
+                    }
+                    KeyCode::Esc => self.mode = Mode::Tree,
+                    _ => {}
+                }
+                Action::Continue
+            }
+            Mode::Table { lines, top } => {
+                let last = lines.len().saturating_sub(self.content_rows);
+                match key.code {
+                    KeyCode::Char('j') | KeyCode::Down => *top = (*top + 1).min(last),
+                    KeyCode::Char('k') | KeyCode::Up => *top = top.saturating_sub(1),
+                    KeyCode::Char(' ') | KeyCode::PageDown => {
+                        *top = (*top + self.content_rows).min(last);
+                    }
+                    KeyCode::Char('b') | KeyCode::PageUp => {
+                        *top = top.saturating_sub(self.content_rows);
+                    }
+                    KeyCode::Char('g') | KeyCode::Home => *top = 0,
+                    KeyCode::Char('G') | KeyCode::End => *top = last,
+                    KeyCode::Char('t' | 'h') | KeyCode::Left | KeyCode::Esc => {
+                        self.mode = Mode::Tree;
+                    }
+                    KeyCode::Char('q') => return Action::Exit { emit: self.emit_on_quit },
+                    KeyCode::Char('o') => return Action::Exit { emit: true },
+                    _ => {}
+                }
+                Action::Continue
+            }
+            Mode::Tree => self.handle_tree_key(key),
+        }
+    }
+
+    fn handle_tree_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.select(self.selected + 1),
+            KeyCode::Char('k') | KeyCode::Up => self.select(self.selected.saturating_sub(1)),
+            KeyCode::Char(' ') | KeyCode::PageDown => self.select(self.selected + self.content_rows),
+            KeyCode::Char('b') | KeyCode::PageUp => {
+                self.select(self.selected.saturating_sub(self.content_rows));
+            }
+            KeyCode::Char('g') | KeyCode::Home => self.select(0),
+            KeyCode::Char('G') | KeyCode::End => self.select(usize::MAX),
+            KeyCode::Char('l') | KeyCode::Right => self.expand(),
+            KeyCode::Char('h') | KeyCode::Left => self.collapse(),
+            KeyCode::Enter => self.toggle(),
+            KeyCode::Char('/') => self.mode = Mode::Search(String::new()),
+            KeyCode::Char('n') => self.find(true),
+            KeyCode::Char('N') => self.find(false),
+            KeyCode::Char('t') => self.open_table(),
+            KeyCode::Char('y') => {
+                let path = path_text(self.selected_path());
+                self.message = Some(translate!("more-obj-copied", "path" => path.clone()));
+                self.clipboard = Some(path);
+            }
+            KeyCode::Char('o') => return Action::Exit { emit: true },
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Exit { emit: self.emit_on_quit },
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Select row `index`, or the last row when there are fewer
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.rows.len().saturating_sub(1));
+        self.scroll_to_selected();
+    }
+
+    fn scroll_to_selected(&mut self) {
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if self.selected >= self.top + self.content_rows {
+            self.top = self.selected + 1 - self.content_rows;
+        }
+    }
+
+    /// Open the selected object or array, or step into it when already open
+    fn expand(&mut self) {
+        if !is_container(self.selected_value()) {
+            return;
+        }
+        let key = self.selected_path().to_string();
+        if self.expanded.insert(key) {
+            self.rebuild_rows();
+        } else {
+            self.select(self.selected + 1);
+        }
+    }
+
+    /// Close the selected object or array, or step out to its parent
+    fn collapse(&mut self) {
+        let key = self.selected_path().to_string();
+        if self.expanded.remove(&key) {
+            self.rebuild_rows();
+            return;
+        }
+        let depth = self.rows[self.selected].depth;
+        if let Some(parent) = self.rows[..self.selected].iter().rposition(|row| row.depth < depth) {
+            self.select(parent);
+        }
+    }
+
+    fn toggle(&mut self) {
+        if self.is_expanded(self.selected_path()) {
+            self.collapse();
+        } else {
+            self.expand();
+        }
+    }
+
+    /// Select the next (or previous) field whose key or scalar value
+    /// contains the query, ignoring case, opening its ancestors
+    fn find(&mut self, forward: bool) {
+        let Some(query) = self.query.as_ref().map(|q| q.to_lowercase()) else {
+            return;
+        };
+        let mut order = Vec::new();
+        collect_matches(&self.root, FieldPath(Vec::new()), &query, &mut order);
+        let current = self.selected_path();
+        let position = order.iter().position(|(path, _)| path == current).unwrap_or(0);
+        let count = order.len();
+        let found = (1..=count)
+            .map(|step| if forward { (position + step) % count } else { (position + count - step) % count })
+            .find(|&i| order[i].1);
+        match found {
+            Some(i) => {
+                let path = order.swap_remove(i).0;
+                self.reveal(&path);
+            }
+            None => self.message = Some(translate!("more-error-pattern-not-found")),
+        }
+    }
+
+    /// Expand every ancestor of `path` and select it
+    fn reveal(&mut self, path: &FieldPath) {
+        for len in 0..path.0.len() {
+            self.expanded.insert(FieldPath(path.0[..len].to_vec()).to_string());
+        }
+        self.rebuild_rows();
+        if let Some(index) = self.rows.iter().position(|row| row.path == *path) {
+            self.select(index);
+        }
+    }
+
+    /// Show the selected array of records, or the nearest one containing
+    /// the selection, as a table
+    fn open_table(&mut self) {
+        let path = self.selected_path();
+        let records = (0..=path.0.len())
+            .rev()
+            .filter_map(|len| FieldPath(path.0[..len].to_vec()).get(&self.root))
+            .find(|value| is_record_array(value))
+            .and_then(Value::as_array);
+        let Some(records) = records else {
+            self.message = Some(translate!("more-obj-no-records"));
+            return;
+        };
+        let options = RenderOptions {
+            width: Some(self.width),
+            color: std::env::var_os("NO_COLOR").is_none(),
+            human: true,
+            ..RenderOptions::default()
+        };
+        let lines = render_table(records, &options).lines().map(String::from).collect();
+        self.mode = Mode::Table { lines, top: 0 };
+    }
+
+    fn row_text(&self, index: usize) -> String {
+        let row = &self.rows[index];
+        let value = row.path.get(&self.root).unwrap_or(&Value::Null);
+        let label = match row.path.0.last() {
+            Some(Segment::Key(key)) => key.clone(),
+            Some(Segment::Index(i)) => format!("[{i}]"),
+            _ => ".".to_string(),
+        };
+        let marker = match value {
+            Value::Object(_) | Value::Array(_) if self.is_expanded(&row.path) => "▾ ",
+            Value::Object(_) | Value::Array(_) => "▸ ",
+            _ => "  ",
+        };
+        format!("{}{marker}{label}: {}", "  ".repeat(row.depth), summary(value))
+    }
+
+    fn status(&self) -> String {
+        match (&self.mode, &self.message) {
+            (Mode::Search(query), _) => format!("/{query}"),
+            (_, Some(message)) => message.clone(),
+            _ => format!("{}  {}", path_text(self.selected_path()), translate!("more-obj-help")),
+        }
+    }
+
+    fn draw(&self, out: &mut impl Write) -> SGResult<()> {
+        out.queue(Clear(ClearType::All))?;
+        match &self.mode {
+            Mode::Table { lines, top } => {
+                for (i, line) in lines.iter().skip(*top).take(self.content_rows).enumerate() {
+                    out.queue(MoveTo(0, i as u16))?;
+                    write!(out, "{line}")?;
+                }
+            }
+            _ => {
+                let visible = self.top..self.rows.len().min(self.top + self.content_rows);
+                for (i, index) in visible.enumerate() {
+                    out.queue(MoveTo(0, i as u16))?;
+                    let text = truncate(&self.row_text(index), self.width);
+                    if index == self.selected {
+                        write!(out, "{}{text}{}", Attribute::Reverse, Attribute::Reset)?;
+                    } else {
+                        write!(out, "{text}")?;
+                    }
+                }
+            }
+        }
+        out.queue(MoveTo(0, self.content_rows as u16))?;
+        let status = truncate(&self.status(), self.width);
+        write!(out, "{}{status}{}", Attribute::Reverse, Attribute::Reset)?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+fn push_rows(value: &Value, path: FieldPath, depth: usize, expanded: &HashSet<String>, rows: &mut Vec<Row>) {
+    let open = expanded.contains(&path.to_string());
+    rows.push(Row { path: path.clone(), depth });
+    if !open {
+        return;
+    }
+    let child = |segment| {
+        let mut child = path.clone();
+        child.0.push(segment);
+        child
+    };
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map {
+                push_rows(field, child(Segment::Key(key.clone())), depth + 1, expanded, rows);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                push_rows(item, child(index_segment(i)), depth + 1, expanded, rows);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Every path in `value` in display order, with whether its key or scalar
+/// value contains `query` (already lowercase)
+fn collect_matches(value: &Value, path: FieldPath, query: &str, out: &mut Vec<(FieldPath, bool)>) {
+    let key_matches = match path.0.last() {
+        Some(Segment::Key(key)) => key.to_lowercase().contains(query),
+        _ => false,
+    };
+    let value_matches = match value {
+        Value::Object(_) | Value::Array(_) => false,
+        Value::String(s) => s.to_lowercase().contains(query),
+        scalar => scalar.to_string().contains(query),
+    };
+    out.push((path.clone(), key_matches || value_matches));
+    let mut descend = |segment, child: &Value| {
+        let mut child_path = path.clone();
+        child_path.0.push(segment);
+        collect_matches(child, child_path, query, out);
+    };
+    match value {
+        Value::Object(map) => map.iter().for_each(|(key, field)| descend(Segment::Key(key.clone()), field)),
+        Value::Array(items) => {
+            items.iter().enumerate().for_each(|(i, item)| descend(index_segment(i), item));
+        }
+        _ => {}
+    }
+}
+
+fn index_segment(index: usize) -> Segment {
+    Segment::Index(i64::try_from(index).unwrap_or(i64::MAX))
+}
+
+fn is_container(value: &Value) -> bool {
+    matches!(value, Value::Object(_) | Value::Array(_))
+}
+
+fn summary(value: &Value) -> String {
+    match value {
+        Value::Object(map) if map.len() == 1 => "{1 field}".to_string(),
+        Value::Object(map) => format!("{{{} fields}}", map.len()),
+        Value::Array(items) if items.len() == 1 => "[1 item]".to_string(),
+        Value::Array(items) => format!("[{} items]", items.len()),
+        scalar => scalar.to_string(),
+    }
+}
+
+/// The path as `--fields` and friends accept it; `.` for the whole value
+fn path_text(path: &FieldPath) -> String {
+    if path.0.is_empty() { ".".to_string() } else { path.to_string() }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn press(view: &mut ObjectView, keys: &str) -> Action {
+        let mut action = Action::Continue;
+        for c in keys.chars() {
+            action = view.handle_key(key(c));
+        }
+        action
+    }
+
+    fn listing() -> ObjectView {
+        let value = json!({
+            "count": 2,
+            "entries": [
+                {"name": "Cargo.toml", "size": 812},
+                {"name": "src", "size": 4096, "labels": {"app.kind": "code"}}
+            ]
+        });
+        ObjectView::new(value, 80, 10, true)
+    }
+
+    #[test]
+    fn test_expand_and_collapse() {
+        let mut view = listing();
+        assert_eq!(view.rows.len(), 3);
+        assert_eq!(view.row_text(2), "  ▸ entries: [2 items]");
+
+        press(&mut view, "jjl");
+        assert_eq!(view.rows.len(), 5);
+        assert_eq!(view.row_text(4), "    ▸ [1]: {3 fields}");
+
+        press(&mut view, "ljh");
+        assert_eq!(path_text(view.selected_path()), "entries");
+        press(&mut view, "h");
+        assert_eq!(view.rows.len(), 3);
+    }
+
+    #[test]
+    fn test_search_reveals_matches() {
+        let mut view = listing();
+        press(&mut view, "/CODE");
+        view.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(path_text(view.selected_path()), r#"entries[1].labels["app.kind"]"#);
+
+        press(&mut view, "g/name");
+        view.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(path_text(view.selected_path()), "entries[0].name");
+        press(&mut view, "n");
+        assert_eq!(path_text(view.selected_path()), "entries[1].name");
+        press(&mut view, "N");
+        assert_eq!(path_text(view.selected_path()), "entries[0].name");
+    }
+
+    #[test]
+    fn test_table_for_enclosing_array() {
+        let mut view = listing();
+        press(&mut view, "t");
+        assert!(matches!(view.mode, Mode::Tree));
+        assert!(view.message.is_some());
+
+        view.query = Some("812".to_string());
+        press(&mut view, "nt");
+        let Mode::Table { lines, .. } = &view.mode else {
+            panic!("expected table mode");
+        };
+        assert!(lines.iter().any(|line| line.contains("Cargo.toml")));
+        press(&mut view, "t");
+        assert!(matches!(view.mode, Mode::Tree));
+    }
+
+    #[test]
+    fn test_copy_path_and_emit_selection() {
+        let mut view = listing();
+        view.query = Some("4096".to_string());
+        press(&mut view, "ny");
+        assert_eq!(view.clipboard.as_deref(), Some("entries[1].size"));
+        press(&mut view, "h");
+        assert_eq!(view.selected_value()["name"], "src");
+        assert_eq!(press(&mut view, "q"), Action::Exit { emit: true });
+
+        let mut view = ObjectView::new(json!([]), 80, 10, false);
+        assert_eq!(press(&mut view, "q"), Action::Exit { emit: false });
+        assert_eq!(press(&mut view, "o"), Action::Exit { emit: true });
+    }
+}
@@ -13,6 +13,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
 use std::ffi::OsString;
+use std::fmt::Display;
 use std::fs;
 use std::io;
 use std::os::unix;
@@ -33,6 +34,8 @@ use sgcore::fs::{
 #[cfg(all(unix, not(target_os = "macos")))]
 use sgcore::fsxattr;
 
+use sgcore::operation_report::{self, Action, Operation};
+use sgcore::stardust_output::{self, StardustOutputOptions};
 use sgcore::translate;
 use sgcore::update_control;
 
@@ -131,6 +134,9 @@ static OPT_DEBUG: &str = "debug";
 #[sgcore::main]
 pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
+    if stardust_output::self_describe(&matches, operation_report::schema())? {
+        return Ok(());
+    }
     sgcore::pledge::apply_pledge(&["stdio", "rpath", "wpath", "cpath", "fattr"])?;
 
     let files: Vec<OsString> = matches
@@ -174,6 +180,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         }
     }
 
+    let stardust = StardustOutputOptions::from_matches(&matches);
     let opts = Options {
         overwrite: overwrite_mode,
         backup: backup_mode,
@@ -181,17 +188,32 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         update: update_mode,
         target_dir,
         no_target_dir: matches.get_flag(OPT_NO_TARGET_DIRECTORY),
-        verbose: matches.get_flag(OPT_VERBOSE) || matches.get_flag(OPT_DEBUG),
+        // With --obj the report replaces the verbose lines on stdout
+        verbose: (matches.get_flag(OPT_VERBOSE) || matches.get_flag(OPT_DEBUG))
+            && !stardust.stardust_output,
         strip_slashes: matches.get_flag(OPT_STRIP_TRAILING_SLASHES),
         progress_bar: matches.get_flag(OPT_PROGRESS),
         debug: matches.get_flag(OPT_DEBUG),
     };
 
-    mv(&files[..], &opts)
+    if !stardust.stardust_output {
+        return mv(&files[..], &opts);
+    }
+
+    operation_report::start();
+    let result = mv(&files[..], &opts);
+    if let Err(e) = &result
+        && !e.to_string().is_empty()
+    {
+        operation_report::record(Operation::new(Action::Moved).error(e));
+    }
+    let report = operation_report::to_value(&operation_report::finish());
+    stardust_output::output(stardust, report, || Ok(()))?;
+    result
 }
 
 pub fn sg_app() -> Command {
-    Command::new(sgcore::util_name())
+    let cmd = Command::new(sgcore::util_name())
         .version(sgcore::crate_version!())
         .about(translate!("mv-about"))
         .help_template(sgcore::localized_help_template(sgcore::util_name()))
@@ -272,7 +294,7 @@ pub fn sg_app() -> Command {
             Arg::new(ARG_FILES)
                 .action(ArgAction::Append)
                 .num_args(1..)
-                .required(true)
+                .required_unless_present(stardust_output::ARG_SCHEMA)
                 .value_parser(ValueParser::os_string())
                 .value_hint(clap::ValueHint::AnyPath)
         )
@@ -281,7 +303,8 @@ pub fn sg_app() -> Command {
                 .long(OPT_DEBUG)
                 .help(translate!("mv-help-debug"))
                 .action(ArgAction::SetTrue)
-        )
+        );
+    stardust_output::add_json_args(cmd)
 }
 
 fn determine_overwrite_mode(matches: &ArgMatches) -> OverwriteMode {
@@ -366,7 +389,10 @@ fn handle_two_paths(source: &Path, target: &Path, opts: &Options) -> SGResult<()
         }
     } else if target.exists() && source_is_dir {
         match opts.overwrite {
-            OverwriteMode::NoClobber => return Ok(()),
+            OverwriteMode::NoClobber => {
+                report_skipped(source, target);
+                return Ok(());
+            }
             OverwriteMode::Interactive => {
                 if !prompt_yes!(
                     "{}",
@@ -552,7 +578,9 @@ fn move_files_into_dir(files: &[PathBuf], target_dir: &Path, options: &Options)
 
     for sourcepath in files {
         if sourcepath.symlink_metadata().is_err() {
-            show!(MvError::NoSuchFile(sourcepath.quote().to_string()));
+            let e = MvError::NoSuchFile(sourcepath.quote().to_string());
+            report_failure(sourcepath, None, &e);
+            show!(e);
             continue;
         }
 
@@ -564,20 +592,25 @@ fn move_files_into_dir(files: &[PathBuf], target_dir: &Path, options: &Options)
         let targetpath = match sourcepath.file_name() {
             Some(name) => target_dir.join(name),
             None => {
-                show!(MvError::NoSuchFile(sourcepath.quote().to_string()));
+                let e = MvError::NoSuchFile(sourcepath.quote().to_string());
+                report_failure(sourcepath, None, &e);
+                show!(e);
                 continue;
             }
         };
 
         if moved_destinations.contains(&targetpath) && options.backup != BackupMode::Numbered {
-            show!(SGSimpleError::new(
+            let e = SGSimpleError::new(
                 1,
                 translate!("mv-error-will-not-overwrite-just-created", "target" => targetpath.display(), "source" => sourcepath.display())
-            ));
+            );
+            report_failure(sourcepath, Some(&targetpath), &e);
+            show!(e);
             continue;
         }
 
         if let Err(e) = assert_not_same_file(sourcepath, target_dir, true, options) {
+            report_failure(sourcepath, Some(&targetpath), &e);
             show!(e);
             continue;
         }
@@ -598,6 +631,7 @@ fn move_files_into_dir(files: &[PathBuf], target_dir: &Path, options: &Options)
                 let e = e.map_err_context(|| {
                     translate!("mv-error-cannot-move", "source" => sourcepath.quote(), "target" => targetpath.quote())
                 });
+                report_failure(sourcepath, Some(&targetpath), &e);
                 match display_manager {
                     Some(ref pb) => pb.suspend(|| show!(e)),
                     None => show!(e),
@@ -613,6 +647,20 @@ fn move_files_into_dir(files: &[PathBuf], target_dir: &Path, options: &Options)
     Ok(())
 }
 
+/// Add a source that could not be moved to the `--obj` report.
+fn report_failure(source: &Path, target: Option<&Path>, error: &dyn Display) {
+    let mut operation = Operation::new(Action::Moved).source(source);
+    if let Some(target) = target {
+        operation = operation.destination(target);
+    }
+    operation_report::record(operation.error(error));
+}
+
+/// Add a source that was left where it is to the `--obj` report.
+fn report_skipped(from: &Path, to: &Path) {
+    operation_report::record(Operation::new(Action::Skipped).source(from).destination(to));
+}
+
 fn rename(
     from: &Path,
     to: &Path,
@@ -630,12 +678,14 @@ fn rename(
             if opts.debug {
                 println!("{}", translate!("mv-debug-skipped", "target" => to.quote()));
             }
+            report_skipped(from, to);
             return Ok(());
         }
 
         if (opts.update == UpdateMode::IfOlder)
             && fs::metadata(from)?.modified()? <= fs::metadata(to)?.modified()?
         {
+            report_skipped(from, to);
             return Ok(());
         }
 
@@ -649,6 +699,7 @@ fn rename(
                 if opts.debug {
                     println!("{}", translate!("mv-debug-skipped", "target" => to.quote()));
                 }
+                report_skipped(from, to);
                 return Ok(());
             }
             OverwriteMode::Interactive => {
@@ -656,6 +707,7 @@ fn rename(
                     "{}",
                     translate!("mv-prompt-overwrite", "target" => to.quote())
                 ) {
+                    report_skipped(from, to);
                     return Err(io::Error::other(""));
                 }
             }
@@ -665,6 +717,11 @@ fn rename(
         backup_path = backup_control::get_backup_path(opts.backup, to, &opts.suffix);
         if let Some(ref backup_path) = backup_path {
             rename_with_fallback(to, backup_path, display_manager, false, None, None)?;
+            operation_report::record(
+                Operation::new(Action::BackedUp)
+                    .source(to)
+                    .destination(backup_path)
+            );
         }
     }
 
@@ -677,16 +734,20 @@ fn rename(
             }
         }
     }
-    {
-        rename_with_fallback(
-            from,
-            to,
-            display_manager,
-            opts.verbose,
-            hardlink_tracker,
-            hardlink_scanner
-        )?;
-    }
+    let bytes = rename_with_fallback(
+        from,
+        to,
+        display_manager,
+        opts.verbose,
+        hardlink_tracker,
+        hardlink_scanner
+    )?;
+    operation_report::record(
+        Operation::new(Action::Moved)
+            .source(from)
+            .destination(to)
+            .bytes(bytes)
+    );
 
     if opts.verbose {
         let message = match backup_path {
@@ -716,6 +777,8 @@ fn is_fifo(_filetype: fs::FileType) -> bool {
 
 /// A wrapper around `fs::rename`, so that if it fails, we try falling back on
 /// copying and removing.
+///
+/// Returns the number of bytes copied, which is zero when the rename worked.
 fn rename_with_fallback(
     from: &Path,
     to: &Path,
@@ -725,8 +788,8 @@ fn rename_with_fallback(
     hardlink_scanner: Option<&HardlinkGroupScanner>,
     #[cfg(not(unix))] _hardlink_tracker: Option<()>,
     #[cfg(not(unix))] _hardlink_scanner: Option<()>
-) -> io::Result<()> {
-    fs::rename(from, to).or_else(|err| {
+) -> io::Result<u64> {
+    fs::rename(from, to).map(|()| 0).or_else(|err| {
         const EXDEV: i32 = libc::EXDEV as _;
 
         let should_fallback =
@@ -737,7 +800,7 @@ fn rename_with_fallback(
         let metadata = from.symlink_metadata()?;
         let file_type = metadata.file_type();
         if file_type.is_symlink() {
-            rename_symlink_fallback(from, to)
+            rename_symlink_fallback(from, to).map(|()| 0)
         } else if file_type.is_dir() {
             {
                 with_optional_hardlink_context(
@@ -760,7 +823,7 @@ fn rename_with_fallback(
                 rename_dir_fallback(from, to, display_manager, verbose)
             }
         } else if is_fifo(file_type) {
-            rename_fifo_fallback(from, to)
+            rename_fifo_fallback(from, to).map(|()| 0)
         } else {
             {
                 with_optional_hardlink_context(
@@ -804,7 +867,7 @@ fn rename_dir_fallback(
     verbose: bool,
     hardlink_tracker: Option<&mut HardlinkTracker>,
     hardlink_scanner: Option<&HardlinkGroupScanner>
-) -> io::Result<()> {
+) -> io::Result<u64> {
     if to.exists() {
         fs::remove_dir_all(to)?;
     }
@@ -841,7 +904,7 @@ fn rename_dir_fallback(
 
     fs::remove_dir_all(from)?;
 
-    Ok(())
+    Ok(total_size.unwrap_or(0))
 }
 
 /// Copy directory recursively, optionally preserving hardlinks
@@ -992,7 +1055,7 @@ fn rename_file_fallback(
     to: &Path,
     hardlink_tracker: Option<&mut HardlinkTracker>,
     hardlink_scanner: Option<&HardlinkGroupScanner>
-) -> io::Result<()> {
+) -> io::Result<u64> {
     if to.is_symlink() {
         fs::remove_file(to).map_err(|err| {
             let inter_device_msg = translate!("mv-error-inter-device-move-failed", "from" => from.display(), "to" => to.display(), "err" => err);
@@ -1011,21 +1074,21 @@ fn rename_file_fallback(
             {
                 fs::hard_link(&existing_target, to)?;
                 fs::remove_file(from)?;
-                return Ok(());
+                return Ok(0);
             }
         }
     }
 
     #[cfg(all(unix, not(target_os = "macos")))]
-    fs::copy(from, to)
-        .and_then(|_| fsxattr::copy_xattrs(&from, &to))
-        .and_then(|_| fs::remove_file(from))
+    let bytes = fs::copy(from, to)
+        .and_then(|bytes| fsxattr::copy_xattrs(&from, &to).map(|()| bytes))
+        .and_then(|bytes| fs::remove_file(from).map(|()| bytes))
         .map_err(|err| io::Error::new(err.kind(), translate!("mv-error-permission-denied")))?;
     #[cfg(any(target_os = "macos", not(unix)))]
-    fs::copy(from, to)
-        .and_then(|_| fs::remove_file(from))
+    let bytes = fs::copy(from, to)
+        .and_then(|bytes| fs::remove_file(from).map(|()| bytes))
         .map_err(|err| io::Error::new(err.kind(), translate!("mv-error-permission-denied")))?;
-    Ok(())
+    Ok(bytes)
 }
 
 fn is_empty_dir(path: &Path) -> bool {
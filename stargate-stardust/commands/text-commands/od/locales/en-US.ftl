@@ -59,6 +59,11 @@ od-error-invalid-suffix = invalid suffix in --{$option} argument {$value}
 od-error-invalid-argument = invalid --{$option} argument {$value}
 od-error-argument-too-large = --{$option} argument {$value} too large
 od-error-skip-past-end = tried to skip past end of input
+od-error-decode-field = invalid field {$field} in --decode-binary; expected TYPE NAME, such as 'u32le magic' or 'bytes[8] name'
+od-error-decode-name = duplicate field name {$name} in --decode-binary (offset is reserved)
+od-error-decode-empty = --decode-binary needs at least one field
+od-error-decode-size = --decode-binary records may take at most {$max} bytes
+od-warning-decode-partial = ignoring {$bytes} trailing bytes, a record takes {$size}
 
 # Help messages
 od-help-help = Print help information.
@@ -91,3 +96,5 @@ od-help-capital-h = hexadecimal 4-byte units
 od-help-e = floating point double precision (64-bit) units
 od-help-f = floating point double precision (32-bit) units
 od-help-capital-f = floating point double precision (64-bit) units
+od-help-decode-binary = decode the input as records laid out as LAYOUT, e.g. 'u32le magic; u16be count; bytes[8] name'
+od-help-obj = output the dump as stardust objects
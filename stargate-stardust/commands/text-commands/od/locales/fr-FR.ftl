@@ -60,6 +60,11 @@ od-error-invalid-suffix = suffixe invalide dans l'argument --{$option} {$value}
 od-error-invalid-argument = argument --{$option} invalide {$value}
 od-error-argument-too-large = argument --{$option} {$value} trop grand
 od-error-skip-past-end = tentative d'ignorer au-delà de la fin de l'entrée
+od-error-decode-field = champ invalide {$field} dans --decode-binary ; TYPE NOM attendu, par exemple 'u32le magic' ou 'bytes[8] name'
+od-error-decode-name = nom de champ en double {$name} dans --decode-binary (offset est réservé)
+od-error-decode-empty = --decode-binary nécessite au moins un champ
+od-error-decode-size = les enregistrements de --decode-binary font au plus {$max} octets
+od-warning-decode-partial = {$bytes} octets finaux ignorés, un enregistrement en occupe {$size}
 
 # Messages d'aide
 od-help-help = Afficher les informations d'aide.
@@ -92,3 +97,5 @@ od-help-capital-h = unités hexadécimales 4-octets
 od-help-e = unités virgule flottante double précision (64-bits)
 od-help-f = unités virgule flottante double précision (32-bits)
 od-help-capital-f = unités virgule flottante double précision (64-bits)
+od-help-decode-binary = décoder l'entrée en enregistrements selon LAYOUT, par ex. 'u32le magic; u16be count; bytes[8] name'
+od-help-obj = afficher le vidage sous forme d'objets stardust
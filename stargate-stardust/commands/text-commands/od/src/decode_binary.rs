@@ -0,0 +1,427 @@
+//! `od --decode-binary`: decode the input as a sequence of fixed-size records.
+//!
+//! The layout is a list of `TYPE NAME` fields separated by `;`, for example
+//! `u32le magic; u16be count; bytes[8] name`. Integer and float types take
+//! an optional `le` or `be` suffix and otherwise use `--endian`; any type
+//! takes `[N]` for an array of N items. `bytes[N]` is printed as hex,
+//! `str[N]` as text up to the first NUL, and `pad[N]` skips N bytes.
+// spell-checker:ignore bfloat
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::io::{self, Read};
+
+use half::{bf16, f16};
+use serde_json::{Map, Value, json};
+use sgcore::error::SGResult;
+use sgcore::stardust_output::{
+    self, FORMAT_BYTES, Property, StardustOutputOptions, array_schema, create_schema,
+    object_schema,
+};
+use sgcore::translate;
+use sgcore::{show_error, show_warning};
+
+use crate::byteorder_io::ByteOrder;
+use crate::multifile_reader::HasError;
+use crate::prn_float::{format_item_bf16, format_item_f16, format_item_f32, format_item_f64};
+
+/// Largest record a layout may describe; one record is held in memory at a time
+const MAX_RECORD_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Unsigned(usize),
+    Signed(usize),
+    Float(usize),
+    BFloat,
+    Bytes,
+    Str,
+    Pad,
+}
+
+impl Kind {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "u8" => Self::Unsigned(1),
+            "u16" => Self::Unsigned(2),
+            "u32" => Self::Unsigned(4),
+            "u64" => Self::Unsigned(8),
+            "i8" => Self::Signed(1),
+            "i16" => Self::Signed(2),
+            "i32" => Self::Signed(4),
+            "i64" => Self::Signed(8),
+            "f16" => Self::Float(2),
+            "f32" => Self::Float(4),
+            "f64" => Self::Float(8),
+            "bf16" => Self::BFloat,
+            "bytes" => Self::Bytes,
+            "str" => Self::Str,
+            "pad" => Self::Pad,
+            _ => return None,
+        })
+    }
+
+    fn item_size(self) -> usize {
+        match self {
+            Self::Unsigned(n) | Self::Signed(n) | Self::Float(n) => n,
+            Self::BFloat => 2,
+            Self::Bytes | Self::Str | Self::Pad => 1,
+        }
+    }
+
+    /// Whether the field is a single string rather than `[N]` items
+    fn is_text(self) -> bool {
+        matches!(self, Self::Bytes | Self::Str)
+    }
+
+    fn item_schema(self) -> Value {
+        match self {
+            Self::Unsigned(_) | Self::Signed(_) => json!({"type": "integer"}),
+            // nan and inf have no JSON number
+            Self::Float(_) | Self::BFloat => json!({"type": ["number", "string"]}),
+            Self::Bytes | Self::Str | Self::Pad => json!({"type": "string"}),
+        }
+    }
+}
+
+/// A float through the printer `od -t f` uses, so an f32 1.1 reads as 1.1
+/// rather than 1.100000023841858
+fn float_value(f: f64, printer: fn(f64) -> String) -> Value {
+    let text = printer(f);
+    let text = text.trim();
+    text.parse::<f64>()
+        .ok()
+        .filter(|f| f.is_finite())
+        .map_or_else(|| text.into(), Value::from)
+}
+
+fn item_value(kind: Kind, byte_order: ByteOrder, data: &[u8]) -> Value {
+    match kind {
+        Kind::Unsigned(1) => data[0].into(),
+        Kind::Unsigned(2) => byte_order.read_u16(data).into(),
+        Kind::Unsigned(4) => byte_order.read_u32(data).into(),
+        Kind::Unsigned(_) => byte_order.read_u64(data).into(),
+        Kind::Signed(1) => i8::from_ne_bytes([data[0]]).into(),
+        Kind::Signed(2) => byte_order.read_i16(data).into(),
+        Kind::Signed(4) => byte_order.read_i32(data).into(),
+        Kind::Signed(_) => byte_order.read_i64(data).into(),
+        Kind::Float(2) => float_value(
+            f64::from(f16::from_bits(byte_order.read_u16(data))),
+            format_item_f16
+        ),
+        Kind::Float(4) => float_value(f64::from(byte_order.read_f32(data)), format_item_f32),
+        Kind::Float(_) => float_value(byte_order.read_f64(data), format_item_f64),
+        Kind::BFloat => float_value(
+            f64::from(f32::from(bf16::from_bits(byte_order.read_u16(data)))),
+            format_item_bf16
+        ),
+        Kind::Bytes | Kind::Str | Kind::Pad => unreachable!("not an item type"),
+    }
+}
+
+/// One `TYPE NAME` entry of a layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Field {
+    /// The type as written, e.g. `u32le` or `bytes[8]`
+    spec: String,
+    /// Empty for `pad`
+    name: String,
+    kind: Kind,
+    byte_order: ByteOrder,
+    /// `[N]`: N items, or N bytes for `bytes`, `str` and `pad`
+    count: Option<usize>,
+    /// Bytes the field takes in a record
+    size: usize,
+}
+
+impl Field {
+    fn parse(decl: &str, default_order: ByteOrder) -> Option<Self> {
+        let mut words = decl.split_whitespace();
+        let spec = words.next()?;
+        let name = words.next().unwrap_or_default();
+        if words.next().is_some() {
+            return None;
+        }
+
+        let (base, count) = match spec.split_once('[') {
+            Some((base, rest)) => {
+                let count = rest.strip_suffix(']')?.parse::<usize>().ok();
+                (base, Some(count.filter(|&n| n > 0)?))
+            }
+            None => (spec, None),
+        };
+        let (base, byte_order) = if let Some(base) = base.strip_suffix("le") {
+            (base, Some(ByteOrder::Little))
+        } else if let Some(base) = base.strip_suffix("be") {
+            (base, Some(ByteOrder::Big))
+        } else {
+            (base, None)
+        };
+        let kind = Kind::parse(base)?;
+
+        // only multi-byte numbers have a byte order, and only padding has no name
+        let has_order = matches!(kind, Kind::Float(_) | Kind::BFloat)
+            || matches!(kind, Kind::Unsigned(n) | Kind::Signed(n) if n > 1);
+        if (byte_order.is_some() && !has_order) || (kind == Kind::Pad) != name.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            spec: spec.to_string(),
+            name: name.to_string(),
+            kind,
+            byte_order: byte_order.unwrap_or(default_order),
+            count,
+            size: kind.item_size().checked_mul(count.unwrap_or(1))?,
+        })
+    }
+
+    fn value(&self, data: &[u8]) -> Value {
+        match (self.kind, self.count) {
+            (Kind::Bytes, _) => {
+                let mut hex = String::with_capacity(data.len() * 2);
+                for b in data {
+                    write!(hex, "{b:02x}").unwrap();
+                }
+                hex.into()
+            }
+            (Kind::Str, _) => {
+                let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+                String::from_utf8_lossy(&data[..end]).into()
+            }
+            (kind, Some(_)) => data
+                .chunks_exact(kind.item_size())
+                .map(|item| item_value(kind, self.byte_order, item))
+                .collect(),
+            (kind, None) => item_value(kind, self.byte_order, data),
+        }
+    }
+
+    fn schema(&self) -> Value {
+        if self.kind.is_text() || self.count.is_none() {
+            self.kind.item_schema()
+        } else {
+            array_schema(self.kind.item_schema())
+        }
+    }
+}
+
+/// The record layout given to `--decode-binary`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    fields: Vec<Field>,
+    size: usize,
+}
+
+impl Layout {
+    /// Parses `spec`; numbers without `le` or `be` are read in `default_order`
+    pub fn parse(spec: &str, default_order: ByteOrder) -> Result<Self, String> {
+        let mut fields = Vec::new();
+        // `offset` is the field every record gets
+        let mut names = HashSet::from(["offset".to_string()]);
+        let mut size = 0usize;
+        for decl in spec.split([';', '\n']).map(str::trim) {
+            if decl.is_empty() {
+                continue;
+            }
+            let field = Field::parse(decl, default_order)
+                .ok_or_else(|| translate!("od-error-decode-field", "field" => decl))?;
+            if !field.name.is_empty() && !names.insert(field.name.clone()) {
+                return Err(translate!("od-error-decode-name", "name" => field.name));
+            }
+            size = size
+                .checked_add(field.size)
+                .filter(|&size| size <= MAX_RECORD_SIZE)
+                .ok_or_else(|| translate!("od-error-decode-size", "max" => MAX_RECORD_SIZE))?;
+            fields.push(field);
+        }
+        if fields.is_empty() {
+            return Err(translate!("od-error-decode-empty"));
+        }
+        Ok(Self { fields, size })
+    }
+
+    /// Bytes per record, at most [`MAX_RECORD_SIZE`]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// `data` holds exactly one record, read at `offset`
+    fn decode(&self, offset: u64, data: &[u8]) -> Value {
+        let mut record = Map::new();
+        record.insert("offset".into(), offset.into());
+        let mut start = 0;
+        for field in &self.fields {
+            let end = start + field.size;
+            if field.kind != Kind::Pad {
+                record.insert(field.name.clone(), field.value(&data[start..end]));
+            }
+            start = end;
+        }
+        Value::Object(record)
+    }
+
+    pub fn schema(&self) -> Value {
+        let mut properties = vec![
+            Property::new("offset", "integer").description("Input offset of the record"),
+        ];
+        properties.extend(
+            self.fields
+                .iter()
+                .filter(|field| field.kind != Kind::Pad)
+                .map(|field| Property::new(&field.name, field.schema()).description(&field.spec)),
+        );
+        create_schema(vec![
+            Property::new("records", array_schema(object_schema(properties)))
+                .description("Records in input order"),
+            Property::new("count", "integer").description("Number of records"),
+            Property::new("record_size", "integer").format(FORMAT_BYTES).description("Bytes per record"),
+        ])
+    }
+}
+
+/// Fills `buf` unless the input ends first; returns the number of bytes read
+fn read_record<R: Read>(input: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match input.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Decodes the input record by record and prints a single
+/// `{records, count, record_size}` document. A trailing partial record is
+/// reported and left out.
+pub fn print_records<R>(layout: &Layout, input: &mut R, offset: u64) -> SGResult<()>
+where
+    R: Read + HasError,
+{
+    let size = layout.size();
+    let mut buf = vec![0; size];
+    let mut offset = offset;
+    let mut records = Vec::new();
+    let mut result = Ok(());
+
+    loop {
+        match read_record(input, &mut buf) {
+            Ok(0) => break,
+            Ok(n) if n < size => {
+                show_warning!(
+                    "{}",
+                    translate!("od-warning-decode-partial", "bytes" => n, "size" => size)
+                );
+                break;
+            }
+            Ok(_) => {
+                records.push(layout.decode(offset, &buf));
+                offset += size as u64;
+            }
+            Err(e) => {
+                show_error!("{e}");
+                result = Err(1.into());
+                break;
+            }
+        }
+    }
+
+    let output = json!({
+        "count": records.len(),
+        "record_size": size,
+        "records": records,
+    });
+    let opts = StardustOutputOptions {
+        stardust_output: true,
+        ..Default::default()
+    };
+    stardust_output::output(opts, output, || Ok(()))?;
+
+    if input.has_error() {
+        return Err(1.into());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_layout() {
+        let layout = Layout::parse("u32le magic; u16be count;bytes[8] name", ByteOrder::Big)
+            .unwrap();
+        assert_eq!(layout.size(), 14);
+        assert_eq!(layout.fields[0].byte_order, ByteOrder::Little);
+        assert_eq!(layout.fields[1].byte_order, ByteOrder::Big);
+        assert_eq!(layout.fields[2].kind, Kind::Bytes);
+        assert_eq!(layout.fields[2].count, Some(8));
+
+        let layout = Layout::parse("u16 a\npad[2]\ni8[3] b;", ByteOrder::Little).unwrap();
+        assert_eq!(layout.size(), 7);
+        assert_eq!(layout.fields[0].byte_order, ByteOrder::Little);
+    }
+
+    #[test]
+    fn test_parse_layout_errors() {
+        for spec in [
+            "",
+            " ; ",
+            "u32",
+            "u24 x",
+            "u8le x",
+            "bytesle[4] x",
+            "pad[2] x",
+            "bytes[0] x",
+            "bytes[4 x",
+            "u32 a b",
+            "u16 a; u8 a",
+            "u32 offset",
+        ] {
+            assert!(Layout::parse(spec, ByteOrder::Native).is_err(), "{spec}");
+        }
+    }
+
+    #[test]
+    fn test_parse_layout_size_limit() {
+        for spec in [
+            "bytes[99999999999999] x",
+            "u64[2305843009213693952] x",
+            "u64[18446744073709551615] x",
+            "bytes[16777216] a; u8 b",
+            "pad[9223372036854775807]; pad[9223372036854775807]; u8 x",
+        ] {
+            assert!(Layout::parse(spec, ByteOrder::Native).is_err(), "{spec}");
+        }
+        let layout = Layout::parse("bytes[16777215] a; u8 b", ByteOrder::Native).unwrap();
+        assert_eq!(layout.size(), MAX_RECORD_SIZE);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_decode() {
+        let layout = Layout::parse(
+            "u32le magic; u16be count; i8 delta; pad[1]; str[4] tag; bytes[2] raw; f32le[2] xy",
+            ByteOrder::Native
+        )
+        .unwrap();
+        let mut data = vec![0x7f, 0x45, 0x4c, 0x46, 0x01, 0x02, 0xff, 0xaa];
+        data.extend(b"ab\0\0");
+        data.extend([0xde, 0xad]);
+        data.extend(1.1_f32.to_le_bytes());
+        data.extend(f32::NAN.to_le_bytes());
+
+        let record = layout.decode(32, &data);
+        assert_eq!(record["offset"], 32);
+        assert_eq!(record["magic"], 0x464c_457f);
+        assert_eq!(record["count"], 0x0102);
+        assert_eq!(record["delta"], -1);
+        assert_eq!(record["tag"], "ab");
+        assert_eq!(record["raw"], "dead");
+        assert_eq!(record["xy"][0].as_f64().unwrap(), 1.1);
+        assert_eq!(record["xy"][1], "NaN");
+        assert_eq!(record.as_object().unwrap().len(), 7);
+    }
+}
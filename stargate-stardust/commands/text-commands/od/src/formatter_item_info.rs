@@ -37,6 +37,8 @@ impl fmt::Debug for FormatWriter {
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct FormatterItemInfo {
+    /// The `-t` type this item prints, e.g. `x4` or `d2`
+    pub type_name: &'static str,
     pub byte_size: usize,
     pub print_width: usize, // including a space in front of the text
     pub formatter: FormatWriter,
@@ -3,6 +3,7 @@
 
 
 mod byteorder_io;
+mod decode_binary;
 mod formatter_item_info;
 mod input_decoder;
 mod input_offset;
@@ -18,12 +19,14 @@ mod peek_reader;
 mod prn_char;
 mod prn_float;
 mod prn_int;
+mod records;
 
 use std::cmp;
 use std::fmt::Write;
 use std::io::{BufReader, Read};
 
 use crate::byteorder_io::ByteOrder;
+use crate::decode_binary::Layout;
 use crate::formatter_item_info::FormatWriter;
 use crate::input_decoder::{InputDecoder, MemoryDecoder};
 use crate::input_offset::{InputOffset, Radix};
@@ -39,6 +42,7 @@ use clap::ArgAction;
 use clap::{Arg, ArgMatches, Command, parser::ValueSource};
 use sgcore::display::Quotable;
 use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::stardust_output;
 use sgcore::translate;
 
 use sgcore::parser::parse_size::ParseSizeError;
@@ -58,6 +62,7 @@ pub(crate) mod options {
     pub const OUTPUT_DUPLICATES: &str = "output-duplicates";
     pub const TRADITIONAL: &str = "traditional";
     pub const WIDTH: &str = "width";
+    pub const DECODE_BINARY: &str = "decode-binary";
     pub const FILENAME: &str = "FILENAME";
 }
 
@@ -72,6 +77,10 @@ struct OdOptions {
     output_duplicates: bool,
     radix: Radix,
     string_min_length: Option<usize>,
+    /// `--decode-binary`
+    layout: Option<Layout>,
+    /// `--obj`
+    obj: bool,
 }
 
 /// Helper function to parse bytes with error handling
@@ -190,6 +199,12 @@ impl OdOptions {
             }
         };
 
+        let layout = matches
+            .get_one::<String>(options::DECODE_BINARY)
+            .map(|spec| Layout::parse(spec, byte_order))
+            .transpose()
+            .map_err(|e| SGSimpleError::new(1, e))?;
+
         Ok(Self {
             byte_order,
             skip_bytes,
@@ -201,6 +216,8 @@ impl OdOptions {
             output_duplicates,
             radix,
             string_min_length,
+            layout,
+            obj: matches.get_flag(stardust_output::ARG_STARDUST_OUTPUT),
         })
     }
 }
@@ -218,7 +235,22 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 
     let od_options = OdOptions::new(&clap_matches, &args)?;
 
-    if let Some(min_length) = od_options.string_min_length {
+    let schema = od_options
+        .layout
+        .as_ref()
+        .map_or_else(records::schema, Layout::schema);
+    if stardust_output::self_describe(&clap_matches, schema)? {
+        return Ok(());
+    }
+
+    if let Some(layout) = &od_options.layout {
+        let mut input = open_input_peek_reader(
+            &od_options.input_strings,
+            od_options.skip_bytes,
+            od_options.read_bytes
+        );
+        decode_binary::print_records(layout, &mut input, od_options.skip_bytes)
+    } else if let Some(min_length) = od_options.string_min_length {
         extract_strings_from_input(
             &od_options.input_strings,
             od_options.skip_bytes,
@@ -248,7 +280,11 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
             od_options.output_duplicates
         );
 
-        odfunc(&mut input_offset, &mut input_decoder, &output_info)
+        if od_options.obj {
+            records::print_lines(od_options.skip_bytes, &mut input_decoder, &output_info)
+        } else {
+            odfunc(&mut input_offset, &mut input_decoder, &output_info)
+        }
     }
 }
 
@@ -451,6 +487,27 @@ pub fn sg_app() -> Command {
                 .help(translate!("od-help-traditional"))
                 .action(ArgAction::SetTrue)
         )
+        .arg(
+            Arg::new(options::DECODE_BINARY)
+                .long(options::DECODE_BINARY)
+                .help(translate!("od-help-decode-binary"))
+                .value_name("LAYOUT")
+                .conflicts_with(options::STRINGS)
+        )
+        .arg(
+            Arg::new(stardust_output::ARG_STARDUST_OUTPUT)
+                .long("obj")
+                .help(translate!("od-help-obj"))
+                .action(ArgAction::SetTrue)
+                .conflicts_with(options::STRINGS)
+        )
+        .arg(
+            Arg::new(stardust_output::ARG_SCHEMA)
+                .long("schema")
+                .help("Print JSON schema of output structure")
+                .action(ArgAction::SetTrue)
+                .hide(true)
+        )
         .arg(
             Arg::new(options::FILENAME)
                 .hide(true)
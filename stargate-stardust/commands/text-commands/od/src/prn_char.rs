@@ -1,12 +1,14 @@
 use crate::formatter_item_info::{FormatWriter, FormatterItemInfo};
 
 pub static FORMAT_ITEM_A: FormatterItemInfo = FormatterItemInfo {
+    type_name: "a",
     byte_size: 1,
     print_width: 4,
     formatter: FormatWriter::IntWriter(format_item_a),
 };
 
 pub static FORMAT_ITEM_C: FormatterItemInfo = FormatterItemInfo {
+    type_name: "c",
     byte_size: 1,
     print_width: 4,
     formatter: FormatWriter::MultibyteWriter(format_item_c),
@@ -4,24 +4,28 @@ use std::num::FpCategory;
 use crate::formatter_item_info::{FormatWriter, FormatterItemInfo};
 
 pub static FORMAT_ITEM_F16: FormatterItemInfo = FormatterItemInfo {
+    type_name: "f2",
     byte_size: 2,
     print_width: 16,
     formatter: FormatWriter::FloatWriter(format_item_f16),
 };
 
 pub static FORMAT_ITEM_F32: FormatterItemInfo = FormatterItemInfo {
+    type_name: "f4",
     byte_size: 4,
     print_width: 16,
     formatter: FormatWriter::FloatWriter(format_item_f32),
 };
 
 pub static FORMAT_ITEM_F64: FormatterItemInfo = FormatterItemInfo {
+    type_name: "f8",
     byte_size: 8,
     print_width: 25,
     formatter: FormatWriter::FloatWriter(format_item_f64),
 };
 
 pub static FORMAT_ITEM_BF16: FormatterItemInfo = FormatterItemInfo {
+    type_name: "fB",
     byte_size: 2,
     print_width: 16,
     formatter: FormatWriter::BFloatWriter(format_item_bf16),
@@ -22,15 +22,16 @@ macro_rules! DEC {
 /// defines a static struct of type `FormatterItemInfo` called `$NAME`
 ///
 /// Used to format unsigned integer types with help of a function called `$function`
-/// `$byte_size` is the size of the type, `$print_width` is the maximum width in
+/// `$type_name` is the `-t` type, `$byte_size` is the size of the type, `$print_width` is the maximum width in
 /// human-readable format. `$format_str` is one of OCT, HEX or DEC
 macro_rules! int_writer_unsigned {
-    ($NAME:ident, $byte_size:expr, $print_width:expr, $function:ident, $format_str:expr) => {
+    ($NAME:ident, $type_name:expr, $byte_size:expr, $print_width:expr, $function:ident, $format_str:expr) => {
         fn $function(p: u64) -> String {
             format!($format_str, p, width = $print_width - 1)
         }
 
         pub static $NAME: FormatterItemInfo = FormatterItemInfo {
+            type_name: $type_name,
             byte_size: $byte_size,
             print_width: $print_width,
             formatter: FormatWriter::IntWriter($function),
@@ -41,16 +42,17 @@ macro_rules! int_writer_unsigned {
 /// defines a static struct of type `FormatterItemInfo` called `$NAME`
 ///
 /// Used to format signed integer types with help of a function called `$function`
-/// `$byte_size` is the size of the type, `$print_width` is the maximum width in
+/// `$type_name` is the `-t` type, `$byte_size` is the size of the type, `$print_width` is the maximum width in
 /// human-readable format. `$format_str` should be DEC
 macro_rules! int_writer_signed {
-    ($NAME:ident, $byte_size:expr, $print_width:expr, $function:ident, $format_str:expr) => {
+    ($NAME:ident, $type_name:expr, $byte_size:expr, $print_width:expr, $function:ident, $format_str:expr) => {
         fn $function(p: u64) -> String {
             let s = sign_extend(p, $byte_size);
             format!($format_str, s, width = $print_width - 1)
         }
 
         pub static $NAME: FormatterItemInfo = FormatterItemInfo {
+            type_name: $type_name,
             byte_size: $byte_size,
             print_width: $print_width,
             formatter: FormatWriter::IntWriter($function),
@@ -64,25 +66,25 @@ fn sign_extend(item: u64, item_bytes: usize) -> i64 {
     (item << shift) as i64 >> shift
 }
 
-int_writer_unsigned!(FORMAT_ITEM_OCT8, 1, 4, format_item_oct8, OCT!()); // max: 377
-int_writer_unsigned!(FORMAT_ITEM_OCT16, 2, 7, format_item_oct16, OCT!()); // max: 177777
-int_writer_unsigned!(FORMAT_ITEM_OCT32, 4, 12, format_item_oct32, OCT!()); // max: 37777777777
-int_writer_unsigned!(FORMAT_ITEM_OCT64, 8, 23, format_item_oct64, OCT!()); // max: 1777777777777777777777
-
-int_writer_unsigned!(FORMAT_ITEM_HEX8, 1, 3, format_item_hex8, HEX!()); // max: ff
-int_writer_unsigned!(FORMAT_ITEM_HEX16, 2, 5, format_item_hex16, HEX!()); // max: ffff
-int_writer_unsigned!(FORMAT_ITEM_HEX32, 4, 9, format_item_hex32, HEX!()); // max: ffffffff
-int_writer_unsigned!(FORMAT_ITEM_HEX64, 8, 17, format_item_hex64, HEX!()); // max: ffffffffffffffff
-
-int_writer_unsigned!(FORMAT_ITEM_DEC8U, 1, 4, format_item_dec_u8, DEC!()); // max: 255
-int_writer_unsigned!(FORMAT_ITEM_DEC16U, 2, 6, format_item_dec_u16, DEC!()); // max: 65535
-int_writer_unsigned!(FORMAT_ITEM_DEC32U, 4, 11, format_item_dec_u32, DEC!()); // max: 4294967295
-int_writer_unsigned!(FORMAT_ITEM_DEC64U, 8, 21, format_item_dec_u64, DEC!()); // max: 18446744073709551615
-
-int_writer_signed!(FORMAT_ITEM_DEC8S, 1, 5, format_item_dec_s8, DEC!()); // max: -128
-int_writer_signed!(FORMAT_ITEM_DEC16S, 2, 7, format_item_dec_s16, DEC!()); // max: -32768
-int_writer_signed!(FORMAT_ITEM_DEC32S, 4, 12, format_item_dec_s32, DEC!()); // max: -2147483648
-int_writer_signed!(FORMAT_ITEM_DEC64S, 8, 21, format_item_dec_s64, DEC!()); // max: -9223372036854775808
+int_writer_unsigned!(FORMAT_ITEM_OCT8, "o1", 1, 4, format_item_oct8, OCT!()); // max: 377
+int_writer_unsigned!(FORMAT_ITEM_OCT16, "o2", 2, 7, format_item_oct16, OCT!()); // max: 177777
+int_writer_unsigned!(FORMAT_ITEM_OCT32, "o4", 4, 12, format_item_oct32, OCT!()); // max: 37777777777
+int_writer_unsigned!(FORMAT_ITEM_OCT64, "o8", 8, 23, format_item_oct64, OCT!()); // max: 1777777777777777777777
+
+int_writer_unsigned!(FORMAT_ITEM_HEX8, "x1", 1, 3, format_item_hex8, HEX!()); // max: ff
+int_writer_unsigned!(FORMAT_ITEM_HEX16, "x2", 2, 5, format_item_hex16, HEX!()); // max: ffff
+int_writer_unsigned!(FORMAT_ITEM_HEX32, "x4", 4, 9, format_item_hex32, HEX!()); // max: ffffffff
+int_writer_unsigned!(FORMAT_ITEM_HEX64, "x8", 8, 17, format_item_hex64, HEX!()); // max: ffffffffffffffff
+
+int_writer_unsigned!(FORMAT_ITEM_DEC8U, "u1", 1, 4, format_item_dec_u8, DEC!()); // max: 255
+int_writer_unsigned!(FORMAT_ITEM_DEC16U, "u2", 2, 6, format_item_dec_u16, DEC!()); // max: 65535
+int_writer_unsigned!(FORMAT_ITEM_DEC32U, "u4", 4, 11, format_item_dec_u32, DEC!()); // max: 4294967295
+int_writer_unsigned!(FORMAT_ITEM_DEC64U, "u8", 8, 21, format_item_dec_u64, DEC!()); // max: 18446744073709551615
+
+int_writer_signed!(FORMAT_ITEM_DEC8S, "d1", 1, 5, format_item_dec_s8, DEC!()); // max: -128
+int_writer_signed!(FORMAT_ITEM_DEC16S, "d2", 2, 7, format_item_dec_s16, DEC!()); // max: -32768
+int_writer_signed!(FORMAT_ITEM_DEC32S, "d4", 4, 12, format_item_dec_s32, DEC!()); // max: -2147483648
+int_writer_signed!(FORMAT_ITEM_DEC64S, "d8", 8, 21, format_item_dec_s64, DEC!()); // max: -9223372036854775808
 
 #[test]
 #[allow(clippy::cognitive_complexity)]
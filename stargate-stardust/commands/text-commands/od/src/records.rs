@@ -0,0 +1,141 @@
+//! Stardust records for `od --obj`.
+//!
+//! Every output line of a text dump becomes a record holding its offset, its
+//! raw bytes, and the items of each `-t` format. Values come from the same
+//! printers as the text dump, so `-t d4` and `-t f8` give numbers and the
+//! octal, hexadecimal and character formats give the text `od` would print.
+//! Duplicate lines are always listed, there is no `*` in a document.
+use serde_json::{Value, json};
+use sgcore::error::SGResult;
+use sgcore::show_error;
+use sgcore::stardust_output::{
+    self, Property, StardustOutputOptions, array_schema, create_schema, object_schema,
+};
+
+use crate::formatter_item_info::{FormatWriter, FormatterItemInfo};
+use crate::input_decoder::{InputDecoder, MemoryDecoder};
+use crate::multifile_reader::HasError;
+use crate::output_info::OutputInfo;
+use crate::peek_reader::PeekRead;
+
+/// The item of `info` at `start`, as JSON
+///
+/// Decimal integers and finite floats are numbers, everything else is the
+/// text the printer produces, without its padding.
+fn item_value(info: &FormatterItemInfo, decoder: &MemoryDecoder, start: usize) -> Value {
+    let text = match info.formatter {
+        FormatWriter::IntWriter(func) => func(decoder.read_uint(start, info.byte_size)),
+        FormatWriter::FloatWriter(func) => func(decoder.read_float(start, info.byte_size)),
+        FormatWriter::BFloatWriter(func) => func(decoder.read_bfloat(start)),
+        FormatWriter::MultibyteWriter(func) => func(decoder.get_full_buffer(start)),
+    };
+    let text = text.trim();
+    let number = match info.type_name.as_bytes()[0] {
+        b'd' => text.parse::<i64>().ok().map(Value::from),
+        b'u' => text.parse::<u64>().ok().map(Value::from),
+        b'f' => text
+            .parse::<f64>()
+            .ok()
+            .filter(|f| f.is_finite())
+            .map(Value::from),
+        _ => None,
+    };
+    number.unwrap_or_else(|| text.into())
+}
+
+fn line_value(offset: u64, decoder: &MemoryDecoder, output_info: &OutputInfo) -> Value {
+    let decoded: Vec<Value> = output_info
+        .spaced_formatters_iter()
+        .map(|f| {
+            let info = &f.formatter_item_info;
+            let values: Vec<Value> = (0..decoder.length())
+                .step_by(info.byte_size)
+                .map(|b| item_value(info, decoder, b))
+                .collect();
+            json!({
+                "type": info.type_name,
+                "values": values,
+            })
+        })
+        .collect();
+    json!({
+        "offset": offset,
+        "bytes": decoder.get_buffer(0),
+        "decoded": decoded,
+    })
+}
+
+/// Reads the input line by line like `odfunc` and prints the lines as a
+/// single `{lines, count}` document.
+pub fn print_lines<I>(
+    offset: u64,
+    input_decoder: &mut InputDecoder<I>,
+    output_info: &OutputInfo
+) -> SGResult<()>
+where
+    I: PeekRead + HasError,
+{
+    let line_bytes = output_info.byte_size_line;
+    let mut offset = offset;
+    let mut lines = Vec::new();
+    let mut result = Ok(());
+
+    loop {
+        match input_decoder.peek_read() {
+            Ok(mut memory_decoder) => {
+                let length = memory_decoder.length();
+                if length == 0 {
+                    break;
+                }
+                if length != line_bytes {
+                    let max_used = (length + output_info.byte_size_block).min(line_bytes);
+                    memory_decoder.zero_out_buffer(length, max_used);
+                }
+                lines.push(line_value(offset, &memory_decoder, output_info));
+                offset += length as u64;
+            }
+            Err(e) => {
+                show_error!("{e}");
+                result = Err(1.into());
+                break;
+            }
+        }
+    }
+
+    let output = json!({
+        "count": lines.len(),
+        "lines": lines,
+    });
+    let opts = StardustOutputOptions {
+        stardust_output: true,
+        ..Default::default()
+    };
+    stardust_output::output(opts, output, || Ok(()))?;
+
+    if input_decoder.has_error() {
+        return Err(1.into());
+    }
+    result
+}
+
+pub fn schema() -> Value {
+    let decoded = object_schema(vec![
+        Property::new("type", "string")
+            .description("The -t type of the values, e.g. x1, d4, f8, a or c"),
+        Property::new("values", array_schema(json!({"type": ["number", "string"]})))
+            .description(
+                "One value per item of the line: numbers for the decimal and float types, \
+                 the printed text for the others"
+            ),
+    ]);
+    let line = object_schema(vec![
+        Property::new("offset", "integer").description("Input offset of the first byte"),
+        Property::new("bytes", array_schema("integer")).description("The bytes of the line"),
+        Property::new("decoded", array_schema(decoded))
+            .description("The line in each requested format, in the order given"),
+    ]);
+    create_schema(vec![
+        Property::new("lines", array_schema(line)).description("Lines of the dump"),
+        Property::new("count", "integer").description("Number of lines"),
+    ])
+}
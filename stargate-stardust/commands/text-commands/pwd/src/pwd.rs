@@ -75,7 +75,6 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
     sgcore::pledge::apply_pledge(&["stdio"])?;
     let opts = StardustOutputOptions::from_matches(&matches);
-    let field_filter = matches.get_one::<String>(stardust_output::ARG_FIELD).map(|s| s.as_str());
 
     let cwd = if matches.get_flag(OPT_PHYSICAL) {
         physical_path()
@@ -95,8 +94,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
                     else if matches.get_flag(OPT_LOGICAL) { "logical" }
                     else { "physical" }
         });
-        let filtered = stardust_output::filter_fields(output, field_filter);
-        stardust_output::output(opts, filtered, || Ok(()))?;
+        stardust_output::output(opts, output, || Ok(()))?;
     } else {
         println_verbatim(cwd)
             .map_err_context(|| translate!("pwd-error-failed-to-print-current-directory"))?;
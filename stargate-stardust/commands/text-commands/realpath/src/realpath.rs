@@ -293,7 +293,7 @@ fn realpath_json(
     let output = RealpathOutput { paths: results };
     let json_value = serde_json::to_value(&output)
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
-    stardust_output::output(*json_output_options, json_value, || Ok(()))?;
+    stardust_output::output(json_output_options.clone(), json_value, || Ok(()))?;
     Ok(())
 }
 
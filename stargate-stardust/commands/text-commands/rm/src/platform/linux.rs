@@ -9,13 +9,12 @@ use std::path::Path;
 use sgcore::display::Quotable;
 use sgcore::error::FromIo;
 use sgcore::safe_traversal::DirFd;
-use sgcore::show_error;
 use sgcore::translate;
 
 use super::super::{
     InteractiveMode, Options, is_dir_empty, is_readable_metadata, prompt_descend, prompt_dir,
-    prompt_file, remove_file, show_permission_denied_error, show_removal_error,
-    verbose_removed_directory, verbose_removed_file,
+    prompt_file, remove_file, report_skipped, show_path_error, show_permission_denied_error,
+    show_removal_error, verbose_removed_directory, verbose_removed_file,
 };
 
 /// Whether the given file or directory is readable.
@@ -45,7 +44,7 @@ pub fn safe_remove_file(
         }
         Err(e) => {
             if e.kind() == std::io::ErrorKind::PermissionDenied {
-                show_error!("cannot remove {}: Permission denied", path.quote());
+                show_permission_denied_error(path);
             } else {
                 let _ = show_removal_error(e, path);
             }
@@ -77,7 +76,7 @@ pub fn safe_remove_empty_dir(
         Err(e) => {
             let e =
                 e.map_err_context(|| translate!("rm-error-cannot-remove", "file" => path.quote()));
-            show_error!("{e}");
+            show_path_error(path, e);
             Some(true)
         }
     }
@@ -94,7 +93,7 @@ fn handle_error_with_force(e: std::io::Error, path: &Path, options: &Options) ->
 
     if !options.force {
         let e = e.map_err_context(|| translate!("rm-error-cannot-remove", "file" => path.quote()));
-        show_error!("{e}");
+        show_path_error(path, e);
     }
     !options.force
 }
@@ -119,7 +118,7 @@ fn handle_permission_denied(
             let remove_err = remove_err.map_err_context(
                 || translate!("rm-error-cannot-remove", "file" => entry_path.quote())
             );
-            show_error!("{remove_err}");
+            show_path_error(entry_path, remove_err);
             return true;
         }
         // With force mode, suppress non-permission errors
@@ -141,7 +140,7 @@ fn handle_unlink(
     if let Err(e) = dir_fd.unlink_at(entry_name, is_dir) {
         let e = e
             .map_err_context(|| translate!("rm-error-cannot-remove", "file" => entry_path.quote()));
-        show_error!("{e}");
+        show_path_error(entry_path, e);
         true
     } else {
         if is_dir {
@@ -332,6 +331,8 @@ pub fn safe_remove_dir_recursive_impl(path: &Path, dir_fd: &DirFd, options: &Opt
             // Remove file - check if user wants to remove it first
             if prompt_file(&entry_path, options) {
                 error = handle_unlink(dir_fd, entry_name.as_ref(), &entry_path, false, options);
+            } else {
+                report_skipped(&entry_path);
             }
         }
     }
@@ -4,6 +4,7 @@ use clap::builder::{PossibleValue, ValueParser};
 use clap::{Arg, ArgAction, Command, parser::ValueSource};
 use indicatif::{ProgressBar, ProgressStyle};
 use std::ffi::{OsStr, OsString};
+use std::fmt::Display;
 use std::fs::{self, Metadata};
 use std::io::{self, IsTerminal, stdin};
 use std::ops::BitOr;
@@ -14,7 +15,9 @@ use std::path::{Path, PathBuf};
 use thiserror::Error;
 use sgcore::display::Quotable;
 use sgcore::error::{FromIo, SGError, SGResult};
+use sgcore::operation_report::{self, Action, Operation};
 use sgcore::parser::shortcut_value_parser::ShortcutValueParser;
+use sgcore::stardust_output::{self, StardustOutputOptions};
 use sgcore::translate;
 use sgcore::{format_usage, os_str_as_bytes, prompt_yes, show_error};
 
@@ -42,8 +45,9 @@ enum RmError {
 
 impl SGError for RmError {}
 
-/// Helper function to print verbose message for removed file
+/// Helper function to report a removed file: recorded for `--obj`, printed with `--verbose`
 fn verbose_removed_file(path: &Path, options: &Options) {
+    operation_report::record(Operation::new(Action::Removed).source(path));
     if options.verbose {
         println!(
             "{}",
@@ -52,8 +56,9 @@ fn verbose_removed_file(path: &Path, options: &Options) {
     }
 }
 
-/// Helper function to print verbose message for removed directory
+/// Helper function to report a removed directory: recorded for `--obj`, printed with `--verbose`
 fn verbose_removed_directory(path: &Path, options: &Options) {
+    operation_report::record(Operation::new(Action::Removed).source(path));
     if options.verbose {
         println!(
             "{}",
@@ -62,21 +67,35 @@ fn verbose_removed_directory(path: &Path, options: &Options) {
     }
 }
 
+/// Helper function to show an error about `path` and record it for `--obj`
+fn show_path_error(path: &Path, error: impl Display) {
+    operation_report::record(Operation::new(Action::Removed).source(path).error(&error));
+    show_error!("{error}");
+}
+
+/// Helper function to record a path the user chose to keep
+fn report_skipped(path: &Path) {
+    operation_report::record(Operation::new(Action::Skipped).source(path));
+}
+
 /// Helper function to show error with context and return error status
 fn show_removal_error(error: std::io::Error, path: &Path) -> bool {
     if error.kind() == io::ErrorKind::PermissionDenied {
-        show_error!("cannot remove {}: Permission denied", path.quote());
+        show_permission_denied_error(path);
     } else {
         let e =
             error.map_err_context(|| translate!("rm-error-cannot-remove", "file" => path.quote()));
-        show_error!("{e}");
+        show_path_error(path, e);
     }
     true
 }
 
 /// Helper function for permission denied errors
 fn show_permission_denied_error(path: &Path) -> bool {
-    show_error!("cannot remove {}: Permission denied", path.quote());
+    show_path_error(
+        path,
+        format_args!("cannot remove {}: Permission denied", path.quote())
+    );
     true
 }
 
@@ -193,6 +212,10 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     sgcore::pledge::apply_pledge(&["stdio", "rpath", "cpath"])?;
 
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
+    if stardust_output::self_describe(&matches, operation_report::schema())? {
+        return Ok(());
+    }
+    let stardust = StardustOutputOptions::from_matches(&matches);
 
     let files: Vec<_> = matches
         .get_many::<OsString>(ARG_FILES)
@@ -234,7 +257,8 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         preserve_root: !matches.get_flag(OPT_NO_PRESERVE_ROOT),
         recursive: matches.get_flag(OPT_RECURSIVE),
         dir: matches.get_flag(OPT_DIR),
-        verbose: matches.get_flag(OPT_VERBOSE),
+        // With --obj the report replaces the verbose lines on stdout
+        verbose: matches.get_flag(OPT_VERBOSE) && !stardust.stardust_output,
         progress: matches.get_flag(OPT_PROGRESS),
         __presume_input_tty: if matches.get_flag(PRESUME_INPUT_TTY) {
             Some(true)
@@ -262,7 +286,16 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         }
     }
 
-    if remove(&files, &options) {
+    if stardust.stardust_output {
+        operation_report::start();
+    }
+    let had_err = remove(&files, &options);
+    if stardust.stardust_output {
+        let report = operation_report::to_value(&operation_report::finish());
+        stardust_output::output(stardust, report, || Ok(()))?;
+    }
+
+    if had_err {
         return Err(1.into());
     }
 
@@ -270,7 +303,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 }
 
 pub fn sg_app() -> Command {
-    Command::new(sgcore::util_name())
+    let cmd = Command::new(sgcore::util_name())
         .version(sgcore::crate_version!())
         .about(translate!("rm-about"))
         .help_template(sgcore::localized_help_template(sgcore::util_name()))
@@ -374,7 +407,8 @@ pub fn sg_app() -> Command {
                 .value_parser(ValueParser::os_string())
                 .num_args(1..)
                 .value_hint(clap::ValueHint::AnyPath)
-        )
+        );
+    stardust_output::add_json_args(cmd)
 }
 
 /// Creates a progress bar for rm operations if conditions are met.
@@ -474,8 +508,8 @@ pub fn remove(files: &[&OsStr], options: &Options) -> bool {
                 if options.force {
                     false
                 } else {
-                    show_error!(
-                        "{}",
+                    show_path_error(
+                        file,
                         RmError::CannotRemoveNoSuchFile(filename.to_os_string())
                     );
                     true
@@ -576,7 +610,7 @@ fn remove_dir_recursive(
                         let e = e.map_err_context(
                             || translate!("rm-error-cannot-remove", "file" => path.quote())
                         );
-                        show_error!("{e}");
+                        show_path_error(path, e);
                         return true;
                     }
                 }
@@ -615,7 +649,7 @@ fn remove_dir_recursive(
                 let e = e.map_err_context(
                     || translate!("rm-error-cannot-remove", "file" => path.quote())
                 );
-                show_error!("{e}");
+                show_path_error(path, e);
                 error = true;
             }
             Err(_) => {
@@ -632,8 +666,8 @@ fn handle_dir(path: &Path, options: &Options, progress_bar: Option<&ProgressBar>
 
     let path = clean_trailing_slashes(path);
     if path_is_current_or_parent_directory(path) {
-        show_error!(
-            "{}",
+        show_path_error(
+            path,
             RmError::RefusingToRemoveDirectory(path.as_os_str().to_os_string())
         );
         return true;
@@ -645,12 +679,12 @@ fn handle_dir(path: &Path, options: &Options, progress_bar: Option<&ProgressBar>
     } else if options.dir && (!is_root || !options.preserve_root) {
         had_err = remove_dir(path, options, progress_bar).bitor(had_err);
     } else if options.recursive {
-        show_error!("{}", RmError::DangerousRecursiveOperation);
+        show_path_error(path, RmError::DangerousRecursiveOperation);
         show_error!("{}", RmError::UseNoPreserveRoot);
         had_err = true;
     } else {
-        show_error!(
-            "{}",
+        show_path_error(
+            path,
             RmError::CannotRemoveIsDirectory(path.as_os_str().to_os_string())
         );
         had_err = true;
@@ -664,12 +698,13 @@ fn handle_dir(path: &Path, options: &Options, progress_bar: Option<&ProgressBar>
 /// Returns true if it has encountered an error.
 fn remove_dir(path: &Path, options: &Options, progress_bar: Option<&ProgressBar>) -> bool {
     if !prompt_dir(path, options) {
+        report_skipped(path);
         return false;
     }
 
     if !options.dir && !options.recursive {
-        show_error!(
-            "{}",
+        show_path_error(
+            path,
             RmError::CannotRemoveIsDirectory(path.as_os_str().to_os_string())
         );
         return true;
@@ -708,8 +743,8 @@ fn remove_file(path: &Path, options: &Options, progress_bar: Option<&ProgressBar
             }
             Err(e) => {
                 if e.kind() == io::ErrorKind::PermissionDenied {
-                    show_error!(
-                        "{}",
+                    show_path_error(
+                        path,
                         RmError::CannotRemovePermissionDenied(path.as_os_str().to_os_string())
                     );
                 } else {
@@ -718,6 +753,8 @@ fn remove_file(path: &Path, options: &Options, progress_bar: Option<&ProgressBar
                 return true;
             }
         }
+    } else {
+        report_skipped(path);
     }
 
     false
@@ -50,6 +50,8 @@ sort-error-no-match-for-key = ^ no match for key
 sort-error-write-failed = write failed: {$output}
 sort-failed-to-delete-temporary-directory = failed to delete temporary directory: {$error}
 sort-failed-to-set-up-signal-handler = failed to set up signal handler: {$error}
+sort-obj-invalid-input = {$file}: {$error}
+sort-obj-tmp-read-failed = cannot read temporary file
 
 # Help messages
 sort-help-help = Print help information.
@@ -81,3 +83,5 @@ sort-help-compress-prog = compress temporary files with PROG, decompress with PR
 sort-help-batch-size = Merge at most N_MERGE inputs at once.
 sort-help-files0-from = read input from the files specified by NUL-terminated NUL_FILE
 sort-help-debug = underline the parts of the line that are actually used for sorting
+sort-help-obj = sort the records of stardust input, by field paths given to --key as PATH[:OPTS], e.g. name,size:nr
+sort-help-records = with --obj, sort the records in top-level FIELD of the input object
@@ -50,6 +50,8 @@ sort-error-no-match-for-key = ^ aucune correspondance pour la clé
 sort-error-write-failed = échec d'écriture : {$output}
 sort-failed-to-delete-temporary-directory = échec de suppression du répertoire temporaire : {$error}
 sort-failed-to-set-up-signal-handler = échec de configuration du gestionnaire de signal : {$error}
+sort-obj-invalid-input = {$file} : {$error}
+sort-obj-tmp-read-failed = impossible de lire le fichier temporaire
 
 # Messages d'aide
 sort-help-help = Affiche les informations d'aide.
@@ -81,3 +83,5 @@ sort-help-compress-prog = compresse les fichiers temporaires avec PROG, décompr
 sort-help-batch-size = Fusionne au maximum N_MERGE entrées à la fois.
 sort-help-files0-from = lit l'entrée depuis les fichiers spécifiés par FICHIER_NUL terminé par NUL
 sort-help-debug = souligne les parties de la ligne qui sont réellement utilisées pour le tri
+sort-help-obj = trie les enregistrements d'une entrée stardust, selon les chemins de champs donnés à --key sous la forme CHEMIN[:OPTS], par ex. name,size:nr
+sort-help-records = avec --obj, trie les enregistrements du CHAMP de premier niveau de l'objet d'entrée
@@ -0,0 +1,589 @@
+//! Sorting stardust records for `sort --obj`.
+//!
+//! Keys are field paths followed by the option letters of `-k`, such as
+//! `name`, `size:n` or `mtime:nr`. Records are read one at a time and cut
+//! into chunks of about `--buffer-size` bytes, each sorted as it fills up.
+//! When one chunk does not hold them all, every full chunk is written to a
+//! temporary file, compressed with `--compress-program` if one is given, and
+//! the files are merged `--batch-size` at a time, as for text input. The
+//! final merge writes its records straight into the output document.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::ffi::{OsStr, OsString};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+use binary_heap_plus::BinaryHeap;
+use compare::Compare;
+use rayon::prelude::*;
+use serde_json::Value;
+use sgcore::display::Quotable;
+use sgcore::error::{FromIo, SGError, SGResult, SGSimpleError};
+use sgcore::field_path::FieldPath;
+use sgcore::json_adapter::compare_values;
+use sgcore::record_stream::RecordStream;
+use sgcore::translate;
+use sgcore::version_cmp::version_cmp;
+
+use crate::custom_str_cmp::custom_str_cmp;
+use crate::merge::{
+    ClosedTmpFile, MergeInput, WriteableCompressedTmpFile, WriteablePlainTmpFile, WriteableTmpFile,
+};
+use crate::numeric_str_cmp::{NumInfo, NumInfoParseSettings, human_numeric_str_cmp, numeric_str_cmp};
+use crate::tmp_dir::TmpDirWrapper;
+use crate::{
+    GlobalSettings, KeySettings, Output, SortError, SortMode, general_bd_parse,
+    general_numeric_compare, get_leading_gen, get_rand_string, month_compare, open,
+    random_shuffle,
+};
+
+/// A key of `sort --obj`: a field path and how to compare what it reaches
+pub struct RecordKey {
+    path: FieldPath,
+    settings: KeySettings,
+}
+
+impl RecordKey {
+    /// Parse a `--key` value, a comma-separated list of `PATH[:OPTS]`. Keys
+    /// without options take the global ordering options, like `-k`.
+    pub fn parse_list(spec: &str, global_settings: &GlobalSettings) -> SGResult<Vec<Self>> {
+        spec.split(',')
+            .map(|key| Self::parse(key, global_settings))
+            .collect()
+    }
+
+    fn parse(key: &str, global_settings: &GlobalSettings) -> SGResult<Self> {
+        let (path, options) = key.rsplit_once(':').unwrap_or((key, ""));
+        (|| -> Result<Self, String> {
+            let settings = if options.is_empty() {
+                KeySettings::from(global_settings)
+            } else {
+                let mut settings = KeySettings::default();
+                settings.ignore_blanks = settings.apply_options(options)?;
+                settings
+            };
+            Ok(Self {
+                path: FieldPath::parse(path)?,
+                settings,
+            })
+        })()
+        .map_err(|msg| {
+            SortError::ParseKeyError {
+                key: key.to_owned(),
+                msg,
+            }
+            .into()
+        })
+    }
+
+    fn is_random(&self) -> bool {
+        self.settings.mode == SortMode::Random
+    }
+}
+
+/// The order records are sorted in
+struct RecordOrder {
+    keys: Vec<RecordKey>,
+    salt: [u8; 16],
+}
+
+impl RecordOrder {
+    /// Without keys the whole record is compared, with the global options
+    fn new(mut keys: Vec<RecordKey>, settings: &GlobalSettings) -> Self {
+        if keys.is_empty() {
+            keys.push(RecordKey {
+                path: FieldPath(Vec::new()),
+                settings: KeySettings::from(settings),
+            });
+        }
+        let salt = match settings.salt {
+            Some(salt) => salt,
+            None if keys.iter().any(RecordKey::is_random) => get_rand_string(),
+            None => [0; 16],
+        };
+        Self { keys, salt }
+    }
+
+    fn compare(&self, a: &Value, b: &Value) -> Ordering {
+        for key in &self.keys {
+            let cmp = compare_field(
+                key.path.get(a).unwrap_or(&Value::Null),
+                key.path.get(b).unwrap_or(&Value::Null),
+                &key.settings,
+                &self.salt
+            );
+            if cmp != Ordering::Equal {
+                return if key.settings.reverse {
+                    cmp.reverse()
+                } else {
+                    cmp
+                };
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+/// Compare two field values. Numbers compare as numbers in every numeric
+/// mode; anything else is compared by its text, as a line would be, except
+/// in the default mode, where only strings are and other values keep the
+/// ordering of `sort-object`.
+fn compare_field(a: &Value, b: &Value, settings: &KeySettings, salt: &[u8]) -> Ordering {
+    let numeric = matches!(
+        settings.mode,
+        SortMode::Numeric | SortMode::HumanNumeric | SortMode::GeneralNumeric
+    );
+    match (a, b) {
+        (Value::Number(_), Value::Number(_)) if numeric => return compare_values(a, b),
+        (Value::String(_), Value::String(_)) => {}
+        _ if settings.mode == SortMode::Default => return compare_values(a, b),
+        _ => {}
+    }
+
+    let (a_text, b_text) = (value_text(a), value_text(b));
+    let (mut a, mut b) = (a_text.as_bytes(), b_text.as_bytes());
+    if settings.ignore_blanks {
+        a = a.trim_ascii_start();
+        b = b.trim_ascii_start();
+    }
+    let text_cmp = || {
+        custom_str_cmp(
+            a,
+            b,
+            settings.ignore_non_printing,
+            settings.dictionary_order,
+            settings.ignore_case
+        )
+    };
+
+    match settings.mode {
+        SortMode::Numeric | SortMode::HumanNumeric => {
+            let parse_settings = NumInfoParseSettings {
+                accept_si_units: settings.mode == SortMode::HumanNumeric,
+                ..Default::default()
+            };
+            let (a_info, a_range) = NumInfo::parse(a, &parse_settings);
+            let (b_info, b_range) = NumInfo::parse(b, &parse_settings);
+            let (a, b) = ((&a[a_range], &a_info), (&b[b_range], &b_info));
+            if settings.mode == SortMode::Numeric {
+                numeric_str_cmp(a, b)
+            } else {
+                human_numeric_str_cmp(a, b)
+            }
+        }
+        SortMode::GeneralNumeric => general_numeric_compare(
+            &general_bd_parse(&a[get_leading_gen(a)]),
+            &general_bd_parse(&b[get_leading_gen(b)])
+        ),
+        SortMode::Month => month_compare(a, b),
+        SortMode::Version => version_cmp(a, b),
+        SortMode::Random => match text_cmp() {
+            Ordering::Equal => Ordering::Equal,
+            _ => random_shuffle(a, b, salt),
+        },
+        SortMode::Default => text_cmp(),
+    }
+}
+
+/// The text a value is compared by: a string's contents, nothing for null,
+/// and the JSON of anything else
+fn value_text(value: &Value) -> Cow<'_, str> {
+    match value {
+        Value::Null => Cow::Borrowed(""),
+        Value::String(s) => Cow::Borrowed(s),
+        other => Cow::Owned(other.to_string()),
+    }
+}
+
+/// Roughly the memory `value` takes, to cut chunks of `--buffer-size`
+fn value_size(value: &Value) -> usize {
+    size_of::<Value>()
+        + match value {
+            Value::String(s) => s.len(),
+            Value::Array(items) => items.iter().map(value_size).sum(),
+            Value::Object(map) => map.iter().map(|(key, value)| key.len() + value_size(value)).sum(),
+            _ => 0,
+        }
+}
+
+/// Sort the records of `files` and write them, in the document of the first
+/// file, to `output`. The records of all files are sorted together.
+pub fn sort_records(
+    files: &[OsString],
+    keys: Vec<RecordKey>,
+    records_field: Option<&str>,
+    settings: &GlobalSettings,
+    output: Output,
+    tmp_dir: &mut TmpDirWrapper
+) -> SGResult<()> {
+    let order = RecordOrder::new(keys, settings);
+    if settings.compress_prog.is_some() {
+        sort_with::<WriteableCompressedTmpFile>(files, &order, records_field, settings, output, tmp_dir)
+    } else {
+        sort_with::<WriteablePlainTmpFile>(files, &order, records_field, settings, output, tmp_dir)
+    }
+}
+
+fn sort_with<Tmp: WriteableTmpFile>(
+    files: &[OsString],
+    order: &RecordOrder,
+    records_field: Option<&str>,
+    settings: &GlobalSettings,
+    output: Output,
+    tmp_dir: &mut TmpDirWrapper
+) -> SGResult<()> {
+    let mut envelope = None;
+    let mut chunks = Chunks::<Tmp>::new();
+    for file in files {
+        let mut stream = RecordStream::new(open(file)?, records_field);
+        for record in stream.by_ref() {
+            chunks.push(record, order, settings, tmp_dir)?;
+        }
+        let document = stream.finish().map_err(|e| input_error(file, &e))?;
+        envelope.get_or_insert(document);
+    }
+    let mut envelope = envelope.unwrap_or_default();
+
+    let Chunks {
+        runs,
+        mut chunk,
+        mut count,
+        ..
+    } = chunks;
+    chunk.par_sort_by(|a, b| order.compare(a, b));
+    let mut unique = settings.unique;
+    if unique && runs.is_empty() {
+        chunk.dedup_by(|b, a| order.compare(a, b) == Ordering::Equal);
+        count = chunk.len();
+        unique = false;
+    }
+    let runs = merge_runs::<Tmp>(runs, order, settings, tmp_dir)?;
+    let mut merged = Merger::new(runs, chunk, order, unique)?;
+    if unique && envelope.has_count() {
+        // The count comes before the records, so the unique ones are counted
+        // on their way through one more temporary file
+        count = 0;
+        let run = write_run::<Tmp>(merged.by_ref().inspect(|_| count += 1), settings, tmp_dir)?;
+        merged.finish()?;
+        merged = Merger::new(vec![run], Vec::new(), order, false)?;
+    }
+    envelope.set_count(count);
+
+    let output_name = output
+        .as_output_name()
+        .unwrap_or(OsStr::new("standard output"))
+        .to_owned();
+    let mut writer = output.into_write();
+    let written = envelope
+        .write(&mut writer, merged.by_ref())
+        .and_then(|()| writer.flush());
+    merged.finish()?;
+    written.map_err_context(|| translate!("sort-error-write-failed", "output" => output_name.maybe_quote()))
+}
+
+fn input_error(file: &OsStr, error: &str) -> Box<dyn SGError> {
+    SGSimpleError::new(
+        2,
+        translate!("sort-obj-invalid-input", "file" => file.maybe_quote(), "error" => error)
+    )
+}
+
+/// Records cut into sorted runs of about `--buffer-size` bytes as they are
+/// read. Full chunks are written to temporary files; the last one stays in
+/// memory.
+struct Chunks<Tmp: WriteableTmpFile> {
+    runs: Vec<Tmp::Closed>,
+    chunk: Vec<Value>,
+    chunk_size: usize,
+    /// Number of records read
+    count: usize,
+}
+
+impl<Tmp: WriteableTmpFile> Chunks<Tmp> {
+    fn new() -> Self {
+        Self {
+            runs: Vec::new(),
+            chunk: Vec::new(),
+            chunk_size: 0,
+            count: 0,
+        }
+    }
+
+    fn push(
+        &mut self,
+        record: Value,
+        order: &RecordOrder,
+        settings: &GlobalSettings,
+        tmp_dir: &mut TmpDirWrapper
+    ) -> SGResult<()> {
+        self.count += 1;
+        self.chunk_size += value_size(&record);
+        self.chunk.push(record);
+        if self.chunk_size >= settings.buffer_size {
+            self.chunk.par_sort_by(|a, b| order.compare(a, b));
+            let run = write_run::<Tmp>(self.chunk.drain(..), settings, tmp_dir)?;
+            self.runs.push(run);
+            self.chunk_size = 0;
+        }
+        Ok(())
+    }
+}
+
+/// Write sorted records to a new temporary file, one JSON value per line
+fn write_run<Tmp: WriteableTmpFile>(
+    records: impl Iterator<Item = Value>,
+    settings: &GlobalSettings,
+    tmp_dir: &mut TmpDirWrapper
+) -> SGResult<Tmp::Closed> {
+    let (file, path) = tmp_dir.next_file()?;
+    let mut tmp_file = Tmp::create((file, path.clone()), settings.compress_prog.as_deref())?;
+    for record in records {
+        write_record(tmp_file.as_write(), &record, &path)?;
+    }
+    tmp_file.finished_writing()
+}
+
+fn write_record(writer: &mut impl Write, record: &Value, path: &Path) -> SGResult<()> {
+    serde_json::to_writer(&mut *writer, record)
+        .map_err(std::io::Error::from)
+        .and_then(|()| writer.write_all(b"\n"))
+        .map_err_context(|| translate!("sort-error-write-failed", "output" => path.maybe_quote()))
+}
+
+/// Merge sorted runs `--batch-size` at a time until at most that many are
+/// left for the final merge
+fn merge_runs<Tmp: WriteableTmpFile>(
+    mut runs: Vec<Tmp::Closed>,
+    order: &RecordOrder,
+    settings: &GlobalSettings,
+    tmp_dir: &mut TmpDirWrapper
+) -> SGResult<Vec<Tmp::Closed>> {
+    while runs.len() > settings.merge_batch_size {
+        let mut merged = Vec::new();
+        let mut remaining = runs.into_iter().peekable();
+        while remaining.peek().is_some() {
+            let batch: Vec<_> = remaining.by_ref().take(settings.merge_batch_size).collect();
+            let mut merger = Merger::new(batch, Vec::new(), order, false)?;
+            let run = write_run::<Tmp>(merger.by_ref(), settings, tmp_dir)?;
+            merger.finish()?;
+            merged.push(run);
+        }
+        runs = merged;
+    }
+    Ok(runs)
+}
+
+/// The next record of a source and the source it came from
+struct Head {
+    record: Value,
+    source: usize,
+}
+
+/// Orders heads for a max-heap so the smallest record comes out first,
+/// and of equal records the one of the earlier source, keeping the sort
+/// stable
+struct HeadOrder<'a> {
+    order: &'a RecordOrder,
+}
+
+impl Compare<Head> for HeadOrder<'_> {
+    fn compare(&self, a: &Head, b: &Head) -> Ordering {
+        self.order
+            .compare(&a.record, &b.record)
+            .then(a.source.cmp(&b.source))
+            .reverse()
+    }
+}
+
+/// Reads a reopened run, keeping it at hand to be cleaned up
+struct RunReader<M>(M);
+
+impl<M: MergeInput> Read for RunReader<M> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.as_read().read(buf)
+    }
+}
+
+/// Where merged records come from: a run in a temporary file, or the last
+/// chunk, which was never written out
+enum Source<M> {
+    Run(BufReader<RunReader<M>>),
+    Memory(std::vec::IntoIter<Value>),
+    Done,
+}
+
+impl<M: MergeInput> Source<M> {
+    fn next(&mut self, line: &mut String) -> SGResult<Option<Value>> {
+        let reader = match self {
+            Self::Run(reader) => reader,
+            Self::Memory(records) => return Ok(records.next()),
+            Self::Done => return Ok(None),
+        };
+        line.clear();
+        let read = reader
+            .read_line(line)
+            .map_err_context(|| translate!("sort-obj-tmp-read-failed"))?;
+        if read == 0 {
+            return self.finish().map(|()| None);
+        }
+        serde_json::from_str(line)
+            .map(Some)
+            .map_err(std::io::Error::from)
+            .map_err_context(|| translate!("sort-obj-tmp-read-failed"))
+    }
+
+    /// Clean up a run, deleting its file
+    fn finish(&mut self) -> SGResult<()> {
+        match std::mem::replace(self, Self::Done) {
+            Self::Run(reader) => reader.into_inner().0.finished_reading(),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Yields the records of sorted runs and the last chunk in their merged
+/// order, without duplicates under `-u`. A run that fails to read ends the
+/// records early; [`Merger::finish`] reports why.
+struct Merger<'a, M: MergeInput> {
+    order: &'a RecordOrder,
+    heap: BinaryHeap<Head, HeadOrder<'a>>,
+    sources: Vec<Source<M>>,
+    line: String,
+    unique: bool,
+    /// Under `-u`, the first record past the last one yielded
+    pending: Option<Value>,
+    error: Option<Box<dyn SGError>>,
+}
+
+impl<'a, M: MergeInput> Merger<'a, M> {
+    fn new<C: ClosedTmpFile<Reopened = M>>(
+        runs: Vec<C>,
+        chunk: Vec<Value>,
+        order: &'a RecordOrder,
+        unique: bool
+    ) -> SGResult<Self> {
+        let mut sources = Vec::with_capacity(runs.len() + 1);
+        for run in runs {
+            sources.push(Source::Run(BufReader::new(RunReader(run.reopen()?))));
+        }
+        sources.push(Source::Memory(chunk.into_iter()));
+        let mut merger = Self {
+            order,
+            heap: BinaryHeap::from_vec_cmp(Vec::new(), HeadOrder { order }),
+            sources,
+            line: String::new(),
+            unique,
+            pending: None,
+            error: None,
+        };
+        for source in 0..merger.sources.len() {
+            if let Some(record) = merger.sources[source].next(&mut merger.line)? {
+                merger.heap.push(Head { record, source });
+            }
+        }
+        Ok(merger)
+    }
+
+    fn next_sorted(&mut self) -> SGResult<Option<Value>> {
+        let Some(Head { record, source }) = self.heap.pop() else {
+            return Ok(None);
+        };
+        if let Some(next) = self.sources[source].next(&mut self.line)? {
+            self.heap.push(Head { record: next, source });
+        }
+        Ok(Some(record))
+    }
+
+    fn next_unique(&mut self) -> SGResult<Option<Value>> {
+        let record = match self.pending.take() {
+            Some(record) => record,
+            None => match self.next_sorted()? {
+                Some(record) => record,
+                None => return Ok(None),
+            },
+        };
+        while let Some(next) = self.next_sorted()? {
+            if self.order.compare(&record, &next) != Ordering::Equal {
+                self.pending = Some(next);
+                break;
+            }
+        }
+        Ok(Some(record))
+    }
+
+    /// Report a failed read, and clean up the runs
+    fn finish(mut self) -> SGResult<()> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        for source in &mut self.sources {
+            source.finish()?;
+        }
+        Ok(())
+    }
+}
+
+impl<M: MergeInput> Iterator for Merger<'_, M> {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        if self.error.is_some() {
+            return None;
+        }
+        let next = if self.unique {
+            self.next_unique()
+        } else {
+            self.next_sorted()
+        };
+        next.unwrap_or_else(|error| {
+            self.error = Some(error);
+            None
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sorted(keys: &str, settings: &GlobalSettings, mut records: Vec<Value>) -> Vec<Value> {
+        let keys = RecordKey::parse_list(keys, settings).unwrap();
+        let order = RecordOrder::new(keys, settings);
+        records.sort_by(|a, b| order.compare(a, b));
+        records
+    }
+
+    #[test]
+    fn test_key_options() {
+        let settings = GlobalSettings::default();
+        let records = vec![json!({"v": "10"}), json!({"v": "9"}), json!({"v": "-1"})];
+        assert_eq!(
+            sorted("v", &settings, records.clone()),
+            vec![json!({"v": "-1"}), json!({"v": "10"}), json!({"v": "9"})]
+        );
+        assert_eq!(
+            sorted("v:nr", &settings, records),
+            vec![json!({"v": "10"}), json!({"v": "9"}), json!({"v": "-1"})]
+        );
+    }
+
+    #[test]
+    fn test_mixed_values() {
+        let settings = GlobalSettings::default();
+        let records = vec![json!({"v": "a"}), json!({"v": 2}), json!({}), json!({"v": 1.5})];
+        assert_eq!(
+            sorted("v", &settings, records),
+            vec![json!({}), json!({"v": 1.5}), json!({"v": 2}), json!({"v": "a"})]
+        );
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        let settings = GlobalSettings::default();
+        assert!(RecordKey::parse_list("size:x", &settings).is_err());
+        assert!(RecordKey::parse_list("size:nM", &settings).is_err());
+        assert!(RecordKey::parse_list("name,", &settings).is_err());
+    }
+}
@@ -11,6 +11,7 @@ mod custom_str_cmp;
 mod ext_sort;
 mod merge;
 mod numeric_str_cmp;
+mod records;
 mod tmp_dir;
 
 use bigdecimal::BigDecimal;
@@ -103,6 +104,8 @@ mod options {
     pub const TMP_DIR: &str = "temporary-directory";
     pub const COMPRESS_PROG: &str = "compress-program";
     pub const BATCH_SIZE: &str = "batch-size";
+    pub const OBJ: &str = "obj";
+    pub const RECORDS: &str = "records";
 
     pub const FILES: &str = "files";
 }
@@ -459,6 +462,30 @@ impl KeySettings {
         self.ignore_non_printing = true;
         Ok(())
     }
+
+    /// Applies the key `options` letters, returning if the 'b'-flag (ignore blanks) was present.
+    fn apply_options(&mut self, options: &str) -> Result<bool, String> {
+        let mut ignore_blanks = false;
+        for option in options.chars() {
+            match option {
+                'M' => self.set_sort_mode(SortMode::Month)?,
+                'b' => ignore_blanks = true,
+                'd' => self.set_dictionary_order()?,
+                'f' => self.ignore_case = true,
+                'g' => self.set_sort_mode(SortMode::GeneralNumeric)?,
+                'h' => self.set_sort_mode(SortMode::HumanNumeric)?,
+                'i' => self.set_ignore_non_printing()?,
+                'n' => self.set_sort_mode(SortMode::Numeric)?,
+                'R' => self.set_sort_mode(SortMode::Random)?,
+                'r' => self.reverse = true,
+                'V' => self.set_sort_mode(SortMode::Version)?,
+                c => {
+                    return Err(translate!("sort-invalid-option", "option" => c));
+                }
+            }
+        }
+        Ok(ignore_blanks)
+    }
 }
 
 impl From<&GlobalSettings> for KeySettings {
@@ -831,39 +858,14 @@ impl FieldSelector {
         (from, from_options): (&str, &str),
         to: Option<(&str, &str)>
     ) -> Result<Self, String> {
-        /// Applies `options` to `key_settings`, returning if the 'b'-flag (ignore blanks) was present.
-        fn parse_key_settings(
-            options: &str,
-            key_settings: &mut KeySettings
-        ) -> Result<bool, String> {
-            let mut ignore_blanks = false;
-            for option in options.chars() {
-                match option {
-                    'M' => key_settings.set_sort_mode(SortMode::Month)?,
-                    'b' => ignore_blanks = true,
-                    'd' => key_settings.set_dictionary_order()?,
-                    'f' => key_settings.ignore_case = true,
-                    'g' => key_settings.set_sort_mode(SortMode::GeneralNumeric)?,
-                    'h' => key_settings.set_sort_mode(SortMode::HumanNumeric)?,
-                    'i' => key_settings.set_ignore_non_printing()?,
-                    'n' => key_settings.set_sort_mode(SortMode::Numeric)?,
-                    'R' => key_settings.set_sort_mode(SortMode::Random)?,
-                    'r' => key_settings.reverse = true,
-                    'V' => key_settings.set_sort_mode(SortMode::Version)?,
-                    c => {
-                        return Err(translate!("sort-invalid-option", "option" => c));
-                    }
-                }
-            }
-            Ok(ignore_blanks)
-        }
-
         let mut key_settings = KeySettings::default();
-        let from = parse_key_settings(from_options, &mut key_settings)
+        let from = key_settings
+            .apply_options(from_options)
             .map(|ignore_blanks| KeyPosition::new(from, 1, ignore_blanks))??;
         let to = if let Some((to, to_options)) = to {
             Some(
-                parse_key_settings(to_options, &mut key_settings)
+                key_settings
+                    .apply_options(to_options)
                     .map(|ignore_blanks| KeyPosition::new(to, 0, ignore_blanks))??
             )
         } else {
@@ -1268,6 +1270,24 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         ));
     }
 
+    if matches.get_flag(options::OBJ) {
+        let mut keys = Vec::new();
+        for value in matches.get_many::<String>(options::KEY).into_iter().flatten() {
+            keys.extend(records::RecordKey::parse_list(value, &settings)?);
+        }
+        let output = Output::new(matches.get_one::<OsString>(options::OUTPUT))?;
+        let result = records::sort_records(
+            &files,
+            keys,
+            matches.get_one::<String>(options::RECORDS).map(String::as_str),
+            &settings,
+            output,
+            &mut tmp_dir
+        );
+        tmp_dir.wait_if_signal();
+        return result;
+    }
+
     if let Some(arg) = matches.get_one::<OsString>(options::SEPARATOR) {
         let mut separator = arg.to_str().ok_or_else(|| {
             SGUsageError::new(
@@ -1565,6 +1585,27 @@ pub fn sg_app() -> Command {
             .help(translate!("sort-help-debug"))
             .action(ArgAction::SetTrue)
     )
+    .arg(
+        Arg::new(options::OBJ)
+            .long(options::OBJ)
+            .help(translate!("sort-help-obj"))
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all([
+                options::check::CHECK,
+                options::check::CHECK_SILENT,
+                options::MERGE,
+                options::SEPARATOR,
+                options::ZERO_TERMINATED,
+                options::DEBUG,
+            ])
+    )
+    .arg(
+        Arg::new(options::RECORDS)
+            .long(options::RECORDS)
+            .help(translate!("sort-help-records"))
+            .value_name("FIELD")
+            .requires(options::OBJ)
+    )
     .arg(
         Arg::new(options::FILES)
             .action(ArgAction::Append)
@@ -22,6 +22,8 @@ split-error-suffix-too-small = the suffix length needs to be at least { $length
 split-error-multi-character-separator = multi-character separator { $separator }
 split-error-multiple-separator-characters = multiple separator characters specified
 split-error-filter-with-kth-chunk = --filter does not process a chunk extracted to stdout
+split-error-obj-with-kth-chunk = --obj does not report a chunk extracted to stdout
+split-error-invalid-records = invalid records: { $error }
 split-error-invalid-io-block-size = invalid IO block size: { $size }
 split-error-not-supported = --filter is currently not supported in this platform
 split-error-invalid-number-of-chunks = invalid number of chunks: { $chunks }
@@ -59,3 +61,5 @@ split-help-hex-suffixes = same as -x, but allow setting the start value
 split-help-suffix-length = generate suffixes of length N (default 2)
 split-help-verbose = print a diagnostic just before each output file is opened
 split-help-separator = use SEP instead of newline as the record separator; '\\0' (zero) specifies the NUL character
+split-help-records = split the records of stardust (JSON) input, at field FIELD if given; -l and -n count records
+split-help-ndjson = with --records, write one record per line instead of a JSON document
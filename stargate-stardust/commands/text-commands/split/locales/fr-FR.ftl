@@ -22,6 +22,8 @@ split-error-suffix-too-small = la longueur du suffixe doit être au moins { $len
 split-error-multi-character-separator = séparateur multi-caractères { $separator }
 split-error-multiple-separator-characters = plusieurs caractères de séparateur spécifiés
 split-error-filter-with-kth-chunk = --filter ne traite pas un chunk extrait vers stdout
+split-error-obj-with-kth-chunk = --obj ne rend pas compte d'un morceau extrait vers la sortie standard
+split-error-invalid-records = enregistrements invalides : { $error }
 split-error-invalid-io-block-size = taille de bloc IO invalide : { $size }
 split-error-not-supported = --filter n'est actuellement pas supporté sur cette plateforme
 split-error-invalid-number-of-chunks = nombre de chunks invalide : { $chunks }
@@ -59,3 +61,5 @@ split-help-hex-suffixes = identique à -x, mais permet de définir la valeur de
 split-help-suffix-length = générer des suffixes de longueur N (par défaut 2)
 split-help-verbose = afficher un diagnostic juste avant l'ouverture de chaque fichier de sortie
 split-help-separator = utiliser SEP au lieu de nouvelle ligne comme séparateur d'enregistrement ; '\\0' (zéro) spécifie le caractère NUL
+split-help-records = découper les enregistrements d'une entrée stardust (JSON), au champ FIELD s'il est donné ; -l et -n comptent des enregistrements
+split-help-ndjson = avec --records, écrire un enregistrement par ligne au lieu d'un document JSON
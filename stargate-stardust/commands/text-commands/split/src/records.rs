@@ -0,0 +1,163 @@
+//! Splitting a stardust array for `split --records`.
+//!
+//! The input is read whole and its records are dealt out by the usual
+//! strategies, counting records instead of lines: `-l K` puts K records in
+//! each file, `-n N` (or `l/N`) spreads them evenly over N files and `r/N`
+//! deals them round robin. Each file is valid on its own, either the input
+//! document holding its share of the records or, with `--ndjson`, one
+//! record per line.
+
+use std::io::{self, Write};
+
+use serde_json::Value;
+use sgcore::display::Quotable;
+use sgcore::error::{SGResult, SGSimpleError};
+use sgcore::json_adapter::{put_records, read_input_value, take_records};
+use sgcore::translate;
+use sgcore::wire;
+
+use crate::filenames::FilenameIterator;
+use crate::strategy::{NumberType, Strategy};
+use crate::{Settings, custom_write_all, ignorable_io_error};
+
+/// How `--records` reads its input and writes each file
+pub struct RecordOptions {
+    /// Field holding the records, found as other stardust commands find it
+    /// when not given
+    pub field: Option<String>,
+    pub ndjson: bool,
+}
+
+pub fn split_records(settings: &Settings, options: &RecordOptions) -> SGResult<()> {
+    let invalid = |e: String| {
+        SGSimpleError::new(1, translate!("split-error-invalid-records", "error" => e))
+    };
+    let mut document = read_input_value(&settings.input).map_err(invalid)?;
+    let field = options.field.as_deref();
+    let records = take_records(&mut document, field).map_err(invalid)?;
+
+    let (groups, kth_chunk) = deal(records, &settings.strategy, settings.elide_empty_files);
+    let file = RecordFile {
+        document,
+        options,
+    };
+
+    if let Some(k) = kth_chunk {
+        let Some(group) = groups.into_iter().nth(k as usize - 1) else {
+            return Ok(());
+        };
+        let mut stdout = io::stdout().lock();
+        return file
+            .write_stdout(group, &mut stdout)
+            .and_then(|()| stdout.flush())
+            .map_err(|e| SGSimpleError::new(1, e.to_string()));
+    }
+
+    let mut filename_iterator = FilenameIterator::new(&settings.prefix, &settings.suffix)?;
+    for group in groups {
+        let filename = filename_iterator.next().ok_or_else(|| {
+            SGSimpleError::new(1, translate!("split-error-output-file-suffixes-exhausted"))
+        })?;
+        if settings.verbose {
+            println!("creating file {}", filename.quote());
+        }
+        let count = group.len() as u64;
+        let mut writer = settings.instantiate_current_writer(&filename, true)?;
+        custom_write_all(&file.encode(group)?, &mut writer, settings)?;
+        match writer.flush() {
+            Err(e) if !ignorable_io_error(&e, settings) => return Err(e.into()),
+            _ => {}
+        }
+        if let Some(report) = &settings.report {
+            report.set_records(&filename, count);
+        }
+    }
+    Ok(())
+}
+
+/// Share `records` out between files following `strategy`, and say which
+/// of them alone goes to stdout under `K/N`
+fn deal(
+    records: Vec<Value>,
+    strategy: &Strategy,
+    elide_empty_files: bool
+) -> (Vec<Vec<Value>>, Option<u64>) {
+    let (num_chunks, kth_chunk, round_robin) = match *strategy {
+        Strategy::Lines(chunk_size) => {
+            let mut groups = Vec::new();
+            let mut records = records.into_iter().peekable();
+            while records.peek().is_some() {
+                groups.push(records.by_ref().take(chunk_size as usize).collect());
+            }
+            return (groups, None);
+        }
+        Strategy::Number(NumberType::Bytes(n) | NumberType::Lines(n)) => (n, None, false),
+        Strategy::Number(NumberType::KthBytes(k, n) | NumberType::KthLines(k, n)) => {
+            (n, Some(k), false)
+        }
+        Strategy::Number(NumberType::RoundRobin(n)) => (n, None, true),
+        Strategy::Number(NumberType::KthRoundRobin(k, n)) => (n, Some(k), true),
+        Strategy::Bytes(_) | Strategy::LineBytes(_) => {
+            unreachable!("--records conflicts with -b and -C")
+        }
+    };
+
+    let num_chunks = num_chunks as usize;
+    let mut groups: Vec<Vec<Value>> = (0..num_chunks).map(|_| Vec::new()).collect();
+    if round_robin {
+        for (i, record) in records.into_iter().enumerate() {
+            groups[i % num_chunks].push(record);
+        }
+    } else {
+        let (base, remainder) = (records.len() / num_chunks, records.len() % num_chunks);
+        let mut records = records.into_iter();
+        for (i, group) in groups.iter_mut().enumerate() {
+            group.extend(records.by_ref().take(base + usize::from(i < remainder)));
+        }
+    }
+    if elide_empty_files && kth_chunk.is_none() {
+        groups.retain(|group| !group.is_empty());
+    }
+    (groups, kth_chunk)
+}
+
+/// The input document with its records taken out, to be written again
+/// around each file's share
+struct RecordFile<'a> {
+    document: Value,
+    options: &'a RecordOptions,
+}
+
+impl RecordFile<'_> {
+    /// The whole content of a file holding `records`, as JSON text
+    fn encode(&self, records: Vec<Value>) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        if self.options.ndjson {
+            for record in &records {
+                serde_json::to_writer(&mut bytes, record)?;
+                bytes.push(b'\n');
+            }
+        } else {
+            serde_json::to_writer(&mut bytes, &self.with_records(records)?)?;
+            bytes.push(b'\n');
+        }
+        Ok(bytes)
+    }
+
+    /// Write the `K/N` chunk, in the encoding stdout was asked for unless
+    /// it is NDJSON
+    fn write_stdout(&self, records: Vec<Value>, out: &mut impl Write) -> io::Result<()> {
+        if self.options.ndjson {
+            out.write_all(&self.encode(records)?)
+        } else {
+            wire::write_value(out, &self.with_records(records)?, false)
+        }
+    }
+
+    fn with_records(&self, records: Vec<Value>) -> io::Result<Value> {
+        let mut document = self.document.clone();
+        put_records(&mut document, self.options.field.as_deref(), records)
+            .map_err(io::Error::other)?;
+        Ok(document)
+    }
+}
@@ -0,0 +1,172 @@
+//! The `--obj` report of the chunks `split` wrote.
+//!
+//! Every writer [`crate::Settings`] hands out is wrapped in a
+//! [`ChunkCounter`] that tallies what passes through it, so the report
+//! covers all strategies, `--filter` included, without each of them
+//! keeping its own books.
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use serde_json::{Map, Value, json};
+use sgcore::stardust_output::{FORMAT_BYTES, Property, array_schema, object_schema};
+
+/// Most bytes of a chunk's first line kept for the report
+const FIRST_LINE_MAX: usize = 1024;
+
+/// What went into one output chunk
+struct ChunkStats {
+    path: String,
+    bytes: u64,
+    /// Number of separators written
+    separators: u64,
+    last_byte: Option<u8>,
+    first_line: Vec<u8>,
+    first_line_done: bool,
+    /// Number of records written by `--records`
+    records: Option<u64>,
+}
+
+impl ChunkStats {
+    fn to_value(&self, separator: u8) -> Value {
+        let unterminated = self.last_byte.is_some_and(|byte| byte != separator);
+        let mut chunk = Map::new();
+        chunk.insert("path".to_string(), self.path.clone().into());
+        chunk.insert("bytes".to_string(), self.bytes.into());
+        chunk.insert(
+            "lines".to_string(),
+            (self.separators + u64::from(unterminated)).into()
+        );
+        chunk.insert(
+            "first_line".to_string(),
+            if self.bytes == 0 {
+                Value::Null
+            } else {
+                String::from_utf8_lossy(&self.first_line).into()
+            }
+        );
+        if let Some(records) = self.records {
+            chunk.insert("records".to_string(), records.into());
+        }
+        Value::Object(chunk)
+    }
+}
+
+/// The chunks written so far, in the order they were created
+#[derive(Clone)]
+pub struct Report {
+    chunks: Rc<RefCell<Vec<ChunkStats>>>,
+    separator: u8,
+}
+
+impl Report {
+    pub fn new(separator: u8) -> Self {
+        Self {
+            chunks: Rc::default(),
+            separator,
+        }
+    }
+
+    /// Count what is written to `inner`, the chunk `path`. A chunk that is
+    /// re-opened for appending keeps adding to its earlier counts.
+    pub fn track(&self, path: &str, is_new: bool, inner: Box<dyn Write>) -> Box<dyn Write> {
+        let mut chunks = self.chunks.borrow_mut();
+        let index = match chunks.iter().position(|chunk| chunk.path == path) {
+            Some(index) if !is_new => index,
+            _ => {
+                chunks.push(ChunkStats {
+                    path: path.to_string(),
+                    bytes: 0,
+                    separators: 0,
+                    last_byte: None,
+                    first_line: Vec::new(),
+                    first_line_done: false,
+                    records: None,
+                });
+                chunks.len() - 1
+            }
+        };
+        Box::new(ChunkCounter {
+            inner,
+            report: self.clone(),
+            index,
+        })
+    }
+
+    /// Note how many records `--records` put in the chunk `path`
+    pub fn set_records(&self, path: &str, records: u64) {
+        let mut chunks = self.chunks.borrow_mut();
+        if let Some(chunk) = chunks.iter_mut().rev().find(|chunk| chunk.path == path) {
+            chunk.records = Some(records);
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        let files: Vec<Value> = self
+            .chunks
+            .borrow()
+            .iter()
+            .map(|chunk| chunk.to_value(self.separator))
+            .collect();
+        json!({
+            "count": files.len(),
+            "files": files,
+        })
+    }
+}
+
+/// A chunk's writer that records what it writes in a [`Report`]
+struct ChunkCounter {
+    inner: Box<dyn Write>,
+    report: Report,
+    index: usize,
+}
+
+impl Write for ChunkCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        let written = &buf[..n];
+        let separator = self.report.separator;
+        let mut chunks = self.report.chunks.borrow_mut();
+        let chunk = &mut chunks[self.index];
+
+        chunk.bytes += n as u64;
+        chunk.separators += memchr::memchr_iter(separator, written).count() as u64;
+        if let Some(&last) = written.last() {
+            chunk.last_byte = Some(last);
+        }
+        if !chunk.first_line_done {
+            let end = memchr::memchr(separator, written);
+            let line = &written[..end.unwrap_or(written.len())];
+            let room = FIRST_LINE_MAX - chunk.first_line.len();
+            chunk.first_line.extend_from_slice(&line[..line.len().min(room)]);
+            chunk.first_line_done = end.is_some() || chunk.first_line.len() == FIRST_LINE_MAX;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Schema of the `--obj` report
+pub fn schema() -> Value {
+    let file = object_schema(vec![
+        Property::new("path", "string").description("Name of the output file"),
+        Property::new("bytes", "integer").format(FORMAT_BYTES).description("Number of bytes written to it"),
+        Property::new("lines", "integer")
+            .description("Number of lines (records with -t) written to it"),
+        Property::new("first_line", json!({"type": ["string", "null"]})).description(
+            "Its first line without the separator, at most 1024 bytes; null when empty"
+        ),
+        Property::new("records", "integer")
+            .description("Number of stardust records written to it, with --records")
+            .optional(),
+    ]);
+    sgcore::schema!(
+        "count" => "integer", "Number of output files";
+        "files" => array_schema(file), "Output files in the order they were created";
+    )
+}
@@ -3,9 +3,13 @@
 mod filenames;
 mod number;
 mod platform;
+mod records;
+mod report;
 mod strategy;
 
 use crate::filenames::{FilenameIterator, Suffix, SuffixError};
+use crate::records::RecordOptions;
+use crate::report::Report;
 use crate::strategy::{NumberType, Strategy, StrategyError};
 use clap::{Arg, ArgAction, ArgMatches, Command, ValueHint, parser::ValueSource};
 use std::env;
@@ -17,6 +21,7 @@ use std::path::Path;
 use thiserror::Error;
 use sgcore::display::Quotable;
 use sgcore::error::{FromIo, SGIoError, SGResult, SGSimpleError, SGUsageError};
+use sgcore::stardust_output::{self, StardustOutputOptions};
 use sgcore::translate;
 
 use sgcore::parser::parse_size::parse_size_u64;
@@ -39,6 +44,8 @@ static OPT_VERBOSE: &str = "verbose";
 static OPT_SEPARATOR: &str = "separator";
 static OPT_ELIDE_EMPTY_FILES: &str = "elide-empty-files";
 static OPT_IO_BLKSIZE: &str = "-io-blksize";
+static OPT_RECORDS: &str = "records";
+static OPT_NDJSON: &str = "ndjson";
 
 static ARG_INPUT: &str = "input";
 static ARG_PREFIX: &str = "prefix";
@@ -49,8 +56,18 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
     sgcore::pledge::apply_pledge(&["stdio", "rpath", "wpath", "cpath"])?;
 
+    if stardust_output::self_describe(&matches, report::schema())? {
+        return Ok(());
+    }
+
     match Settings::from(&matches, obs_lines.as_deref()) {
-        Ok(settings) => split(&settings),
+        Ok(settings) => {
+            split(&settings)?;
+            if let Some(report) = &settings.report {
+                stardust_output::output(settings.stardust.clone(), report.to_value(), || Ok(()))?;
+            }
+            Ok(())
+        }
         Err(e) if e.requires_usage() => Err(SGUsageError::new(1, format!("{e}"))),
         Err(e) => Err(SGSimpleError::new(1, format!("{e}"))),
     }
@@ -182,7 +199,8 @@ fn handle_preceding_options(
             || &slice[2..] == OPT_FILTER
             || &slice[2..] == OPT_NUMBER
             || &slice[2..] == OPT_SUFFIX_LENGTH
-            || &slice[2..] == OPT_SEPARATOR;
+            || &slice[2..] == OPT_SEPARATOR
+            || &slice[2..] == stardust_output::ARG_FIELD;
     }
     *preceding_short_opt_req_value = slice == "-b"
         || slice == "-C"
@@ -197,7 +215,7 @@ fn handle_preceding_options(
 }
 
 pub fn sg_app() -> Command {
-    Command::new(sgcore::util_name())
+    let cmd = Command::new(sgcore::util_name())
         .version(sgcore::crate_version!())
         .help_template(sgcore::localized_help_template(sgcore::util_name()))
         .about(translate!("split-about"))
@@ -325,6 +343,7 @@ pub fn sg_app() -> Command {
             Arg::new(OPT_VERBOSE)
                 .long(OPT_VERBOSE)
                 .help(translate!("split-help-verbose"))
+                .conflicts_with(stardust_output::ARG_STARDUST_OUTPUT)
                 .action(ArgAction::SetTrue)
         )
         .arg(
@@ -342,6 +361,23 @@ pub fn sg_app() -> Command {
                 .alias(OPT_IO_BLKSIZE)
                 .hide(true)
         )
+        .arg(
+            Arg::new(OPT_RECORDS)
+                .long(OPT_RECORDS)
+                .require_equals(true)
+                .num_args(0..=1)
+                .default_missing_value("")
+                .value_name("FIELD")
+                .conflicts_with_all([OPT_BYTES, OPT_LINE_BYTES, OPT_SEPARATOR])
+                .help(translate!("split-help-records"))
+        )
+        .arg(
+            Arg::new(OPT_NDJSON)
+                .long(OPT_NDJSON)
+                .requires(OPT_RECORDS)
+                .help(translate!("split-help-ndjson"))
+                .action(ArgAction::SetTrue)
+        )
         .arg(
             Arg::new(ARG_INPUT)
                 .default_value("-")
@@ -352,7 +388,9 @@ pub fn sg_app() -> Command {
             Arg::new(ARG_PREFIX)
                 .default_value("x")
                 .value_parser(clap::value_parser!(OsString))
-        )
+        );
+
+    stardust_output::add_json_args(cmd)
 }
 
 /// Parameters that control how a file gets split.
@@ -379,6 +417,12 @@ struct Settings {
     /// created.
     elide_empty_files: bool,
     io_blksize: Option<u64>,
+    /// With `--records`, split the records of a stardust input instead of
+    /// its lines or bytes
+    records: Option<RecordOptions>,
+    stardust: StardustOutputOptions,
+    /// With `--obj`, what went into each output file
+    report: Option<Report>,
 }
 
 #[derive(Debug, Error)]
@@ -407,6 +451,11 @@ enum SettingsError {
     #[error("{}", translate!("split-error-filter-with-kth-chunk"))]
     FilterWithKthChunkNumber,
 
+    /// Using `--obj` with the `--number` option sub-strategies that print
+    /// the Kth chunk to stdout, where the report would be mixed into it
+    #[error("{}", translate!("split-error-obj-with-kth-chunk"))]
+    ObjWithKthChunkNumber,
+
     /// Invalid IO block size
     #[error("{}", translate!("split-error-invalid-io-block-size", "size" => .0.quote()))]
     InvalidIOBlockSize(String),
@@ -454,6 +503,7 @@ impl Settings {
             None
         };
 
+        let stardust = StardustOutputOptions::from_matches(matches);
         let result = Self {
             prefix: matches.get_one::<OsString>(ARG_PREFIX).unwrap().clone(),
             suffix,
@@ -464,6 +514,12 @@ impl Settings {
             separator,
             elide_empty_files: matches.get_flag(OPT_ELIDE_EMPTY_FILES),
             io_blksize,
+            records: matches.get_one::<String>(OPT_RECORDS).map(|field| RecordOptions {
+                field: (!field.is_empty()).then(|| field.clone()),
+                ndjson: matches.get_flag(OPT_NDJSON),
+            }),
+            report: stardust.stardust_output.then(|| Report::new(separator)),
+            stardust,
         };
 
         let kth_chunk = matches!(
@@ -477,6 +533,9 @@ impl Settings {
         if kth_chunk && result.filter.is_some() {
             return Err(SettingsError::FilterWithKthChunkNumber);
         }
+        if kth_chunk && result.report.is_some() {
+            return Err(SettingsError::ObjWithKthChunkNumber);
+        }
 
         Ok(result)
     }
@@ -492,7 +551,15 @@ impl Settings {
             ));
         }
 
-        platform::instantiate_current_writer(self.filter.as_deref(), filename, is_new)
+        let writer =
+            platform::instantiate_current_writer(self.filter.as_deref(), filename, is_new)?;
+        match &self.report {
+            Some(report) => {
+                let (inner, _) = writer.into_parts();
+                Ok(BufWriter::new(report.track(filename, is_new, inner)))
+            }
+            None => Ok(writer),
+        }
     }
 }
 
@@ -1317,6 +1384,10 @@ where
 
 #[allow(clippy::cognitive_complexity)]
 fn split(settings: &Settings) -> SGResult<()> {
+    if let Some(options) = &settings.records {
+        return records::split_records(settings, options);
+    }
+
     let r_box = if settings.input == "-" {
         Box::new(stdin()) as Box<dyn Read>
     } else {
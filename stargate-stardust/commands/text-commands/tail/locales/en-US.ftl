@@ -19,6 +19,9 @@ tail-help-follow-retry = Same as --follow=name --retry
 tail-help-polling-linux = Disable 'inotify' support and use polling instead
 tail-help-polling-unix = Disable 'kqueue' support and use polling instead
 tail-help-polling-windows = Disable 'ReadDirectoryChanges' support and use polling instead
+tail-help-obj = Write each line, and each change to a followed file, as a stardust record (one JSON object per line)
+tail-help-parse = With --obj, parse each line into fields: json, logfmt or regex (see --regex)
+tail-help-regex = With --obj, parse each line with REGEX; (?P<name>...) names a field
 
 # Error messages
 tail-error-cannot-follow-stdin-by-name = cannot follow { $stdin } by name
@@ -42,6 +45,7 @@ tail-error-backend-cannot-be-used-too-many-files = { $backend } cannot be used,
 tail-error-backend-resources-exhausted = { $backend } resources exhausted
 tail-error-notify-error = NotifyError: { $error }
 tail-error-recv-timeout-error = RecvTimeoutError: { $error }
+tail-error-invalid-regex = invalid regular expression { $pattern }: { $error }
 
 # Warning messages
 tail-warning-retry-ignored = --retry ignored; --retry is useful only when following
@@ -19,6 +19,9 @@ tail-help-follow-retry = Identique à --follow=name --retry
 tail-help-polling-linux = Désactiver le support 'inotify' et utiliser le sondage à la place
 tail-help-polling-unix = Désactiver le support 'kqueue' et utiliser le sondage à la place
 tail-help-polling-windows = Désactiver le support 'ReadDirectoryChanges' et utiliser le sondage à la place
+tail-help-obj = Écrire chaque ligne, et chaque changement d'un fichier suivi, comme un enregistrement stardust (un objet JSON par ligne)
+tail-help-parse = Avec --obj, découper chaque ligne en champs : json, logfmt ou regex (voir --regex)
+tail-help-regex = Avec --obj, découper chaque ligne avec REGEX ; (?P<nom>...) nomme un champ
 
 # Messages d'erreur
 tail-error-cannot-follow-stdin-by-name = impossible de suivre { $stdin } par nom
@@ -42,6 +45,7 @@ tail-error-backend-cannot-be-used-too-many-files = { $backend } ne peut pas êtr
 tail-error-backend-resources-exhausted = ressources { $backend } épuisées
 tail-error-notify-error = Erreur de notification : { $error }
 tail-error-recv-timeout-error = Erreur de délai de réception : { $error }
+tail-error-invalid-regex = expression régulière invalide { $pattern } : { $error }
 
 # Messages d'avertissement
 tail-warning-retry-ignored = --retry ignoré ; --retry n'est utile que lors du suivi
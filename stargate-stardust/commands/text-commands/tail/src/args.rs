@@ -1,8 +1,10 @@
 
 
 use crate::paths::Input;
+use crate::records::{LineParser, RecordOptions};
 use crate::{Quotable, parse, platform};
 use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use regex::Regex;
 use same_file::Handle;
 use std::ffi::OsString;
 use std::io::IsTerminal;
@@ -11,6 +13,7 @@ use sgcore::error::{SGResult, SGSimpleError, SGUsageError};
 use sgcore::parser::parse_size::{ParseSizeError, parse_size_u64};
 use sgcore::parser::parse_time;
 use sgcore::parser::shortcut_value_parser::ShortcutValueParser;
+use sgcore::stardust_output;
 use sgcore::translate;
 use sgcore::{format_usage, show_warning};
 
@@ -32,6 +35,8 @@ pub mod options {
     pub const MAX_UNCHANGED_STATS: &str = "max-unchanged-stats";
     pub const ARG_FILES: &str = "files";
     pub const PRESUME_INPUT_PIPE: &str = "-presume-input-pipe";
+    pub const PARSE: &str = "parse";
+    pub const REGEX: &str = "regex";
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -135,6 +140,8 @@ pub struct Settings {
     pub presume_input_pipe: bool,
     /// `FILE(s)` positional arguments
     pub inputs: Vec<Input>,
+    /// Line and event records instead of text, with `--obj`
+    pub records: Option<RecordOptions>,
 }
 
 impl Default for Settings {
@@ -150,6 +157,7 @@ impl Default for Settings {
             verbose: Default::default(),
             presume_input_pipe: Default::default(),
             inputs: Vec::default(),
+            records: None,
         }
     }
 }
@@ -253,6 +261,15 @@ impl Settings {
             || settings.inputs.len() > 1)
             && !matches.get_flag(options::verbosity::QUIET);
 
+        if matches.get_flag(stardust_output::ARG_STARDUST_OUTPUT) {
+            // Every record names its file, so there are no headers
+            settings.verbose = false;
+            settings.records = Some(RecordOptions {
+                parser: LineParser::from(matches)?,
+                delimiter: if matches.get_flag(options::ZERO_TERM) { 0 } else { b'\n' },
+            });
+        }
+
         Ok(settings)
     }
 
@@ -326,6 +343,25 @@ impl Settings {
     }
 }
 
+impl LineParser {
+    fn from(matches: &ArgMatches) -> SGResult<Option<Self>> {
+        let parser = match matches.get_one::<String>(options::PARSE).map(String::as_str) {
+            Some("json") => Some(Self::Json),
+            Some("logfmt") => Some(Self::Logfmt),
+            _ => match matches.get_one::<String>(options::REGEX) {
+                Some(pattern) => Some(Self::Regex(Regex::new(pattern).map_err(|e| {
+                    SGUsageError::new(
+                        1,
+                        translate!("tail-error-invalid-regex", "pattern" => pattern.quote(), "error" => e)
+                    )
+                })?)),
+                None => None,
+            },
+        };
+        Ok(parser)
+    }
+}
+
 pub fn parse_obsolete(arg: &OsString, input: Option<&OsString>) -> SGResult<Option<Settings>> {
     match parse::parse_obsolete(arg) {
         Some(Ok(args)) => Ok(Some(Settings::from_obsolete_args(&args, input))),
@@ -518,6 +554,35 @@ pub fn sg_app() -> Command {
                 .value_parser(value_parser!(OsString))
                 .value_hint(clap::ValueHint::FilePath)
         )
+        .arg(
+            Arg::new(options::PARSE)
+                .long(options::PARSE)
+                .value_name("FORMAT")
+                .value_parser(["json", "logfmt", "regex"])
+                .requires(stardust_output::ARG_STARDUST_OUTPUT)
+                .help(translate!("tail-help-parse"))
+        )
+        .arg(
+            Arg::new(options::REGEX)
+                .long(options::REGEX)
+                .value_name("REGEX")
+                .requires(stardust_output::ARG_STARDUST_OUTPUT)
+                .required_if_eq(options::PARSE, "regex")
+                .help(translate!("tail-help-regex"))
+        )
+        .arg(
+            Arg::new(stardust_output::ARG_STARDUST_OUTPUT)
+                .long("obj")
+                .help(translate!("tail-help-obj"))
+                .action(ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new(stardust_output::ARG_SCHEMA)
+                .long("schema")
+                .help("Print JSON schema of output structure")
+                .action(ArgAction::SetTrue)
+                .hide(true)
+        )
 }
 
 #[cfg(test)]
@@ -3,6 +3,7 @@
 use crate::args::Settings;
 use crate::chunks::BytesChunkBuffer;
 use crate::paths::{HeaderPrinter, PathExtTail};
+use crate::records::{self, FileEvent, LineRecords, RecordOptions};
 use crate::text;
 use std::collections::HashMap;
 use std::collections::hash_map::Keys;
@@ -20,6 +21,7 @@ pub struct FileHandling {
     map: HashMap<PathBuf, PathData>,
     last: Option<PathBuf>,
     header_printer: HeaderPrinter,
+    records: Option<RecordOptions>,
 }
 
 impl FileHandling {
@@ -28,6 +30,7 @@ impl FileHandling {
             map: HashMap::with_capacity(settings.inputs.len()),
             last: None,
             header_printer: HeaderPrinter::new(settings.verbose, false),
+            records: settings.records.clone(),
         }
     }
 
@@ -102,15 +105,48 @@ impl FileHandling {
     }
 
     /// Set `reader` to None to indicate that `path` is not an existing file anymore.
-    pub fn reset_reader(&mut self, path: &Path) {
+    pub fn reset_reader(&mut self, path: &Path) -> SGResult<()> {
+        self.finish_lines(path)?;
         self.get_mut(path).reader = None;
+        Ok(())
     }
 
     /// Reopen the file at the monitored `path`
     pub fn update_reader(&mut self, path: &Path) -> SGResult<()> {
-        self.get_mut(path)
-            .reader
-            .replace(Box::new(BufReader::new(File::open(path)?)));
+        self.finish_lines(path)?;
+        let data = self.get_mut(path);
+        data.reader.replace(Box::new(BufReader::new(File::open(path)?)));
+        data.lines = LineRecords::new(0);
+        Ok(())
+    }
+
+    /// With `--obj`, write the unterminated line the old reader of `path`
+    /// left behind, as nothing more will be added to it
+    fn finish_lines(&mut self, path: &Path) -> SGResult<()> {
+        if let Some(options) = &self.records {
+            let data = self.map.get_mut(&Self::canonicalize_path(path)).unwrap();
+            data.lines.finish(&data.display_name, options)?;
+        }
+        Ok(())
+    }
+
+    /// With `--obj`, write the unterminated lines of every file, when
+    /// following stops
+    pub fn finish(&mut self) -> SGResult<()> {
+        let paths: Vec<PathBuf> = self.map.keys().cloned().collect();
+        for path in paths {
+            self.finish_lines(&path)?;
+        }
+        Ok(())
+    }
+
+    /// With `--obj`, write a record for `event` on `path`
+    pub fn report(&self, path: &Path, event: FileEvent) -> SGResult<()> {
+        if self.records.is_some() {
+            let data = self.get(path);
+            let to = (event == FileEvent::Renamed).then_some(path);
+            records::print_event(&data.display_name, event, data.lines.offset(), to)?;
+        }
         Ok(())
     }
 
@@ -130,15 +166,22 @@ impl FileHandling {
             chunks.fill(reader)?;
         }
         if chunks.has_data() {
-            if self.needs_header(path, verbose) {
-                let display_name = self.get(path).display_name.clone();
-                self.header_printer.print(display_name.as_str());
+            if let Some(options) = &self.records {
+                let mut bytes = Vec::new();
+                chunks.print(&mut bytes)?;
+                let data = self.map.get_mut(&Self::canonicalize_path(path)).unwrap();
+                data.lines.push(&data.display_name, &bytes, options)?;
+            } else {
+                if self.needs_header(path, verbose) {
+                    let display_name = self.get(path).display_name.clone();
+                    self.header_printer.print(display_name.as_str());
+                }
+
+                let mut writer = BufWriter::new(stdout().lock());
+                chunks.print(&mut writer)?;
+                writer.flush()?;
             }
 
-            let mut writer = BufWriter::new(stdout().lock());
-            chunks.print(&mut writer)?;
-            writer.flush()?;
-
             self.last.replace(path.to_owned());
             self.update_metadata(path, None);
             Ok(true)
@@ -163,35 +206,39 @@ impl FileHandling {
 
 /// Data structure to keep a handle on the [`BufReader`], [`Metadata`]
 /// and the `display_name` (`header_name`) of files that are being followed.
+/// `lines` keeps the position of the reader for `--obj` records.
 pub struct PathData {
     pub reader: Option<Box<dyn BufRead>>,
     pub metadata: Option<Metadata>,
     pub display_name: String,
+    pub lines: LineRecords,
 }
 
 impl PathData {
     pub fn new(
         reader: Option<Box<dyn BufRead>>,
         metadata: Option<Metadata>,
-        display_name: &str
+        display_name: &str,
+        lines: LineRecords
     ) -> Self {
         Self {
             reader,
             metadata,
             display_name: display_name.to_owned(),
+            lines,
         }
     }
     pub fn from_other_with_path(data: Self, path: &Path) -> Self {
-        let old_reader = data.reader;
-        let reader = if old_reader.is_some() {
-            old_reader
+        let (reader, lines) = if data.reader.is_some() {
+            (data.reader, data.lines)
         } else if let Ok(file) = File::open(path) {
-            Some(Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+            let reader = Box::new(BufReader::new(file)) as Box<dyn BufRead>;
+            (Some(reader), LineRecords::default())
         } else {
-            None
+            (None, LineRecords::default())
         };
 
-        Self::new(reader, path.metadata().ok(), data.display_name.as_str())
+        Self::new(reader, path.metadata().ok(), data.display_name.as_str(), lines)
     }
 }
 
@@ -3,6 +3,7 @@
 use crate::args::{FollowMode, Settings};
 use crate::follow::files::{FileHandling, PathData};
 use crate::paths::{Input, InputKind, MetadataExtTail, PathExtTail};
+use crate::records::{FileEvent, LineRecords};
 use crate::{platform, text};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher, WatcherKind};
 use std::io::BufRead;
@@ -126,6 +127,7 @@ impl Observer {
         path: &Path,
         display_name: &str,
         reader: Option<Box<dyn BufRead>>,
+        lines: LineRecords,
         update_last: bool
     ) -> SGResult<()> {
         if self.follow.is_some() {
@@ -137,7 +139,7 @@ impl Observer {
             let metadata = path.metadata().ok();
             self.files.insert(
                 &path,
-                PathData::new(reader, metadata, display_name),
+                PathData::new(reader, metadata, display_name, lines),
                 update_last
             );
         }
@@ -149,6 +151,7 @@ impl Observer {
         &mut self,
         display_name: &str,
         reader: Option<Box<dyn BufRead>>,
+        lines: LineRecords,
         update_last: bool
     ) -> SGResult<()> {
         if self.follow == Some(FollowMode::Descriptor) {
@@ -156,6 +159,7 @@ impl Observer {
                 &PathBuf::from(text::DEV_STDIN),
                 display_name,
                 reader,
+                lines,
                 update_last
             );
         }
@@ -170,7 +174,7 @@ impl Observer {
         update_last: bool
     ) -> SGResult<()> {
         if self.retry && self.follow.is_some() {
-            return self.add_path(path, display_name, None, update_last);
+            return self.add_path(path, display_name, None, LineRecords::default(), update_last);
         }
 
         Ok(())
@@ -285,12 +289,14 @@ impl Observer {
                                     translate!("tail-status-has-become-accessible", "file" => display_name.quote())
                                 );
                                 self.files.update_reader(event_path)?;
+                                self.files.report(event_path, FileEvent::Appeared)?;
                             } else if pd.reader.is_none() {
                                 show_error!(
                                     "{}",
                                     translate!("tail-status-has-appeared-following-new-file", "file" => display_name.quote())
                                 );
                                 self.files.update_reader(event_path)?;
+                                self.files.report(event_path, FileEvent::Appeared)?;
                             } else if event.kind == EventKind::Modify(ModifyKind::Name(RenameMode::To))
                             || (self.use_polling && !old_md.file_id_eq(&new_md)) {
                                 show_error!(
@@ -298,17 +304,20 @@ impl Observer {
                                     translate!("tail-status-has-been-replaced-following-new-file", "file" => display_name.quote())
                                 );
                                 self.files.update_reader(event_path)?;
+                                self.files.report(event_path, FileEvent::Rotated)?;
                             } else if old_md.got_truncated(&new_md)? {
                                 show_error!(
                                     "{}",
                                     translate!("tail-status-file-truncated", "file" => display_name)
                                 );
                                 self.files.update_reader(event_path)?;
+                                self.files.report(event_path, FileEvent::Truncated)?;
                             }
                             paths.push(event_path.clone());
                         } else if !is_tailable && old_md.is_tailable() {
                             if pd.reader.is_some() {
-                                self.files.reset_reader(event_path);
+                                self.files.reset_reader(event_path)?;
+                                self.files.report(event_path, FileEvent::Disappeared)?;
                             } else {
                                 show_error!(
                                     "{}",
@@ -322,6 +331,7 @@ impl Observer {
                             translate!("tail-status-has-appeared-following-new-file", "file" => display_name.quote())
                         );
                         self.files.update_reader(event_path)?;
+                        self.files.report(event_path, FileEvent::Appeared)?;
                         paths.push(event_path.clone());
                     } else if settings.retry {
                         if self.follow_descriptor() {
@@ -329,6 +339,8 @@ impl Observer {
                                 "{}",
                                 translate!("tail-status-replaced-with-untailable-file-giving-up", "file" => display_name.quote())
                             );
+                            self.files.reset_reader(event_path)?;
+                            self.files.report(event_path, FileEvent::Disappeared)?;
                             let _ = self.watcher_rx.as_mut().unwrap().watcher.unwatch(event_path);
                             self.files.remove(event_path);
                             if self.files.no_files_remaining(settings) {
@@ -347,6 +359,7 @@ impl Observer {
             EventKind::Remove(RemoveKind::File | RemoveKind::Any)
 
                 | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                let had_reader = self.files.get(event_path).reader.is_some();
                 if self.follow_name() {
                     if settings.retry {
                         if let Some(old_md) = self.files.get_mut_metadata(event_path) {
@@ -375,9 +388,16 @@ impl Observer {
                             return Err(SGSimpleError::new(1, translate!("tail-no-files-remaining")));
                         }
                     }
-                    self.files.reset_reader(event_path);
+                    self.files.reset_reader(event_path)?;
+                    if had_reader {
+                        self.files.report(event_path, FileEvent::Disappeared)?;
+                    }
                 } else if self.follow_descriptor_retry() {
                     let _ = self.watcher_rx.as_mut().unwrap().unwatch(event_path);
+                    if had_reader {
+                        self.files.reset_reader(event_path)?;
+                        self.files.report(event_path, FileEvent::Disappeared)?;
+                    }
                     self.files.remove(event_path);
                 } else if self.use_polling && event.kind == EventKind::Remove(RemoveKind::Any) {
                 }
@@ -394,6 +414,7 @@ impl Observer {
                         new_data,
                         self.files.get_last().unwrap() == event_path
                     );
+                    self.files.report(new_path, FileEvent::Renamed)?;
 
                     let _ = self.watcher_rx.as_mut().unwrap().unwatch(event_path);
                     self.watcher_rx.as_mut().unwrap().watch_with_parent(new_path)?;
@@ -434,6 +455,7 @@ pub fn follow(mut observer: Observer, settings: &Settings) -> SGResult<()> {
                         );
                         observer.files.update_metadata(new_path, Some(md));
                         observer.files.update_reader(new_path)?;
+                        observer.files.report(new_path, FileEvent::Appeared)?;
                         _read_some = observer.files.tail_file(new_path, settings.verbose)?;
                         observer
                             .watcher_rx
@@ -518,6 +540,6 @@ pub fn follow(mut observer: Observer, settings: &Settings) -> SGResult<()> {
         }
     }
 
-    Ok(())
+    observer.files.finish()
 }
 
@@ -0,0 +1,313 @@
+//! Stardust records for `tail --obj`.
+//!
+//! Every line is a record of its own, written as one line of compact JSON as
+//! soon as its delimiter has been read, so `tail -f --obj` can feed a
+//! pipeline that never sees the end of its input. Changes to a followed file
+//! (truncation, rotation, a file appearing or going away) arrive in the same
+//! stream as records with their own `type`.
+use std::io::{self, Read, Write, stdout};
+use std::path::Path;
+
+use chrono::{SecondsFormat, Utc};
+use memchr::memchr_iter;
+use regex::Regex;
+use serde_json::{Map, Value, json};
+use sgcore::stardust_output::{Property, create_schema, enum_schema};
+use sgcore::tabular::{default_name, infer_value};
+
+/// How `--parse` turns a line into `fields`
+#[derive(Debug, Clone)]
+pub enum LineParser {
+    Json,
+    Logfmt,
+    Regex(Regex),
+}
+
+impl LineParser {
+    /// The fields of `line`, or null when it is not in the expected format
+    pub fn parse(&self, line: &str) -> Value {
+        match self {
+            Self::Json => serde_json::from_str(line).unwrap_or(Value::Null),
+            Self::Logfmt => parse_logfmt(line).map_or(Value::Null, Value::Object),
+            Self::Regex(regex) => regex.captures(line).map_or(Value::Null, |captures| {
+                let fields: Map<String, Value> = regex
+                    .capture_names()
+                    .enumerate()
+                    .skip(1)
+                    .map(|(i, name)| {
+                        let name = name.map_or_else(|| default_name(i - 1), str::to_string);
+                        let value = captures
+                            .get(i)
+                            .map_or(Value::Null, |m| infer_value(m.as_str()));
+                        (name, value)
+                    })
+                    .collect();
+                Value::Object(fields)
+            }),
+        }
+    }
+}
+
+/// What `--obj` asked for, shared by the initial section and `--follow`
+#[derive(Debug, Clone)]
+pub struct RecordOptions {
+    pub parser: Option<LineParser>,
+    /// `\n`, or NUL with `-z`
+    pub delimiter: u8,
+}
+
+/// Kinds of change to a followed file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileEvent {
+    /// Cut back to a shorter length; reading starts over from the beginning
+    Truncated,
+    /// Replaced by another file under the same name, as log rotation does
+    Rotated,
+    /// Created, or readable again after it was not
+    Appeared,
+    /// Removed, renamed away or no longer readable
+    Disappeared,
+    /// Renamed while followed by descriptor; `to` is the new path
+    Renamed,
+}
+
+impl FileEvent {
+    pub const ALL: [Self; 5] = [
+        Self::Truncated,
+        Self::Rotated,
+        Self::Appeared,
+        Self::Disappeared,
+        Self::Renamed,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Truncated => "truncated",
+            Self::Rotated => "rotated",
+            Self::Appeared => "appeared",
+            Self::Disappeared => "disappeared",
+            Self::Renamed => "renamed",
+        }
+    }
+}
+
+/// Splits the bytes read from one file into line records. A line is held
+/// back until its delimiter arrives, since a writer may still be in the
+/// middle of it.
+#[derive(Debug, Default)]
+pub struct LineRecords {
+    /// Offset in the file of the first byte not yet part of a record
+    offset: u64,
+    pending: Vec<u8>,
+}
+
+impl LineRecords {
+    pub fn new(offset: u64) -> Self {
+        Self {
+            offset,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Write a record for every line of `data` that is complete
+    pub fn push(&mut self, file: &str, data: &[u8], options: &RecordOptions) -> io::Result<()> {
+        self.pending.extend_from_slice(data);
+        let mut out = stdout().lock();
+        let mut start = 0;
+        for end in memchr_iter(options.delimiter, &self.pending) {
+            let record = line_record(file, &self.pending[start..end], self.offset, options);
+            write_record(&mut out, &record)?;
+            self.offset += (end + 1 - start) as u64;
+            start = end + 1;
+        }
+        self.pending.drain(..start);
+        out.flush()
+    }
+
+    /// Write what is left of an unterminated last line
+    pub fn finish(&mut self, file: &str, options: &RecordOptions) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let record = line_record(file, &self.pending, self.offset, options);
+        self.offset += self.pending.len() as u64;
+        self.pending.clear();
+        let mut out = stdout().lock();
+        write_record(&mut out, &record)?;
+        out.flush()
+    }
+}
+
+/// Write a record for `event` on `file`; `offset` is where reading goes on
+pub fn print_event(file: &str, event: FileEvent, offset: u64, to: Option<&Path>) -> io::Result<()> {
+    let mut record = json!({
+        "type": event.as_str(),
+        "file": file,
+        "offset": offset,
+        "timestamp": timestamp(),
+    });
+    if let Some(to) = to {
+        record["to"] = Value::String(to.to_string_lossy().into_owned());
+    }
+    let mut out = stdout().lock();
+    write_record(&mut out, &record)?;
+    out.flush()
+}
+
+/// Counts the bytes read through it, to give lines from a pipe their offsets
+pub struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R> CountingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, count: 0 }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count += n as u64;
+        Ok(n)
+    }
+}
+
+fn line_record(file: &str, line: &[u8], offset: u64, options: &RecordOptions) -> Value {
+    let line = String::from_utf8_lossy(line);
+    let line = line.strip_suffix('\r').unwrap_or(&line);
+    let mut record = json!({
+        "type": "line",
+        "file": file,
+        "line": line,
+        "offset": offset,
+        "timestamp": timestamp(),
+    });
+    if let Some(parser) = &options.parser {
+        record["fields"] = parser.parse(line);
+    }
+    record
+}
+
+fn write_record(out: &mut impl Write, record: &Value) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, record)?;
+    out.write_all(b"\n")
+}
+
+fn timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Parse `key=value key2="quoted value" flag` pairs. Bare keys are `true`,
+/// unquoted values get their types inferred and quoted values stay strings.
+/// A line without any `key=value` pair is not logfmt.
+fn parse_logfmt(line: &str) -> Option<Map<String, Value>> {
+    let mut fields = Map::new();
+    let mut has_pair = false;
+    let mut chars = line.trim().chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let mut key = String::new();
+        while let Some(c) = chars.next_if(|&c| c != '=' && !c.is_whitespace()) {
+            if c == '"' {
+                return None;
+            }
+            key.push(c);
+        }
+        if chars.next_if_eq(&'=').is_none() {
+            fields.insert(key, Value::Bool(true));
+            continue;
+        }
+        if key.is_empty() {
+            return None;
+        }
+        has_pair = true;
+
+        let value = if chars.next_if_eq(&'"').is_some() {
+            let mut value = String::new();
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => match chars.next()? {
+                        'n' => value.push('\n'),
+                        't' => value.push('\t'),
+                        c => value.push(c),
+                    },
+                    c => value.push(c),
+                }
+            }
+            Value::String(value)
+        } else {
+            let mut value = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                value.push(c);
+            }
+            infer_value(&value)
+        };
+        fields.insert(key, value);
+    }
+
+    has_pair.then_some(fields)
+}
+
+/// Schema of one `tail --obj` record
+pub fn schema() -> Value {
+    let mut types = vec!["line"];
+    types.extend(FileEvent::ALL.map(FileEvent::as_str));
+
+    create_schema(vec![
+        Property::new("type", enum_schema(&types))
+            .description("`line`, or the change a followed file went through"),
+        Property::new("file", "string")
+            .description("Name of the file as given on the command line"),
+        Property::new("line", "string")
+            .description("The line, without its delimiter")
+            .optional(),
+        Property::new("offset", "integer")
+            .description("Byte offset of the line, or where reading goes on after an event"),
+        Property::new("timestamp", "string")
+            .description("When tail read the line or saw the event (RFC 3339)"),
+        Property::new("to", "string")
+            .description("New path of a renamed file")
+            .optional(),
+        Property::new(
+            "fields",
+            json!({ "type": ["object", "array", "string", "number", "boolean", "null"] }),
+        )
+        .description("The line parsed with --parse; null when it did not parse")
+        .optional(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_logfmt() {
+        let fields =
+            parse_logfmt(r#"level=info msg="disk \"sda\" full" took=12 retry= debug"#).unwrap();
+        assert_eq!(fields["level"], "info");
+        assert_eq!(fields["msg"], "disk \"sda\" full");
+        assert_eq!(fields["took"], 12);
+        assert_eq!(fields["retry"], Value::Null);
+        assert_eq!(fields["debug"], true);
+
+        assert!(parse_logfmt("just some words").is_none());
+        assert!(parse_logfmt(r#"msg="unterminated"#).is_none());
+    }
+}
@@ -14,6 +14,7 @@ mod follow;
 mod parse;
 mod paths;
 mod platform;
+mod records;
 pub mod text;
 
 pub use args::sg_app;
@@ -22,13 +23,16 @@ use chunks::ReverseChunks;
 use follow::Observer;
 use memchr::{memchr_iter, memrchr_iter};
 use paths::{FileExtTail, HeaderPrinter, Input, InputKind};
+use records::{CountingReader, LineRecords};
 use same_file::Handle;
 use std::cmp::Ordering;
+use std::ffi::OsString;
 use std::fs::File;
 use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write, stdin, stdout};
 use std::path::{Path, PathBuf};
 use sgcore::display::Quotable;
 use sgcore::error::{FromIo, SGResult, SGSimpleError, get_exit_code, set_exit_code};
+use sgcore::stardust_output;
 use sgcore::translate;
 
 use sgcore::{show, show_error};
@@ -40,7 +44,14 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     }
     sgcore::pledge::apply_pledge(&["stdio", "rpath"])?;
 
-    let settings = parse_args(args)?;
+    let args: Vec<OsString> = args.collect();
+    if let Ok(matches) = sg_app().try_get_matches_from(&args)
+        && stardust_output::self_describe(&matches, records::schema())?
+    {
+        return Ok(());
+    }
+
+    let settings = parse_args(args.into_iter())?;
 
     settings.check_warnings();
 
@@ -136,22 +147,35 @@ fn tail_file(
                 let st = file.metadata()?;
                 let blksize_limit = sgcore::fs::sane_blksize::sane_blksize_from_metadata(&st);
                 header_printer.print_input(input);
+                // With --obj the section is gathered first and turned into records
+                let mut section = Vec::new();
                 let mut reader;
-                if !settings.presume_input_pipe
-                    && file.is_seekable(if input.is_stdin() { offset } else { 0 })
-                    && (!st.is_file() || st.len() > blksize_limit)
                 {
-                    bounded_tail(&mut file, settings);
-                    reader = BufReader::new(file);
-                } else {
-                    reader = BufReader::new(file);
-                    unbounded_tail(&mut reader, settings)?;
+                    let mut stdout = stdout().lock();
+                    let writer: &mut dyn Write = if settings.records.is_some() {
+                        &mut section
+                    } else {
+                        &mut stdout
+                    };
+                    if !settings.presume_input_pipe
+                        && file.is_seekable(if input.is_stdin() { offset } else { 0 })
+                        && (!st.is_file() || st.len() > blksize_limit)
+                    {
+                        bounded_tail(&mut file, settings, writer);
+                        reader = BufReader::new(file);
+                    } else {
+                        reader = BufReader::new(file);
+                        unbounded_tail(&mut reader, settings, writer)?;
+                    }
                 }
+                let end = reader.stream_position().unwrap_or(section.len() as u64);
+                let lines = section_records(settings, input, &section, end)?;
                 if input.is_tailable() {
                     observer.add_path(
                         path,
                         input.display_name.as_str(),
                         Some(Box::new(reader)),
+                        lines,
                         true
                     )?;
                 } else {
@@ -232,9 +256,21 @@ fn tail_stdin(
                     );
                 }
             } else {
-                let mut reader = BufReader::new(stdin());
-                unbounded_tail(&mut reader, settings)?;
-                observer.add_stdin(input.display_name.as_str(), Some(Box::new(reader)), true)?;
+                let mut reader = BufReader::new(CountingReader::new(stdin()));
+                let mut section = Vec::new();
+                if settings.records.is_some() {
+                    unbounded_tail(&mut reader, settings, &mut section)?;
+                } else {
+                    unbounded_tail(&mut reader, settings, &mut stdout().lock())?;
+                }
+                let end = reader.get_ref().count();
+                let lines = section_records(settings, input, &section, end)?;
+                observer.add_stdin(
+                    input.display_name.as_str(),
+                    Some(Box::new(reader)),
+                    lines,
+                    true
+                )?;
             }
         }
     }
@@ -242,6 +278,25 @@ fn tail_stdin(
     Ok(())
 }
 
+/// With `--obj`, write the records of the initial `section` of `input`, which
+/// ended at byte `end`. Without `--follow` an unterminated last line is
+/// written as well; when following it waits for the rest of the line.
+fn section_records(
+    settings: &Settings,
+    input: &Input,
+    section: &[u8],
+    end: u64
+) -> io::Result<LineRecords> {
+    let mut lines = LineRecords::new(end.saturating_sub(section.len() as u64));
+    if let Some(options) = &settings.records {
+        lines.push(&input.display_name, section, options)?;
+        if settings.follow.is_none() {
+            lines.finish(&input.display_name, options)?;
+        }
+    }
+    Ok(lines)
+}
+
 /// Find the index after the given number of instances of a given byte.
 ///
 /// This function reads through a given reader until `num_delimiters`
@@ -352,7 +407,7 @@ fn backwards_thru_file(file: &mut File, num_delimiters: u64, delimiter: u8) {
 /// end of the file, and then read the file "backwards" in blocks of size
 /// `BLOCK_SIZE` until we find the location of the first line/byte. This ends up
 /// being a nice performance win for very large files.
-fn bounded_tail(file: &mut File, settings: &Settings) {
+fn bounded_tail(file: &mut File, settings: &Settings, writer: &mut dyn Write) {
     debug_assert!(!settings.presume_input_pipe);
     let mut limit = None;
 
@@ -380,11 +435,15 @@ fn bounded_tail(file: &mut File, settings: &Settings) {
         _ => {}
     }
 
-    print_target_section(file, limit);
+    print_target_section(file, limit, writer);
 }
 
-fn unbounded_tail<T: Read>(reader: &mut BufReader<T>, settings: &Settings) -> SGResult<()> {
-    let mut writer = BufWriter::new(stdout().lock());
+fn unbounded_tail<T: Read>(
+    reader: &mut BufReader<T>,
+    settings: &Settings,
+    writer: &mut dyn Write
+) -> SGResult<()> {
+    let mut writer = BufWriter::new(writer);
     match &settings.mode {
         FilterMode::Lines(Signum::Negative(count), sep) => {
             let mut chunks = chunks::LinesChunkBuffer::new(*sep, *count);
@@ -448,17 +507,15 @@ fn unbounded_tail<T: Read>(reader: &mut BufReader<T>, settings: &Settings) -> SG
     Ok(())
 }
 
-fn print_target_section<R>(file: &mut R, limit: Option<u64>)
+fn print_target_section<R>(file: &mut R, limit: Option<u64>, writer: &mut dyn Write)
 where
     R: Read + ?Sized,
 {
-    let stdout = stdout();
-    let mut stdout = stdout.lock();
     if let Some(limit) = limit {
         let mut reader = file.take(limit);
-        io::copy(&mut reader, &mut stdout).unwrap();
+        io::copy(&mut reader, writer).unwrap();
     } else {
-        io::copy(file, &mut stdout).unwrap();
+        io::copy(file, writer).unwrap();
     }
 }
 
@@ -17,6 +17,9 @@ uniq-help-skip-chars = avoid comparing the first N characters
 uniq-help-skip-fields = avoid comparing the first N fields
 uniq-help-unique = only print unique lines
 uniq-help-zero-terminated = end lines with 0 byte, not newline
+uniq-help-obj = filter adjacent matching records of stardust input; -c adds a count field
+uniq-help-by = with --obj, compare records by these comma-separated field paths instead of whole
+uniq-help-records = with --obj, use the records in top-level FIELD of the input object
 
 # Error messages
 uniq-error-write-line-terminator = Could not write line terminator
@@ -42,3 +45,5 @@ uniq-error-counts-and-repeated-meaningless = printing all duplicated lines and r
   Try 'uniq --help' for more information.
 
 uniq-error-could-not-open = Could not open { $path }
+uniq-obj-invalid-field = invalid --by field: { $error }
+uniq-obj-invalid-input = invalid stardust input: { $error }
@@ -16,6 +16,9 @@ uniq-help-skip-chars = éviter de comparer les N premiers caractères
 uniq-help-skip-fields = éviter de comparer les N premiers champs
 uniq-help-unique = afficher seulement les lignes uniques
 uniq-help-zero-terminated = terminer les lignes avec un octet 0, pas une nouvelle ligne
+uniq-help-obj = filtrer les enregistrements adjacents identiques d'une entrée stardust ; -c ajoute un champ count
+uniq-help-by = avec --obj, comparer les enregistrements selon ces chemins de champs séparés par des virgules plutôt qu'en entier
+uniq-help-records = avec --obj, utiliser les enregistrements du CHAMP de premier niveau de l'objet d'entrée
 
 # Messages d'erreur
 uniq-error-write-line-terminator = Impossible d'écrire le terminateur de ligne
@@ -40,3 +43,5 @@ uniq-error-all-repeated-badoption = argument invalide 'badoption' pour '--all-re
 uniq-error-counts-and-repeated-meaningless = afficher toutes les lignes dupliquées et les nombres de répétitions n'a pas de sens
   Essayez 'uniq --help' pour plus d'informations.
 uniq-error-could-not-open = Impossible d'ouvrir { $path }
+uniq-obj-invalid-field = champ --by invalide : { $error }
+uniq-obj-invalid-input = entrée stardust invalide : { $error }
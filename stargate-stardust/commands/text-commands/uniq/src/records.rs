@@ -0,0 +1,142 @@
+//! Adjacent duplicate records for `uniq --obj`.
+//!
+//! Records are compared by the values of the `--by` field paths, or whole
+//! when there are none, and each run of equal records is one group. The
+//! input document is written back with its records replaced by the kept ones.
+
+use std::io::{BufRead, Write};
+
+use serde_json::{Map, Value};
+use sgcore::error::{FromIo, SGResult, SGSimpleError};
+use sgcore::field_path::FieldPath;
+use sgcore::json_adapter::{parse_value, put_records, take_records};
+use sgcore::translate;
+use sgcore::wire;
+
+/// Which groups `uniq --obj` keeps and how
+pub struct RecordUniq {
+    pub by: Vec<FieldPath>,
+    pub records_field: Option<String>,
+    pub repeats_only: bool,
+    pub uniques_only: bool,
+    pub all_repeated: bool,
+    pub show_counts: bool,
+    pub ignore_case: bool,
+}
+
+impl RecordUniq {
+    /// Parse `--by` values, each a comma-separated list of field paths
+    pub fn parse_by<'a>(specs: impl Iterator<Item = &'a String>) -> SGResult<Vec<FieldPath>> {
+        specs
+            .flat_map(|spec| spec.split(','))
+            .map(|path| {
+                FieldPath::parse(path).map_err(|e| {
+                    SGSimpleError::new(1, translate!("uniq-obj-invalid-field", "error" => e))
+                })
+            })
+            .collect()
+    }
+
+    pub fn print_uniq(&self, mut reader: impl BufRead, mut writer: impl Write) -> SGResult<()> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err_context(|| translate!("uniq-error-read-error"))?;
+        let invalid = |e: String| {
+            SGSimpleError::new(1, translate!("uniq-obj-invalid-input", "error" => e))
+        };
+
+        let mut document = parse_value(&bytes).map_err(invalid)?;
+        let field = self.records_field.as_deref();
+        let records = take_records(&mut document, field).map_err(invalid)?;
+        put_records(&mut document, field, self.uniq_records(records)).map_err(invalid)?;
+
+        wire::write_value(&mut writer, &document, false)
+            .and_then(|()| writer.flush())
+            .map_err_context(|| translate!("uniq-error-write-error"))
+    }
+
+    fn uniq_records(&self, records: Vec<Value>) -> Vec<Value> {
+        let mut kept = Vec::new();
+        let mut group: Vec<Value> = Vec::new();
+        for record in records {
+            if group
+                .first()
+                .is_some_and(|first| !self.same_key(first, &record))
+            {
+                self.close_group(&mut group, &mut kept);
+            }
+            group.push(record);
+        }
+        self.close_group(&mut group, &mut kept);
+        kept
+    }
+
+    fn close_group(&self, group: &mut Vec<Value>, kept: &mut Vec<Value>) {
+        let count = group.len();
+        if count == 0
+            || (count == 1 && (self.repeats_only || self.all_repeated))
+            || (count > 1 && self.uniques_only)
+        {
+            group.clear();
+            return;
+        }
+        if self.all_repeated {
+            kept.append(group);
+            return;
+        }
+        let first = group.drain(..).next().unwrap();
+        kept.push(if self.show_counts {
+            with_count(first, count)
+        } else {
+            first
+        });
+    }
+
+    fn same_key(&self, a: &Value, b: &Value) -> bool {
+        if self.by.is_empty() {
+            return values_equal(a, b, self.ignore_case);
+        }
+        self.by.iter().all(|path| {
+            values_equal(
+                path.get(a).unwrap_or(&Value::Null),
+                path.get(b).unwrap_or(&Value::Null),
+                self.ignore_case
+            )
+        })
+    }
+}
+
+/// `record` with a `count` field; records that are not objects are wrapped
+/// as `{value, count}`
+fn with_count(record: Value, count: usize) -> Value {
+    let mut map = match record {
+        Value::Object(map) => map,
+        value => {
+            let mut map = Map::new();
+            map.insert("value".to_string(), value);
+            map
+        }
+    };
+    map.insert("count".to_string(), Value::from(count));
+    Value::Object(map)
+}
+
+/// Deep equality, with strings compared ignoring ASCII case under `-i`
+fn values_equal(a: &Value, b: &Value, ignore_case: bool) -> bool {
+    if !ignore_case {
+        return a == b;
+    }
+    match (a, b) {
+        (Value::String(a), Value::String(b)) => a.eq_ignore_ascii_case(b),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| values_equal(a, b, true))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| values_equal(a, b, true)))
+        }
+        _ => a == b,
+    }
+}
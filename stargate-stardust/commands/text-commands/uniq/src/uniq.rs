@@ -15,6 +15,8 @@ use sgcore::parser::shortcut_value_parser::ShortcutValueParser;
 use sgcore::posix::{OBSOLETE, posix_version};
 use sgcore::translate;
 
+mod records;
+
 pub mod options {
     pub static ALL_REPEATED: &str = "all-repeated";
     pub static CHECK_CHARS: &str = "check-chars";
@@ -26,6 +28,9 @@ pub mod options {
     pub static UNIQUE: &str = "unique";
     pub static ZERO_TERMINATED: &str = "zero-terminated";
     pub static GROUP: &str = "group";
+    pub static OBJ: &str = "obj";
+    pub static BY: &str = "by";
+    pub static RECORDS: &str = "records";
 }
 
 static ARG_FILES: &str = "files";
@@ -645,6 +650,24 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         ));
     }
 
+    if matches.get_flag(options::OBJ) {
+        let record_uniq = records::RecordUniq {
+            by: records::RecordUniq::parse_by(
+                matches.get_many::<String>(options::BY).into_iter().flatten()
+            )?,
+            records_field: matches.get_one::<String>(options::RECORDS).cloned(),
+            repeats_only: uniq.repeats_only,
+            uniques_only: uniq.uniques_only,
+            all_repeated: uniq.all_repeated,
+            show_counts: uniq.show_counts,
+            ignore_case: uniq.ignore_case,
+        };
+        return record_uniq.print_uniq(
+            open_input_file(in_file_name)?,
+            open_output_file(out_file_name)?
+        );
+    }
+
     uniq.print_uniq(
         open_input_file(in_file_name)?,
         open_output_file(out_file_name)?
@@ -744,6 +767,34 @@ pub fn sg_app() -> Command {
                 .help(translate!("uniq-help-zero-terminated"))
                 .action(ArgAction::SetTrue)
         )
+        .arg(
+            Arg::new(options::OBJ)
+                .long(options::OBJ)
+                .help(translate!("uniq-help-obj"))
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all([
+                    options::GROUP,
+                    options::CHECK_CHARS,
+                    options::SKIP_CHARS,
+                    options::SKIP_FIELDS,
+                    options::ZERO_TERMINATED,
+                ])
+        )
+        .arg(
+            Arg::new(options::BY)
+                .long(options::BY)
+                .help(translate!("uniq-help-by"))
+                .value_name("FIELDS")
+                .action(ArgAction::Append)
+                .requires(options::OBJ)
+        )
+        .arg(
+            Arg::new(options::RECORDS)
+                .long(options::RECORDS)
+                .help(translate!("uniq-help-records"))
+                .value_name("FIELD")
+                .requires(options::OBJ)
+        )
         .arg(
             Arg::new(ARG_FILES)
                 .action(ArgAction::Append)
@@ -6,10 +6,14 @@ use crate::sg_app;
 use sgcore::display::Quotable;
 use sgcore::error::{FromIo, SGResult};
 use sgcore::libc::{S_IWGRP, STDIN_FILENO, ttyname};
+use sgcore::stardust_output::{
+    self, Property, StardustOutputOptions, array_schema, create_schema, object_schema,
+};
 use sgcore::translate;
 
 use sgcore::utmpx::{self, UtmpxRecord, time};
 
+use serde_json::{Value, json};
 use std::borrow::Cow;
 use std::ffi::CStr;
 use std::fmt::Write;
@@ -23,6 +27,9 @@ fn get_long_usage() -> String {
 pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
     let matches =
         sgcore::clap_localization::handle_clap_result(sg_app().after_help(get_long_usage()), args)?;
+    if stardust_output::self_describe(&matches, schema())? {
+        return Ok(());
+    }
     sgcore::pledge::apply_pledge(&["stdio"])?;
 
     let files: Vec<String> = matches
@@ -71,6 +78,8 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 
     let my_line_only = matches.get_flag(options::ONLY_HOSTNAME_USER) || files.len() == 2;
 
+    let stardust = StardustOutputOptions::from_matches(&matches);
+
     let mut who = Who {
         do_lookup,
         short_list,
@@ -87,6 +96,7 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
         need_runlevel,
         need_users,
         my_line_only,
+        stardust,
         args: files,
     };
 
@@ -109,6 +119,7 @@ struct Who {
     need_runlevel: bool,
     need_users: bool,
     my_line_only: bool,
+    stardust: StardustOutputOptions,
     args: Vec<String>,
 }
 
@@ -151,6 +162,48 @@ fn time_string(ut: &UtmpxRecord) -> String {
     ut.login_time().format(&time_format).unwrap()
 }
 
+/// Seconds since the terminal of `ut` was last used, if it still exists
+fn idle_seconds(ut: &UtmpxRecord) -> Option<i64> {
+    let mut p = PathBuf::from("/dev");
+    p.push(ut.tty_device().as_str());
+    let last_change = p.metadata().ok()?.atime();
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    Some((now - last_change).max(0))
+}
+
+fn lookup_host(ut: &UtmpxRecord) -> SGResult<String> {
+    ut.canon_host().map_err_context(|| {
+        let host = ut.host();
+        translate!("who-canonicalize-error", "host" => host.split(':').next().unwrap_or(&host).quote())
+        .to_string()
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn is_runlevel(record_type: i16) -> bool {
+    record_type == utmpx::RUN_LVL
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_runlevel(_record_type: i16) -> bool {
+    false
+}
+
+/// The schema printed by `who --schema`
+fn schema() -> Value {
+    let mut session = utmpx::session_properties();
+    session.push(
+        Property::new("idle", json!({"type": ["integer", "null"]})).description(
+            "Seconds since the terminal was last used, null when unknown or not a user session"
+        )
+    );
+    create_schema(vec![
+        Property::new("sessions", array_schema(object_schema(session)))
+            .description("The records the text output would list"),
+        Property::new("count", "integer").description("Number of sessions"),
+    ])
+}
+
 #[inline]
 fn current_tty() -> String {
     unsafe {
@@ -169,19 +222,14 @@ fn current_tty() -> String {
 impl Who {
     #[allow(clippy::cognitive_complexity)]
     fn exec(&mut self) -> SGResult<()> {
-        let run_level_chk = |_record: i16| {
-            #[cfg(not(target_os = "linux"))]
-            return false;
-
-            #[cfg(target_os = "linux")]
-            return _record == utmpx::RUN_LVL;
-        };
-
         let f = if self.args.len() == 1 {
             self.args[0].as_ref()
         } else {
             utmpx::DEFAULT_FILE
         };
+        if self.stardust.stardust_output {
+            return self.print_sessions(f);
+        }
         if self.short_list {
             let users = utmpx::Utmpx::iter_all_records_from(f)
                 .filter(|ut| ut.is_user_process())
@@ -207,7 +255,7 @@ impl Who {
                         self.print_user(&ut)?;
                     } else {
                         match ut.record_type() {
-                            rt if self.need_runlevel && run_level_chk(rt) => {
+                            rt if self.need_runlevel && is_runlevel(rt) => {
                                 if cfg!(target_os = "linux") {
                                     self.print_runlevel(&ut);
                                 }
@@ -228,6 +276,55 @@ impl Who {
         Ok(())
     }
 
+    /// Whether the text output has a line for `ut`
+    fn is_selected(&self, ut: &UtmpxRecord) -> bool {
+        if self.need_users && ut.is_user_process() {
+            return true;
+        }
+        match ut.record_type() {
+            rt if is_runlevel(rt) => self.need_runlevel,
+            utmpx::BOOT_TIME => self.need_boottime,
+            utmpx::NEW_TIME => self.need_clockchange,
+            utmpx::INIT_PROCESS => self.need_initspawn,
+            utmpx::LOGIN_PROCESS => self.need_login,
+            utmpx::DEAD_PROCESS => self.need_deadprocs,
+            _ => false,
+        }
+    }
+
+    /// Print the records the text output would list as session objects
+    fn print_sessions(&self, f: &str) -> SGResult<()> {
+        let cur_tty = if self.my_line_only {
+            current_tty()
+        } else {
+            String::new()
+        };
+
+        let mut sessions = Vec::new();
+        for ut in utmpx::Utmpx::iter_all_records_from(f) {
+            if (!self.my_line_only || cur_tty == ut.tty_device()) && self.is_selected(&ut) {
+                let mut session = ut.session_fields();
+                if self.do_lookup && !ut.host().is_empty() {
+                    session.insert("host".into(), lookup_host(&ut)?.into());
+                }
+                let idle = if ut.is_user_process() {
+                    idle_seconds(&ut)
+                } else {
+                    None
+                };
+                session.insert("idle".into(), idle.into());
+                sessions.push(Value::Object(session));
+            }
+        }
+
+        let output = json!({
+            "sessions": sessions,
+            "count": sessions.len(),
+        });
+        stardust_output::output(self.stardust.clone(), output, || Ok(()))?;
+        Ok(())
+    }
+
     #[inline]
     fn print_runlevel(&self, ut: &UtmpxRecord) {
         let last = (ut.pid() / 256) as u8 as char;
@@ -356,11 +453,7 @@ impl Who {
         };
 
         let s = if self.do_lookup {
-            ut.canon_host().map_err_context(|| {
-                let host = ut.host();
-                translate!("who-canonicalize-error", "host" => host.split(':').next().unwrap_or(&host).quote())
-                .to_string()
-            })?
+            lookup_host(ut)?
         } else {
             ut.host()
         };
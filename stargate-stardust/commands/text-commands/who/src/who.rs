@@ -2,6 +2,7 @@
 
 use clap::{Arg, ArgAction, Command};
 use sgcore::format_usage;
+use sgcore::stardust_output;
 use sgcore::translate;
 
 mod platform;
@@ -45,7 +46,7 @@ pub fn sg_app() -> Command {
         .about(about)
         .override_usage(format_usage(&translate!("who-usage")))
         .infer_long_args(true);
-    sgcore::clap_localization::configure_localized_command(cmd)
+    let cmd = sgcore::clap_localization::configure_localized_command(cmd)
         .arg(
             Arg::new(options::ALL)
                 .long(options::ALL)
@@ -97,6 +98,8 @@ pub fn sg_app() -> Command {
             Arg::new(options::PROCESS)
                 .long(options::PROCESS)
                 .short('p')
+                // GNU accepts --p; --pretty would make the prefix ambiguous
+                .alias("p")
                 .help(translate!("who-help-process"))
                 .action(ArgAction::SetTrue)
         )
@@ -148,5 +151,7 @@ pub fn sg_app() -> Command {
             Arg::new(options::FILE)
                 .num_args(1..=2)
                 .value_hint(clap::ValueHint::FilePath)
-        )
+        );
+
+    stardust_output::add_json_args(cmd)
 }
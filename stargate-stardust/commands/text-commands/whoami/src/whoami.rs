@@ -14,12 +14,11 @@ pub fn sgmain(args: impl sgcore::Args) -> SGResult<()> {
 
     let matches = sgcore::clap_localization::handle_clap_result(sg_app(), args)?;
     let opts = StardustOutputOptions::from_matches(&matches);
-    let field_filter = matches.get_one::<String>(stardust_output::ARG_FIELD).map(|s| s.as_str());
     let username = whoami()?;
 
     if opts.stardust_output {
         let username_str = username.to_string_lossy().to_string();
-        let output = stardust_output::filter_fields(json!({"username": username_str}), field_filter);
+        let output = json!({"username": username_str});
         stardust_output::output(opts, output, || Ok(()))?;
     } else {
         println_verbatim(username).map_err_context(|| translate!("whoami-error-failed-to-print"))?;
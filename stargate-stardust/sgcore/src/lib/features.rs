@@ -30,14 +30,20 @@ pub mod i18n;
 pub mod lines;
 #[cfg(feature = "parser")]
 pub mod parser;
+#[cfg(feature = "predicate")]
+pub mod predicate;
 #[cfg(feature = "quoting-style")]
 pub mod quoting_style;
 #[cfg(feature = "ranges")]
 pub mod ranges;
+#[cfg(feature = "render")]
+pub mod render;
 #[cfg(feature = "ringbuffer")]
 pub mod ringbuffer;
 #[cfg(feature = "sum")]
 pub mod sum;
+#[cfg(feature = "tabular")]
+pub mod tabular;
 #[cfg(feature = "time")]
 pub mod time;
 #[cfg(feature = "update-control")]
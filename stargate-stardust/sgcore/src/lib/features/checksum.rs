@@ -2,6 +2,7 @@
 
 use data_encoding::BASE64;
 use os_display::Quotable;
+use serde_json::{Value, json};
 use std::{
     borrow::Cow,
     ffi::OsStr,
@@ -22,6 +23,7 @@ use crate::{
         Blake2b, Blake3, Bsd, CRC32B, Crc, Digest, DigestWriter, Md5, Sha1, Sha3_224, Sha3_256,
         Sha3_384, Sha3_512, Sha224, Sha256, Sha384, Sha512, Shake128, Shake256, Sm3, SysV,
     },
+    stardust_output::{FORMAT_BYTES, Property, array_schema, create_schema, enum_schema, object_schema},
     util_name,
 };
 use thiserror::Error;
@@ -101,6 +103,146 @@ impl ChecksumResult {
     }
 }
 
+/// How checking one listed file turned out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Failed,
+    Missing,
+    Unreadable,
+}
+
+impl CheckStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Failed => "failed",
+            Self::Missing => "missing",
+            Self::Unreadable => "unreadable",
+        }
+    }
+}
+
+/// One properly formatted line of a checksum file and its outcome
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckRecord {
+    /// The checksum file the line comes from, `-` for standard input
+    pub checksum_file: String,
+    /// Line number in the checksum file, starting at 1
+    pub line: usize,
+    pub path: String,
+    pub algorithm: String,
+    /// The digest the line expects, in hexadecimal
+    pub expected: String,
+    /// The digest computed from the file, if it could be read
+    pub actual: Option<String>,
+    pub status: CheckStatus,
+}
+
+/// What [`perform_checksum_validation_report`] found
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    pub records: Vec<CheckRecord>,
+    /// Number of improperly formatted lines, which have no record
+    pub bad_format: usize,
+}
+
+impl CheckReport {
+    /// The `--obj` document for `--check`: the records and a summary
+    pub fn to_value(&self) -> Value {
+        let count = |status| self.records.iter().filter(|r| r.status == status).count();
+        let results: Vec<Value> = self
+            .records
+            .iter()
+            .map(|record| {
+                json!({
+                    "path": record.path,
+                    "algorithm": record.algorithm,
+                    "expected": record.expected,
+                    "actual": record.actual,
+                    "status": record.status.as_str(),
+                    "checksum_file": record.checksum_file,
+                    "line": record.line,
+                })
+            })
+            .collect();
+        json!({
+            "results": results,
+            "summary": {
+                "total": self.records.len(),
+                "ok": count(CheckStatus::Ok),
+                "failed": count(CheckStatus::Failed),
+                "missing": count(CheckStatus::Missing),
+                "unreadable": count(CheckStatus::Unreadable),
+                "bad_format": self.bad_format,
+            },
+        })
+    }
+}
+
+/// The `--obj` record for the digest of one file
+pub fn digest_record(path: &OsStr, algorithm: &str, digest: &str, size: usize) -> Value {
+    json!({
+        "path": path.to_string_lossy(),
+        "algorithm": algorithm.to_ascii_lowercase(),
+        "digest": digest,
+        "size": size,
+    })
+}
+
+/// The schema of `--obj` output shared by cksum and hashsum: `files` when
+/// computing digests, `results` and `summary` with `--check`
+pub fn stardust_schema() -> Value {
+    let file = object_schema(vec![
+        Property::new("path", "string").description("The file that was read, - for standard input"),
+        Property::new("algorithm", "string").description("Digest algorithm, e.g. sha256 or crc"),
+        Property::new("digest", "string")
+            .description("The digest as the text output shows it (hexadecimal, base64 or decimal)"),
+        Property::new("size", "integer").format(FORMAT_BYTES).description("Number of bytes read"),
+    ]);
+    let result = object_schema(vec![
+        Property::new("path", "string").description("The file named by the checksum line"),
+        Property::new("algorithm", "string").description("Digest algorithm used for the line"),
+        Property::new("expected", "string")
+            .description("Digest from the checksum line, in hexadecimal"),
+        Property::new("actual", json!({"type": ["string", "null"]}))
+            .description("Digest of the file, null if it could not be read"),
+        Property::new("status", enum_schema(&["ok", "failed", "missing", "unreadable"]))
+            .description("Outcome of the check"),
+        Property::new("checksum_file", "string")
+            .description("Checksum file holding the line, - for standard input"),
+        Property::new("line", "integer").description("Line number in the checksum file"),
+    ]);
+    let summary = object_schema(
+        ["total", "ok", "failed", "missing", "unreadable", "bad_format"]
+            .into_iter()
+            .map(|name| {
+                Property::new(name, "integer").description("Number of checked lines with this outcome")
+            })
+            .collect(),
+    );
+    create_schema(vec![
+        Property::new("files", array_schema(file))
+            .description("Digests of the input files")
+            .optional(),
+        Property::new("results", array_schema(result))
+            .description("Outcome of each checksum line (with --check)")
+            .optional(),
+        Property::new("summary", summary)
+            .description("Counts of the outcomes (with --check)")
+            .optional(),
+    ])
+}
+
+/// What checking a line found, before its outcome is known
+#[derive(Default)]
+struct CheckedLine {
+    path: String,
+    algorithm: String,
+    expected: String,
+    actual: Option<String>,
+}
+
 /// Represents a reason for which the processing of a checksum line
 /// could not proceed to digest comparison.
 enum LineCheckError {
@@ -726,16 +868,17 @@ fn get_expected_digest_as_hex_string(
 /// Returns a reader that reads from the specified file, or from stdin if `filename_to_check` is "-".
 fn get_file_to_check(
     filename: &OsStr,
-    opts: ChecksumOptions
+    opts: ChecksumOptions,
+    out: &mut dyn Write
 ) -> Result<Box<dyn Read>, LineCheckError> {
     let filename_bytes = os_str_as_bytes(filename).expect("UTF-8 error");
 
     if filename == "-" {
         Ok(Box::new(stdin()))
     } else {
-        let failed_open = || {
+        let mut failed_open = || {
             print_file_report(
-                std::io::stdout(),
+                &mut *out,
                 filename_bytes,
                 FileChecksumResult::CantOpen,
                 "",
@@ -850,12 +993,17 @@ fn compute_and_check_digest_from_file(
     filename: &[u8],
     expected_checksum: &str,
     mut algo: HashAlgorithm,
-    opts: ChecksumOptions
+    opts: ChecksumOptions,
+    checked: &mut CheckedLine,
+    out: &mut dyn Write
 ) -> Result<(), LineCheckError> {
     let (filename_to_check_unescaped, prefix) = unescape_filename(filename);
     let real_filename_to_check = os_str_from_bytes(&filename_to_check_unescaped)?;
+    checked.path = real_filename_to_check.to_string_lossy().into_owned();
+    checked.algorithm = algo.name.to_ascii_lowercase();
+    checked.expected = expected_checksum.to_string();
 
-    let file_to_check = get_file_to_check(&real_filename_to_check, opts)?;
+    let file_to_check = get_file_to_check(&real_filename_to_check, opts, out)?;
     let mut file_reader = BufReader::new(file_to_check);
 
     let create_fn = &mut algo.create_fn;
@@ -864,8 +1012,9 @@ fn compute_and_check_digest_from_file(
         digest_reader(&mut digest, &mut file_reader, opts.binary, algo.bits).unwrap();
 
     let checksum_correct = expected_checksum == calculated_checksum;
+    checked.actual = Some(calculated_checksum);
     print_file_report(
-        std::io::stdout(),
+        out,
         filename,
         FileChecksumResult::from_bool(checksum_correct),
         prefix,
@@ -884,7 +1033,9 @@ fn process_algo_based_line(
     line_info: &LineInfo,
     cli_algo_name: Option<&str>,
     opts: ChecksumOptions,
-    last_algo: &mut Option<String>
+    last_algo: &mut Option<String>,
+    checked: &mut CheckedLine,
+    out: &mut dyn Write
 ) -> Result<(), LineCheckError> {
     let filename_to_check = line_info.filename.as_slice();
 
@@ -901,7 +1052,14 @@ fn process_algo_based_line(
 
     let algo = detect_algo(&algo_name, algo_byte_len)?;
 
-    compute_and_check_digest_from_file(filename_to_check, &expected_checksum, algo, opts)
+    compute_and_check_digest_from_file(
+        filename_to_check,
+        &expected_checksum,
+        algo,
+        opts,
+        checked,
+        out
+    )
 }
 
 /// Check a digest checksum with non-algo based pre-treatment.
@@ -910,7 +1068,9 @@ fn process_non_algo_based_line(
     line_info: &LineInfo,
     cli_algo_name: &str,
     cli_algo_length: Option<usize>,
-    opts: ChecksumOptions
+    opts: ChecksumOptions,
+    checked: &mut CheckedLine,
+    out: &mut dyn Write
 ) -> Result<(), LineCheckError> {
     let mut filename_to_check = line_info.filename.as_slice();
     if filename_to_check.starts_with(b"*")
@@ -937,7 +1097,14 @@ fn process_non_algo_based_line(
 
     let algo = detect_algo(&algo_name, algo_byte_len)?;
 
-    compute_and_check_digest_from_file(filename_to_check, &expected_checksum, algo, opts)
+    compute_and_check_digest_from_file(
+        filename_to_check,
+        &expected_checksum,
+        algo,
+        opts,
+        checked,
+        out
+    )
 }
 
 /// Parses a checksum line, detect the algorithm to use, read the file and produce
@@ -946,6 +1113,7 @@ fn process_non_algo_based_line(
 /// Returns `Ok(bool)` if the comparison happened, bool indicates if the digest
 /// matched the expected.
 /// If the comparison didn't happen, return a `LineChecksumError`.
+#[allow(clippy::too_many_arguments)]
 fn process_checksum_line(
     line: &OsStr,
     i: usize,
@@ -953,7 +1121,9 @@ fn process_checksum_line(
     cli_algo_length: Option<usize>,
     opts: ChecksumOptions,
     cached_line_format: &mut Option<LineFormat>,
-    last_algo: &mut Option<String>
+    last_algo: &mut Option<String>,
+    checked: &mut CheckedLine,
+    out: &mut dyn Write
 ) -> Result<(), LineCheckError> {
     let line_bytes = os_str_as_bytes(line).map_err(|e| LineCheckError::SGError(Box::new(e)))?;
 
@@ -966,9 +1136,9 @@ fn process_checksum_line(
     };
 
     if line_info.format == LineFormat::AlgoBased {
-        process_algo_based_line(&line_info, cli_algo_name, opts, last_algo)
+        process_algo_based_line(&line_info, cli_algo_name, opts, last_algo, checked, out)
     } else if let Some(cli_algo) = cli_algo_name {
-        process_non_algo_based_line(i, &line_info, cli_algo, cli_algo_length, opts)
+        process_non_algo_based_line(i, &line_info, cli_algo, cli_algo_length, opts, checked, out)
     } else {
         Err(LineCheckError::ImproperlyFormatted)
     }
@@ -978,9 +1148,16 @@ fn process_checksum_file(
     filename_input: &OsStr,
     cli_algo_name: Option<&str>,
     cli_algo_length: Option<usize>,
-    opts: ChecksumOptions
+    opts: ChecksumOptions,
+    mut report: Option<&mut CheckReport>
 ) -> Result<(), FileCheckError> {
     let mut res = ChecksumResult::default();
+    // With a report, the per-file status lines go into it instead
+    let mut out: Box<dyn Write> = if report.is_some() {
+        Box::new(io::sink())
+    } else {
+        Box::new(io::stdout())
+    };
 
     let input_is_stdin = filename_input == OsStr::new("-");
 
@@ -1003,6 +1180,7 @@ fn process_checksum_file(
     let mut last_algo = None;
 
     for (i, line) in lines.iter().enumerate() {
+        let mut checked = CheckedLine::default();
         let line_result = process_checksum_line(
             line,
             i,
@@ -1010,7 +1188,9 @@ fn process_checksum_file(
             cli_algo_length,
             opts,
             &mut cached_line_format,
-            &mut last_algo
+            &mut last_algo,
+            &mut checked,
+            &mut out
         );
 
         use LineCheckError::*;
@@ -1046,6 +1226,29 @@ fn process_checksum_file(
             Err(FileNotFound) if !opts.ignore_missing => res.failed_open_file += 1,
             _ => (),
         }
+
+        if let Some(report) = report.as_deref_mut() {
+            let status = match line_result {
+                Ok(()) => CheckStatus::Ok,
+                Err(DigestMismatch) => CheckStatus::Failed,
+                Err(CantOpenFile | FileIsDirectory) => CheckStatus::Unreadable,
+                Err(FileNotFound) if !opts.ignore_missing => CheckStatus::Missing,
+                Err(ImproperlyFormatted) => {
+                    report.bad_format += 1;
+                    continue;
+                }
+                _ => continue,
+            };
+            report.records.push(CheckRecord {
+                checksum_file: filename_input.to_string_lossy().into_owned(),
+                line: i + 1,
+                path: checked.path,
+                algorithm: checked.algorithm,
+                expected: checked.expected,
+                actual: checked.actual,
+                status,
+            });
+        }
     }
 
     if res.total_properly_formatted() == 0 {
@@ -1055,7 +1258,7 @@ fn process_checksum_file(
         return Err(FileCheckError::Failed);
     }
 
-    if opts.verbose.over_status() {
+    if opts.verbose.over_status() && report.is_none() {
         print_cksum_report(&res);
     }
 
@@ -1092,6 +1295,34 @@ pub fn perform_checksum_validation<'a, I>(
     length_input: Option<usize>,
     opts: ChecksumOptions
 ) -> SGResult<()>
+where
+    I: Iterator<Item = &'a OsStr>,
+{
+    validate_checksum_files(files, algo_name_input, length_input, opts, None)
+}
+
+/// Do the checksum validation like [`perform_checksum_validation`], but
+/// gather the outcome of each line into `report` instead of printing it
+pub fn perform_checksum_validation_report<'a, I>(
+    files: I,
+    algo_name_input: Option<&str>,
+    length_input: Option<usize>,
+    opts: ChecksumOptions,
+    report: &mut CheckReport
+) -> SGResult<()>
+where
+    I: Iterator<Item = &'a OsStr>,
+{
+    validate_checksum_files(files, algo_name_input, length_input, opts, Some(report))
+}
+
+fn validate_checksum_files<'a, I>(
+    files: I,
+    algo_name_input: Option<&str>,
+    length_input: Option<usize>,
+    opts: ChecksumOptions,
+    mut report: Option<&mut CheckReport>
+) -> SGResult<()>
 where
     I: Iterator<Item = &'a OsStr>,
 {
@@ -1099,7 +1330,13 @@ where
 
     for filename_input in files {
         use FileCheckError::*;
-        match process_checksum_file(filename_input, algo_name_input, length_input, opts) {
+        match process_checksum_file(
+            filename_input,
+            algo_name_input,
+            length_input,
+            opts,
+            report.as_deref_mut()
+        ) {
             Err(SGError(e)) => return Err(e),
             Err(Failed | CantOpenChecksumFile) => failed = true,
             Ok(_) => (),
@@ -0,0 +1,694 @@
+// spell-checker:ignore (vars) KiB MiB GiB TiB PiB EiB
+
+//! Predicate expressions over stardust records, as used by `where-object`
+//! and the language's `.where()` method.
+//!
+//! ```text
+//! size > 10k and name ~ "\.rs$"
+//! type in ["file", "symlink"] and not hidden
+//! modified >= 2024-01-01 or owner.name == "root"
+//! modified > 7d ago
+//! ```
+//!
+//! Comparisons are `==` (or `=`), `!=`, `<`, `<=`, `>`, `>=`, `~` (regex
+//! match) and `!~`, combined with `and`/`&&`, `or`/`||` and `not`/`!`.
+//! A field path on its own tests the field for truthiness. Paths are those
+//! of [`crate::field_path`]; one that fans out, as in `tags[*] == "x"`,
+//! holds when any value it reaches does.
+//!
+//! Size literals (`10k`, `1.5M`, `2GiB`) use powers of 1024, except the
+//! SI forms `KB`, `MB`, … which use powers of 1000. Date literals
+//! (`2024-01-01`, `2024-01-01T12:00:00`) are taken in the local time zone
+//! and compared against fields holding unix seconds or date strings.
+
+use jiff::civil::{Date, DateTime};
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, Zoned};
+use regex::Regex;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::field_path::{FieldPath, Segment};
+use crate::json_adapter::records_key;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+pub enum Literal {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    /// Unix seconds, from a date literal or a `… ago` age
+    Time(i64),
+}
+
+/// A parsed predicate expression
+#[derive(Debug, Clone)]
+pub enum Predicate {
+    And(Box<Self>, Box<Self>),
+    Or(Box<Self>, Box<Self>),
+    Not(Box<Self>),
+    Compare(FieldPath, CompareOp, Literal),
+    Matches(FieldPath, Regex),
+    In(FieldPath, Vec<Literal>),
+    Truthy(FieldPath),
+}
+
+impl Predicate {
+    /// Parse a predicate expression
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err("empty predicate".to_string());
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let predicate = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(predicate),
+            Some(token) => Err(format!("unexpected {token} in predicate")),
+        }
+    }
+
+    /// Whether `record` satisfies the predicate
+    pub fn matches(&self, record: &Value) -> bool {
+        match self {
+            Self::And(left, right) => left.matches(record) && right.matches(record),
+            Self::Or(left, right) => left.matches(record) || right.matches(record),
+            Self::Not(inner) => !inner.matches(record),
+            Self::Compare(path, op, literal) => test_field(path, record, |field| compare(field, *op, literal)),
+            Self::Matches(path, regex) => test_field(path, record, |field| {
+                scalar_text(field).is_some_and(|text| regex.is_match(&text))
+            }),
+            Self::In(path, literals) => test_field(path, record, |field| {
+                literals.iter().any(|literal| compare(field, CompareOp::Eq, literal))
+            }),
+            Self::Truthy(path) => test_field(path, record, is_truthy),
+        }
+    }
+
+    /// Keep the records of `value` that satisfy the predicate.
+    ///
+    /// `value` is either an array of records or a stardust object whose
+    /// records sit under `key`, or under the array found by [`records_key`]
+    /// when no key is given. Objects keep their other fields, and a `count`
+    /// field is updated to the number of records kept.
+    pub fn filter(&self, value: &Value, key: Option<&str>) -> Result<Value, String> {
+        match value {
+            Value::Array(records) if key.is_none() => Ok(Value::Array(self.filter_records(records))),
+            Value::Object(map) => {
+                let key = match key {
+                    Some(key) => key,
+                    None => records_key(map)
+                        .ok_or_else(|| "input object has no array of records".to_string())?,
+                };
+                let records = map
+                    .get(key)
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| format!("field '{key}' is not an array"))?;
+
+                let kept = self.filter_records(records);
+                let mut result = map.clone();
+                if result.contains_key("count") {
+                    result.insert("count".to_string(), Value::from(kept.len()));
+                }
+                result.insert(key.to_string(), Value::Array(kept));
+                Ok(Value::Object(result))
+            }
+            _ => Err("input must be an array or an object with an array of records".to_string()),
+        }
+    }
+
+    fn filter_records(&self, records: &[Value]) -> Vec<Value> {
+        records.iter().filter(|record| self.matches(record)).cloned().collect()
+    }
+}
+
+/// Test the field at `path`, null when missing; a path that fans out, such
+/// as `tags[*]`, passes when any of the values it reaches does
+fn test_field(path: &FieldPath, record: &Value, test: impl Fn(&Value) -> bool) -> bool {
+    match path.select(record) {
+        (values, true) => values.into_iter().any(test),
+        (values, false) => test(values.first().copied().unwrap_or(&Value::Null)),
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(arr) => !arr.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn field_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// A field as unix seconds: numbers are taken as seconds, strings are parsed
+/// as timestamps or local dates
+fn field_time(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_f64().map(|n| n as i64),
+        Value::String(s) => parse_date(s.trim()),
+        _ => None,
+    }
+}
+
+fn compare(field: &Value, op: CompareOp, literal: &Literal) -> bool {
+    let ordering = match literal {
+        Literal::Null => Some(if field.is_null() { Ordering::Equal } else { Ordering::Greater }),
+        Literal::Bool(b) => field.as_bool().map(|f| f.cmp(b)),
+        Literal::Number(n) => field_number(field).and_then(|f| f.partial_cmp(n)),
+        Literal::Time(t) => field_time(field).map(|f| f.cmp(t)),
+        Literal::String(s) => match field {
+            Value::String(f) => Some(f.as_str().cmp(s.as_str())),
+            _ => scalar_text(field).map(|f| f.as_str().cmp(s.as_str())),
+        },
+    };
+
+    match (op, ordering) {
+        (CompareOp::Ne, ordering) => ordering != Some(Ordering::Equal),
+        (_, None) => false,
+        // null only supports equality
+        (_, Some(_)) if matches!(literal, Literal::Null) && op != CompareOp::Eq => false,
+        (CompareOp::Eq, Some(ordering)) => ordering == Ordering::Equal,
+        (CompareOp::Lt, Some(ordering)) => ordering == Ordering::Less,
+        (CompareOp::Le, Some(ordering)) => ordering != Ordering::Greater,
+        (CompareOp::Gt, Some(ordering)) => ordering == Ordering::Greater,
+        (CompareOp::Ge, Some(ordering)) => ordering != Ordering::Less,
+    }
+}
+
+/// Parse `2024-01-01`, `2024-01-01T12:00:00` (local time) or a timestamp
+/// with an offset such as `2024-01-01T12:00:00Z` into unix seconds
+pub fn parse_date(text: &str) -> Option<i64> {
+    if let Ok(timestamp) = text.parse::<Timestamp>() {
+        return Some(timestamp.as_second());
+    }
+    let zoned: Option<Zoned> = if let Ok(datetime) = text.parse::<DateTime>() {
+        datetime.to_zoned(TimeZone::system()).ok()
+    } else if let Ok(date) = text.parse::<Date>() {
+        date.to_zoned(TimeZone::system()).ok()
+    } else {
+        None
+    };
+    zoned.map(|z| z.timestamp().as_second())
+}
+
+/// Multiplier for a size suffix: `k`/`K`/`KiB` are 1024, `KB` is 1000
+fn size_multiplier(unit: &str) -> Option<f64> {
+    let (prefix, base) = match unit {
+        "" | "b" | "B" => return Some(1.0),
+        _ if unit.len() == 3 && unit.ends_with("iB") => (&unit[..1], 1024_f64),
+        _ if unit.len() == 2 && unit.ends_with('B') => (&unit[..1], 1000_f64),
+        _ if unit.len() == 1 => (unit, 1024_f64),
+        _ => return None,
+    };
+    let power = match prefix {
+        "k" | "K" => 1,
+        "M" => 2,
+        "G" => 3,
+        "T" => 4,
+        "P" => 5,
+        "E" => 6,
+        _ => return None,
+    };
+    Some(base.powi(power))
+}
+
+/// Seconds in a duration unit, for `… ago` literals
+fn duration_seconds(unit: &str) -> Option<f64> {
+    match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(1.0),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(60.0),
+        "h" | "hour" | "hours" => Some(3600.0),
+        "d" | "day" | "days" => Some(86_400.0),
+        "w" | "week" | "weeks" => Some(604_800.0),
+        "y" | "year" | "years" => Some(31_557_600.0),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Path(FieldPath),
+    Word(String),
+    String(String),
+    /// A numeric word, possibly with a unit or date syntax, resolved by the parser
+    Number(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "'{path}'"),
+            Self::Word(word) | Self::Number(word) => write!(f, "'{word}'"),
+            Self::String(s) => write!(f, "\"{s}\""),
+            Self::Op(op) => write!(f, "'{op}'"),
+            Self::LParen => write!(f, "'('"),
+            Self::RParen => write!(f, "')'"),
+            Self::LBracket => write!(f, "'['"),
+            Self::RBracket => write!(f, "']'"),
+            Self::Comma => write!(f, "','"),
+        }
+    }
+}
+
+const KEYWORDS: [&str; 7] = ["and", "or", "not", "in", "true", "false", "null"];
+
+// Longest first so that `<=` is not read as `<`
+const OPERATORS: [&str; 13] = [
+    "==", "!=", "<=", ">=", "=~", "!~", "&&", "||", "=", "<", ">", "~", "!",
+];
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$' || c == '@'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '$' || c == '@'
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '(' => tokens.push(Token::LParen),
+            ')' => tokens.push(Token::RParen),
+            '[' => tokens.push(Token::LBracket),
+            ']' => tokens.push(Token::RBracket),
+            ',' => tokens.push(Token::Comma),
+            '"' | '\'' => {
+                let (text, next) = read_string(&chars, i)?;
+                tokens.push(Token::String(text));
+                i = next;
+                continue;
+            }
+            _ if c.is_ascii_digit()
+                || (c == '-' && chars.get(i + 1).is_some_and(char::is_ascii_digit)) =>
+            {
+                let start = i;
+                i += 1;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '.' | ':' | '-' | '+'))
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Number(chars[start..i].iter().collect()));
+                continue;
+            }
+            _ if is_ident_start(c) => {
+                let (path, next) = read_path(&chars, i)?;
+                match path.0.as_slice() {
+                    [Segment::Key(word)] if KEYWORDS.contains(&word.as_str()) || word == "ago" => {
+                        tokens.push(Token::Word(word.clone()));
+                    }
+                    _ => tokens.push(Token::Path(path)),
+                }
+                i = next;
+                continue;
+            }
+            _ => {
+                let rest: String = chars[i..].iter().take(2).collect();
+                let op = OPERATORS
+                    .into_iter()
+                    .find(|op| rest.starts_with(op))
+                    .ok_or_else(|| format!("unexpected character '{c}' in predicate"))?;
+                tokens.push(Token::Op(op));
+                i += op.len();
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// Read a quoted string starting at `start`. A backslash escapes the quote
+/// character and itself; other backslashes are kept, so regex escapes such
+/// as `"\.rs$"` pass through unchanged.
+fn read_string(chars: &[char], start: usize) -> Result<(String, usize), String> {
+    let quote = chars[start];
+    let mut text = String::new();
+    let mut i = start + 1;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == quote {
+            return Ok((text, i + 1));
+        }
+        if c == '\\' && chars.get(i + 1).is_some_and(|&next| next == quote || next == '\\') {
+            text.push(chars[i + 1]);
+            i += 2;
+            continue;
+        }
+        text.push(c);
+        i += 1;
+    }
+
+    Err("unterminated string in predicate".to_string())
+}
+
+/// Read a field path such as `owner.name`, `items[0].size` or `tags[*]`
+fn read_path(chars: &[char], start: usize) -> Result<(FieldPath, usize), String> {
+    let mut i = start;
+    let read_key = |i: &mut usize| {
+        while *i < chars.len() && is_ident_char(chars[*i]) {
+            *i += 1;
+        }
+    };
+
+    read_key(&mut i);
+    let first: String = chars[start..i].iter().collect();
+    if !KEYWORDS.contains(&first.as_str()) {
+        loop {
+            match chars.get(i) {
+                Some('.') if chars.get(i + 1).is_some_and(|&c| is_ident_char(c) || c == '*') => {
+                    i += 2;
+                    read_key(&mut i);
+                }
+                Some('[') if chars.get(i + 1).is_some_and(|&c| c.is_ascii_digit() || "-:*\"'".contains(c)) => {
+                    let mut quote = None;
+                    let close = chars[i + 1..]
+                        .iter()
+                        .position(|&c| match quote {
+                            Some(q) if c == q => {
+                                quote = None;
+                                false
+                            }
+                            Some(_) => false,
+                            None if c == '"' || c == '\'' => {
+                                quote = Some(c);
+                                false
+                            }
+                            None => c == ']',
+                        })
+                        .ok_or_else(|| "unterminated '[' in field path".to_string())?;
+                    i += close + 2;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    let text: String = chars[start..i].iter().collect();
+    Ok((FieldPath::parse(&text)?, i))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        if self.peek() == Some(&Token::Word(word.to_string())) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_op(&mut self, op: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Op(o)) if *o == op) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Predicate, String> {
+        let mut left = self.parse_and()?;
+        while self.eat_word("or") || self.eat_op("||") {
+            let right = self.parse_and()?;
+            left = Predicate::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Predicate, String> {
+        let mut left = self.parse_not()?;
+        while self.eat_word("and") || self.eat_op("&&") {
+            let right = self.parse_not()?;
+            left = Predicate::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Predicate, String> {
+        if self.eat_word("not") || self.eat_op("!") {
+            return Ok(Predicate::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Predicate, String> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err("missing ')' in predicate".to_string()),
+                }
+            }
+            Some(Token::Path(path)) => self.parse_comparison(path),
+            Some(token) => Err(format!("expected a field name, found {token}")),
+            None => Err("unexpected end of predicate".to_string()),
+        }
+    }
+
+    fn parse_comparison(&mut self, path: FieldPath) -> Result<Predicate, String> {
+        if self.eat_word("in") {
+            return Ok(Predicate::In(path, self.parse_list()?));
+        }
+        if matches!(self.tokens.get(self.pos..self.pos + 2), Some([Token::Word(not), Token::Word(in_)]) if not == "not" && in_ == "in")
+        {
+            self.pos += 2;
+            return Ok(Predicate::Not(Box::new(Predicate::In(path, self.parse_list()?))));
+        }
+
+        let op = match self.peek() {
+            Some(Token::Op(op)) if !matches!(*op, "&&" | "||" | "!") => *op,
+            _ => return Ok(Predicate::Truthy(path)),
+        };
+        self.pos += 1;
+
+        if matches!(op, "~" | "=~" | "!~") {
+            let Some(Token::String(pattern)) = self.next() else {
+                return Err(format!("'{op}' expects a quoted regular expression"));
+            };
+            let regex = Regex::new(&pattern).map_err(|e| format!("invalid regex \"{pattern}\": {e}"))?;
+            let matches = Predicate::Matches(path, regex);
+            return Ok(if op == "!~" { Predicate::Not(Box::new(matches)) } else { matches });
+        }
+
+        let op = match op {
+            "==" | "=" => CompareOp::Eq,
+            "!=" => CompareOp::Ne,
+            "<" => CompareOp::Lt,
+            "<=" => CompareOp::Le,
+            ">" => CompareOp::Gt,
+            ">=" => CompareOp::Ge,
+            _ => unreachable!("operator {op} handled above"),
+        };
+        Ok(Predicate::Compare(path, op, self.parse_literal()?))
+    }
+
+    fn parse_list(&mut self) -> Result<Vec<Literal>, String> {
+        if self.next() != Some(Token::LBracket) {
+            return Err("'in' expects a list such as [\"a\", \"b\"]".to_string());
+        }
+        let mut literals = Vec::new();
+        if self.peek() == Some(&Token::RBracket) {
+            self.pos += 1;
+            return Ok(literals);
+        }
+        loop {
+            literals.push(self.parse_literal()?);
+            match self.next() {
+                Some(Token::Comma) => {}
+                Some(Token::RBracket) => return Ok(literals),
+                _ => return Err("expected ',' or ']' in list".to_string()),
+            }
+        }
+    }
+
+    fn parse_literal(&mut self) -> Result<Literal, String> {
+        match self.next() {
+            Some(Token::String(s)) => Ok(Literal::String(s)),
+            Some(Token::Word(word)) => match word.as_str() {
+                "true" => Ok(Literal::Bool(true)),
+                "false" => Ok(Literal::Bool(false)),
+                "null" => Ok(Literal::Null),
+                _ => Err(format!("expected a value, found '{word}'")),
+            },
+            // Bare words compare as strings: `type == file`
+            Some(Token::Path(path)) => Ok(Literal::String(path.to_string())),
+            Some(Token::Number(text)) => {
+                if self.eat_word("ago") {
+                    return parse_age(&text);
+                }
+                parse_number(&text)
+            }
+            Some(token) => Err(format!("expected a value, found {token}")),
+            None => Err("unexpected end of predicate, expected a value".to_string()),
+        }
+    }
+}
+
+fn split_unit(text: &str) -> (&str, &str) {
+    let split = text
+        .find(|c: char| c.is_alphabetic())
+        .unwrap_or(text.len());
+    text.split_at(split)
+}
+
+/// A number, size (`10k`) or date (`2024-01-01`) literal
+fn parse_number(text: &str) -> Result<Literal, String> {
+    if let Ok(n) = text.parse::<f64>() {
+        return Ok(Literal::Number(n));
+    }
+    let (number, unit) = split_unit(text);
+    if let (Ok(n), Some(multiplier)) = (number.parse::<f64>(), size_multiplier(unit)) {
+        return Ok(Literal::Number(n * multiplier));
+    }
+    if let Some(seconds) = parse_date(text) {
+        return Ok(Literal::Time(seconds));
+    }
+    Err(format!("invalid number, size or date '{text}'"))
+}
+
+/// An age such as `7d ago` or `90min ago`, as unix seconds
+fn parse_age(text: &str) -> Result<Literal, String> {
+    let (number, unit) = split_unit(text);
+    let n: f64 = number.parse().map_err(|_| format!("invalid age '{text} ago'"))?;
+    let unit_seconds = duration_seconds(unit)
+        .ok_or_else(|| format!("invalid age '{text} ago': use s, m, h, d, w or y"))?;
+    Ok(Literal::Time(Timestamp::now().as_second() - (n * unit_seconds) as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn check(predicate: &str, record: &Value) -> bool {
+        Predicate::parse(predicate).unwrap().matches(record)
+    }
+
+    #[test]
+    fn comparisons_and_logic() {
+        let record = json!({"name": "main.rs", "size": 20480, "type": "file", "hidden": false});
+        assert!(check(r#"size > 10k and name ~ "\.rs$""#, &record));
+        assert!(!check("size > 1M", &record));
+        assert!(check("size >= 20KiB && size < 21KB", &record));
+        assert!(check(r#"type == "directory" or name = 'main.rs'"#, &record));
+        assert!(check("type == file and not hidden", &record));
+        assert!(check(r#"name !~ "^\." && !(size <= 0)"#, &record));
+        assert!(check("missing == null and missing != 1", &record));
+    }
+
+    #[test]
+    fn in_lists_and_nested_paths() {
+        let record = json!({"owner": {"name": "root"}, "items": [{"size": 3}], "type": "symlink"});
+        assert!(check(r#"type in ["file", "symlink"]"#, &record));
+        assert!(check(r#"type not in ["file"]"#, &record));
+        assert!(check(r#"owner.name == "root""#, &record));
+        assert!(check("items[0].size == 3 and items.0.size == 3", &record));
+        assert!(!check("items[1]", &record));
+        assert!(check("items[-1].size == 3", &record));
+    }
+
+    #[test]
+    fn wildcard_paths_match_any_value() {
+        let record = json!({"tags": ["a", "b"], "results": [{"line": 2}, {"line": 40}]});
+        assert!(check(r#"tags[*] == "b""#, &record));
+        assert!(!check(r#"tags[*] == "c""#, &record));
+        assert!(check("results[*].line > 30 and results.line < 3", &record));
+        assert!(!check("results[0:1].line > 30", &record));
+    }
+
+    #[test]
+    fn dates_and_ages() {
+        let now = Timestamp::now().as_second();
+        let record = json!({"modified": now - 3600, "created": "2020-06-01T10:00:00Z"});
+        assert!(check("modified > 1d ago and modified < 30min ago", &record));
+        assert!(check("created < 2021-01-01 and created >= 2020-01-01T00:00:00", &record));
+        assert!(check("modified > 2024-01-01", &record));
+    }
+
+    #[test]
+    fn filter_keeps_shape() {
+        let listing = json!({"entries": [{"size": 1}, {"size": 5000}], "count": 2});
+        let result = Predicate::parse("size > 1k").unwrap().filter(&listing, None).unwrap();
+        assert_eq!(result, json!({"entries": [{"size": 5000}], "count": 1}));
+
+        let array = json!([1, 2]);
+        assert!(Predicate::parse("x").unwrap().filter(&json!("text"), None).is_err());
+        assert_eq!(Predicate::parse("x").unwrap().filter(&array, None).unwrap(), json!([]));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Predicate::parse("").is_err());
+        assert!(Predicate::parse("size >").is_err());
+        assert!(Predicate::parse("size > 10q").is_err());
+        assert!(Predicate::parse("(size > 1").is_err());
+        assert!(Predicate::parse(r#"name ~ "[""#).is_err());
+        assert!(Predicate::parse("size > 1 size").is_err());
+    }
+}
@@ -0,0 +1,569 @@
+// spell-checker:ignore (vars) mtime atime ctime btime
+
+//! Terminal renderers for stardust: aligned tables, key/value lists and
+//! trees, as used by `format-table`, `format-list`, `format-tree` and the
+//! shell's automatic rendering of pipeline results.
+//!
+//! Column types drive formatting: sizes and unix timestamps are shown in
+//! human-readable form, numbers are right-aligned, and with color enabled
+//! each type gets its own color. Types come from the `format`/`type` of a
+//! JSON schema property when one is given (`"format": "bytes"`,
+//! `"format": "unix-time"`), otherwise from the field name and values.
+
+use jiff::Timestamp;
+use jiff::tz::TimeZone;
+use serde_json::{Map, Value};
+use std::io::IsTerminal;
+use unicode_width::UnicodeWidthChar;
+
+use crate::json_adapter::extract_records;
+use crate::stardust_output::{FORMAT_BYTES, FORMAT_UNIX_TIME};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const BOLD_UNDERLINE: &str = "\x1b[1;4m";
+const DIM: &str = "\x1b[2m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const BLUE: &str = "\x1b[34m";
+const MAGENTA: &str = "\x1b[35m";
+const CYAN: &str = "\x1b[36m";
+
+// Columns are never squeezed below this many cells to fit the terminal
+const MIN_COLUMN_WIDTH: usize = 6;
+const COLUMN_GAP: &str = "  ";
+
+/// How the values of a field are formatted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    Text,
+    Number,
+    /// Byte counts, shown as `1.5K`, `12M`, ...
+    Size,
+    /// Unix seconds, shown as local `YYYY-MM-DD HH:MM`
+    Timestamp,
+    Bool,
+    /// Arrays and objects
+    Nested,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Maximum line width; `None` never truncates or wraps
+    pub width: Option<usize>,
+    pub color: bool,
+    /// Human-readable sizes and timestamps
+    pub human: bool,
+    /// Wrap long cells onto several lines instead of truncating them
+    pub wrap: bool,
+    /// Columns to show, in order; all fields when `None`
+    pub columns: Option<Vec<String>>,
+    /// JSON schema whose `properties` (or `items.properties`) describe the
+    /// record fields
+    pub schema: Option<Value>,
+}
+
+impl RenderOptions {
+    /// Options for output to stdout: the terminal's width and color when
+    /// stdout is a terminal (and `NO_COLOR` is unset), human-readable values
+    pub fn for_stdout() -> Self {
+        let is_terminal = std::io::stdout().is_terminal();
+        Self {
+            width: if is_terminal { terminal_width() } else { None },
+            color: is_terminal && std::env::var_os("NO_COLOR").is_none(),
+            human: true,
+            ..Self::default()
+        }
+    }
+
+    fn paint(&self, text: &str, color: &str) -> String {
+        if self.color && !color.is_empty() && !text.is_empty() {
+            format!("{color}{text}{RESET}")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Width of the terminal on stdout, or `$COLUMNS`
+pub fn terminal_width() -> Option<usize> {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(w), _)| usize::from(w))
+        .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+        .filter(|&w| w > 0)
+}
+
+/// Whether `value` is a non-empty array whose elements are all objects
+pub fn is_record_array(value: &Value) -> bool {
+    value
+        .as_array()
+        .is_some_and(|arr| !arr.is_empty() && arr.iter().all(Value::is_object))
+}
+
+/// Kind of the field `name` given its values and an optional schema
+pub fn column_kind(name: &str, values: &[&Value], schema: Option<&Value>) -> ColumnKind {
+    if let Some(kind) = schema.and_then(|schema| schema_kind(schema, name)) {
+        return kind;
+    }
+
+    let present: Vec<&Value> = values.iter().copied().filter(|v| !v.is_null()).collect();
+    let all = |check: fn(&Value) -> bool| !present.is_empty() && present.iter().all(|v| check(v));
+
+    if all(Value::is_number) {
+        if is_size_name(name) {
+            ColumnKind::Size
+        } else if is_time_name(name) && all(|v| v.as_f64().is_some_and(|n| (1e8..1e11).contains(&n))) {
+            ColumnKind::Timestamp
+        } else {
+            ColumnKind::Number
+        }
+    } else if all(Value::is_boolean) {
+        ColumnKind::Bool
+    } else if present.iter().any(|v| v.is_array() || v.is_object()) {
+        ColumnKind::Nested
+    } else {
+        ColumnKind::Text
+    }
+}
+
+fn schema_kind(schema: &Value, name: &str) -> Option<ColumnKind> {
+    let property = schema_property(schema, name)?;
+    match property.get("format").and_then(Value::as_str) {
+        Some(FORMAT_BYTES) => return Some(ColumnKind::Size),
+        Some(FORMAT_UNIX_TIME) => return Some(ColumnKind::Timestamp),
+        _ => {}
+    }
+    match property.get("type").and_then(Value::as_str)? {
+        "integer" | "number" => Some(ColumnKind::Number),
+        "boolean" => Some(ColumnKind::Bool),
+        "array" | "object" => Some(ColumnKind::Nested),
+        "string" => Some(ColumnKind::Text),
+        _ => None,
+    }
+}
+
+/// The schema of the field `name` of the records `schema` describes: a
+/// record schema, an array schema of records, or a command's `--schema`
+/// whose records sit in an array property such as `entries`
+fn schema_property<'a>(schema: &'a Value, name: &str) -> Option<&'a Value> {
+    if let Some(items) = schema.get("items") {
+        return schema_property(items, name);
+    }
+    let properties = schema.get("properties")?.as_object()?;
+    properties.get(name).or_else(|| {
+        properties
+            .values()
+            .filter(|property| property.get("items").is_some())
+            .find_map(|property| schema_property(property, name))
+    })
+}
+
+fn is_size_name(name: &str) -> bool {
+    matches!(name, "size" | "bytes") || name.ends_with("_size") || name.ends_with("_bytes")
+}
+
+fn is_time_name(name: &str) -> bool {
+    matches!(
+        name,
+        "modified" | "created" | "accessed" | "changed" | "mtime" | "atime" | "ctime" | "btime"
+            | "time" | "timestamp"
+    ) || name.ends_with("_at")
+        || name.ends_with("_time")
+}
+
+/// `1536` → `1.5K`, as `ls -h` prints sizes
+pub fn human_size(bytes: f64) -> String {
+    const UNITS: [&str; 7] = ["", "K", "M", "G", "T", "P", "E"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value.abs() >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}", bytes as i64)
+    } else if value < 10.0 {
+        format!("{:.1}{}", value, UNITS[unit])
+    } else {
+        format!("{:.0}{}", value, UNITS[unit])
+    }
+}
+
+/// Unix seconds as local `YYYY-MM-DD HH:MM`
+pub fn human_time(seconds: i64) -> Option<String> {
+    let timestamp = Timestamp::from_second(seconds).ok()?;
+    Some(timestamp.to_zoned(TimeZone::system()).strftime("%Y-%m-%d %H:%M").to_string())
+}
+
+/// A value as one line of text, formatted for its kind
+pub fn format_value(value: &Value, kind: ColumnKind, human: bool) -> String {
+    let text = match (value, kind) {
+        (Value::Null, _) => String::new(),
+        (Value::Number(n), ColumnKind::Size) if human => human_size(n.as_f64().unwrap_or(0.0)),
+        (Value::Number(n), ColumnKind::Timestamp) if human => n
+            .as_i64()
+            .and_then(human_time)
+            .unwrap_or_else(|| n.to_string()),
+        (Value::String(s), _) => s.clone(),
+        (Value::Array(items), _) if items.iter().all(|v| !v.is_array() && !v.is_object()) => items
+            .iter()
+            .map(|item| format_value(item, ColumnKind::Text, human))
+            .collect::<Vec<_>>()
+            .join(", "),
+        (other, _) => other.to_string(),
+    };
+    // Keep every cell on one line
+    text.chars().map(|c| if c.is_control() { ' ' } else { c }).collect()
+}
+
+fn kind_color(kind: ColumnKind, value: &Value) -> &'static str {
+    match (kind, value) {
+        (_, Value::Null) => "",
+        (ColumnKind::Number, _) => CYAN,
+        (ColumnKind::Size, _) => GREEN,
+        (ColumnKind::Timestamp, _) => BLUE,
+        (ColumnKind::Bool, _) => YELLOW,
+        (ColumnKind::Nested, _) => DIM,
+        (ColumnKind::Text, _) => "",
+    }
+}
+
+fn is_right_aligned(kind: ColumnKind) -> bool {
+    matches!(kind, ColumnKind::Number | ColumnKind::Size)
+}
+
+fn text_width(text: &str) -> usize {
+    text.chars().map(|c| c.width().unwrap_or(0)).sum()
+}
+
+/// Split `text` into pieces at most `width` cells wide
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    let mut line_width = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if line_width + w > width && line_width > 0 {
+            lines.push(String::new());
+            line_width = 0;
+        }
+        lines.last_mut().expect("lines is never empty").push(c);
+        line_width += w;
+    }
+    lines
+}
+
+/// Cut `text` to `width` cells, marking the cut with `…`
+fn truncate_text(text: &str, width: usize) -> String {
+    if text_width(text) <= width {
+        return text.to_string();
+    }
+    let mut result = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        result.push(c);
+        used += w;
+    }
+    result.push('…');
+    result
+}
+
+fn pad(text: &str, width: usize, right_align: bool) -> String {
+    let padding = " ".repeat(width.saturating_sub(text_width(text)));
+    if right_align { format!("{padding}{text}") } else { format!("{text}{padding}") }
+}
+
+/// Column names: the requested ones, or every field in order of first appearance
+fn table_columns(records: &[Value], options: &RenderOptions) -> Vec<String> {
+    if let Some(columns) = &options.columns {
+        return columns.clone();
+    }
+    let mut columns: Vec<String> = Vec::new();
+    for record in records {
+        if let Value::Object(map) = record {
+            for key in map.keys() {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+        }
+    }
+    columns
+}
+
+/// Narrow the widest columns until the row fits in `available` cells. Text
+/// columns give way first so numbers, sizes and dates stay readable.
+fn fit_widths(widths: &mut [usize], kinds: &[ColumnKind], available: usize) {
+    let gaps = COLUMN_GAP.len() * widths.len().saturating_sub(1);
+    let squeezable = |kind: &ColumnKind| matches!(kind, ColumnKind::Text | ColumnKind::Nested);
+
+    for text_only in [true, false] {
+        while widths.iter().sum::<usize>() + gaps > available {
+            let widest = (0..widths.len())
+                .filter(|&i| !text_only || squeezable(&kinds[i]))
+                .max_by_key(|&i| widths[i]);
+            match widest {
+                Some(i) if widths[i] > MIN_COLUMN_WIDTH => widths[i] -= 1,
+                _ => break,
+            }
+        }
+    }
+}
+
+/// Render records as an aligned table with a header row
+pub fn render_table(records: &[Value], options: &RenderOptions) -> String {
+    let columns = table_columns(records, options);
+    if columns.is_empty() {
+        return String::new();
+    }
+
+    let cells: Vec<Vec<&Value>> = records
+        .iter()
+        .map(|record| columns.iter().map(|c| record.get(c).unwrap_or(&Value::Null)).collect())
+        .collect();
+    let kinds: Vec<ColumnKind> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let values: Vec<&Value> = cells.iter().map(|row| row[i]).collect();
+            column_kind(name, &values, options.schema.as_ref())
+        })
+        .collect();
+    let texts: Vec<Vec<String>> = cells
+        .iter()
+        .map(|row| row.iter().zip(&kinds).map(|(v, k)| format_value(v, *k, options.human)).collect())
+        .collect();
+
+    let mut widths: Vec<usize> = columns.iter().map(|c| text_width(c)).collect();
+    for row in &texts {
+        for (width, text) in widths.iter_mut().zip(row) {
+            *width = (*width).max(text_width(text));
+        }
+    }
+    if let Some(available) = options.width {
+        fit_widths(&mut widths, &kinds, available);
+    }
+
+    let mut out = String::new();
+    let header: Vec<String> = columns
+        .iter()
+        .zip(&widths)
+        .zip(&kinds)
+        .map(|((name, &w), &kind)| {
+            let text = pad(&truncate_text(name, w), w, is_right_aligned(kind));
+            options.paint(&text, BOLD_UNDERLINE)
+        })
+        .collect();
+    push_line(&mut out, &header);
+
+    for (row, values) in texts.iter().zip(&cells) {
+        let pieces: Vec<Vec<String>> = row
+            .iter()
+            .zip(&widths)
+            .map(|(text, &w)| {
+                if options.wrap { wrap_text(text, w) } else { vec![truncate_text(text, w)] }
+            })
+            .collect();
+        let height = pieces.iter().map(Vec::len).max().unwrap_or(1);
+
+        for line in 0..height {
+            let parts: Vec<String> = pieces
+                .iter()
+                .enumerate()
+                .map(|(i, piece)| {
+                    let text = piece.get(line).map_or("", String::as_str);
+                    let padded = pad(text, widths[i], is_right_aligned(kinds[i]));
+                    options.paint(&padded, kind_color(kinds[i], values[i]))
+                })
+                .collect();
+            push_line(&mut out, &parts);
+        }
+    }
+
+    out
+}
+
+fn push_line(out: &mut String, parts: &[String]) {
+    out.push_str(parts.join(COLUMN_GAP).trim_end());
+    out.push('\n');
+}
+
+/// Render each record as `key : value` lines, with nested objects and arrays
+/// indented beneath their key. Records are separated by a blank line.
+pub fn render_list(value: &Value, options: &RenderOptions) -> String {
+    let records: Vec<&Value> = match extract_records(value, None) {
+        Ok(records) => records.iter().collect(),
+        Err(_) => vec![value],
+    };
+
+    let blocks: Vec<String> = records
+        .into_iter()
+        .map(|record| {
+            let mut out = String::new();
+            match record {
+                Value::Object(map) => list_fields(map, 0, options, &mut out),
+                other => {
+                    out.push_str(&format_value(other, ColumnKind::Text, options.human));
+                    out.push('\n');
+                }
+            }
+            out
+        })
+        .collect();
+    blocks.join("\n")
+}
+
+fn list_fields(map: &Map<String, Value>, depth: usize, options: &RenderOptions, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    let key_width = map.keys().map(|k| text_width(k)).max().unwrap_or(0);
+
+    for (key, value) in map {
+        let label = options.paint(&pad(key, key_width, false), BOLD);
+        match value {
+            Value::Object(inner) if !inner.is_empty() => {
+                out.push_str(&format!("{indent}{label} :\n"));
+                list_fields(inner, depth + 1, options, out);
+            }
+            Value::Array(items) if items.iter().any(|v| v.is_array() || v.is_object()) => {
+                out.push_str(&format!("{indent}{label} :\n"));
+                for (i, item) in items.iter().enumerate() {
+                    match item {
+                        Value::Object(inner) => {
+                            out.push_str(&format!("{indent}  [{i}]\n"));
+                            list_fields(inner, depth + 2, options, out);
+                        }
+                        other => out.push_str(&format!(
+                            "{indent}  [{i}] {}\n",
+                            format_value(other, ColumnKind::Text, options.human)
+                        )),
+                    }
+                }
+            }
+            _ => {
+                let kind = column_kind(key, &[value], options.schema.as_ref());
+                let text = format_value(value, kind, options.human);
+                let text = match options.width {
+                    Some(width) if !options.wrap => {
+                        let used = text_width(&indent) + key_width + 3;
+                        truncate_text(&text, width.saturating_sub(used).max(MIN_COLUMN_WIDTH))
+                    }
+                    _ => text,
+                };
+                out.push_str(&format!("{indent}{label} : {}\n", options.paint(&text, kind_color(kind, value))));
+            }
+        }
+    }
+}
+
+/// Render nested data as a tree. Array elements that are objects are labelled
+/// by their `name`, `path` or `id` field when they have one.
+pub fn render_tree(value: &Value, options: &RenderOptions) -> String {
+    let mut out = String::from(".\n");
+    tree_children(value, "", options, &mut out);
+    out
+}
+
+fn tree_children(value: &Value, prefix: &str, options: &RenderOptions, out: &mut String) {
+    let children: Vec<(String, &Value)> = match value {
+        Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (element_label(i, item), item))
+            .collect(),
+        _ => return,
+    };
+
+    let count = children.len();
+    for (i, (plain_label, child)) in children.into_iter().enumerate() {
+        let last = i + 1 == count;
+        let branch = if last { "└── " } else { "├── " };
+        let label = options.paint(&plain_label, MAGENTA);
+        match child {
+            Value::Object(_) | Value::Array(_) => {
+                out.push_str(&format!("{prefix}{branch}{label}\n"));
+                let next = format!("{prefix}{}", if last { "    " } else { "│   " });
+                tree_children(child, &next, options, out);
+            }
+            scalar => {
+                // Field names pick the kind, as for table columns
+                let name = if value.is_object() { plain_label.as_str() } else { "" };
+                let kind = column_kind(name, &[scalar], options.schema.as_ref());
+                let text = options.paint(&format_value(scalar, kind, options.human), kind_color(kind, scalar));
+                out.push_str(&format!("{prefix}{branch}{label}: {text}\n"));
+            }
+        }
+    }
+}
+
+fn element_label(index: usize, item: &Value) -> String {
+    ["name", "path", "id"]
+        .iter()
+        .find_map(|field| item.get(field).and_then(Value::as_str))
+        .map_or_else(|| format!("[{index}]"), |name| format!("[{index}] {name}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plain() -> RenderOptions {
+        RenderOptions { human: true, ..RenderOptions::default() }
+    }
+
+    #[test]
+    fn table_aligns_and_humanizes() {
+        let records = json!([
+            {"name": "a.rs", "size": 1536},
+            {"name": "long_name.rs", "size": 12},
+        ]);
+        let table = render_table(records.as_array().unwrap(), &plain());
+        assert_eq!(table, "name          size\na.rs          1.5K\nlong_name.rs    12\n");
+    }
+
+    #[test]
+    fn table_fits_width() {
+        let records = json!([{"path": "x".repeat(40), "n": 1}]);
+        let options = RenderOptions { width: Some(20), ..plain() };
+        let table = render_table(records.as_array().unwrap(), &options);
+        assert!(table.lines().all(|line| text_width(line) <= 20), "{table}");
+        assert!(table.contains('…'));
+
+        let wrapped = RenderOptions { wrap: true, ..options };
+        assert_eq!(render_table(records.as_array().unwrap(), &wrapped).lines().count(), 4);
+    }
+
+    #[test]
+    fn kinds_from_names_and_schema() {
+        let t = json!(1_700_000_000);
+        assert_eq!(column_kind("modified", &[&t], None), ColumnKind::Timestamp);
+        assert_eq!(column_kind("pid", &[&t], None), ColumnKind::Number);
+        let schema = json!({"properties": {"used": {"type": "integer", "format": "bytes"}}});
+        assert_eq!(column_kind("used", &[&t], Some(&schema)), ColumnKind::Size);
+        let command_schema = json!({"properties": {
+            "count": {"type": "integer"},
+            "entries": {"type": "array", "items": {"properties": {
+                "size": {"type": "integer"},
+                "when": {"type": "integer", "format": "unix-time"},
+            }}},
+        }});
+        assert_eq!(column_kind("when", &[&t], Some(&command_schema)), ColumnKind::Timestamp);
+        assert_eq!(column_kind("size", &[&t], Some(&command_schema)), ColumnKind::Number);
+        assert_eq!(human_size(5.0 * 1024.0 * 1024.0 * 1024.0), "5.0G");
+        assert_eq!(render_tree(&json!({"size": 2048}), &plain()), ".\n└── size: 2.0K\n");
+    }
+
+    #[test]
+    fn list_and_tree() {
+        let value = json!({"entries": [{"name": "a", "owner": {"uid": 0}}], "count": 1});
+        assert_eq!(render_list(&value, &plain()), "name  : a\nowner :\n  uid : 0\n");
+        assert_eq!(
+            render_tree(&value, &plain()),
+            ".\n├── count: 1\n└── entries\n    └── [0] a\n        ├── name: a\n        └── owner\n            └── uid: 0\n"
+        );
+    }
+}
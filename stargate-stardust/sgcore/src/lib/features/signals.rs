@@ -3,11 +3,14 @@
 
 //! This module provides a way to handle signals in a platform-independent way.
 //! It provides a way to convert signal names to their corresponding values and vice versa.
-//! It also provides a way to ignore the SIGINT signal and enable pipe errors.
+//! It also provides a way to ignore the SIGINT signal and enable pipe errors,
+//! and to catch signals so that they can be handled at a safe point.
 use nix::errno::Errno;
 use nix::sys::signal::{
-    SigHandler::SigDfl, SigHandler::SigIgn, Signal::SIGINT, Signal::SIGPIPE, signal,
+    SaFlags, SigAction, SigHandler, SigHandler::SigDfl, SigHandler::SigIgn, SigSet, Signal,
+    Signal::SIGINT, Signal::SIGPIPE, sigaction, signal,
 };
+use std::sync::atomic::{AtomicU64, Ordering};
 
 /// The default signal value.
 pub static DEFAULT_SIGNAL: usize = 15;
@@ -302,6 +305,41 @@ pub fn ignore_interrupts() -> Result<(), Errno> {
     unsafe { signal(SIGINT, SigIgn) }.map(|_| ())
 }
 
+/// Bit set of caught signals delivered since the last [`take_pending_signals`].
+static PENDING_SIGNALS: AtomicU64 = AtomicU64::new(0);
+
+extern "C" fn record_signal(signal_value: nix::libc::c_int) {
+    if (0..64).contains(&signal_value) {
+        PENDING_SIGNALS.fetch_or(1 << signal_value, Ordering::SeqCst);
+    }
+}
+
+fn set_handler(signal_value: usize, handler: SigHandler) -> Result<(), Errno> {
+    let signal = i32::try_from(signal_value)
+        .map_err(|_| Errno::EINVAL)
+        .and_then(Signal::try_from)?;
+    let action = SigAction::new(handler, SaFlags::SA_RESTART, SigSet::empty());
+    // SAFETY: the handler only performs an atomic store, which is async-signal-safe.
+    unsafe { sigaction(signal, &action) }.map(|_| ())
+}
+
+/// Catches the given signal: instead of running its default action, its
+/// delivery is recorded and reported by [`take_pending_signals`].
+pub fn catch_signal(signal_value: usize) -> Result<(), Errno> {
+    set_handler(signal_value, SigHandler::Handler(record_signal))
+}
+
+/// Restores the default action of the given signal.
+pub fn reset_signal(signal_value: usize) -> Result<(), Errno> {
+    set_handler(signal_value, SigDfl)
+}
+
+/// Returns the caught signals delivered since the last call, lowest first.
+pub fn take_pending_signals() -> Vec<usize> {
+    let pending = PENDING_SIGNALS.swap(0, Ordering::SeqCst);
+    (0..64).filter(|n| pending & (1 << n) != 0).collect()
+}
+
 #[test]
 fn signal_by_value() {
     assert_eq!(signal_by_name_or_value("0"), Some(0));
@@ -333,3 +371,13 @@ fn name() {
         assert_eq!(signal_name_by_value(value), Some(*signal));
     }
 }
+
+#[test]
+fn catch_and_take_pending() {
+    let usr1 = signal_by_name_or_value("USR1").unwrap();
+    catch_signal(usr1).unwrap();
+    nix::sys::signal::raise(Signal::SIGUSR1).unwrap();
+    assert_eq!(take_pending_signals(), vec![usr1]);
+    assert!(take_pending_signals().is_empty());
+    reset_signal(usr1).unwrap();
+}
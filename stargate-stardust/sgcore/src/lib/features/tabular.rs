@@ -0,0 +1,396 @@
+//! Conversion between delimited text (CSV, TSV) and stardust records, as
+//! used by the `from-*` and `to-*` converters.
+//!
+//! Parsing and writing follow RFC 4180: fields containing the delimiter, the
+//! quote character or a line break are quoted, and quotes inside quoted
+//! fields are doubled. Parsed fields can have their types inferred: numbers,
+//! `true`/`false` and empty fields (as null) become JSON values, while dates
+//! keep their text and are typed in the `columns` description of the output,
+//! which also keeps the original column order.
+
+use serde_json::{Map, Value};
+
+use crate::predicate::parse_date;
+
+/// Delimiter, quote and line ending of a delimited text format
+#[derive(Debug, Clone, Copy)]
+pub struct Dialect {
+    pub delimiter: char,
+    pub quote: char,
+    pub line_ending: &'static str,
+}
+
+impl Dialect {
+    pub fn csv() -> Self {
+        Self { delimiter: ',', quote: '"', line_ending: "\n" }
+    }
+
+    pub fn tsv() -> Self {
+        Self { delimiter: '\t', ..Self::csv() }
+    }
+}
+
+/// Parse a delimiter given on the command line: a single character, or
+/// `\t`/`tab` for a tab
+pub fn parse_delimiter(text: &str) -> Result<char, String> {
+    match text {
+        "\\t" | "tab" => Ok('\t'),
+        _ => {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c != '\n' && c != '\r' => Ok(c),
+                _ => Err(format!("delimiter must be a single character, got '{text}'")),
+            }
+        }
+    }
+}
+
+/// Split delimited text into rows of fields. Quoted fields may contain
+/// delimiters and line breaks; blank lines are skipped.
+pub fn parse_delimited(text: &str, dialect: &Dialect) -> Result<Vec<Vec<String>>, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut chars = text.chars().peekable();
+
+    // Whether the current field started with a quote, and the line it started on
+    let mut quoted_since: Option<usize> = None;
+    let mut in_quotes = false;
+    let mut row_has_content = false;
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == dialect.quote {
+                if chars.peek() == Some(&dialect.quote) {
+                    chars.next();
+                    field.push(c);
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+            continue;
+        }
+
+        match c {
+            c if c == dialect.quote && field.is_empty() && quoted_since.is_none() => {
+                in_quotes = true;
+                quoted_since = Some(line);
+                row_has_content = true;
+            }
+            c if c == dialect.delimiter => {
+                row.push(std::mem::take(&mut field));
+                quoted_since = None;
+                row_has_content = true;
+            }
+            '\r' | '\n' => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                line += 1;
+                if row_has_content || !field.is_empty() {
+                    row.push(std::mem::take(&mut field));
+                    rows.push(std::mem::take(&mut row));
+                }
+                quoted_since = None;
+                row_has_content = false;
+            }
+            // Text after a closing quote is kept, as most readers do
+            c => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(format!(
+            "line {}: unterminated quoted field",
+            quoted_since.unwrap_or(line)
+        ));
+    }
+    if row_has_content || !field.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Append one row of fields to `out`, quoting only the fields that need it
+pub fn write_delimited_row<S: AsRef<str>>(fields: &[S], dialect: &Dialect, out: &mut String) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(dialect.delimiter);
+        }
+        let field = field.as_ref();
+        let needs_quotes = field
+            .chars()
+            .any(|c| c == dialect.delimiter || c == dialect.quote || c == '\n' || c == '\r');
+        if needs_quotes {
+            out.push(dialect.quote);
+            for c in field.chars() {
+                if c == dialect.quote {
+                    out.push(c);
+                }
+                out.push(c);
+            }
+            out.push(dialect.quote);
+        } else {
+            out.push_str(field);
+        }
+    }
+    out.push_str(dialect.line_ending);
+}
+
+/// Field names from a header row: blank names become `columnN` and repeated
+/// names get a `_2`, `_3`, ... suffix
+pub fn header_names<S: AsRef<str>>(row: &[S]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(row.len());
+    for (i, name) in row.iter().enumerate() {
+        let name = name.as_ref().trim();
+        let base = if name.is_empty() { default_name(i) } else { name.to_string() };
+        let mut unique = base.clone();
+        let mut n = 2;
+        while names.contains(&unique) {
+            unique = format!("{base}_{n}");
+            n += 1;
+        }
+        names.push(unique);
+    }
+    names
+}
+
+/// Name of the unnamed column at `index`: `column1`, `column2`, ...
+pub fn default_name(index: usize) -> String {
+    format!("column{}", index + 1)
+}
+
+/// The value of a text field: with `infer`, empty fields are null and
+/// numbers, booleans and dates get their own types
+pub fn field_value(text: &str, infer: bool) -> Value {
+    if infer { infer_value(text) } else { Value::String(text.to_string()) }
+}
+
+/// Infer the type of a text field. Numbers with leading zeros (`007`, zip
+/// codes) stay strings so they survive a round trip.
+pub fn infer_value(text: &str) -> Value {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return Value::Null;
+    }
+    match trimmed {
+        "true" | "True" | "TRUE" => return Value::Bool(true),
+        "false" | "False" | "FALSE" => return Value::Bool(false),
+        _ => {}
+    }
+
+    let digits = trimmed.strip_prefix(['-', '+']).unwrap_or(trimmed);
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    let numeric_chars = digits
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+'));
+    if numeric_chars && !leading_zero && digits.starts_with(|c: char| c.is_ascii_digit()) {
+        if let Ok(n) = trimmed.parse::<i64>() {
+            return Value::from(n);
+        }
+        if let Some(n) = trimmed.parse::<f64>().ok().filter(|n| n.is_finite()) {
+            return Value::from(n);
+        }
+    }
+
+    Value::String(text.to_string())
+}
+
+/// `date` for `2024-01-31`, `datetime` for `2024-01-31 12:00` or
+/// `2024-01-31T12:00:00Z`, as understood by `where-object`
+fn date_type(text: &str) -> Option<&'static str> {
+    let text = text.trim();
+    let looks_like_date = text.len() >= 10 && text.as_bytes()[4] == b'-';
+    looks_like_date.then(|| parse_date(text))??;
+    Some(if text.len() == 10 { "date" } else { "datetime" })
+}
+
+/// The type of one value, for the `columns` description
+fn value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(s) => date_type(s).unwrap_or("string"),
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Describe the fields of `records` in the order of `names`:
+/// `[{"name": "id", "type": "integer"}, {"name": "joined", "type": "date"}]`.
+/// A column of integers and decimals is `number`, one of dates and times is
+/// `datetime`, and any other mix is `string`.
+pub fn describe_columns(names: &[String], records: &[Value]) -> Value {
+    let columns = names
+        .iter()
+        .map(|name| {
+            let mut column_type = "null";
+            for value in records.iter().filter_map(|record| record.get(name)) {
+                column_type = match (column_type, value_type(value)) {
+                    (current, "null") => current,
+                    ("null", next) => next,
+                    (current, next) if current == next => current,
+                    ("integer" | "number", "integer" | "number") => "number",
+                    ("date" | "datetime", "date" | "datetime") => "datetime",
+                    _ => "string",
+                };
+            }
+            let mut column = Map::new();
+            column.insert("name".to_string(), Value::String(name.clone()));
+            column.insert("type".to_string(), Value::String(column_type.to_string()));
+            Value::Object(column)
+        })
+        .collect();
+    Value::Array(columns)
+}
+
+/// Column names from a `columns` description, or from a plain list of names
+pub fn column_names(columns: &Value) -> Option<Vec<String>> {
+    columns
+        .as_array()?
+        .iter()
+        .map(|column| column.get("name").unwrap_or(column).as_str().map(str::to_string))
+        .collect()
+}
+
+/// Records from rows of text fields named by `names`. Fields beyond the
+/// names get default names and missing fields are null.
+pub fn rows_to_records<I>(names: &[String], rows: I, infer: bool) -> Vec<Value>
+where
+    I: IntoIterator<Item = Vec<String>>,
+{
+    rows.into_iter()
+        .map(|row| {
+            let width = names.len().max(row.len());
+            let mut fields = row.into_iter();
+            let record: Map<String, Value> = (0..width)
+                .map(|i| {
+                    let name = names.get(i).cloned().unwrap_or_else(|| default_name(i));
+                    let value = fields.next().map_or(Value::Null, |text| field_value(&text, infer));
+                    (name, value)
+                })
+                .collect();
+            Value::Object(record)
+        })
+        .collect()
+}
+
+/// Field names of `records` in order of first appearance
+pub fn record_columns(records: &[Value]) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+    for record in records {
+        if let Value::Object(map) = record {
+            for key in map.keys() {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+        }
+    }
+    columns
+}
+
+/// A value as the text of one field: strings as they are, null as an empty
+/// field, and arrays and objects as compact JSON
+pub fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// The stardust object wrapping converted records, with their columns
+/// described when their names are known
+pub fn records_output(records: Vec<Value>, names: Option<&[String]>) -> Value {
+    let mut output = Map::new();
+    if let Some(names) = names {
+        output.insert("columns".to_string(), describe_columns(names, &records));
+    }
+    output.insert("count".to_string(), Value::from(records.len()));
+    output.insert("records".to_string(), Value::Array(records));
+    Value::Object(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_rfc4180_quoting() {
+        let text = "name,note\r\n\"Smith, J\",\"said \"\"hi\"\"\nand left\"\n\nlast,\n";
+        let rows = parse_delimited(text, &Dialect::csv()).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["name", "note"],
+                vec!["Smith, J", "said \"hi\"\nand left"],
+                vec!["last", ""],
+            ]
+        );
+        assert!(parse_delimited("a,\"b\n", &Dialect::csv()).unwrap_err().contains("line 1"));
+    }
+
+    #[test]
+    fn writes_and_reads_back() {
+        let fields = ["plain", "with,comma", "with \"quote\"", "two\nlines", ""];
+        let mut out = String::new();
+        write_delimited_row(&fields, &Dialect::csv(), &mut out);
+        assert_eq!(out, "plain,\"with,comma\",\"with \"\"quote\"\"\",\"two\nlines\",\n");
+        assert_eq!(parse_delimited(&out, &Dialect::csv()).unwrap(), vec![fields.to_vec()]);
+    }
+
+    #[test]
+    fn infers_types() {
+        assert_eq!(infer_value("42"), json!(42));
+        assert_eq!(infer_value("-1.5e3"), json!(-1500.0));
+        assert_eq!(infer_value("007"), json!("007"));
+        assert_eq!(infer_value("TRUE"), json!(true));
+        assert_eq!(infer_value(""), Value::Null);
+        assert_eq!(infer_value("2024-01-01"), json!("2024-01-01"));
+        assert_eq!(infer_value("1.2.3"), json!("1.2.3"));
+        assert_eq!(infer_value("inf"), json!("inf"));
+    }
+
+    #[test]
+    fn describes_columns() {
+        let names = vec!["n".to_string(), "when".to_string(), "note".to_string()];
+        let records = vec![
+            json!({"n": 1, "when": "2024-01-01", "note": null}),
+            json!({"n": 1.5, "when": "2024-01-02 10:00", "note": "x"}),
+        ];
+        let columns = describe_columns(&names, &records);
+        assert_eq!(
+            columns,
+            json!([
+                {"name": "n", "type": "number"},
+                {"name": "when", "type": "datetime"},
+                {"name": "note", "type": "string"},
+            ])
+        );
+        assert_eq!(column_names(&columns), Some(names));
+        assert_eq!(column_names(&json!(["a"])), Some(vec!["a".to_string()]));
+    }
+
+    #[test]
+    fn names_and_records() {
+        let names = header_names(&["id", "", "id"]);
+        assert_eq!(names, ["id", "column2", "id_2"]);
+        let records = rows_to_records(&names[..2], vec![vec!["1".into()], vec!["2".into(), "x".into(), "y".into()]], true);
+        assert_eq!(
+            records,
+            vec![json!({"id": 1, "column2": null}), json!({"id": 2, "column2": "x", "column3": "y"})]
+        );
+    }
+}
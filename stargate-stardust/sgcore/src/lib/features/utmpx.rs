@@ -37,6 +37,10 @@ use std::path::Path;
 use std::ptr;
 use std::sync::{Mutex, MutexGuard};
 
+use serde_json::{Map, Value, json};
+
+use crate::stardust_output::{Property, enum_schema};
+
 #[cfg(feature = "feat_systemd_logind")]
 use crate::features::systemd_logind;
 
@@ -78,6 +82,8 @@ macro_rules! chars2string {
 #[cfg(target_os = "linux")]
 mod ut {
     pub static DEFAULT_FILE: &str = "/var/run/utmp";
+    pub static WTMP_FILE: &str = "/var/log/wtmp";
+    pub static BTMP_FILE: &str = "/var/log/btmp";
 
     #[cfg(not(target_env = "musl"))]
     pub use libc::__UT_HOSTSIZE as UT_HOSTSIZE;
@@ -111,6 +117,9 @@ mod ut {
 #[cfg(target_vendor = "apple")]
 mod ut {
     pub static DEFAULT_FILE: &str = "/var/run/utmpx";
+    // Login history is kept by the unified log, not in a file utmpxname() can read
+    pub static WTMP_FILE: &str = "";
+    pub static BTMP_FILE: &str = "";
 
     pub use libc::_UTX_HOSTSIZE as UT_HOSTSIZE;
     pub use libc::_UTX_IDSIZE as UT_IDSIZE;
@@ -134,6 +143,8 @@ mod ut {
 #[cfg(target_os = "freebsd")]
 mod ut {
     pub static DEFAULT_FILE: &str = "";
+    pub static WTMP_FILE: &str = "";
+    pub static BTMP_FILE: &str = "";
 
     pub const UT_LINESIZE: usize = 16;
     pub const UT_NAMESIZE: usize = 32;
@@ -474,6 +485,86 @@ impl UtmpxRecord {
     }
 }
 
+/// Names of the record types in session objects, see [`record_type_name`]
+pub const RECORD_TYPE_NAMES: &[&str] = &[
+    "empty",
+    "run_level",
+    "boot",
+    "new_time",
+    "old_time",
+    "init",
+    "login",
+    "user",
+    "dead",
+    "accounting",
+    "shutdown",
+    "signature",
+    "unknown",
+];
+
+/// A.K.A. ut.ut_type, as the name used in session objects
+pub fn record_type_name(record_type: i16) -> &'static str {
+    match record_type {
+        EMPTY => "empty",
+        #[cfg(any(target_vendor = "apple", target_os = "linux"))]
+        RUN_LVL => "run_level",
+        BOOT_TIME => "boot",
+        NEW_TIME => "new_time",
+        OLD_TIME => "old_time",
+        INIT_PROCESS => "init",
+        LOGIN_PROCESS => "login",
+        USER_PROCESS => "user",
+        DEAD_PROCESS => "dead",
+        #[cfg(any(target_vendor = "apple", target_os = "linux"))]
+        ACCOUNTING => "accounting",
+        #[cfg(any(target_vendor = "apple", target_os = "freebsd"))]
+        SHUTDOWN_TIME => "shutdown",
+        #[cfg(target_vendor = "apple")]
+        SIGNATURE => "signature",
+        _ => "unknown",
+    }
+}
+
+/// Format a login record time as RFC 3339
+pub fn format_time(when: time::OffsetDateTime) -> String {
+    when.format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default()
+}
+
+impl UtmpxRecord {
+    /// The fields every session object shares, whether it comes from the
+    /// current sessions (`who --obj`) or the login history (`list-logins`):
+    /// `user`, `tty`, `host` (null when local), `login_time`, `pid` and `type`
+    pub fn session_fields(&self) -> Map<String, Value> {
+        let host = self.host();
+        let mut fields = Map::new();
+        fields.insert("user".into(), self.user().into());
+        fields.insert("tty".into(), self.tty_device().into());
+        fields.insert(
+            "host".into(),
+            if host.is_empty() { Value::Null } else { host.into() },
+        );
+        fields.insert("login_time".into(), format_time(self.login_time()).into());
+        fields.insert("pid".into(), self.pid().into());
+        fields.insert("type".into(), record_type_name(self.record_type()).into());
+        fields
+    }
+}
+
+/// Schema of the fields in [`UtmpxRecord::session_fields`]
+pub fn session_properties() -> Vec<Property> {
+    vec![
+        Property::new("user", "string").description("Login name"),
+        Property::new("tty", "string").description("Terminal line, without /dev/"),
+        Property::new("host", json!({"type": ["string", "null"]}))
+            .description("Remote host or X display, null for a local login"),
+        Property::new("login_time", "string").description("When the record was written (RFC 3339)"),
+        Property::new("pid", "integer").description("Process id of the login process"),
+        Property::new("type", enum_schema(RECORD_TYPE_NAMES))
+            .description("Kind of record, e.g. user, login, boot or dead"),
+    ]
+}
+
 impl Iterator for UtmpxIter {
     type Item = UtmpxRecord;
     fn next(&mut self) -> Option<Self::Item> {
@@ -17,15 +17,19 @@ pub use sgcore_procs::*;
 pub use crate::mods::clap_localization;
 pub use crate::mods::display;
 pub use crate::mods::error;
+pub use crate::mods::field_path;
 #[cfg(feature = "fs")]
 pub use crate::mods::io;
 pub use crate::mods::json_adapter;
 pub use crate::mods::stardust_output;
+pub use crate::mods::operation_report;
 pub use crate::mods::line_ending;
 pub use crate::mods::locale;
 pub use crate::mods::panic;
 pub use crate::mods::pledge;
+pub use crate::mods::record_stream;
 pub use crate::mods::posix;
+pub use crate::mods::wire;
 
 #[cfg(feature = "backup-control")]
 pub use crate::features::backup_control;
@@ -53,14 +57,20 @@ pub use crate::features::i18n;
 pub use crate::features::lines;
 #[cfg(feature = "parser")]
 pub use crate::features::parser;
+#[cfg(feature = "predicate")]
+pub use crate::features::predicate;
 #[cfg(feature = "quoting-style")]
 pub use crate::features::quoting_style;
 #[cfg(feature = "ranges")]
 pub use crate::features::ranges;
+#[cfg(feature = "render")]
+pub use crate::features::render;
 #[cfg(feature = "ringbuffer")]
 pub use crate::features::ringbuffer;
 #[cfg(feature = "sum")]
 pub use crate::features::sum;
+#[cfg(feature = "tabular")]
+pub use crate::features::tabular;
 #[cfg(feature = "feat_systemd_logind")]
 pub use crate::features::systemd_logind;
 #[cfg(feature = "time")]
@@ -3,12 +3,16 @@
 pub mod clap_localization;
 pub mod display;
 pub mod error;
+pub mod field_path;
 #[cfg(feature = "fs")]
 pub mod io;
 pub mod json_adapter;
 pub mod stardust_output;
+pub mod operation_report;
 pub mod line_ending;
 pub mod locale;
 pub mod panic;
 pub mod pledge;
+pub mod record_stream;
 pub mod posix;
+pub mod wire;
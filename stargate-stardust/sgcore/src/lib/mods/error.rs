@@ -491,8 +491,12 @@ impl From<std::io::Error> for SGIoError {
 
 impl From<std::io::Error> for Box<dyn SGError> {
     fn from(f: std::io::Error) -> Self {
-        let u_error: SGIoError = f.into();
-        Box::new(u_error) as Self
+        // An error that carries its own exit code, such as a bad `--field`
+        // spec from `stardust_output::output`, keeps it.
+        match f.downcast::<SGSimpleError>() {
+            Ok(simple) => Box::new(simple),
+            Err(f) => Box::new(SGIoError::from(f)),
+        }
     }
 }
 
@@ -0,0 +1,362 @@
+//! Field paths and selectors over stardust values, shared by `--field`,
+//! slice-object, dice-object and the language's property access.
+//!
+//! ```text
+//! owner.name                    a nested field
+//! entries.name                  the name of every entry
+//! entries[0], entries[-1]       one element, counting from the end when negative
+//! entries[0:5].name             the names of the first five entries
+//! results[*].matches[*].line    every line of every match
+//! name as file                  a selector whose result is named `file`
+//! ```
+//!
+//! A key applied to an array is applied to each of its elements, so
+//! `entries.name` and `entries[*].name` are the same. Keys holding `.`,
+//! `[` or spaces are written quoted: `labels["app.kubernetes.io/name"]`.
+
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// One step of a field path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// `name` or `["name"]`; a numeric key also indexes an array (`cmdline.0`)
+    Key(String),
+    /// `[n]`, from the end when negative
+    Index(i64),
+    /// `[start:end]` with Python-style bounds
+    Slice(Option<i64>, Option<i64>),
+    /// `[*]` or `.*`: every element of an array or value of an object
+    Wildcard,
+}
+
+impl Segment {
+    /// Apply the step to `value`, pushing the results onto `out`. Returns
+    /// whether the step may give several results rather than at most one.
+    fn apply<'a>(&self, value: &'a Value, out: &mut Vec<&'a Value>) -> bool {
+        match (self, value) {
+            (Self::Key(key), Value::Object(map)) => {
+                out.extend(map.get(key));
+                false
+            }
+            (Self::Key(key), Value::Array(arr)) => match key.parse::<usize>() {
+                Ok(index) => {
+                    out.extend(arr.get(index));
+                    false
+                }
+                Err(_) => {
+                    for element in arr {
+                        self.apply(element, out);
+                    }
+                    true
+                }
+            },
+            (Self::Index(index), Value::Array(arr)) => {
+                out.extend(resolve_index(*index, arr.len()).and_then(|i| arr.get(i)));
+                false
+            }
+            (Self::Slice(start, end), Value::Array(arr)) => {
+                let (start, end) = slice_bounds(*start, *end, arr.len());
+                out.extend(&arr[start..end]);
+                true
+            }
+            (Self::Wildcard, Value::Array(arr)) => {
+                out.extend(arr);
+                true
+            }
+            (Self::Wildcard, Value::Object(map)) => {
+                out.extend(map.values());
+                true
+            }
+            (Self::Slice(..) | Self::Wildcard, _) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The position of `index` in a sequence of `len` elements, counting from
+/// the end when negative
+pub fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    let len = i64::try_from(len).ok()?;
+    let index = if index < 0 { len + index } else { index };
+    (0..len).contains(&index).then_some(index as usize)
+}
+
+/// The range `[start:end]` selects from a sequence of `len` elements:
+/// negative bounds count from the end and bounds past either end are clamped
+pub fn slice_bounds(start: Option<i64>, end: Option<i64>, len: usize) -> (usize, usize) {
+    let clamp = |bound: i64| {
+        let len = i64::try_from(len).unwrap_or(i64::MAX);
+        let bound = if bound < 0 { len + bound } else { bound };
+        bound.clamp(0, len) as usize
+    };
+    let start = start.map_or(0, clamp);
+    let end = end.map_or(len, clamp);
+    (start, end.max(start))
+}
+
+/// A parsed field path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPath(pub Vec<Segment>);
+
+impl FieldPath {
+    /// Parse a path such as `results[*].matches[0:3].line`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let chars: Vec<char> = text.trim().chars().collect();
+        if chars.is_empty() {
+            return Err("empty field path".to_string());
+        }
+
+        let mut segments = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '[' => {
+                    let close = find_close(&chars, i)
+                        .ok_or_else(|| format!("unterminated '[' in field path '{text}'"))?;
+                    let inner: String = chars[i + 1..close].iter().collect();
+                    segments.push(parse_bracket(inner.trim()).map_err(|e| format!("{e} in field path '{text}'"))?);
+                    i = close + 1;
+                }
+                '.' if i > 0 && i + 1 < chars.len() && chars[i + 1] != '.' && chars[i + 1] != '[' => i += 1,
+                '.' | ']' => return Err(format!("unexpected '{}' in field path '{text}'", chars[i])),
+                _ => {
+                    let start = i;
+                    while i < chars.len() && !matches!(chars[i], '.' | '[' | ']') {
+                        i += 1;
+                    }
+                    let key: String = chars[start..i].iter().collect();
+                    segments.push(match key.trim() {
+                        "*" => Segment::Wildcard,
+                        key => Segment::Key(key.to_string()),
+                    });
+                }
+            }
+        }
+
+        Ok(Self(segments))
+    }
+
+    /// A path of one key, taken literally
+    pub fn key(key: impl Into<String>) -> Self {
+        Self(vec![Segment::Key(key.into())])
+    }
+
+    /// Every value the path reaches in `value`, and whether the path fanned
+    /// out over an array or object on the way
+    pub fn select<'a>(&self, value: &'a Value) -> (Vec<&'a Value>, bool) {
+        let mut current = vec![value];
+        let mut plural = false;
+        for segment in &self.0 {
+            let mut next = Vec::new();
+            for value in current {
+                plural |= segment.apply(value, &mut next);
+            }
+            current = next;
+        }
+        (current, plural)
+    }
+
+    /// The first value the path reaches in `value`
+    pub fn get<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.select(value).0.into_iter().next()
+    }
+
+    /// The value the path selects from `value`: the one value it reaches,
+    /// or an array of every value it reaches when it fans out. `None` when a
+    /// path that does not fan out reaches nothing.
+    pub fn extract(&self, value: &Value) -> Option<Value> {
+        match self.select(value) {
+            (values, true) => Some(Value::Array(values.into_iter().cloned().collect())),
+            (values, false) => values.into_iter().next().cloned(),
+        }
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                Segment::Key(key) if key.contains(['.', '[', ']']) => write!(f, "[{key:?}]")?,
+                Segment::Key(key) if i == 0 => write!(f, "{key}")?,
+                Segment::Key(key) => write!(f, ".{key}")?,
+                Segment::Index(index) => write!(f, "[{index}]")?,
+                Segment::Slice(start, end) => {
+                    let bound = |b: &Option<i64>| b.map(|b| b.to_string()).unwrap_or_default();
+                    write!(f, "[{}:{}]", bound(start), bound(end))?;
+                }
+                Segment::Wildcard => write!(f, "[*]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The `]` closing the `[` at `open`, skipping quoted keys
+fn find_close(chars: &[char], open: usize) -> Option<usize> {
+    let mut quote = None;
+    for (i, &c) in chars.iter().enumerate().skip(open + 1) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, ']') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_bracket(inner: &str) -> Result<Segment, String> {
+    let number = |text: &str| -> Result<Option<i64>, String> {
+        match text.trim() {
+            "" => Ok(None),
+            text => text.parse().map(Some).map_err(|_| format!("invalid index '{text}'")),
+        }
+    };
+
+    if inner == "*" {
+        return Ok(Segment::Wildcard);
+    }
+    if let Some(key) = ['"', '\'']
+        .iter()
+        .find_map(|&q| inner.strip_prefix(q).and_then(|rest| rest.strip_suffix(q)))
+    {
+        return Ok(Segment::Key(key.to_string()));
+    }
+    if let Some((start, end)) = inner.split_once(':') {
+        return Ok(Segment::Slice(number(start)?, number(end)?));
+    }
+    number(inner)?
+        .map(Segment::Index)
+        .ok_or_else(|| "empty '[]'".to_string())
+}
+
+/// A field path with the name its result is given, as in `path as name`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    pub path: FieldPath,
+    pub name: String,
+}
+
+impl Selector {
+    /// Parse `path` or `path as name`; the name defaults to the path as written
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (path, name) = match text.rsplit_once(" as ") {
+            Some((path, name)) if !name.trim().is_empty() => (path.trim(), name.trim()),
+            _ => (text, text),
+        };
+        Ok(Self {
+            path: FieldPath::parse(path)?,
+            name: name.to_string(),
+        })
+    }
+}
+
+/// Parse a comma-separated list of selectors, as given to `--field`
+pub fn parse_selectors(spec: &str) -> Result<Vec<Selector>, String> {
+    let mut selectors = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in spec.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                selectors.push(&spec[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    selectors.push(&spec[start..]);
+
+    selectors
+        .into_iter()
+        .filter(|selector| !selector.trim().is_empty())
+        .map(Selector::parse)
+        .collect()
+}
+
+/// An object of the values `selectors` pick from `value`, under their
+/// names; selectors that reach nothing are left out
+pub fn select_fields(value: &Value, selectors: &[Selector]) -> Map<String, Value> {
+    selectors
+        .iter()
+        .filter_map(|selector| {
+            selector
+                .path
+                .extract(value)
+                .map(|found| (selector.name.clone(), found))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn extract(value: &Value, path: &str) -> Option<Value> {
+        FieldPath::parse(path).unwrap().extract(value)
+    }
+
+    #[test]
+    fn parses_paths() {
+        assert_eq!(
+            FieldPath::parse("results[*].matches[1:-1].line").unwrap().0,
+            vec![
+                Segment::Key("results".into()),
+                Segment::Wildcard,
+                Segment::Key("matches".into()),
+                Segment::Slice(Some(1), Some(-1)),
+                Segment::Key("line".into()),
+            ]
+        );
+        assert_eq!(
+            FieldPath::parse("labels[\"a.b\"].*").unwrap().0,
+            vec![Segment::Key("labels".into()), Segment::Key("a.b".into()), Segment::Wildcard]
+        );
+        assert_eq!(FieldPath::parse("a[0:]").unwrap().to_string(), "a[0:]");
+        assert!(FieldPath::parse("a[x]").is_err());
+        assert!(FieldPath::parse("a[0").is_err());
+        assert!(FieldPath::parse("a..b").is_err());
+    }
+
+    #[test]
+    fn extracts_nested_values() {
+        let value = json!({
+            "owner": {"name": "root"},
+            "entries": [{"name": "a", "size": 1}, {"name": "b", "size": 2}, {"name": "c"}],
+            "results": [{"matches": [{"line": 1}, {"line": 4}]}, {"matches": [{"line": 9}]}],
+        });
+        assert_eq!(extract(&value, "owner.name"), Some(json!("root")));
+        assert_eq!(extract(&value, "owner.missing"), None);
+        assert_eq!(extract(&value, "entries[-1].name"), Some(json!("c")));
+        assert_eq!(extract(&value, "entries.1.size"), Some(json!(2)));
+        assert_eq!(extract(&value, "entries.name"), Some(json!(["a", "b", "c"])));
+        assert_eq!(extract(&value, "entries[0:2].size"), Some(json!([1, 2])));
+        assert_eq!(extract(&value, "entries[*].size"), Some(json!([1, 2])));
+        assert_eq!(extract(&value, "results[*].matches[*].line"), Some(json!([1, 4, 9])));
+        assert_eq!(extract(&value, "results.matches.line"), Some(json!([1, 4, 9])));
+        assert_eq!(extract(&json!([1, 2, 3]), "[-2:]"), Some(json!([2, 3])));
+    }
+
+    #[test]
+    fn selectors_rename_and_split_on_top_level_commas() {
+        let selectors = parse_selectors("name as file, entries[0:2].size,tags[\"a,b\"]").unwrap();
+        assert_eq!(selectors.len(), 3);
+        assert_eq!(selectors[0].name, "file");
+        assert_eq!(selectors[1].name, "entries[0:2].size");
+
+        let value = json!({"name": "x", "entries": [{"size": 5}]});
+        assert_eq!(
+            Value::Object(select_fields(&value, &selectors)),
+            json!({"file": "x", "entries[0:2].size": [5]})
+        );
+    }
+}
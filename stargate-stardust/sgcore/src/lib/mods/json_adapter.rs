@@ -1,6 +1,13 @@
-use serde_json::Value;
-use std::io::Read;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
 use std::path::PathBuf;
+use std::process;
+
+use crate::field_path::FieldPath;
+use crate::wire;
+
+/// Keys that hold the records of a stardust object, in lookup order
+pub const RECORD_KEYS: [&str; 4] = ["entries", "files", "results", "records"];
 
 pub fn extract_file_paths(value: &Value) -> Vec<PathBuf> {
     match value {
@@ -69,10 +76,7 @@ pub fn try_extract_paths_from_stdin() -> Option<Vec<PathBuf>> {
         return None;
     }
 
-    let mut stdin_data = String::new();
-    std::io::stdin().read_to_string(&mut stdin_data).ok()?;
-    
-    let json: Value = serde_json::from_str(stdin_data.trim()).ok()?;
+    let json = read_stdin_value().ok()?;
     let paths = extract_file_paths(&json);
     
     if paths.is_empty() {
@@ -105,15 +109,7 @@ pub fn try_extract_from_stdin() -> Option<StdinResult> {
         return None;
     }
 
-    let mut stdin_data = String::new();
-    if std::io::stdin().read_to_string(&mut stdin_data).is_err() {
-        return None;
-    }
-
-    let json: serde_json::Value = match serde_json::from_str(stdin_data.trim()) {
-        Ok(v) => v,
-        Err(_) => return None,
-    };
+    let json = read_stdin_value().ok()?;
 
     if let Some(count) = extract_count_from_list_directory(&json) {
         return Some(StdinResult::Count(count));
@@ -127,7 +123,206 @@ pub fn try_extract_from_stdin() -> Option<StdinResult> {
     }
 }
 
+/// Read stardust from stdin, as JSON text or as the binary encoding a
+/// stargate stage writes when the shell asks it to (see [`crate::wire`])
+pub fn read_stdin_value() -> Result<Value, String> {
+    wire::read_value(std::io::stdin().lock())
+}
+
+/// Parse stardust held in memory, such as a file named on the command
+/// line, in either encoding
+pub fn parse_value(bytes: &[u8]) -> Result<Value, String> {
+    wire::decode(bytes)
+}
+
 pub enum StdinResult {
     Count(u64),
     Paths(Vec<PathBuf>),
 }
+
+/// The key of the record array in a stardust object: the first of
+/// [`RECORD_KEYS`] that holds an array, or else the object's only array
+/// field (`processes`, `users`, ...)
+pub fn records_key(map: &Map<String, Value>) -> Option<&str> {
+    if let Some(key) = RECORD_KEYS.into_iter().find(|key| map.get(*key).is_some_and(Value::is_array)) {
+        return Some(key);
+    }
+    let mut arrays = map.iter().filter(|(_, value)| value.is_array());
+    match (arrays.next(), arrays.next()) {
+        (Some((key, _)), None) => Some(key),
+        _ => None,
+    }
+}
+
+/// The records of a stardust value: the value itself when it is an array,
+/// otherwise the array at the field path `key`, or the one found by
+/// [`records_key`] when no key is given
+pub fn extract_records<'a>(value: &'a Value, key: Option<&str>) -> Result<&'a [Value], String> {
+    match (value, key) {
+        (Value::Array(records), None) => Ok(records),
+        (_, Some(key)) => lookup_field(value, key)
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .ok_or_else(|| format!("field '{key}' is not an array")),
+        (Value::Object(map), None) => records_key(map)
+            .and_then(|key| map[key].as_array())
+            .map(Vec::as_slice)
+            .ok_or_else(|| "input object has no array of records".to_string()),
+        _ => Err("input must be an array or an object with an array of records".to_string()),
+    }
+}
+
+/// Move the records out of a stardust value, leaving an empty array in their
+/// place so [`put_records`] can return them once a command is done with
+/// them. `key` names a top-level field.
+pub fn take_records(value: &mut Value, key: Option<&str>) -> Result<Vec<Value>, String> {
+    let slot = records_slot(value, key)?;
+    Ok(std::mem::take(slot))
+}
+
+/// Put records back where [`take_records`] found them, keeping the rest of
+/// the document and updating its `count` when it has one
+pub fn put_records(value: &mut Value, key: Option<&str>, records: Vec<Value>) -> Result<(), String> {
+    let count = records.len();
+    *records_slot(value, key)? = records;
+    set_count(value, count);
+    Ok(())
+}
+
+/// Update the `count` of a stardust object, when it has one
+pub fn set_count(value: &mut Value, count: usize) {
+    if let Some(slot) = value.as_object_mut().and_then(|map| map.get_mut("count")) {
+        *slot = Value::from(count);
+    }
+}
+
+fn records_slot<'a>(value: &'a mut Value, key: Option<&str>) -> Result<&'a mut Vec<Value>, String> {
+    match (value, key) {
+        (Value::Array(records), None) => Ok(records),
+        (Value::Object(map), key) => {
+            let key = match key {
+                Some(key) => key.to_string(),
+                None => records_key(map)
+                    .ok_or_else(|| "input object has no array of records".to_string())?
+                    .to_string(),
+            };
+            map.get_mut(&key)
+                .and_then(Value::as_array_mut)
+                .ok_or_else(|| format!("field '{key}' is not an array"))
+        }
+        _ => Err("input must be an array or an object with an array of records".to_string()),
+    }
+}
+
+/// Read a stardust input named on the command line, `-` being stdin
+pub fn read_input_value(path: &std::ffi::OsStr) -> Result<Value, String> {
+    if path == "-" {
+        return read_stdin_value();
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {e}", path.to_string_lossy()))?;
+    parse_value(&bytes).map_err(|e| format!("{}: {e}", path.to_string_lossy()))
+}
+
+/// Read a stardust input named on the command line of a command such as
+/// `join-object` or `diff-object`: the output of `(command args)`, inline
+/// JSON, or a file of JSON or binary stardust
+pub fn load_source(source: &str) -> Result<Value, String> {
+    let trimmed = source.trim();
+    let bytes = if let Some(command) = trimmed.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        run_source_command(command)?
+    } else if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        trimmed.as_bytes().to_vec()
+    } else {
+        std::fs::read(source).map_err(|e| format!("Failed to read '{source}': {e}"))?
+    };
+
+    parse_value(&bytes).map_err(|e| format!("{source}: {e}"))
+}
+
+/// Run a stargate command with `--obj` and capture its stardust output, which
+/// it is asked to write in the binary encoding. The `stargate` binary next to
+/// this one is preferred, as the shell does.
+fn run_source_command(command: &str) -> Result<Vec<u8>, String> {
+    let words = shlex::split(command).ok_or_else(|| format!("Unbalanced quotes in '({command})'"))?;
+    let Some((name, rest)) = words.split_first() else {
+        return Err("Empty command in '()'".to_string());
+    };
+
+    let mut args: Vec<&str> = rest.iter().map(String::as_str).collect();
+    if !args.iter().any(|arg| *arg == "-o" || *arg == "--obj") {
+        args.insert(0, "--obj");
+    }
+
+    let stargate_bin = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join("stargate")))
+        .filter(|bin| bin.exists());
+    let mut child = match stargate_bin {
+        Some(bin) => {
+            let mut child = process::Command::new(bin);
+            child.arg(name);
+            child
+        }
+        None => process::Command::new(PathBuf::from(name)),
+    };
+
+    let output = child
+        .args(&args)
+        .env(wire::ENV_WIRE, wire::WIRE_CBOR)
+        .stderr(process::Stdio::inherit())
+        .output()
+        .map_err(|e| format!("Failed to run '{name}': {e}"))?;
+    if !output.status.success() {
+        return Err(format!("'{command}' failed with {}", output.status));
+    }
+    Ok(output.stdout)
+}
+
+/// Look up a field path such as `owner.name`, `cmdline.0` or `items[-1]`,
+/// giving the first value it reaches; see [`crate::field_path`]
+pub fn lookup_field<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    FieldPath::parse(path).ok()?.get(value)
+}
+
+/// Text of a JSON number that is the same for equal numbers in any notation:
+/// integers exactly, anything else through f64 (`1000.0` reads as `1000`)
+pub fn number_key(n: &serde_json::Number) -> String {
+    if let Some(i) = n.as_i64() {
+        i.to_string()
+    } else if let Some(u) = n.as_u64() {
+        u.to_string()
+    } else {
+        n.as_f64().map_or_else(|| n.to_string(), |f| f.to_string())
+    }
+}
+
+/// Order two JSON values: null, then booleans, numbers, strings, arrays and
+/// objects. Numbers compare numerically and strings byte-wise.
+pub fn compare_values(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) => 4,
+            Value::Object(_) => 5,
+        }
+    }
+
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => {
+            let (a, b) = (a.as_f64().unwrap_or(0.0), b.as_f64().unwrap_or(0.0));
+            a.total_cmp(&b)
+        }
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| compare_values(a, b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
@@ -0,0 +1,225 @@
+//! Reports of what file operations did, for the `--obj` output of cp, mv,
+//! rm and install.
+//!
+//! Those commands act deep inside long call chains that print their
+//! `--verbose` lines as they go. Rather than threading a collector through
+//! every one of them, a command calls [`start`] when asked for stardust and
+//! the places that act call [`record`], which does nothing otherwise;
+//! [`finish`] hands back what was recorded, in order.
+
+use serde_json::{Value, json};
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+use crate::stardust_output::{FORMAT_BYTES, Property, array_schema, create_schema, enum_schema, object_schema};
+
+/// What was done to a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Copied,
+    /// Copied by sharing the source's blocks (copy-on-write clone)
+    Reflinked,
+    Linked,
+    Symlinked,
+    Moved,
+    /// A directory made to hold the destination
+    Created,
+    /// An existing destination kept as a backup before being replaced
+    BackedUp,
+    Removed,
+    Skipped,
+    Failed,
+}
+
+impl Action {
+    pub const ALL: [Self; 10] = [
+        Self::Copied,
+        Self::Reflinked,
+        Self::Linked,
+        Self::Symlinked,
+        Self::Moved,
+        Self::Created,
+        Self::BackedUp,
+        Self::Removed,
+        Self::Skipped,
+        Self::Failed,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Copied => "copied",
+            Self::Reflinked => "reflinked",
+            Self::Linked => "linked",
+            Self::Symlinked => "symlinked",
+            Self::Moved => "moved",
+            Self::Created => "created",
+            Self::BackedUp => "backed_up",
+            Self::Removed => "removed",
+            Self::Skipped => "skipped",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// One thing a command did, or failed to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operation {
+    pub action: Action,
+    pub source: Option<String>,
+    pub destination: Option<String>,
+    /// Bytes of file data written
+    pub bytes: u64,
+    /// Attributes carried over from the source, e.g. `mode` or `timestamps`
+    pub preserved: Vec<&'static str>,
+    pub error: Option<String>,
+}
+
+impl Operation {
+    pub fn new(action: Action) -> Self {
+        Self {
+            action,
+            source: None,
+            destination: None,
+            bytes: 0,
+            preserved: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn source(mut self, path: &Path) -> Self {
+        self.source = Some(path.to_string_lossy().into_owned());
+        self
+    }
+
+    pub fn destination(mut self, path: &Path) -> Self {
+        self.destination = Some(path.to_string_lossy().into_owned());
+        self
+    }
+
+    pub fn bytes(mut self, bytes: u64) -> Self {
+        self.bytes = bytes;
+        self
+    }
+
+    pub fn preserved(mut self, attributes: Vec<&'static str>) -> Self {
+        self.preserved = attributes;
+        self
+    }
+
+    /// A failed operation, with the message the command showed for it
+    pub fn error(mut self, error: impl Display) -> Self {
+        self.action = Action::Failed;
+        self.error = Some(error.to_string());
+        self
+    }
+
+    fn to_value(&self) -> Value {
+        json!({
+            "action": self.action.as_str(),
+            "source": self.source,
+            "destination": self.destination,
+            "bytes": self.bytes,
+            "preserved": self.preserved,
+            "error": self.error,
+        })
+    }
+}
+
+static REPORT: Mutex<Option<Vec<Operation>>> = Mutex::new(None);
+
+/// Start recording operations, dropping any earlier report
+pub fn start() {
+    *REPORT.lock().unwrap_or_else(PoisonError::into_inner) = Some(Vec::new());
+}
+
+/// Whether operations are being recorded
+pub fn is_active() -> bool {
+    REPORT.lock().unwrap_or_else(PoisonError::into_inner).is_some()
+}
+
+/// Add `operation` to the report, if one was started
+pub fn record(operation: Operation) {
+    if let Some(report) = REPORT.lock().unwrap_or_else(PoisonError::into_inner).as_mut() {
+        report.push(operation);
+    }
+}
+
+/// Stop recording and return the operations recorded since [`start`]
+pub fn finish() -> Vec<Operation> {
+    REPORT.lock().unwrap_or_else(PoisonError::into_inner).take().unwrap_or_default()
+}
+
+/// The `--obj` document for `operations`, with counts per action
+pub fn to_value(operations: &[Operation]) -> Value {
+    let mut summary = json!({
+        "total": operations.len(),
+        "bytes": operations.iter().map(|op| op.bytes).sum::<u64>(),
+    });
+    for action in Action::ALL {
+        let count = operations.iter().filter(|op| op.action == action).count();
+        summary[action.as_str()] = count.into();
+    }
+    json!({
+        "operations": operations.iter().map(Operation::to_value).collect::<Vec<_>>(),
+        "summary": summary,
+    })
+}
+
+/// The schema of [`to_value`] documents
+pub fn schema() -> Value {
+    let actions: Vec<&str> = Action::ALL.iter().map(|action| action.as_str()).collect();
+    let path = json!({"type": ["string", "null"]});
+    let operation = object_schema(vec![
+        Property::new("action", enum_schema(&actions)).description("What was done"),
+        Property::new("source", path.clone()).description("The path acted on or copied from"),
+        Property::new("destination", path)
+            .description("Where the source went, or the backup of a replaced file"),
+        Property::new("bytes", "integer").format(FORMAT_BYTES).description("Bytes of file data written"),
+        Property::new("preserved", array_schema("string"))
+            .description("Attributes kept from the source, e.g. mode, ownership or timestamps"),
+        Property::new("error", json!({"type": ["string", "null"]}))
+            .description("Why the operation failed"),
+    ]);
+    let mut counts = vec![
+        Property::new("total", "integer").description("Number of operations"),
+        Property::new("bytes", "integer").format(FORMAT_BYTES).description("Bytes of file data written in all"),
+    ];
+    counts.extend(actions.iter().map(|action| {
+        Property::new(action, "integer").description("Operations with this action")
+    }));
+    create_schema(vec![
+        Property::new("operations", array_schema(operation))
+            .description("Everything done, in order"),
+        Property::new("summary", object_schema(counts)).description("Counts per action"),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_only_while_started() {
+        record(Operation::new(Action::Removed).source(Path::new("ignored")));
+        start();
+        assert!(is_active());
+        record(
+            Operation::new(Action::Copied)
+                .source(Path::new("a"))
+                .destination(Path::new("b"))
+                .bytes(3),
+        );
+        record(Operation::new(Action::Copied).source(Path::new("c")).error("denied"));
+        let operations = finish();
+        assert!(!is_active());
+
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[1].action, Action::Failed);
+        let value = to_value(&operations);
+        assert_eq!(value["operations"][0]["destination"], "b");
+        assert_eq!(value["summary"]["bytes"], 3);
+        assert_eq!(value["summary"]["copied"], 1);
+        assert_eq!(value["summary"]["failed"], 1);
+    }
+}
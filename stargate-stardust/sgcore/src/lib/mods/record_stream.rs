@@ -0,0 +1,427 @@
+//! Stardust records one at a time, for commands whose input may not fit in
+//! memory.
+//!
+//! A [`RecordStream`] parses a document, in either encoding, on a thread of
+//! its own and yields its records as they are read. What is left of the
+//! document is an [`Envelope`] holding an empty array where the records
+//! were, which [`Envelope::write`] writes back around records drawn from an
+//! iterator. Records are found where [`take_records`] finds them; only an
+//! object whose records are neither in the given field nor under one of
+//! [`RECORD_KEYS`] is read whole before its records are handed out.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{self, BufReader, Read, Write};
+use std::sync::mpsc::{Receiver, SyncSender, sync_channel};
+use std::thread::{self, JoinHandle};
+
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::json_adapter::{RECORD_KEYS, records_key, set_count, take_records};
+use crate::wire;
+
+/// Number of records parsed ahead of the consumer
+const READ_AHEAD: usize = 1024;
+
+const READER_GONE: &str = "record consumer went away";
+
+thread_local! {
+    /// Where the document parsed on this thread sends its records
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+struct Sink {
+    sender: SyncSender<Value>,
+    field: Option<String>,
+}
+
+/// The records of a stardust document, yielded while it is parsed
+pub struct RecordStream {
+    records: Receiver<Value>,
+    parser: JoinHandle<Result<Envelope, String>>,
+}
+
+impl RecordStream {
+    /// Start reading the document in `reader`, taking its records from the
+    /// top-level `field` when one is given
+    pub fn new(reader: impl Read + Send + 'static, field: Option<&str>) -> Self {
+        let (sender, records) = sync_channel(READ_AHEAD);
+        let field = field.map(str::to_string);
+        let parser = thread::spawn(move || parse(reader, Sink { sender, field }));
+        Self { records, parser }
+    }
+
+    /// Once every record has been taken, what is left of the document, or
+    /// why it could not be read
+    pub fn finish(self) -> Result<Envelope, String> {
+        drop(self.records);
+        self.parser
+            .join()
+            .unwrap_or_else(|_| Err("record reader panicked".to_string()))
+    }
+}
+
+impl Iterator for RecordStream {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        self.records.recv().ok()
+    }
+}
+
+fn parse(reader: impl Read, sink: Sink) -> Result<Envelope, String> {
+    let mut reader = BufReader::new(reader);
+    let mut prefix = Vec::new();
+    reader
+        .by_ref()
+        .take(wire::CBOR_MAGIC.len() as u64)
+        .read_to_end(&mut prefix)
+        .map_err(|e| format!("Failed to read input: {e}"))?;
+
+    let sender = sink.sender.clone();
+    let wanted = sink.field.clone();
+    SINK.with(|slot| *slot.borrow_mut() = Some(sink));
+    let Document(envelope) = if wire::is_binary(&prefix) {
+        ciborium::from_reader(reader).map_err(|e| format!("Failed to parse binary stardust: {e}"))?
+    } else {
+        let mut json = serde_json::Deserializer::from_reader(prefix.as_slice().chain(reader));
+        let Document(envelope) = Document::deserialize(&mut json)
+            .map_err(|e| format!("Failed to parse JSON: {e}"))?;
+        match json.end() {
+            Ok(()) => Document(envelope),
+            // a record followed by more: one record per line, as `tail --obj` writes
+            Err(_) if envelope.field.is_none() && envelope.value.is_object() => {
+                return send_json_lines(envelope.value, json, &sender);
+            }
+            Err(e) => return Err(format!("Failed to parse JSON: {e}")),
+        }
+    };
+    if envelope.field.is_some() || envelope.value.is_array() {
+        return Ok(envelope);
+    }
+
+    let mut value = envelope.value;
+    let field = match &wanted {
+        Some(field) => Some(field.clone()),
+        None => value.as_object().and_then(records_key).map(str::to_string),
+    };
+    for record in take_records(&mut value, wanted.as_deref())? {
+        sender.send(record).map_err(|_| READER_GONE.to_string())?;
+    }
+    Ok(Envelope { value, field })
+}
+
+/// Send `first` and then the objects that follow it in `json`
+fn send_json_lines<'de, R: serde_json::de::Read<'de>>(
+    first: Value,
+    json: serde_json::Deserializer<R>,
+    sender: &SyncSender<Value>,
+) -> Result<Envelope, String> {
+    sender.send(first).map_err(|_| READER_GONE.to_string())?;
+    for record in json.into_iter::<Value>() {
+        let record = record.map_err(|e| format!("Failed to parse JSON: {e}"))?;
+        if !record.is_object() {
+            return Err("Failed to parse JSON: expected one record per line".to_string());
+        }
+        sender.send(record).map_err(|_| READER_GONE.to_string())?;
+    }
+    Ok(Envelope::default())
+}
+
+/// What a document parses to once its records have been sent away
+struct Document(Envelope);
+
+impl<'de> Deserialize<'de> for Document {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let sink = SINK
+            .with(|slot| slot.borrow_mut().take())
+            .ok_or_else(|| de::Error::custom("no record sink"))?;
+        deserializer.deserialize_any(DocumentVisitor { sink }).map(Document)
+    }
+}
+
+struct DocumentVisitor {
+    sink: Sink,
+}
+
+impl<'de> Visitor<'de> for DocumentVisitor {
+    type Value = Envelope;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array or an object with an array of records")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Envelope, A::Error> {
+        if self.sink.field.is_some() {
+            return Err(de::Error::custom(
+                "input must be an array or an object with an array of records"
+            ));
+        }
+        send_all(&self.sink.sender, seq)?;
+        Ok(Envelope::default())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Envelope, A::Error> {
+        let mut map = Map::new();
+        let mut field = None;
+        while let Some(key) = access.next_key::<String>()? {
+            let holds_records = field.is_none()
+                && match &self.sink.field {
+                    Some(wanted) => *wanted == key,
+                    None => RECORD_KEYS.contains(&key.as_str()),
+                };
+            let value = if holds_records {
+                let seed = RecordsSeed {
+                    sender: &self.sink.sender,
+                };
+                access.next_value_seed(seed)?.unwrap_or_else(|| {
+                    field = Some(key.clone());
+                    Value::Array(Vec::new())
+                })
+            } else {
+                access.next_value()?
+            };
+            map.insert(key, value);
+        }
+        Ok(Envelope {
+            value: Value::Object(map),
+            field,
+        })
+    }
+}
+
+/// Sends the elements of an array away, or gives back any other value
+struct RecordsSeed<'a> {
+    sender: &'a SyncSender<Value>,
+}
+
+impl<'de> DeserializeSeed<'de> for RecordsSeed<'_> {
+    type Value = Option<Value>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for RecordsSeed<'_> {
+    type Value = Option<Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        send_all(self.sender, seq)?;
+        Ok(None)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        Value::deserialize(de::value::MapAccessDeserializer::new(map)).map(Some)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Some(v.into()))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Some(v.into()))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Some(v.into()))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Some(v.into()))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Some(v.into()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Some(v.into()))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Some(Value::Null))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Some(Value::Null))
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        Value::deserialize(deserializer).map(Some)
+    }
+}
+
+fn send_all<'de, A: SeqAccess<'de>>(sender: &SyncSender<Value>, mut seq: A) -> Result<(), A::Error> {
+    while let Some(record) = seq.next_element::<Value>()? {
+        sender
+            .send(record)
+            .map_err(|_| de::Error::custom(READER_GONE))?;
+    }
+    Ok(())
+}
+
+/// A stardust document with its records taken out
+pub struct Envelope {
+    value: Value,
+    /// The field that held the records, `None` when the document is the
+    /// array of them
+    field: Option<String>,
+}
+
+impl Default for Envelope {
+    /// A document that is nothing but its records
+    fn default() -> Self {
+        Self {
+            value: Value::Array(Vec::new()),
+            field: None,
+        }
+    }
+}
+
+impl Envelope {
+    /// Whether the document counts its records, so the count has to be
+    /// known before [`Envelope::write`]
+    pub fn has_count(&self) -> bool {
+        self.value.get("count").is_some()
+    }
+
+    /// Set the document's `count`, when it has one
+    pub fn set_count(&mut self, count: usize) {
+        set_count(&mut self.value, count);
+    }
+
+    /// Write the document with `records` in place of its records, in the
+    /// encoding this process was asked for (see [`wire::write_value`])
+    pub fn write(&self, out: &mut impl Write, records: impl Iterator<Item = Value>) -> io::Result<()> {
+        let document = Streamed {
+            envelope: self,
+            records: RefCell::new(records),
+        };
+        if wire::binary_requested() {
+            out.write_all(&wire::CBOR_MAGIC)?;
+            return ciborium::into_writer(&document, &mut *out).map_err(|e| match e {
+                ciborium::ser::Error::Io(e) => e,
+                ciborium::ser::Error::Value(e) => io::Error::other(e),
+            });
+        }
+        serde_json::to_writer(&mut *out, &document)?;
+        writeln!(out)
+    }
+}
+
+/// An envelope being written around records drawn from an iterator
+struct Streamed<'a, I> {
+    envelope: &'a Envelope,
+    records: RefCell<I>,
+}
+
+impl<I: Iterator<Item = Value>> Serialize for Streamed<'_, I> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let records = Records(&self.records);
+        let (Value::Object(map), Some(field)) = (&self.envelope.value, &self.envelope.field) else {
+            return records.serialize(serializer);
+        };
+        let mut out = serializer.serialize_map(Some(map.len()))?;
+        for (key, value) in map {
+            if key == field {
+                out.serialize_entry(key, &records)?;
+            } else {
+                out.serialize_entry(key, value)?;
+            }
+        }
+        out.end()
+    }
+}
+
+struct Records<'a, I>(&'a RefCell<I>);
+
+impl<I: Iterator<Item = Value>> Serialize for Records<'_, I> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for record in &mut *self.0.borrow_mut() {
+            seq.serialize_element(&record)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn round_trip(input: &Value, field: Option<&str>) -> (Vec<Value>, Value) {
+        let mut stream = RecordStream::new(io::Cursor::new(input.to_string()), field);
+        let records: Vec<Value> = stream.by_ref().collect();
+        let envelope = stream.finish().unwrap();
+        let mut out = Vec::new();
+        envelope.write(&mut out, records.clone().into_iter()).unwrap();
+        (records, serde_json::from_slice(&out).unwrap())
+    }
+
+    #[test]
+    fn test_array_and_envelope() {
+        let array = json!([{"n": 1}, {"n": 2}]);
+        assert_eq!(round_trip(&array, None), (vec![json!({"n": 1}), json!({"n": 2})], array));
+
+        let object = json!({"count": 2, "entries": [1, 2], "path": "."});
+        assert_eq!(round_trip(&object, None), (vec![json!(1), json!(2)], object));
+    }
+
+    #[test]
+    fn test_field_and_fallback() {
+        let object = json!({"tags": ["x"], "items": [3, 4]});
+        assert_eq!(round_trip(&object, Some("items")).0, vec![json!(3), json!(4)]);
+
+        let processes = json!({"host": "h", "processes": [{"pid": 1}]});
+        assert_eq!(round_trip(&processes, None), (vec![json!({"pid": 1})], processes));
+    }
+
+    #[test]
+    fn test_binary_input() {
+        let value = json!({"records": [{"a": 1}], "count": 1});
+        let mut stream = RecordStream::new(io::Cursor::new(wire::encode(&value)), None);
+        assert_eq!(stream.by_ref().collect::<Vec<_>>(), vec![json!({"a": 1})]);
+        assert!(stream.finish().unwrap().has_count());
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let input = "{\"line\": \"a\"}\n{\"line\": \"b\"}\n\n{\"line\": \"c\"}\n";
+        let mut stream = RecordStream::new(io::Cursor::new(input), None);
+        let lines: Vec<Value> = stream.by_ref().map(|record| record["line"].clone()).collect();
+        assert_eq!(lines, ["a", "b", "c"]);
+        let envelope = stream.finish().unwrap();
+        assert!(!envelope.has_count());
+
+        let mut out = Vec::new();
+        envelope.write(&mut out, [json!({"line": "a"})].into_iter()).unwrap();
+        assert_eq!(out, b"[{\"line\":\"a\"}]\n");
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        for (input, field) in [
+            ("[1, 2", None),
+            ("3", None),
+            ("{\"a\": 1}", None),
+            ("[1]", Some("a")),
+            ("[1]\n[2]", None),
+            ("{\"a\": 1}\n2", None),
+            ("{\"a\": 1}\n{\"a\":", None),
+            ("{\"entries\": []}\n{\"a\": 1}", None),
+        ] {
+            let mut stream = RecordStream::new(io::Cursor::new(input.to_string()), field);
+            stream.by_ref().for_each(drop);
+            assert!(stream.finish().is_err(), "{input}");
+        }
+    }
+}
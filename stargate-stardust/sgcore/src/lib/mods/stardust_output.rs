@@ -4,11 +4,17 @@ use clap::{Arg, ArgAction};
 use serde_json::json;
 use serde_json::Value as JsonValue;
 
-#[derive(Debug, Clone, Copy)]
+use crate::error::SGSimpleError;
+use crate::field_path::{parse_selectors, select_fields};
+use crate::wire;
+
+#[derive(Debug, Clone)]
 pub struct StardustOutputOptions {
     pub stardust_output: bool,
     pub verbose: bool,
     pub pretty: bool,
+    /// The `--field` spec, applied by [`output`]
+    pub field: Option<String>,
 }
 
 impl StardustOutputOptions {
@@ -17,6 +23,7 @@ impl StardustOutputOptions {
             stardust_output: false,
             verbose: false,
             pretty: false,
+            field: None,
         }
     }
 
@@ -25,6 +32,7 @@ impl StardustOutputOptions {
             stardust_output: matches.get_flag(ARG_STARDUST_OUTPUT),
             verbose: matches.get_flag(ARG_VERBOSE),
             pretty: matches.get_flag(ARG_PRETTY),
+            field: matches.try_get_one::<String>(ARG_FIELD).ok().flatten().cloned(),
         }
     }
 }
@@ -64,7 +72,7 @@ pub fn add_json_args(cmd: clap::Command) -> clap::Command {
         Arg::new(ARG_FIELD)
             .long("field")
             .value_name("FIELD")
-            .help("Filter stardust output to specific field(s) (comma-separated)")
+            .help("Filter stardust output to field paths, e.g. name,owner.name as owner,entries[0:5] (comma-separated)")
             .action(ArgAction::Set),
     )
     .arg(
@@ -76,37 +84,43 @@ pub fn add_json_args(cmd: clap::Command) -> clap::Command {
     )
 }
 
-pub fn filter_fields(value: JsonValue, field_spec: Option<&str>) -> JsonValue {
-    let Some(spec) = field_spec else { return value; };
-    let JsonValue::Object(mut obj) = value else { return value; };
-    
-    let fields: Vec<&str> = spec.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()).collect();
-    if fields.is_empty() {
-        return JsonValue::Object(obj);
-    }
-    
-    let mut filtered = serde_json::map::Map::new();
-    for field in fields {
-        if let Some(val) = obj.remove(field) {
-            filtered.insert(field.to_string(), val);
-        }
+/// Keep the fields of `value` named by `field_spec`, a comma-separated list
+/// of field paths such as `name,owner.name as owner,entries[0:5]` (see
+/// [`crate::field_path`]). An array has each of its elements filtered.
+/// A spec that does not parse is an error.
+pub fn filter_fields(value: JsonValue, field_spec: Option<&str>) -> Result<JsonValue, String> {
+    let Some(spec) = field_spec else { return Ok(value); };
+    let selectors = parse_selectors(spec)?;
+    if selectors.is_empty() {
+        return Ok(value);
     }
-    JsonValue::Object(filtered)
+
+    Ok(match value {
+        JsonValue::Object(_) => JsonValue::Object(select_fields(&value, &selectors)),
+        JsonValue::Array(elements) => elements
+            .iter()
+            .map(|element| JsonValue::Object(select_fields(element, &selectors)))
+            .collect(),
+        _ => value,
+    })
 }
 
+/// Print `value` when stardust output was asked for, or run `default_output`.
+/// Output is JSON unless the shell negotiated the binary encoding for this
+/// stage (see [`crate::wire`]). An invalid `--field` spec fails with an
+/// error that converts to a usage failure (exit status 2).
 pub fn output<F>(options: StardustOutputOptions, value: JsonValue, default_output: F) -> std::io::Result<()>
 where
     F: FnOnce() -> std::io::Result<()>,
 {
     if options.stardust_output {
-        if options.pretty {
-            match serde_json::to_string_pretty(&value) {
-                Ok(s) => println!("{}", s),
-                Err(_) => println!("{}", value),
-            }
-        } else {
-            println!("{}", value);
-        }
+        let value = filter_fields(value, options.field.as_deref()).map_err(|message| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                SGSimpleError { code: 2, message },
+            )
+        })?;
+        wire::print_value(&value, options.pretty)?;
     } else {
         default_output()?;
     }
@@ -127,43 +141,138 @@ pub fn response_with_fields(fields: Vec<(&str, JsonValue)>) -> JsonValue {
     JsonValue::Object(obj)
 }
 
-/// Helper to create a JSON schema for command output
-/// Returns a simple schema with property names and types
-pub fn create_schema(properties: Vec<(&str, &str, Option<&str>)>) -> JsonValue {
+/// JSON Schema dialect of the schemas commands print with `--schema`
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// `format` of an integer property counting bytes; renderers show it as a
+/// human-readable size
+pub const FORMAT_BYTES: &str = "bytes";
+/// `format` of an integer property holding seconds since the epoch;
+/// renderers show it as a local date and time
+pub const FORMAT_UNIX_TIME: &str = "unix-time";
+
+/// Something usable as the schema of a value: a JSON type name such as
+/// `"string"` or `"integer"`, or a schema built with [`object_schema`],
+/// [`array_schema`] or [`enum_schema`]
+pub trait IntoSchema {
+    fn into_schema(self) -> JsonValue;
+}
+
+impl IntoSchema for &str {
+    fn into_schema(self) -> JsonValue {
+        json!({ "type": self })
+    }
+}
+
+impl IntoSchema for JsonValue {
+    fn into_schema(self) -> JsonValue {
+        self
+    }
+}
+
+/// A property of an object schema, required unless marked optional
+#[derive(Debug, Clone)]
+pub struct Property {
+    name: String,
+    schema: JsonValue,
+    required: bool,
+}
+
+impl Property {
+    pub fn new(name: &str, schema: impl IntoSchema) -> Self {
+        Self {
+            name: name.to_string(),
+            schema: schema.into_schema(),
+            required: true,
+        }
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        if let JsonValue::Object(map) = &mut self.schema {
+            map.insert("description".to_string(), JsonValue::String(description.to_string()));
+        }
+        self
+    }
+
+    /// How the value is to be read, e.g. [`FORMAT_BYTES`] or [`FORMAT_UNIX_TIME`]
+    pub fn format(mut self, format: &str) -> Self {
+        if let JsonValue::Object(map) = &mut self.schema {
+            map.insert("format".to_string(), JsonValue::String(format.to_string()));
+        }
+        self
+    }
+
+    /// The property may be missing, e.g. a field only some entries have
+    pub fn optional(mut self) -> Self {
+        self.required = false;
+        self
+    }
+}
+
+/// An object with the given properties and no others
+pub fn object_schema(properties: Vec<Property>) -> JsonValue {
     let mut props = serde_json::map::Map::new();
-    for (name, type_name, description) in properties {
-        let mut prop = serde_json::map::Map::new();
-        prop.insert("type".to_string(), JsonValue::String(type_name.to_string()));
-        if let Some(desc) = description {
-            prop.insert("description".to_string(), JsonValue::String(desc.to_string()));
+    let mut required = Vec::new();
+    for property in properties {
+        if property.required {
+            required.push(JsonValue::String(property.name.clone()));
         }
-        props.insert(name.to_string(), JsonValue::Object(prop));
+        props.insert(property.name, property.schema);
     }
-    
+
     json!({
         "type": "object",
-        "properties": props
+        "properties": props,
+        "required": required,
+        "additionalProperties": false
     })
 }
 
+/// An array whose elements all match `items`
+pub fn array_schema(items: impl IntoSchema) -> JsonValue {
+    json!({
+        "type": "array",
+        "items": items.into_schema()
+    })
+}
+
+/// A string that is one of `values`
+pub fn enum_schema(values: &[&str]) -> JsonValue {
+    json!({
+        "type": "string",
+        "enum": values
+    })
+}
+
+/// The schema of a command's `--obj` output: an object with the given
+/// properties, as a JSON Schema document
+pub fn create_schema(properties: Vec<Property>) -> JsonValue {
+    let mut schema = object_schema(properties);
+    schema["$schema"] = JsonValue::String(JSON_SCHEMA_DIALECT.to_string());
+    schema
+}
+
 /// Print schema to stdout
 pub fn print_schema(schema: JsonValue) -> std::io::Result<()> {
     println!("{}", serde_json::to_string_pretty(&schema).unwrap_or_else(|_| schema.to_string()));
     Ok(())
 }
 
+/// Build a command's output schema; every property is required. A type is
+/// a JSON type name or any [`IntoSchema`], e.g. an [`array_schema`] of
+/// [`object_schema`] records.
 #[macro_export]
 macro_rules! schema {
     // With descriptions: name => type, description
     ($($name:expr => $type:expr, $desc:expr);* $(;)?) => {
         $crate::stardust_output::create_schema(vec![
-            $(($name, $type, Some($desc)),)*
+            $($crate::stardust_output::Property::new($name, $type).description($desc),)*
         ])
     };
     // Without descriptions: name => type
     ($($name:expr => $type:expr);* $(;)?) => {
         $crate::stardust_output::create_schema(vec![
-            $(($name, $type, None),)*
+            $($crate::stardust_output::Property::new($name, $type),)*
         ])
     };
 }
@@ -205,4 +314,48 @@ mod tests {
         assert_eq!(resp["field1"], "value1");
         assert_eq!(resp["field2"], 42);
     }
+
+    #[test]
+    fn test_create_schema() {
+        let schema = crate::schema!(
+            "entries" => array_schema(object_schema(vec![
+                Property::new("name", "string"),
+                Property::new("type", enum_schema(&["file", "directory"])),
+                Property::new("modified", "integer").format(FORMAT_UNIX_TIME).optional(),
+            ])), "Entries";
+            "count" => "integer", "Number of entries";
+        );
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(schema["required"], json!(["entries", "count"]));
+        assert_eq!(schema["properties"]["count"]["description"], "Number of entries");
+        let entry = &schema["properties"]["entries"]["items"];
+        assert_eq!(entry["required"], json!(["name", "type"]));
+        assert_eq!(entry["properties"]["type"]["enum"], json!(["file", "directory"]));
+        assert_eq!(entry["additionalProperties"], false);
+        assert_eq!(entry["properties"]["modified"]["format"], FORMAT_UNIX_TIME);
+    }
+
+    #[test]
+    fn test_filter_fields() {
+        let value = json!([{"name": "a", "size": 1}, {"name": "b", "size": 2}]);
+        assert_eq!(
+            filter_fields(value.clone(), Some("name")).unwrap(),
+            json!([{"name": "a"}, {"name": "b"}])
+        );
+        assert_eq!(filter_fields(value.clone(), None).unwrap(), value);
+        assert!(filter_fields(value, Some("name..first")).is_err());
+    }
+
+    #[test]
+    fn test_output_rejects_invalid_field() {
+        let options = StardustOutputOptions {
+            stardust_output: true,
+            field: Some("name..first".to_string()),
+            ..StardustOutputOptions::default()
+        };
+        let err: Box<dyn crate::error::SGError> =
+            output(options, json!({"name": "a"}), || Ok(())).unwrap_err().into();
+        assert_eq!(err.code(), 2);
+        assert!(err.to_string().contains("name..first"));
+    }
 }
@@ -0,0 +1,144 @@
+//! The encodings stardust travels in between processes.
+//!
+//! Stardust is JSON wherever a human or an external tool may see it. When
+//! the shell wires two stargate commands together it sets
+//! [`ENV_WIRE`]`=cbor` on the producing stage, which then writes its value
+//! as CBOR behind the self-describe tag (`d9 d9 f7`). That prefix can never
+//! start JSON text, so readers tell the two apart without being told.
+
+use serde_json::Value;
+use std::io::{self, Read, Write};
+
+/// Environment variable naming the encoding a stage should write
+pub const ENV_WIRE: &str = "STARGATE_WIRE";
+
+/// Value of [`ENV_WIRE`] selecting CBOR
+pub const WIRE_CBOR: &str = "cbor";
+
+/// The CBOR self-describe tag (55799) that opens every binary payload
+pub const CBOR_MAGIC: [u8; 3] = [0xd9, 0xd9, 0xf7];
+
+/// Whether this process was asked to write binary stardust
+pub fn binary_requested() -> bool {
+    std::env::var(ENV_WIRE).is_ok_and(|wire| wire == WIRE_CBOR)
+}
+
+/// Whether `bytes` hold binary stardust rather than JSON text
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.starts_with(&CBOR_MAGIC)
+}
+
+/// Encode `value` as tagged CBOR
+pub fn encode(value: &Value) -> Vec<u8> {
+    let mut bytes = CBOR_MAGIC.to_vec();
+    ciborium::into_writer(value, &mut bytes).expect("writing to a Vec cannot fail");
+    bytes
+}
+
+/// Decode stardust in either encoding. JSON text may also be a stream of
+/// records, one object per line as `tail --obj` writes them, which decodes
+/// to an array of those records.
+pub fn decode(bytes: &[u8]) -> Result<Value, String> {
+    if is_binary(bytes) {
+        ciborium::from_reader(&bytes[CBOR_MAGIC.len()..])
+            .map_err(|e| format!("Failed to parse binary stardust: {e}"))
+    } else {
+        serde_json::from_slice(bytes.trim_ascii()).or_else(|e| {
+            decode_json_lines(bytes).ok_or_else(|| format!("Failed to parse JSON: {e}"))
+        })
+    }
+}
+
+/// Two or more lines of JSON objects (blank lines aside) as an array
+fn decode_json_lines(bytes: &[u8]) -> Option<Value> {
+    let records = bytes
+        .split(|&b| b == b'\n')
+        .map(<[u8]>::trim_ascii)
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_slice::<Value>(line).ok().filter(Value::is_object))
+        .collect::<Option<Vec<Value>>>()?;
+    (records.len() > 1).then_some(Value::Array(records))
+}
+
+/// Read all of `reader` and decode it as stardust in either encoding
+pub fn read_value(mut reader: impl Read) -> Result<Value, String> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read input: {e}"))?;
+    decode(&bytes)
+}
+
+/// Turn stardust in either encoding into JSON text, leaving anything that
+/// is not binary stardust untouched
+pub fn to_json_text(bytes: Vec<u8>) -> String {
+    match is_binary(&bytes).then(|| decode(&bytes)) {
+        Some(Ok(value)) => format!("{value}\n"),
+        _ => String::from_utf8_lossy(&bytes).into_owned(),
+    }
+}
+
+/// Write `value` in the encoding this process was asked for: CBOR under
+/// [`ENV_WIRE`]`=cbor`, otherwise a line of (optionally pretty) JSON
+pub fn write_value(out: &mut impl Write, value: &Value, pretty: bool) -> io::Result<()> {
+    if binary_requested() {
+        return out.write_all(&encode(value));
+    }
+    if pretty {
+        serde_json::to_writer_pretty(&mut *out, value)?;
+    } else {
+        serde_json::to_writer(&mut *out, value)?;
+    }
+    writeln!(out)
+}
+
+/// [`write_value`] to stdout
+pub fn print_value(value: &Value, pretty: bool) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    write_value(&mut stdout, value, pretty)?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_binary_round_trip() {
+        let value = json!({
+            "entries": [{"name": "a.rs", "size": 1024, "ratio": 0.5, "link": null}],
+            "count": -1,
+            "big": u64::MAX,
+        });
+        let bytes = encode(&value);
+        assert!(is_binary(&bytes));
+        assert_eq!(decode(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn test_decode_json_text() {
+        assert_eq!(decode(b"  [1, 2]\n").unwrap(), json!([1, 2]));
+        assert!(!is_binary(b"{\"a\": 1}"));
+        assert!(decode(b"not json").is_err());
+    }
+
+    #[test]
+    fn test_decode_json_lines() {
+        let lines = b"{\"line\": \"a\", \"n\": 1}\n\n{\"line\": \"b\", \"n\": 2}\n";
+        assert_eq!(
+            decode(lines).unwrap(),
+            json!([{"line": "a", "n": 1}, {"line": "b", "n": 2}])
+        );
+        // Only lines of records: other value streams stay an error
+        assert!(decode(b"1 2 3").is_err());
+        assert!(decode(b"1\n2\n").is_err());
+        assert!(decode(b"{\"a\": 1}\nplain text\n").is_err());
+    }
+
+    #[test]
+    fn test_to_json_text() {
+        assert_eq!(to_json_text(encode(&json!({"a": 1}))), "{\"a\":1}\n");
+        assert_eq!(to_json_text(b"plain text\n".to_vec()), "plain text\n");
+    }
+}
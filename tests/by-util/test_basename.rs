@@ -292,3 +292,17 @@ fn test_emoji_handling() {
         .succeeds()
         .stdout_only("file🎯\n");
 }
+
+#[test]
+fn test_obj_field() {
+    new_ucmd!()
+        .args(&["--obj", "--field", "name", "a/b"])
+        .succeeds()
+        .stdout_is("{\"name\":[\"b\"]}\n");
+
+    new_ucmd!()
+        .args(&["--obj", "--field", "name..first", "a/b"])
+        .fails_with_code(2)
+        .no_stdout()
+        .stderr_contains("unexpected '.' in field path 'name..first'");
+}
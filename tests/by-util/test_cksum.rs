@@ -2730,3 +2730,53 @@ mod format_mix {
             .stderr_contains("cksum: WARNING: 1 line is improperly formatted");
     }
 }
+
+#[test]
+fn test_obj_digests() {
+    let ts = TestScenario::new(util_name!());
+    let text = ts
+        .ucmd()
+        .args(&["-a", "sha256", "--untagged", "lorem_ipsum.txt"])
+        .succeeds();
+    let result = ts
+        .ucmd()
+        .args(&["-a", "sha256", "--obj", "lorem_ipsum.txt", "alice_in_wonderland.txt"])
+        .succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    let files = output["files"].as_array().unwrap();
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0]["path"], "lorem_ipsum.txt");
+    assert_eq!(files[0]["algorithm"], "sha256");
+    assert_eq!(files[0]["digest"], text.stdout_str().split(' ').next().unwrap());
+    assert_eq!(files[0]["size"], ts.fixtures.read("lorem_ipsum.txt").len());
+}
+
+#[test]
+fn test_obj_legacy_digest() {
+    let (_, mut ucmd) = at_and_ucmd!();
+    let result = ucmd.args(&["--obj", "lorem_ipsum.txt"]).succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(output["files"][0]["algorithm"], "crc");
+    assert!(output["files"][0]["digest"].as_str().unwrap().parse::<u32>().is_ok());
+}
+
+#[test]
+fn test_obj_check() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    at.write("foo", "foo\n");
+    let sums = ts.ucmd().args(&["-a", "md5", "foo"]).succeeds();
+    at.write("sums", &format!("{}not a checksum line\n", sums.stdout_str()));
+
+    let result = ts.ucmd().args(&["--check", "--obj", "sums"]).succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    let record = &output["results"][0];
+    assert_eq!(record["path"], "foo");
+    assert_eq!(record["algorithm"], "md5");
+    assert_eq!(record["status"], "ok");
+    assert_eq!(record["expected"], record["actual"]);
+    assert_eq!(record["checksum_file"], "sums");
+    assert_eq!(record["line"], 1);
+    assert_eq!(output["summary"]["bad_format"], 1);
+}
@@ -581,3 +581,82 @@ fn test_comm_eintr_handling() {
         .stdout_contains("line2")
         .stdout_contains("line3");
 }
+
+#[test]
+fn test_obj_key() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.write(
+        "a.json",
+        r#"{"entries": [{"path": "a", "v": 1}, {"path": "b", "v": 1}, {"path": "d", "v": 1}]}"#
+    );
+    at.write(
+        "b.json",
+        r#"[{"path": "b", "v": 2}, {"path": "c", "v": 2}, {"path": "d", "v": 2}]"#
+    );
+
+    let result = scene
+        .ucmd()
+        .args(&["--obj", "--key", "path", "a.json", "b.json"])
+        .succeeds();
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value["count"], 4);
+    assert_eq!(
+        value["totals"],
+        serde_json::json!({"first": 1, "second": 1, "both": 2})
+    );
+    let sides: Vec<_> = value["records"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| format!("{}:{}", r["side"].as_str().unwrap(), r["key"].as_str().unwrap()))
+        .collect();
+    assert_eq!(sides, ["first:a", "both:b", "second:c", "both:d"]);
+    assert_eq!(value["records"][1]["first"]["v"], 1);
+    assert_eq!(value["records"][1]["second"]["v"], 2);
+    assert!(value["records"][2].get("first").is_none());
+
+    let result = scene
+        .ucmd()
+        .args(&["--obj", "--key", "path,v", "-3", "b.json", "a.json"])
+        .succeeds();
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value["count"], 6);
+    assert_eq!(value["records"][0]["key"], serde_json::json!(["a", 1]));
+    assert_eq!(value["records"][0]["side"], "second");
+}
+
+#[test]
+fn test_obj_stdin_and_order_check() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.write("sorted.json", "[1, 2, 3]");
+    at.write("unsorted.json", "[3, 1, 2]");
+
+    let result = scene
+        .ucmd()
+        .args(&["--obj", "-1", "-2", "sorted.json", "-"])
+        .pipe_in("[2, 3, 4]")
+        .succeeds();
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value["records"], serde_json::json!([
+        {"side": "both", "key": 2, "first": 2, "second": 2},
+        {"side": "both", "key": 3, "first": 3, "second": 3}
+    ]));
+
+    scene
+        .ucmd()
+        .args(&["--obj", "sorted.json", "unsorted.json"])
+        .fails_with_code(1)
+        .stderr_contains("file 2 is not in sorted order")
+        .stderr_contains("input is not in sorted order");
+    scene
+        .ucmd()
+        .args(&["--obj", "--nocheck-order", "sorted.json", "unsorted.json"])
+        .succeeds()
+        .no_stderr();
+    scene
+        .ucmd()
+        .args(&["--key", "path", "sorted.json", "unsorted.json"])
+        .fails();
+}
@@ -6566,3 +6566,52 @@ fn test_cp_recurse_verbose_output_with_symlink_already_exists() {
         .no_stderr()
         .stdout_is(output);
 }
+
+#[test]
+fn test_cp_obj_report_backup() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("source", "hello");
+    at.write("dest", "old");
+    let result = ucmd
+        .args(&["--obj", "-v", "-b", "source", "dest"])
+        .succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    let operations = output["operations"].as_array().unwrap();
+    assert_eq!(operations.len(), 2);
+    assert_eq!(operations[0]["action"], "backed_up");
+    assert_eq!(operations[0]["source"], "dest");
+    assert_eq!(operations[0]["destination"], "dest~");
+    assert_eq!(operations[1]["action"], "copied");
+    assert_eq!(operations[1]["source"], "source");
+    assert_eq!(operations[1]["destination"], "dest");
+    assert_eq!(operations[1]["bytes"], 5);
+    assert!(operations[1]["error"].is_null());
+    assert_eq!(output["summary"]["bytes"], 5);
+    assert_eq!(at.read("dest~"), "old");
+}
+
+#[test]
+fn test_cp_obj_report_recursive_with_failure() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("dir");
+    at.mkdir("dest");
+    at.write("dir/file", "abc");
+    let result = ucmd
+        .args(&["--obj", "-r", "--preserve=mode,timestamps", "dir", "missing", "dest"])
+        .fails_with_code(1);
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    let operations = output["operations"].as_array().unwrap();
+    let created = operations.iter().find(|op| op["action"] == "created").unwrap();
+    assert_eq!(created["destination"], "dest/dir");
+    let copied = operations.iter().find(|op| op["action"] == "copied").unwrap();
+    assert_eq!(copied["source"], "dir/file");
+    assert_eq!(copied["bytes"], 3);
+    let preserved = copied["preserved"].as_array().unwrap();
+    assert!(preserved.contains(&"mode".into()) && preserved.contains(&"timestamps".into()));
+    let failed = operations.iter().find(|op| op["action"] == "failed").unwrap();
+    assert_eq!(failed["source"], "missing");
+    assert!(failed["error"].as_str().unwrap().contains("missing"));
+    assert_eq!(output["summary"]["failed"], 1);
+}
@@ -1542,3 +1542,54 @@ fn test_csplit_non_utf8_paths() {
 
     ucmd.arg(&filename).arg("3").succeeds();
 }
+
+#[test]
+fn test_obj_reports_splits() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let result = ucmd
+        .args(&["--obj", "-", "/^3/", "/^5/"])
+        .pipe_in("1\n2\n3\n4\n5\n6\n7\n")
+        .succeeds();
+
+    let report: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(
+        report,
+        serde_json::json!({
+            "count": 3,
+            "files": [
+                {"path": "xx00", "bytes": 4, "lines": 2, "first_line": "1"},
+                {"path": "xx01", "bytes": 4, "lines": 2, "first_line": "3"},
+                {"path": "xx02", "bytes": 6, "lines": 3, "first_line": "5"},
+            ],
+        })
+    );
+    assert_eq!(at.read("xx01"), "3\n4\n");
+}
+
+#[test]
+fn test_obj_elided_and_kept_splits() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let result = ucmd
+        .args(&["--obj", "-z", "-", "1", "3"])
+        .pipe_in("1\n2\n3\n")
+        .succeeds();
+    let report: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    let paths: Vec<_> = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| file["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, ["xx00", "xx01"]);
+    assert_eq!(at.read("xx00"), "1\n2\n");
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    let result = ucmd
+        .args(&["--obj", "-k", "-", "2", "9"])
+        .pipe_in("1\n2\n3\n")
+        .fails();
+    let report: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(report["count"], 2);
+    assert_eq!(report["files"][0]["first_line"], "1");
+    assert!(at.file_exists("xx01"));
+}
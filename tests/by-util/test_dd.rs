@@ -1699,3 +1699,63 @@ fn test_oflag_direct_partial_block() {
     at.remove(input_file);
     at.remove(output_file);
 }
+
+#[test]
+fn test_obj_summary() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("in", &"x".repeat(1500));
+
+    let result = ucmd
+        .args(&["--obj", "if=in", "of=out", "bs=1K", "count=1"])
+        .succeeds();
+    assert_eq!(at.read("out").len(), 1024);
+    let lines: Vec<&str> = result.stderr_str().lines().collect();
+    let summary: serde_json::Value = serde_json::from_str(lines.last().unwrap()).unwrap();
+    assert_eq!(summary["type"], "summary");
+    assert_eq!(summary["bytes_read"], 1024);
+    assert_eq!(summary["bytes_written"], 1024);
+    assert_eq!(summary["records_in"]["full"], 1);
+    assert_eq!(summary["records_in"]["partial"], 0);
+    assert_eq!(summary["records_out"]["full"], 1);
+    assert_eq!(summary["total"], 1024);
+    assert_eq!(summary["percent"], 100.0);
+    assert_eq!(summary["eta"], 0.0);
+}
+
+#[test]
+fn test_obj_unknown_size() {
+    let result = new_ucmd!()
+        .args(&["--obj", "status=noxfer"])
+        .pipe_in("hello")
+        .succeeds()
+        .stdout_is("hello");
+    let summary: serde_json::Value = serde_json::from_str(result.stderr_str().trim()).unwrap();
+    assert_eq!(summary["records_in"]["partial"], 1);
+    assert!(summary["total"].is_null());
+    assert!(summary["eta"].is_null());
+}
+
+#[test]
+fn test_obj_status_none() {
+    new_ucmd!()
+        .args(&["--obj", "status=none"])
+        .pipe_in("hello")
+        .succeeds()
+        .stdout_is("hello")
+        .no_stderr();
+}
+
+#[test]
+fn test_obj_bad_fd() {
+    new_ucmd!()
+        .args(&["--obj", "--obj-fd", "99"])
+        .pipe_in("hello")
+        .fails_with_code(1)
+        .stderr_contains("Bad file descriptor");
+}
+
+#[test]
+fn test_obj_schema() {
+    let schema = TestScenario::new(util_name!()).declared_schema().unwrap();
+    assert_eq!(schema["properties"]["type"]["enum"], serde_json::json!(["progress", "summary"]));
+}
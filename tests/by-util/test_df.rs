@@ -968,3 +968,35 @@ fn test_nonexistent_file() {
         .stderr_is("df: does-not-exist: No such file or directory\n")
         .stdout_is("File\n.\n");
 }
+
+#[test]
+#[cfg(not(any(target_os = "freebsd", target_os = "windows")))] // FIXME: fix test for FreeBSD & Win
+fn test_obj_filesystem_records() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.touch("a");
+    let result = ucmd.args(&["--obj", "-h", "--output=target", "a"]).succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    assert_eq!(output["count"], 1);
+    let fs = &output["filesystems"][0];
+    assert_eq!(fs["file"], "a");
+    // all fields are present and in bytes whatever -h and --output say
+    assert!(fs["source"].is_string() && fs["fs_type"].is_string() && fs["mount_point"].is_string());
+    assert!(fs["mount_options"].is_array());
+    let size = fs["size"].as_u64().unwrap();
+    assert!(fs["used"].as_u64().unwrap() <= size);
+    assert!(fs["available"].as_u64().unwrap() <= size);
+    assert_eq!(size % fs["block_size"].as_u64().unwrap(), 0);
+    assert!(output.get("total").is_none());
+}
+
+#[test]
+fn test_obj_total() {
+    let result = new_ucmd!().args(&["--obj", "--total"]).succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    let filesystems = output["filesystems"].as_array().unwrap();
+    let sum = |field: &str| filesystems.iter().map(|fs| fs[field].as_u64().unwrap()).sum::<u64>();
+    assert_eq!(output["count"], filesystems.len());
+    assert_eq!(output["total"]["size"], sum("size"));
+    assert_eq!(output["total"]["inodes_used"], sum("inodes_used"));
+}
@@ -1640,3 +1640,103 @@ fn test_du_long_path_from_unreadable() {
     perms.set_mode(0o755);
     fs::set_permissions(&inaccessible_path, perms).unwrap();
 }
+
+#[test]
+fn test_du_obj_tree() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("a/b/c");
+    at.write("a/b/file", "hello");
+    let result = ucmd.args(&["--obj", "-a", "a"]).succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    let root = &output["entries"][0];
+    assert_eq!(root["path"], "a");
+    assert_eq!(root["type"], "directory");
+    assert_eq!(root["apparent_size"], 5);
+    let b = &root["children"][0];
+    assert_eq!(b["path"], "a/b");
+    let mut children: Vec<_> = b["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|child| (child["path"].as_str().unwrap(), child["type"].as_str().unwrap()))
+        .collect();
+    children.sort_unstable();
+    assert_eq!(children, [("a/b/c", "directory"), ("a/b/file", "file")]);
+    assert!(output.get("total").is_none());
+}
+
+#[test]
+fn test_du_obj_flat_max_depth() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("a/b/c");
+    let result = ucmd
+        .args(&["--obj", "--flat", "--max-depth=1", "-c", "a"])
+        .succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    let mut entries: Vec<_> = output["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            assert!(entry.get("children").is_none());
+            (entry["path"].as_str().unwrap(), entry["depth"].as_u64().unwrap())
+        })
+        .collect();
+    entries.sort_unstable();
+    assert_eq!(entries, [("a", 0), ("a/b", 1)]);
+    assert_eq!(output["total"]["inodes"], 3);
+}
+
+#[test]
+fn test_du_obj_exclude() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("a/b/c");
+    at.mkdir_all("a/skip");
+    let result = ucmd.args(&["--obj", "--exclude=skip", "a"]).succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    let root = &output["entries"][0];
+    assert_eq!(root["children"].as_array().unwrap().len(), 1);
+    assert_eq!(root["children"][0]["children"][0]["path"], "a/b/c");
+}
+
+#[test]
+fn test_du_obj_threshold_hoists_children() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("a/b");
+    at.mkdir_all("a/c");
+    at.write("a/b/big", &"x".repeat(10000));
+    // a and a/b are over the limit, so a/c is left without a shown parent
+    let result = ucmd
+        .args(&["--obj", "-b", "--threshold=-4096", "a"])
+        .succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    let entries = output["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["path"], "a/c");
+}
+
+#[test]
+fn test_du_threads_same_total() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    for dir in ["t/a/x", "t/a/y", "t/b/z", "t/c"] {
+        at.mkdir_all(dir);
+        at.write(&format!("{dir}/data"), "some bytes");
+    }
+    let single = ts.ucmd().args(&["-s", "--inodes", "t"]).succeeds();
+    let threaded = ts
+        .ucmd()
+        .args(&["-s", "--inodes", "--threads=4", "t"])
+        .succeeds();
+    assert_eq!(single.stdout_str(), threaded.stdout_str());
+
+    let single = ts.ucmd().args(&["-b", "t"]).succeeds();
+    let threaded = ts.ucmd().args(&["-b", "--threads=0", "t"]).succeeds();
+    let mut single_lines: Vec<_> = single.stdout_str().lines().collect();
+    let mut threaded_lines: Vec<_> = threaded.stdout_str().lines().collect();
+    single_lines.sort_unstable();
+    threaded_lines.sort_unstable();
+    assert_eq!(single_lines, threaded_lines);
+}
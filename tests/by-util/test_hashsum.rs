@@ -1051,3 +1051,106 @@ fn test_help_shows_correct_utility_name() {
         .succeeds()
         .stdout_contains("Usage: hashsum --<digest>");
 }
+
+#[test]
+fn test_obj_digests() {
+    let ts = TestScenario::new(util_name!());
+    ts.fixtures.write("a", "alpha\n");
+    let text = ts.ucmd().args(&["--sha256", "a"]).succeeds();
+    let result = ts.ucmd().args(&["--sha256", "--obj", "a"]).succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    let file = &output["files"][0];
+    assert_eq!(file["path"], "a");
+    assert_eq!(file["algorithm"], "sha256");
+    assert_eq!(file["digest"], get_hash!(text.stdout_str()));
+    assert_eq!(file["size"], 6);
+}
+
+#[test]
+fn test_obj_check() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    at.write("good", "good\n");
+    at.write("bad", "bad\n");
+    let sums = ts.ucmd().args(&["--md5", "good", "bad", "gone"]).fails();
+    at.write("sums", &format!("{}{}  gone\n", sums.stdout_str(), "0".repeat(32)));
+    at.write("bad", "changed\n");
+
+    let result = ts.ucmd().args(&["--md5", "--check", "--obj", "sums"]).fails_with_code(1);
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    let statuses: Vec<_> = output["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| (r["path"].as_str().unwrap(), r["status"].as_str().unwrap()))
+        .collect();
+    assert_eq!(statuses, [("good", "ok"), ("bad", "failed"), ("gone", "missing")]);
+    assert_eq!(output["results"][1]["expected"], get_hash!(sums.stdout_str().lines().nth(1).unwrap()));
+    assert!(output["results"][2]["actual"].is_null());
+    assert_eq!(output["summary"]["total"], 3);
+    assert_eq!(output["summary"]["ok"], 1);
+    assert_eq!(output["summary"]["failed"], 1);
+    assert_eq!(output["summary"]["missing"], 1);
+}
+
+#[test]
+fn test_stardust_file_list_on_stdin() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    at.write("a", "alpha\n");
+    at.write("b", "beta\n");
+    let expected = ts.ucmd().args(&["--sha1", "a", "b"]).succeeds();
+
+    let listing = r#"{"entries": [
+        {"path": "a", "type": "file"},
+        {"path": "sub", "type": "directory"},
+        {"path": "b", "type": "file"}
+    ]}"#;
+    ts.ucmd()
+        .args(&["--sha1", "--files-from-stdin"])
+        .pipe_in(listing)
+        .succeeds()
+        .stdout_is(expected.stdout_str());
+}
+
+#[test]
+fn test_binary_stardust_file_list_on_stdin() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    at.write("a", "alpha\n");
+    at.write("b", "beta\n");
+    let expected = ts.ucmd().args(&["--sha1", "a", "b"]).succeeds();
+
+    let listing = serde_json::json!({"entries": [{"path": "a"}, {"path": "b"}]});
+    ts.ucmd()
+        .arg("--sha1")
+        .pipe_in(sgcore::wire::encode(&listing))
+        .succeeds()
+        .stdout_is(expected.stdout_str());
+}
+
+#[test]
+fn test_stdin_json_with_paths_is_data() {
+    let ts = TestScenario::new(util_name!());
+    let manifest = r#"{"files": [{"path": "a"}]}"#;
+    ts.fixtures.write("manifest.json", manifest);
+    let expected = ts.ucmd().args(&["--md5", "manifest.json"]).succeeds();
+    let result = ts.ucmd().arg("--md5").pipe_in(manifest).succeeds();
+    assert_eq!(get_hash!(result.stdout_str()), get_hash!(expected.stdout_str()));
+
+    ts.ucmd()
+        .args(&["--md5", "--files-from-stdin"])
+        .pipe_in("[1, 2, 3]")
+        .fails()
+        .stderr_contains("standard input lists no files");
+}
+
+#[test]
+fn test_stdin_json_without_paths_is_data() {
+    let ts = TestScenario::new(util_name!());
+    ts.fixtures.write("data.json", "[1, 2, 3]\n");
+    let expected = ts.ucmd().args(&["--md5", "data.json"]).succeeds();
+    let result = ts.ucmd().arg("--md5").pipe_in("[1, 2, 3]\n").succeeds();
+    assert_eq!(get_hash!(result.stdout_str()), get_hash!(expected.stdout_str()));
+}
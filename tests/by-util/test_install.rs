@@ -2219,3 +2219,38 @@ fn test_install_no_target_basic() {
 }
 
 
+
+#[test]
+fn test_install_obj_report() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("source", "hello");
+    at.write("dest", "old");
+    let result = ucmd
+        .args(&["--obj", "-v", "-b", "-p", "source", "dest"])
+        .succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    let operations = output["operations"].as_array().unwrap();
+    assert_eq!(operations[0]["action"], "backed_up");
+    assert_eq!(operations[0]["destination"], "dest~");
+    assert_eq!(operations[1]["action"], "copied");
+    assert_eq!(operations[1]["bytes"], 5);
+    assert_eq!(operations[1]["preserved"], serde_json::json!(["timestamps"]));
+    assert_eq!(output["summary"]["total"], 2);
+}
+
+#[test]
+fn test_install_obj_report_directories() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let result = ucmd.args(&["--obj", "-d", "a/b", "c"]).succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    let created: Vec<_> = output["operations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|op| (op["action"].as_str().unwrap(), op["destination"].as_str().unwrap()))
+        .collect();
+    assert_eq!(created, [("created", "a/b"), ("created", "c")]);
+    assert!(at.dir_exists("a/b"));
+}
@@ -0,0 +1,156 @@
+use sgtests::new_ucmd;
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+use sgtests::{at_and_ucmd, util::TestScenario, util_name};
+
+#[test]
+fn test_invalid_arg() {
+    new_ucmd!().arg("--definitely-invalid").fails_with_code(1);
+}
+
+#[test]
+fn test_missing_file() {
+    new_ucmd!()
+        .args(&["-f", "does-not-exist"])
+        .fails_with_code(1)
+        .stderr_contains("does-not-exist");
+}
+
+#[test]
+fn test_invalid_time() {
+    new_ucmd!()
+        .args(&["--since", "not a time", "-f", "/dev/null"])
+        .fails_with_code(1)
+        .stderr_contains("invalid time");
+}
+
+/// Login records in the glibc layout shared by x86_64 and aarch64
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+mod wtmp {
+    pub const BOOT_TIME: i16 = 2;
+    pub const USER_PROCESS: i16 = 7;
+    pub const DEAD_PROCESS: i16 = 8;
+
+    /// Seconds of the first record, 2023-11-14T22:13:20Z
+    pub const T0: i32 = 1_700_000_000;
+
+    pub fn record(kind: i16, line: &str, user: &str, host: &str, seconds: i32) -> Vec<u8> {
+        fn put(buf: &mut [u8], at: usize, value: &str) {
+            buf[at..at + value.len()].copy_from_slice(value.as_bytes());
+        }
+        let mut buf = vec![0; 384];
+        buf[0..2].copy_from_slice(&kind.to_le_bytes());
+        buf[4..8].copy_from_slice(&1234_i32.to_le_bytes());
+        put(&mut buf, 8, line);
+        put(&mut buf, 44, user);
+        put(&mut buf, 76, host);
+        buf[340..344].copy_from_slice(&(T0 + seconds).to_le_bytes());
+        buf
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn history() -> Vec<u8> {
+    use wtmp::{BOOT_TIME, DEAD_PROCESS, USER_PROCESS, record};
+    [
+        record(USER_PROCESS, "pts/0", "alice", "10.0.0.1", 0),
+        record(USER_PROCESS, "pts/1", "bob", "", 60),
+        record(DEAD_PROCESS, "pts/0", "", "", 3600),
+        record(BOOT_TIME, "~", "reboot", "", 7200),
+        record(USER_PROCESS, "pts/2", "carol", "", 8000),
+    ]
+    .concat()
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn logins(result: &sgtests::util::CmdResult) -> Vec<serde_json::Value> {
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value["count"], value["logins"].as_array().unwrap().len());
+    value["logins"].as_array().unwrap().clone()
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn test_obj_pairs_logins() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write_bytes("wtmp", &history());
+
+    let result = ucmd.args(&["--obj", "-f", "wtmp"]).succeeds();
+    let logins = logins(&result);
+    let users: Vec<_> = logins.iter().map(|l| l["user"].as_str().unwrap()).collect();
+    assert_eq!(users, ["alice", "bob", "carol"]);
+
+    assert_eq!(logins[0]["tty"], "pts/0");
+    assert_eq!(logins[0]["host"], "10.0.0.1");
+    assert_eq!(logins[0]["type"], "user");
+    assert_eq!(logins[0]["pid"], 1234);
+    assert_eq!(logins[0]["status"], "logged_out");
+    assert_eq!(logins[0]["duration"], 3600);
+    assert!(logins[0]["logout_time"].is_string());
+
+    assert_eq!(logins[1]["host"], serde_json::Value::Null);
+    assert_eq!(logins[1]["status"], "crash");
+    assert_eq!(logins[1]["duration"], 7140);
+
+    assert_eq!(logins[2]["status"], "active");
+    assert_eq!(logins[2]["logout_time"], serde_json::Value::Null);
+    assert_eq!(logins[2]["duration"], serde_json::Value::Null);
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn test_obj_time_range() {
+    let ts = TestScenario::new(util_name!());
+    ts.fixtures.write_bytes("wtmp", &history());
+    let at = |seconds: i32| format!("@{}", wtmp::T0 + seconds);
+
+    // alice logged out before the window starts
+    let result = ts
+        .ucmd()
+        .args(&["--obj", "-f", "wtmp", "--since", &at(5000)])
+        .succeeds();
+    let users: Vec<_> = logins(&result).iter().map(|l| l["user"].clone()).collect();
+    assert_eq!(users, ["bob", "carol"]);
+
+    // carol logged in after the window ends
+    let result = ts
+        .ucmd()
+        .args(&["--obj", "-f", "wtmp", "--since", &at(30), "--until", &at(4000)])
+        .succeeds();
+    let users: Vec<_> = logins(&result).iter().map(|l| l["user"].clone()).collect();
+    assert_eq!(users, ["alice", "bob"]);
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn test_obj_failed() {
+    use wtmp::{USER_PROCESS, record};
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write_bytes(
+        "btmp",
+        &[
+            record(USER_PROCESS, "ssh:notty", "root", "203.0.113.9", 0),
+            record(USER_PROCESS, "ssh:notty", "admin", "203.0.113.9", 5),
+        ]
+        .concat(),
+    );
+
+    let result = ucmd.args(&["--obj", "--failed", "-f", "btmp"]).succeeds();
+    let logins = logins(&result);
+    assert_eq!(logins.len(), 2);
+    assert!(logins.iter().all(|l| l["status"] == "failed"));
+    assert_eq!(logins[1]["user"], "admin");
+    assert_eq!(logins[1]["host"], "203.0.113.9");
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn test_text_output() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write_bytes("wtmp", &history());
+
+    ucmd.args(&["-f", "wtmp"])
+        .succeeds()
+        .stdout_contains("(01:00)")
+        .stdout_contains("crash")
+        .stdout_contains("still logged in");
+}
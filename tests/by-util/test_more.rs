@@ -139,3 +139,36 @@ fn test_more_non_utf8_paths() {
         ucmd.arg(file_name).succeeds();
     }
 }
+
+#[test]
+fn test_obj_invalid_input() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("notes.txt", "just some text\n");
+    ucmd.args(&["--obj", "notes.txt"])
+        .fails()
+        .stderr_contains("Failed to parse JSON");
+
+    new_ucmd!()
+        .args(&["--obj", "missing.json"])
+        .fails()
+        .stderr_contains("missing.json");
+}
+
+#[test]
+fn test_obj_passes_through_without_terminal() {
+    // With a controlling terminal more opens the viewer on /dev/tty instead
+    if std::fs::OpenOptions::new().write(true).open("/dev/tty").is_ok() {
+        return;
+    }
+    let input = r#"{"entries":[{"name":"a","size":1},{"name":"b","size":2}],"count":2}"#;
+
+    let result = new_ucmd!().arg("--obj").pipe_in(input).succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(output, serde_json::from_str::<serde_json::Value>(input).unwrap());
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("listing.json", input);
+    let result = ucmd.args(&["--obj", "listing.json"]).succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(output["entries"][1]["name"], "b");
+}
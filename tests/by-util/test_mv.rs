@@ -2609,3 +2609,41 @@ fn test_mv_verbose_directory_recursive() {
     assert!(stdout.contains("'mv-dir/d/e/f' -> "));
     assert!(stdout.contains("'mv-dir/d/e/f/file2' -> "));
 }
+
+#[test]
+fn test_mv_obj_report() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("target");
+    at.write("a", "hello");
+    at.write("target/a", "old");
+    let result = ucmd
+        .args(&["--obj", "-v", "-b", "a", "missing", "target"])
+        .fails_with_code(1);
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    let operations = output["operations"].as_array().unwrap();
+    assert_eq!(operations[0]["action"], "backed_up");
+    assert_eq!(operations[0]["source"], "target/a");
+    assert_eq!(operations[0]["destination"], "target/a~");
+    assert_eq!(operations[1]["action"], "moved");
+    assert_eq!(operations[1]["source"], "a");
+    assert_eq!(operations[1]["destination"], "target/a");
+    assert_eq!(operations[2]["action"], "failed");
+    assert_eq!(operations[2]["source"], "missing");
+    assert_eq!(output["summary"]["total"], 3);
+    assert_eq!(at.read("target/a"), "hello");
+}
+
+#[test]
+fn test_mv_obj_report_skipped() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "new");
+    at.write("b", "old");
+    let result = ucmd.args(&["--obj", "-n", "a", "b"]).succeeds();
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    assert_eq!(output["operations"][0]["action"], "skipped");
+    assert_eq!(output["operations"][0]["source"], "a");
+    assert_eq!(output["summary"]["skipped"], 1);
+    assert_eq!(at.read("b"), "old");
+}
@@ -1041,3 +1041,136 @@ fn test_od_eintr_handling() {
         .no_stderr()
         .stdout_contains("e"); // Should contain 'e' from "ello"
 }
+
+#[test]
+fn test_obj() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("test", "abcdefghijklmnopqrstuvwxyz\n");
+
+    let result = ucmd
+        .args(&["--obj", "--endian=little", "-t", "x2", "-t", "d1", "-c", "-j", "2", "test"])
+        .succeeds();
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value["count"], 2);
+
+    let line = &value["lines"][0];
+    assert_eq!(line["offset"], 2);
+    assert_eq!(line["bytes"][0], b'c');
+    assert_eq!(line["bytes"].as_array().unwrap().len(), 16);
+    assert_eq!(line["decoded"][0]["type"], "x2");
+    assert_eq!(line["decoded"][0]["values"][0], "6463");
+    assert_eq!(line["decoded"][1]["type"], "d1");
+    assert_eq!(line["decoded"][1]["values"][0], 99);
+    assert_eq!(line["decoded"][2]["type"], "c");
+    assert_eq!(line["decoded"][2]["values"][0], "c");
+
+    let last = &value["lines"][1];
+    assert_eq!(last["offset"], 18);
+    assert_eq!(last["bytes"].as_array().unwrap().len(), 9);
+    assert_eq!(last["decoded"][2]["values"][8], "\\n");
+    // the last x2 item holds the newline and a zero
+    assert_eq!(last["decoded"][0]["values"].as_array().unwrap().len(), 5);
+    assert_eq!(last["decoded"][0]["values"][4], "000a");
+}
+
+#[test]
+fn test_obj_floats_and_duplicates() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let data: Vec<u8> = [1.5_f32, -2.0, 1.5, -2.0]
+        .iter()
+        .flat_map(|f| f.to_le_bytes())
+        .collect();
+    at.write_bytes("floats", &data);
+
+    let result = ucmd
+        .args(&["--obj", "--endian=little", "-t", "f4", "-w8", "floats"])
+        .succeeds();
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    // duplicate lines are not folded into `*`
+    assert_eq!(value["count"], 2);
+    assert_eq!(value["lines"][1]["offset"], 8);
+    assert_eq!(value["lines"][1]["decoded"][0]["values"], serde_json::json!([1.5, -2.0]));
+}
+
+#[test]
+fn test_decode_binary() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let mut data = b"skip".to_vec();
+    for (count, name) in [(1_u16, "first"), (0x0203, "second")] {
+        data.extend(0xcafe_u32.to_le_bytes());
+        data.extend(count.to_be_bytes());
+        let mut name = name.as_bytes().to_vec();
+        name.resize(8, 0);
+        data.extend(name);
+    }
+    at.write_bytes("records", &data);
+
+    let result = ucmd
+        .args(&[
+            "--decode-binary",
+            "u32le magic; u16be count; str[8] name",
+            "-j",
+            "4",
+            "records",
+        ])
+        .succeeds();
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value["count"], 2);
+    assert_eq!(value["record_size"], 14);
+    let records = value["records"].as_array().unwrap();
+    assert_eq!(records[0]["offset"], 4);
+    assert_eq!(records[0]["magic"], 0xcafe);
+    assert_eq!(records[0]["count"], 1);
+    assert_eq!(records[0]["name"], "first");
+    assert_eq!(records[1]["offset"], 18);
+    assert_eq!(records[1]["count"], 0x0203);
+    assert_eq!(records[1]["name"], "second");
+}
+
+#[test]
+fn test_decode_binary_endian_and_partial_record() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write_bytes("data", &[0x00, 0x01, 0xff, 0xfe, 0x12, 0x34, 0x56]);
+
+    let result = ucmd
+        .args(&["--endian=big", "--decode-binary", "u16 a; i8 b; bytes[1] c", "data"])
+        .succeeds();
+    result.stderr_contains("ignoring 3 trailing bytes");
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value["count"], 1);
+    assert_eq!(value["records"][0]["a"], 1);
+    assert_eq!(value["records"][0]["b"], -1);
+    assert_eq!(value["records"][0]["c"], "fe");
+}
+
+#[test]
+fn test_decode_binary_invalid_layout() {
+    new_ucmd!()
+        .args(&["--decode-binary", "u24 magic", "/dev/null"])
+        .fails_with_code(1)
+        .stderr_contains("invalid field");
+    new_ucmd!()
+        .args(&["--decode-binary", "u8 a; u8 a", "/dev/null"])
+        .fails_with_code(1)
+        .stderr_contains("duplicate field name");
+    new_ucmd!()
+        .args(&["--decode-binary", "bytes[99999999999999] x", "/dev/null"])
+        .fails_with_code(1)
+        .stderr_contains("records may take at most 16777216 bytes");
+    new_ucmd!()
+        .args(&["--decode-binary", "u64[2305843009213693952] x", "/dev/null"])
+        .fails_with_code(1)
+        .stderr_contains("invalid field");
+}
+
+#[test]
+fn test_decode_binary_schema() {
+    let result = new_ucmd!()
+        .args(&["--decode-binary", "u32le magic; f64[2] point; pad[4]", "--schema"])
+        .succeeds();
+    let schema: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    let record = &schema["properties"]["records"]["items"]["properties"];
+    assert_eq!(record["magic"]["type"], "integer");
+    assert_eq!(record["point"]["type"], "array");
+    assert_eq!(record.as_object().unwrap().len(), 3);
+}
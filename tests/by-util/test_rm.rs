@@ -1104,3 +1104,30 @@ fn test_progress_no_output_on_error() {
         .stderr_contains("cannot remove")
         .stderr_contains("No such file or directory");
 }
+
+#[test]
+fn test_rm_obj_report() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("dir");
+    at.touch("dir/inner");
+    at.touch("file");
+    let result = ucmd
+        .args(&["--obj", "-rv", "dir", "file", "missing"])
+        .fails_with_code(1);
+    let output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    let removed: Vec<_> = output["operations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|op| op["action"] == "removed")
+        .map(|op| op["source"].as_str().unwrap())
+        .collect();
+    assert_eq!(removed, ["dir/inner", "dir", "file"]);
+    let failed = &output["operations"][3];
+    assert_eq!(failed["action"], "failed");
+    assert_eq!(failed["source"], "missing");
+    assert!(failed["error"].as_str().unwrap().contains("No such file"));
+    assert_eq!(output["summary"]["removed"], 3);
+    assert!(!at.dir_exists("dir"));
+}
@@ -1890,3 +1890,175 @@ fn test_start_buffer() {
 }
 
 /* spell-checker: enable */
+
+fn obj_records(stdout: &str, field: &str) -> Vec<serde_json::Value> {
+    let value: serde_json::Value = serde_json::from_str(stdout).unwrap();
+    value[field].as_array().unwrap().clone()
+}
+
+#[test]
+fn test_obj_keys() {
+    let input = r#"{"count": 4, "entries": [
+        {"name": "b", "size": 10},
+        {"name": "a", "size": 9},
+        {"name": "c", "size": "100"},
+        {"name": "a", "size": 2}
+    ]}"#;
+    let result = new_ucmd!()
+        .args(&["--obj", "--key", "name,size:n"])
+        .pipe_in(input)
+        .succeeds();
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value["count"], 4);
+    let names: Vec<_> = value["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| format!("{}{}", r["name"].as_str().unwrap(), r["size"]))
+        .collect();
+    assert_eq!(names, ["a2", "a9", "b10", "c\"100\""]);
+
+    let result = new_ucmd!()
+        .args(&["--obj", "-k", "size:nr"])
+        .pipe_in(input)
+        .succeeds();
+    let sizes: Vec<_> = obj_records(result.stdout_str(), "entries")
+        .iter()
+        .map(|r| r["size"].to_string())
+        .collect();
+    assert_eq!(sizes, ["\"100\"", "10", "9", "2"]);
+}
+
+#[test]
+fn test_obj_whole_records_reverse_unique() {
+    new_ucmd!()
+        .args(&["--obj", "-r", "-u"])
+        .pipe_in(r#"["b", "a", "c", "a", "b"]"#)
+        .succeeds()
+        .stdout_only("[\"c\",\"b\",\"a\"]\n");
+}
+
+#[test]
+fn test_obj_json_lines() {
+    // one record per line, as `tail --obj` writes them
+    let input = "{\"line\": \"b\", \"offset\": 0}\n{\"line\": \"c\", \"offset\": 2}\n{\"line\": \"a\", \"offset\": 4}\n";
+    new_ucmd!()
+        .args(&["--obj", "--key", "line"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_only(
+            "[{\"line\":\"a\",\"offset\":4},{\"line\":\"b\",\"offset\":0},{\"line\":\"c\",\"offset\":2}]\n"
+        );
+}
+
+#[test]
+fn test_obj_stable_and_files() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("one.json", r#"{"records": [{"k": 2, "id": 1}, {"k": 1, "id": 2}]}"#);
+    at.write("two.json", r#"[{"k": 2, "id": 3}, {"k": 1, "id": 4}]"#);
+    let result = ucmd
+        .args(&["--obj", "--key", "k", "one.json", "two.json", "-o", "out.json"])
+        .succeeds();
+    result.no_stdout();
+    let ids: Vec<_> = obj_records(&at.read("out.json"), "records")
+        .iter()
+        .map(|r| r["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, [2, 4, 1, 3]);
+}
+
+#[test]
+fn test_obj_spills_to_temporary_files() {
+    let records: Vec<_> = (0..500)
+        .map(|i| serde_json::json!({"n": (i * 7919) % 500, "pad": "x".repeat(40)}))
+        .collect();
+    let input = serde_json::Value::Array(records).to_string();
+    for extra in [&[][..], &["--batch-size=2"][..]] {
+        let result = new_ucmd!()
+            .args(&["--obj", "--key", "n:n", "-S", "4K"])
+            .args(extra)
+            .pipe_in(input.clone())
+            .succeeds();
+        let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+        let numbers: Vec<_> = value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["n"].as_i64().unwrap())
+            .collect();
+        assert_eq!(numbers, (0..500).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn test_obj_spilled_unique_count() {
+    let records: Vec<_> = (0..600)
+        .map(|i| serde_json::json!({"n": i % 150, "pad": "x".repeat(40)}))
+        .collect();
+    let input = serde_json::json!({"count": 600, "path": ".", "entries": records});
+    let result = new_ucmd!()
+        .args(&["--obj", "-u", "-k", "n:n", "-S", "4K"])
+        .pipe_in(input.to_string())
+        .succeeds();
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value["count"], 150);
+    assert_eq!(value["path"], ".");
+    let numbers: Vec<_> = obj_records(result.stdout_str(), "entries")
+        .iter()
+        .map(|r| r["n"].as_i64().unwrap())
+        .collect();
+    assert_eq!(numbers, (0..150).collect::<Vec<_>>());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_obj_compress() {
+    let input: Vec<_> = (0..200).rev().map(|i| serde_json::json!({"n": i})).collect();
+    let result = new_ucmd!()
+        .args(&["--obj", "-k", "n:n", "-S", "1K", "--compress-program", "gzip"])
+        .pipe_in(serde_json::Value::Array(input).to_string())
+        .succeeds();
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value[0]["n"], 0);
+    assert_eq!(value[199]["n"], 199);
+}
+
+#[test]
+fn test_obj_binary_wire() {
+    let produced = new_ucmd!()
+        .env("STARGATE_WIRE", "cbor")
+        .args(&["--obj", "-k", "n:n"])
+        .pipe_in(r#"{"count": 3, "records": [{"n": 3}, {"n": 1}, {"n": 2}]}"#)
+        .succeeds()
+        .stdout_move_bytes();
+    assert!(produced.starts_with(&[0xd9, 0xd9, 0xf7]), "{produced:?}");
+    assert_eq!(
+        sgcore::wire::decode(&produced).unwrap(),
+        serde_json::json!({"count": 3, "records": [{"n": 1}, {"n": 2}, {"n": 3}]})
+    );
+
+    new_ucmd!()
+        .args(&["--obj", "-k", "n:nr"])
+        .pipe_in(produced)
+        .succeeds()
+        .stdout_only("{\"count\":3,\"records\":[{\"n\":3},{\"n\":2},{\"n\":1}]}\n");
+}
+
+#[test]
+fn test_obj_errors() {
+    new_ucmd!()
+        .args(&["--obj", "--key", "size:x"])
+        .pipe_in("[]")
+        .fails_with_code(2)
+        .stderr_contains("failed to parse key 'size:x'");
+    new_ucmd!()
+        .args(&["--obj"])
+        .pipe_in("not json")
+        .fails_with_code(2);
+    new_ucmd!()
+        .args(&["--obj", "--records", "missing"])
+        .pipe_in(r#"{"entries": []}"#)
+        .fails_with_code(2)
+        .stderr_contains("field 'missing' is not an array");
+    new_ucmd!().args(&["--obj", "-c"]).fails_with_code(2);
+}
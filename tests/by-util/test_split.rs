@@ -2042,3 +2042,177 @@ fn test_split_non_utf8_additional_suffix() {
         "Expected at least one split file to be created"
     );
 }
+
+fn obj_files(stdout: &str) -> Vec<serde_json::Value> {
+    let report: serde_json::Value = serde_json::from_str(stdout).unwrap();
+    let files = report["files"].as_array().unwrap().clone();
+    assert_eq!(report["count"], files.len());
+    files
+}
+
+#[test]
+fn test_obj_reports_line_chunks() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let result = ucmd
+        .args(&["-l", "2", "--obj"])
+        .pipe_in("a\nb\nc\nd\ne\n")
+        .succeeds();
+
+    assert_eq!(
+        obj_files(result.stdout_str()),
+        [
+            serde_json::json!({"path": "xaa", "bytes": 4, "lines": 2, "first_line": "a"}),
+            serde_json::json!({"path": "xab", "bytes": 4, "lines": 2, "first_line": "c"}),
+            serde_json::json!({"path": "xac", "bytes": 2, "lines": 1, "first_line": "e"}),
+        ]
+    );
+    assert_eq!(at.read("xab"), "c\nd\n");
+}
+
+#[test]
+fn test_obj_reports_byte_chunks() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("input", "abc\ndefg");
+    let result = ucmd.args(&["-b", "3", "--obj", "input", "part-"]).succeeds();
+
+    let files = obj_files(result.stdout_str());
+    let summary: Vec<_> = files
+        .iter()
+        .map(|file| {
+            (
+                file["path"].as_str().unwrap(),
+                file["bytes"].as_u64().unwrap(),
+                file["lines"].as_u64().unwrap(),
+                file["first_line"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("part-aa", 3, 1, "abc"),
+            ("part-ab", 3, 2, ""),
+            ("part-ac", 2, 1, "fg"),
+        ]
+    );
+}
+
+#[test]
+fn test_obj_reports_empty_chunks() {
+    let result = new_ucmd!()
+        .args(&["-n", "3", "--obj"])
+        .pipe_in("ab")
+        .succeeds();
+
+    let files = obj_files(result.stdout_str());
+    assert_eq!(files.len(), 3);
+    assert_eq!(files[2]["bytes"], 0);
+    assert_eq!(files[2]["lines"], 0);
+    assert!(files[2]["first_line"].is_null());
+}
+
+#[test]
+fn test_obj_conflicts() {
+    new_ucmd!()
+        .args(&["-n", "1/2", "--obj"])
+        .pipe_in("ab")
+        .fails_with_code(1)
+        .stderr_contains("--obj does not report a chunk extracted to stdout");
+    new_ucmd!()
+        .args(&["--obj", "--verbose"])
+        .pipe_in("ab")
+        .fails();
+}
+
+#[test]
+fn test_records_per_file() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.args(&["--records", "-l", "2"])
+        .pipe_in(r#"{"entries": [1, 2, 3, 4, 5], "count": 5, "path": "/tmp"}"#)
+        .succeeds()
+        .no_output();
+
+    let read = |name| serde_json::from_str::<serde_json::Value>(&at.read(name)).unwrap();
+    assert_eq!(
+        read("xaa"),
+        serde_json::json!({"entries": [1, 2], "count": 2, "path": "/tmp"})
+    );
+    assert_eq!(
+        read("xab"),
+        serde_json::json!({"entries": [3, 4], "count": 2, "path": "/tmp"})
+    );
+    assert_eq!(
+        read("xac"),
+        serde_json::json!({"entries": [5], "count": 1, "path": "/tmp"})
+    );
+    assert!(!at.file_exists("xad"));
+}
+
+#[test]
+fn test_records_ndjson_round_robin() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.args(&["--records", "--ndjson", "-n", "r/2", "-d"])
+        .pipe_in(r#"[{"id": 1}, {"id": 2}, {"id": 3}, {"id": 4}, {"id": 5}]"#)
+        .succeeds();
+
+    assert_eq!(at.read("x00"), "{\"id\":1}\n{\"id\":3}\n{\"id\":5}\n");
+    assert_eq!(at.read("x01"), "{\"id\":2}\n{\"id\":4}\n");
+}
+
+#[test]
+fn test_records_field_with_obj() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("data.json", r#"{"items": [1, 2, 3, 4, 5, 6, 7], "tags": ["a"]}"#);
+    let result = ucmd
+        .args(&["--records=items", "-n", "3", "--obj", "data.json", "batch-"])
+        .succeeds();
+
+    let files = obj_files(result.stdout_str());
+    let records: Vec<_> = files.iter().map(|file| file["records"].clone()).collect();
+    assert_eq!(records, [3, 2, 2]);
+    assert_eq!(files[1]["path"], "batch-ab");
+    assert_eq!(files[1]["lines"], 1);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&at.read("batch-ab")).unwrap(),
+        serde_json::json!({"items": [4, 5], "tags": ["a"]})
+    );
+}
+
+#[test]
+fn test_records_kth_chunk_and_elide() {
+    new_ucmd!()
+        .args(&["--records", "-n", "2/3"])
+        .pipe_in("[1, 2, 3, 4, 5, 6, 7]")
+        .succeeds()
+        .stdout_only("[4,5]\n");
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.args(&["--records", "-n", "4", "-e"])
+        .pipe_in("[1, 2]")
+        .succeeds();
+    assert_eq!(at.read("xaa"), "[1]\n");
+    assert_eq!(at.read("xab"), "[2]\n");
+    assert!(!at.file_exists("xac"));
+}
+
+#[test]
+fn test_records_errors() {
+    new_ucmd!()
+        .args(&["--records", "-b", "10"])
+        .pipe_in("[1]")
+        .fails();
+    new_ucmd!()
+        .args(&["--ndjson"])
+        .pipe_in("[1]")
+        .fails();
+    new_ucmd!()
+        .args(&["--records"])
+        .pipe_in("not json")
+        .fails_with_code(1)
+        .stderr_contains("invalid records");
+    new_ucmd!()
+        .args(&["--records=missing"])
+        .pipe_in(r#"{"entries": [1]}"#)
+        .fails_with_code(1)
+        .stderr_contains("field 'missing' is not an array");
+}
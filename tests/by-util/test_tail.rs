@@ -4210,3 +4210,144 @@ fn tail_n_lines_with_emoji() {
         .succeeds()
         .stdout_only("💐\n");
 }
+
+fn obj_records(stdout: &str) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_obj_lines() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("log", "one\ntwo\nthree\n");
+
+    let result = ucmd.args(&["-n", "2", "--obj", "log"]).succeeds();
+    let records = obj_records(result.stdout_str());
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["type"], "line");
+    assert_eq!(records[0]["file"], "log");
+    assert_eq!(records[0]["line"], "two");
+    assert_eq!(records[0]["offset"], 4);
+    assert_eq!(records[1]["line"], "three");
+    assert_eq!(records[1]["offset"], 8);
+    assert!(records[1]["timestamp"].is_string());
+    assert!(records[1].get("fields").is_none());
+}
+
+#[test]
+fn test_obj_lines_decode_as_records() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("log", "one\ntwo\nthree\n");
+
+    let result = ucmd.args(&["--obj", "log"]).succeeds();
+    let value = sgcore::wire::decode(result.stdout_str().as_bytes()).unwrap();
+    let lines: Vec<_> = value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["line"].clone())
+        .collect();
+    assert_eq!(lines, ["one", "two", "three"]);
+}
+
+#[test]
+fn test_obj_stdin_unterminated_line() {
+    let result = new_ucmd!()
+        .args(&["--obj", "-n", "+2"])
+        .pipe_in("a\nb\nc")
+        .succeeds();
+    let records = obj_records(result.stdout_str());
+    let lines: Vec<_> = records.iter().map(|r| (r["line"].clone(), r["offset"].clone())).collect();
+    assert_eq!(
+        lines,
+        [
+            (serde_json::json!("b"), serde_json::json!(2)),
+            (serde_json::json!("c"), serde_json::json!(4))
+        ]
+    );
+}
+
+#[test]
+fn test_obj_parse() {
+    let ts = TestScenario::new(util_name!());
+    ts.fixtures.write(
+        "app.log",
+        "level=error msg=\"disk full\" code=28\n{\"level\":\"info\"}\nplain text\n",
+    );
+
+    let result = ts.ucmd().args(&["--obj", "--parse", "logfmt", "app.log"]).succeeds();
+    let records = obj_records(result.stdout_str());
+    assert_eq!(records[0]["fields"]["level"], "error");
+    assert_eq!(records[0]["fields"]["msg"], "disk full");
+    assert_eq!(records[0]["fields"]["code"], 28);
+    assert!(records[2]["fields"].is_null());
+
+    let result = ts.ucmd().args(&["--obj", "--parse", "json", "app.log"]).succeeds();
+    let records = obj_records(result.stdout_str());
+    assert!(records[0]["fields"].is_null());
+    assert_eq!(records[1]["fields"]["level"], "info");
+
+    let result = ts
+        .ucmd()
+        .args(&["--obj", "--regex", r"^(?P<word>\w+) (\w+)$", "app.log"])
+        .succeeds();
+    let records = obj_records(result.stdout_str());
+    assert!(records[0]["fields"].is_null());
+    assert_eq!(records[2]["fields"]["word"], "plain");
+    assert_eq!(records[2]["fields"]["column2"], "text");
+}
+
+#[test]
+fn test_obj_parse_requires_obj() {
+    new_ucmd!()
+        .args(&["--parse", "json"])
+        .pipe_in("x\n")
+        .fails_with_code(1);
+    new_ucmd!()
+        .args(&["--obj", "--parse", "regex"])
+        .pipe_in("x\n")
+        .fails_with_code(1);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_obj_follow_truncate() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    at.write("log", "one\n");
+
+    let mut p = ts
+        .ucmd()
+        .args(&["--follow=name", "--obj", "log"])
+        .run_no_wait();
+    let delay = 1000;
+    p.make_assertion().is_alive();
+
+    at.append("log", "two\npart");
+    p.delay(delay);
+
+    at.truncate("log", "three\n");
+    p.delay(delay);
+
+    p.make_assertion().is_alive();
+    let result = p.kill().make_assertion().with_all_output();
+    let records: Vec<_> = obj_records(result.stdout_str())
+        .into_iter()
+        .map(|r| {
+            let text = r.get("line").unwrap_or(&r["type"]).as_str().unwrap().to_string();
+            (text, r["offset"].as_u64().unwrap())
+        })
+        .collect();
+    assert_eq!(
+        records,
+        [
+            ("one".to_string(), 0),
+            ("two".to_string(), 4),
+            ("part".to_string(), 8),
+            ("truncated".to_string(), 0),
+            ("three".to_string(), 0)
+        ]
+    );
+}
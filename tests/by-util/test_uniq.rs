@@ -1186,3 +1186,93 @@ fn test_failed_write_is_reported() {
         .fails()
         .stderr_is("uniq: write error: No space left on device\n");
 }
+
+#[test]
+fn test_obj_by_field_with_count() {
+    let input = r#"{"count": 5, "entries": [
+        {"name": "a", "n": 1},
+        {"name": "a", "n": 2},
+        {"name": "b", "n": 3},
+        {"name": "A", "n": 4},
+        {"name": "c", "n": 5}
+    ]}"#;
+    let result = new_ucmd!()
+        .args(&["--obj", "--by", "name", "--count"])
+        .pipe_in(input)
+        .succeeds();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(result.stdout_str()).unwrap(),
+        serde_json::json!({"count": 4, "entries": [
+            {"name": "a", "n": 1, "count": 2},
+            {"name": "b", "n": 3, "count": 1},
+            {"name": "A", "n": 4, "count": 1},
+            {"name": "c", "n": 5, "count": 1}
+        ]})
+    );
+
+    let result = new_ucmd!()
+        .args(&["--obj", "--by", "name", "-d"])
+        .pipe_in(input)
+        .succeeds();
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value["count"], 1);
+    assert_eq!(value["entries"][0]["n"], 1);
+
+    new_ucmd!()
+        .args(&["--obj", "--by", "name", "-u", "-i"])
+        .pipe_in(r#"[{"name": "a"}, {"name": "A"}, {"name": "b"}]"#)
+        .succeeds()
+        .stdout_only("[{\"name\":\"b\"}]\n");
+}
+
+#[test]
+fn test_obj_whole_records() {
+    let result = new_ucmd!()
+        .args(&["--obj", "-c"])
+        .pipe_in(r#"["x", "x", {"a": [1]}, {"a": [1]}, {"a": [2]}]"#)
+        .succeeds();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(result.stdout_str()).unwrap(),
+        serde_json::json!([
+            {"value": "x", "count": 2},
+            {"a": [1], "count": 2},
+            {"a": [2], "count": 1}
+        ])
+    );
+
+    new_ucmd!()
+        .args(&["--obj", "-D"])
+        .pipe_in(r#"[1, 1, 2, 3, 3]"#)
+        .succeeds()
+        .stdout_only("[1,1,3,3]\n");
+}
+
+#[test]
+fn test_obj_records_field_and_output_file() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("in.json", r#"{"users": [{"u": 1}, {"u": 1}], "other": [0, 0]}"#);
+    ucmd.args(&["--obj", "--records", "users", "in.json", "out.json"])
+        .succeeds()
+        .no_stdout();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&at.read("out.json")).unwrap(),
+        serde_json::json!({"users": [{"u": 1}], "other": [0, 0]})
+    );
+}
+
+#[test]
+fn test_obj_errors() {
+    new_ucmd!()
+        .args(&["--obj", "-f", "1"])
+        .pipe_in("[]")
+        .fails();
+    new_ucmd!()
+        .args(&["--by", "name"])
+        .pipe_in("a\n")
+        .fails();
+    new_ucmd!()
+        .args(&["--obj"])
+        .pipe_in(r#"{"a": 1}"#)
+        .fails_with_code(1)
+        .stderr_contains("input object has no array of records");
+}
@@ -114,3 +114,36 @@ fn test_dead() {
         ts.ucmd().arg(opt).succeeds().stdout_is(expected_stdout);
     }
 }
+
+#[cfg(unix)]
+#[test]
+#[cfg(not(target_os = "openbsd"))]
+fn test_obj() {
+    let ts = TestScenario::new(util_name!());
+    for args in [&["--obj"][..], &["--obj", "-a"]] {
+        let result = ts.ucmd().args(args).succeeds();
+        let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+        let sessions = value["sessions"].as_array().unwrap();
+        assert_eq!(value["count"], sessions.len());
+        for session in sessions {
+            for field in ["user", "tty", "host", "login_time", "idle", "pid", "type"] {
+                assert!(session.get(field).is_some(), "{field} missing in {session}");
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+#[cfg(not(target_os = "openbsd"))]
+fn test_obj_field_and_pretty() {
+    let ts = TestScenario::new(util_name!());
+    let result = ts.ucmd().args(&["--obj", "-a", "--field", "count"]).succeeds();
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert_eq!(value.as_object().unwrap().keys().collect::<Vec<_>>(), ["count"]);
+
+    let result = ts.ucmd().args(&["--obj", "--pretty"]).succeeds();
+    assert!(result.stdout_str().starts_with("{\n"));
+    let value: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+    assert!(value["sessions"].is_array());
+}
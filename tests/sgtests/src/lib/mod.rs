@@ -1,4 +1,5 @@
 #[macro_use]
 pub mod macros;
 pub mod random;
+pub mod schema;
 pub mod util;
@@ -0,0 +1,150 @@
+//! Checking stardust against the JSON Schema a command declares with
+//! `--schema`.
+//!
+//! Only the keywords those schemas use are understood: `type`, `enum`,
+//! `properties`, `required`, `additionalProperties` and `items`. Anything
+//! else in a schema is ignored.
+
+use serde_json::Value;
+
+/// Check `value` against `schema`, giving one message per violation. Each
+/// message starts with the path of the offending value, e.g.
+/// `$.entries[3].size`.
+pub fn validate(value: &Value, schema: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    check(value, schema, "$", &mut errors);
+    errors
+}
+
+fn check(value: &Value, schema: &Value, path: &str, errors: &mut Vec<String>) {
+    if let Some(expected) = schema.get("type") {
+        let names: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !names.iter().any(|name| type_matches(value, name)) {
+            errors.push(format!("{path}: expected {}, got {}", names.join(" or "), type_name(value)));
+            return;
+        }
+    }
+
+    match schema.get("enum") {
+        Some(Value::Array(allowed)) if !allowed.contains(value) => {
+            errors.push(format!("{path}: {value} is not one of {}", Value::Array(allowed.clone())));
+        }
+        _ => {}
+    }
+
+    if let Value::Object(map) = value {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !map.contains_key(name) {
+                    errors.push(format!("{path}: missing required field '{name}'"));
+                }
+            }
+        }
+        for (key, field) in map {
+            match properties.and_then(|properties| properties.get(key)) {
+                Some(field_schema) => check(field, field_schema, &format!("{path}.{key}"), errors),
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    errors.push(format!("{path}: field '{key}' is not in the schema"));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Value::Array(elements), Some(items)) = (value, schema.get("items")) {
+        for (index, element) in elements.iter().enumerate() {
+            check(element, items, &format!("{path}[{index}]"), errors);
+        }
+    }
+}
+
+fn type_matches(value: &Value, name: &str) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn listing_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": "integer" },
+                "entries": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "type": { "type": "string", "enum": ["file", "directory"] },
+                            "modified": { "type": "integer" }
+                        },
+                        "required": ["name", "type"],
+                        "additionalProperties": false
+                    }
+                }
+            },
+            "required": ["count", "entries"]
+        })
+    }
+
+    #[test]
+    fn test_conforming_value() {
+        let value = json!({
+            "count": 2,
+            "entries": [
+                { "name": "a", "type": "file", "modified": 1_700_000_000 },
+                { "name": "b", "type": "directory" }
+            ]
+        });
+        assert!(validate(&value, &listing_schema()).is_empty());
+    }
+
+    #[test]
+    fn test_violations_name_their_path() {
+        let value = json!({
+            "count": "2",
+            "entries": [
+                { "name": "a", "type": "socket" },
+                { "type": "file", "size": 10 }
+            ]
+        });
+        assert_eq!(
+            validate(&value, &listing_schema()),
+            vec![
+                "$.count: expected integer, got string",
+                "$.entries[0].type: \"socket\" is not one of [\"file\",\"directory\"]",
+                "$.entries[1]: missing required field 'name'",
+                "$.entries[1]: field 'size' is not in the schema",
+            ]
+        );
+    }
+}
@@ -887,6 +887,22 @@ impl CmdResult {
         );
         self
     }
+
+    /// Assert that stdout is stardust conforming to `schema`, a JSON Schema
+    /// such as the one a util prints with `--schema`
+    #[track_caller]
+    pub fn stdout_conforms_to_schema(&self, schema: &serde_json::Value) -> &Self {
+        let value: serde_json::Value = serde_json::from_str(self.stdout_str())
+            .unwrap_or_else(|e| panic!("Stdout is not JSON ({e}):\n{}", self.stdout_str()));
+        let errors = crate::schema::validate(&value, schema);
+        assert!(
+            errors.is_empty(),
+            "Stdout does not conform to its schema:\n  {}\n{}",
+            errors.join("\n  "),
+            self.stdout_str()
+        );
+        self
+    }
 }
 
 pub fn log_info<T: AsRef<str>, U: AsRef<str>>(msg: T, par: U) {
@@ -1340,6 +1356,18 @@ impl TestScenario {
         ts
     }
 
+    /// The JSON Schema the util declares for its `--obj` output with
+    /// `--schema`, or `None` when it declares none
+    pub fn declared_schema(&self) -> Option<serde_json::Value> {
+        let result = self.ucmd().arg("--schema").run();
+        if !result.succeeded() {
+            return None;
+        }
+        serde_json::from_str::<serde_json::Value>(result.stdout_str())
+            .ok()
+            .filter(|schema| schema.get("properties").is_some())
+    }
+
     /// Returns builder for invoking the target uutils binary. Paths given are
     /// treated relative to the environment's unique temporary test directory.
     pub fn ucmd(&self) -> UCommand {
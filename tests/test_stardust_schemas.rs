@@ -0,0 +1,247 @@
+// Every stardust command that declares a schema with --schema must produce
+// --obj output conforming to it, so the schema (and completion built on it)
+// cannot drift from what the command actually prints, and every command that
+// accepts --schema must declare one unless it is listed in SCHEMALESS.
+
+use sgtests::schema::validate;
+use sgtests::util::TestScenario;
+
+use std::env;
+pub const TESTS_BINARY: &str = env!("CARGO_BIN_EXE_stargate");
+
+#[ctor::ctor]
+fn init() {
+    unsafe {
+        std::env::set_var("SGTESTS_BINARY_PATH", TESTS_BINARY);
+    }
+}
+
+/// Argument lists to check each command that declares a schema with. The
+/// commands themselves are found from the sources (see [`schema_utils`]),
+/// so a new schema without a case here fails the tests.
+const SCHEMA_CASES: &[(&str, &[&[&str]])] = &[
+    ("list-directory", &[&[], &["-a"], &["-R"], &["-l", "dir"], &["file"]]),
+    ("get-fqdn", &[&[]]),
+    ("df", &[&[], &["--total"], &["-a"], &["file", "dir"]]),
+    ("du", &[&[], &["-a"], &["--flat"], &["-c", "dir"]]),
+    ("cksum", &[&["file"], &["-a", "sha256", "--base64", "file", "dir/nested/deep"]]),
+    ("hashsum", &[&["--md5", "file"], &["--sha256", "file", "link"]]),
+    ("cp", &[&["file", "copy"], &["-b", "file", "copy"], &["-r", "dir", "tree"]]),
+    ("mv", &[&["link", "moved"], &["-b", "file", "moved"]]),
+    ("rm", &[&["link"], &["-r", "dir"]]),
+    ("install", &[&["file", "installed"], &["-C", "file", "installed"], &["-d", "made/here"]]),
+    ("tail", &[&["file"], &["--parse", "logfmt", "file"]]),
+    ("who", &[&[], &["-a"]]),
+    ("list-logins", &[&["-f", "file"], &["--failed", "-f", "file"]]),
+    ("od", &[&["file"], &["-t", "x1d2z", "-c", "file"], &["-t", "f4", "-w8", "file"]]),
+    // dd reports on stderr unless told otherwise
+    ("dd", &[&["--obj-fd", "1", "if=file", "of=out"], &["--obj-fd", "1", "if=lines", "of=out", "bs=2", "count=3"]]),
+    ("split", &[&["file"], &["-l", "2", "lines", "part"], &["-n", "2", "lines"]]),
+    ("csplit", &[&["lines", "3"], &["-f", "section", "lines", "/c/"]]),
+];
+
+/// Commands that declare a schema but cannot be run here: scan-wifi needs
+/// root and a wireless adapter
+const UNCHECKED: &[&str] = &["scan-wifi"];
+
+/// Commands that accept --obj and --schema through `add_json_args` but do not
+/// declare a schema yet. A new --obj command must declare one instead of
+/// joining this list.
+const SCHEMALESS: &[&str] = &[
+    "basename",
+    "collect-count",
+    "detect-signals",
+    "dirname",
+    "factor",
+    "find-text",
+    "get-architecture",
+    "get-domainname",
+    "get-environment",
+    "get-hostname",
+    "get-linenumber",
+    "get-username",
+    "groups",
+    "id",
+    "kill",
+    "last-touch",
+    "link",
+    "list-processes",
+    "listen-radio",
+    "mktemp",
+    "monitor-spectrum",
+    "monitor-traffic",
+    "nproc",
+    "pathchk",
+    "printenv",
+    "pwd",
+    "readlink",
+    "realpath",
+    "record-audio",
+    "record-video",
+    "say-text",
+    "scan-arp",
+    "scan-bt",
+    "scan-neighbors",
+    "scan-radio",
+    "sleep",
+    "sum",
+    "sync",
+    "tsort",
+    "tty",
+    "uname",
+    "unlink",
+    "uptime",
+    "users",
+    "whoami",
+];
+
+/// Commands built into the binary under test
+fn built_utils() -> Vec<String> {
+    let output = std::process::Command::new(TESTS_BINARY).arg("--list").output().unwrap();
+    String::from_utf8(output.stdout).unwrap().lines().map(|line| line.trim().to_string()).collect()
+}
+
+/// Every command in the manifest, with its source directory
+fn source_utils() -> Vec<(String, String)> {
+    let root = env!("CARGO_MANIFEST_DIR");
+    let manifest = std::fs::read_to_string(format!("{root}/Cargo.toml")).unwrap();
+    manifest
+        .lines()
+        .filter(|line| line.contains("optional = true"))
+        .filter_map(|line| {
+            let util = line.split_once(" = ")?.0.replace('_', "-");
+            let path = line.split_once("path = \"")?.1.split_once('"')?.0;
+            Some((util, format!("{root}/{path}/src")))
+        })
+        .collect()
+}
+
+/// Whether any source file under `src` contains `needle`
+fn sources_contain(src: &str, needle: &str) -> bool {
+    walkdir::WalkDir::new(src)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "rs"))
+        .any(|entry| std::fs::read_to_string(entry.path()).is_ok_and(|text| text.contains(needle)))
+}
+
+/// Commands built into the binary under test whose sources answer
+/// `--schema`, i.e. call `stardust_output::self_describe`
+fn schema_utils() -> Vec<String> {
+    let built = built_utils();
+    let mut utils: Vec<String> = source_utils()
+        .into_iter()
+        .filter(|(util, _)| built.contains(util))
+        .filter(|(_, src)| sources_contain(src, "self_describe("))
+        .map(|(util, _)| util)
+        .collect();
+    utils.sort();
+    utils
+}
+
+fn cases(util: &str) -> &'static [&'static [&'static str]] {
+    SCHEMA_CASES
+        .iter()
+        .find(|(name, _)| *name == util)
+        .map(|(_, arg_lists)| *arg_lists)
+        .unwrap_or_else(|| panic!("{util} declares a schema but has no SCHEMA_CASES entry"))
+}
+
+/// The commands to check, with their argument lists
+fn checked_utils() -> Vec<(String, &'static [&'static [&'static str]])> {
+    schema_utils()
+        .into_iter()
+        .filter(|util| !UNCHECKED.contains(&util.as_str()))
+        .map(|util| {
+            let arg_lists = cases(&util);
+            (util, arg_lists)
+        })
+        .collect()
+}
+
+fn scenario(util: &str) -> TestScenario {
+    let ts = TestScenario::new(util);
+    ts.fixtures.mkdir_all("dir/nested");
+    ts.fixtures.write("file", "contents\n");
+    ts.fixtures.write("lines", "a\nb\nc\nd\ne\n");
+    ts.fixtures.write("dir/nested/deep", "");
+    ts.fixtures.symlink_file("file", "link");
+    ts
+}
+
+#[test]
+fn test_every_schema_has_cases() {
+    let utils = schema_utils();
+    for util in &utils {
+        if !UNCHECKED.contains(&util.as_str()) {
+            cases(util);
+        }
+    }
+    // A case for a command that no longer declares a schema is stale
+    let built = built_utils();
+    for (util, _) in SCHEMA_CASES {
+        if built.iter().any(|b| b == util) {
+            assert!(utils.iter().any(|u| u == util), "{util} has a case but declares no schema");
+        }
+    }
+}
+
+#[test]
+fn test_every_obj_command_declares_a_schema() {
+    for (util, src) in source_utils() {
+        let takes_schema = sources_contain(&src, "add_json_args(");
+        let declares_schema = sources_contain(&src, "self_describe(");
+        if SCHEMALESS.contains(&util.as_str()) {
+            assert!(takes_schema && !declares_schema, "{util} is listed in SCHEMALESS but is not schemaless");
+        } else {
+            assert!(!takes_schema || declares_schema, "{util} accepts --schema but declares no schema");
+        }
+    }
+}
+
+#[test]
+fn test_declared_schemas_are_json_schema() {
+    for util in schema_utils() {
+        let util = util.as_str();
+        let schema = scenario(util)
+            .declared_schema()
+            .unwrap_or_else(|| panic!("{util} should declare a schema"));
+        assert_eq!(schema["$schema"], "https://json-schema.org/draft/2020-12/schema", "{util}");
+        assert_eq!(schema["type"], "object", "{util}");
+        let properties = schema["properties"].as_object().expect("properties");
+        for name in schema["required"].as_array().expect("required") {
+            let name = name.as_str().expect("required names are strings");
+            assert!(properties.contains_key(name), "{util}: required '{name}' is not a property");
+        }
+    }
+}
+
+#[test]
+fn test_obj_output_conforms_to_declared_schema() {
+    for (util, arg_lists) in checked_utils() {
+        let ts = scenario(&util);
+        let schema = ts.declared_schema().expect("schema");
+        for args in arg_lists {
+            ts.ucmd()
+                .arg("--obj")
+                .args(args)
+                .succeeds()
+                .stdout_conforms_to_schema(&schema);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "list_directory")]
+fn test_schema_drift_is_reported() {
+    let ts = scenario("list-directory");
+    let schema = ts.declared_schema().expect("schema");
+    let result = ts.ucmd().arg("--obj").succeeds();
+    let mut output: serde_json::Value = serde_json::from_str(result.stdout_str()).unwrap();
+
+    output["entries"][0]["owner"] = "root".into();
+    output["count"] = "many".into();
+    let errors = validate(&output, &schema);
+    assert!(errors.iter().any(|e| e.contains("'owner' is not in the schema")), "{errors:?}");
+    assert!(errors.iter().any(|e| e.starts_with("$.count: expected integer")), "{errors:?}");
+}
@@ -175,6 +175,10 @@ mod test_kill;
 #[path = "by-util/test_link.rs"]
 mod test_link;
 
+#[cfg(feature = "list_logins")]
+#[path = "by-util/test_list_logins.rs"]
+mod test_list_logins;
+
 #[cfg(feature = "ln")]
 #[path = "by-util/test_ln.rs"]
 mod test_ln;